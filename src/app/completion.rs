@@ -0,0 +1,203 @@
+//! Tab completion and the signature hint for the input line. Command names
+//! come from the registry; scene, preset and theme names and KIT paths from
+//! disk.
+
+use super::App;
+use crate::commands::registry::COMMAND_REGISTRY;
+use crate::commands::resolve_to_short;
+use std::path::{Path, PathBuf};
+
+/// Completions for the token before the cursor, cycled by repeated Tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    /// Char index where the completed token starts
+    start: usize,
+    pub candidates: Vec<String>,
+    pub index: usize,
+    /// Input and cursor after the last Tab; anything else typed ends the cycle
+    input: String,
+    cursor: usize,
+}
+
+/// The command being typed at `cursor`, its argument index there and the
+/// token start: commands follow `:` and `;`, as in `IF X: PF 440; TR`
+fn token_at(line: &str, cursor: usize) -> (Option<String>, usize, usize) {
+    let before: Vec<char> = line.chars().take(cursor).collect();
+    let segment = before.iter().rposition(|c| *c == ':' || *c == ';').map_or(0, |i| i + 1);
+    let start = before.iter().rposition(|c| c.is_whitespace()).map_or(0, |i| i + 1).max(segment);
+    let words: Vec<String> = before[segment..start].iter().collect::<String>().split_whitespace().map(str::to_string).collect();
+    let command = match words.first() {
+        Some(command) => Some(command.to_uppercase()),
+        None => {
+            // Still typing the command: take it up to the end of its token
+            let rest: String = line.chars().skip(segment).collect();
+            rest.split(|c: char| c.is_whitespace() || c == ';').find(|word| !word.is_empty()).map(str::to_uppercase)
+        }
+    };
+    (command, words.len(), start)
+}
+
+/// Hint for the command at the cursor: name, arguments, range and help text
+pub fn signature_hint(line: &str, cursor: usize) -> Option<String> {
+    let (command, _, _) = token_at(line, cursor);
+    let command = command?;
+    let def = COMMAND_REGISTRY.get(command.as_str())?;
+    let name = match def.canonical {
+        Some(canonical) => format!("{} ({})", def.name, canonical),
+        None if resolve_to_short(def.name) != def.name => format!("{} ({})", def.name, resolve_to_short(def.name)),
+        None => def.name.to_string(),
+    };
+    let usage = def.usage();
+    let usage = if usage.is_empty() { String::new() } else { format!(" {}", usage) };
+    Some(format!("{}{} - {}", name, usage, def.help.to_uppercase()))
+}
+
+/// Candidates for the token before the cursor, sorted
+pub fn candidates(line: &str, cursor: usize) -> (usize, Vec<String>) {
+    let (command, arg, start) = token_at(line, cursor);
+    let prefix: String = line.chars().skip(start).take(cursor - start).collect();
+    let prefix = prefix.to_uppercase();
+    let matching = |names: Vec<String>| -> Vec<String> {
+        let mut names: Vec<String> = names.into_iter().filter(|name| name.to_uppercase().starts_with(&prefix)).collect();
+        names.sort();
+        names.dedup();
+        names
+    };
+
+    let candidates = match (command.as_deref(), arg) {
+        (_, 0) if prefix.is_empty() => Vec::new(),
+        (_, 0) => matching(COMMAND_REGISTRY.keys().map(|name| name.to_string()).collect()),
+        (Some("LOAD" | "SAVE" | "DELETE"), 1) => {
+            matching(crate::scene::list_scenes().unwrap_or_default().into_iter().map(|(name, _)| name).collect())
+        }
+        (Some("PSET"), 2) | (Some("PSET.DEL"), 1) => {
+            let mut names = crate::preset::factory::list_factory_presets();
+            names.extend(crate::preset::list_user_presets().unwrap_or_default().into_iter().map(|(name, _)| name));
+            matching(names.iter().map(|name| name.to_uppercase()).collect())
+        }
+        (Some("THEME"), 1) => {
+            let config = crate::config::load_config().unwrap_or_default();
+            matching(crate::config::list_themes(&config).iter().map(|name| name.to_uppercase()).collect())
+        }
+        (Some("KIT"), 1) => kit_paths(&line.chars().skip(start).take(cursor - start).collect::<String>()),
+        _ => Vec::new(),
+    };
+    (start, candidates)
+}
+
+/// Folders and audio files under the samples library, or under an absolute or
+/// `~` path, matching `typed` case-insensitively
+fn kit_paths(typed: &str) -> Vec<String> {
+    let (folder, name) = typed.rsplit_once('/').map_or(("", typed), |(folder, name)| (folder, name));
+    let base = if typed.starts_with('/') || typed.starts_with('~') {
+        let folder = if folder.is_empty() && typed.starts_with('/') { "/" } else { folder };
+        match folder.strip_prefix('~') {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest.trim_start_matches('/'))),
+            None => Some(PathBuf::from(folder)),
+        }
+    } else {
+        crate::config::monokit_config_dir().ok().map(|dir| dir.join("samples").join(folder))
+    };
+    let Some(entries) = base.and_then(|base| std::fs::read_dir(base).ok()) else {
+        return Vec::new();
+    };
+    let prefix = if typed.contains('/') { format!("{}/", folder) } else { String::new() };
+    let mut paths: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with('.') || !file_name.to_uppercase().starts_with(&name.to_uppercase()) {
+                return None;
+            }
+            if path.is_dir() {
+                Some(format!("{}{}/", prefix, file_name))
+            } else if crate::sampler::library::is_audio_file(Path::new(&file_name)) {
+                Some(format!("{}{}", prefix, file_name))
+            } else {
+                None
+            }
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Longest prefix shared by all candidates, ignoring case
+fn common_prefix(candidates: &[String]) -> String {
+    let first = &candidates[0];
+    let len = candidates[1..].iter().fold(first.chars().count(), |len, candidate| {
+        first.chars().zip(candidate.chars()).take(len).take_while(|(a, b)| a.eq_ignore_ascii_case(b)).count()
+    });
+    first.chars().take(len).collect()
+}
+
+impl App {
+    /// Tab on the input line: complete the token before the cursor. One match
+    /// is completed outright, several to their common prefix, then each Tab
+    /// steps through them.
+    pub fn complete_input(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            if completion.input == self.input && completion.cursor == self.cursor_position {
+                completion.index = (completion.index + 1) % completion.candidates.len();
+                let (start, text) = (completion.start, completion.candidates[completion.index].clone());
+                self.replace_token(start, &text);
+                let completion = self.completion.as_mut().unwrap();
+                completion.input = self.input.clone();
+                completion.cursor = self.cursor_position;
+                return;
+            }
+        }
+        self.completion = None;
+
+        let (start, candidates) = candidates(&self.input, self.cursor_position);
+        let typed = self.cursor_position - start;
+        match candidates.len() {
+            0 => {}
+            1 => {
+                // Folders stay open for the next part of the path
+                let text = if candidates[0].ends_with('/') { candidates[0].clone() } else { format!("{} ", candidates[0]) };
+                self.replace_token(start, &text);
+            }
+            _ => {
+                let common = common_prefix(&candidates);
+                if common.chars().count() > typed {
+                    self.replace_token(start, &common);
+                    return;
+                }
+                // Start with the first candidate that changes what's typed
+                let typed_text: String = self.input.chars().skip(start).take(typed).collect();
+                let index = candidates.iter().position(|c| !c.eq_ignore_ascii_case(&typed_text)).unwrap_or(0);
+                let text = candidates[index].clone();
+                self.replace_token(start, &text);
+                self.completion = Some(Completion {
+                    start,
+                    candidates,
+                    index,
+                    input: self.input.clone(),
+                    cursor: self.cursor_position,
+                });
+            }
+        }
+    }
+
+    /// Replace the input from `start` to the cursor with `text`
+    fn replace_token(&mut self, start: usize, text: &str) {
+        let before: String = self.input.chars().take(start).collect();
+        let after: String = self.input.chars().skip(self.cursor_position).collect();
+        self.input = format!("{}{}{}", before, text, after);
+        self.cursor_position = start + text.chars().count();
+    }
+
+    /// Footer hint for the input line, with the place in a completion cycle
+    pub fn input_hint(&self) -> Option<String> {
+        let hint = signature_hint(&self.input, self.cursor_position);
+        match &self.completion {
+            Some(c) if c.input == self.input && c.cursor == self.cursor_position => {
+                let place = format!("{}/{}", c.index + 1, c.candidates.len());
+                Some(hint.map_or(place.clone(), |hint| format!("{} {}", place, hint)))
+            }
+            _ => hint,
+        }
+    }
+}
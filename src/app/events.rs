@@ -0,0 +1,239 @@
+use crate::output::OutputDecider;
+use crate::sc_process::ScProcess;
+use crate::types::{MetroCommand, MetroEvent};
+use std::sync::Mutex;
+
+use super::App;
+
+impl App {
+    /// Apply one event from the metro/meter threads. `sc_process` is None when
+    /// no audio engine was started (headless dry run).
+    pub fn handle_metro_event(&mut self, event: MetroEvent, sc_process: Option<&Mutex<ScProcess>>) {
+        match event {
+            MetroEvent::ExecuteScript(index) => {
+                self.execute_script(index);
+            }
+            MetroEvent::ExecuteDelayed(command, script_index) => {
+                self.execute_delayed_command(&command, script_index);
+            }
+            MetroEvent::MeterUpdate(meter_data) => {
+                crate::eval::audio::store_meter(&meter_data);
+                self.meter_data = meter_data;
+            }
+            MetroEvent::VoiceMeterUpdate(voice_meter_data) => {
+                crate::eval::audio::store_voice_meter(&voice_meter_data);
+                self.voice_meter_data = voice_meter_data;
+            }
+            MetroEvent::SpectrumUpdate(spectrum_data) => {
+                crate::eval::audio::store_spectrum(&spectrum_data);
+                self.spectrum_data = spectrum_data;
+            }
+            MetroEvent::ScopeUpdate(scope_data) => {
+                self.scope_data = scope_data;
+            }
+            MetroEvent::CpuUpdate(cpu_data) => {
+                self.cpu_data = cpu_data;
+            }
+            MetroEvent::CompressorUpdate(comp_data) => {
+                // Guard against NaN/inf from SC - skip update if invalid
+                if comp_data.input_level.is_finite() && comp_data.output_level.is_finite() && comp_data.gain_reduction_db.is_finite() {
+                    // Smooth compressor meters: fast attack (0.5), slow release (0.15)
+                    let attack = 0.5;
+                    let release = 0.15;
+
+                    let in_coef = if comp_data.input_level > self.compressor_data.input_level { attack } else { release };
+                    let out_coef = if comp_data.output_level > self.compressor_data.output_level { attack } else { release };
+                    let gr_coef = if comp_data.gain_reduction_db < self.compressor_data.gain_reduction_db { attack } else { release };
+
+                    self.compressor_data.input_level += in_coef * (comp_data.input_level - self.compressor_data.input_level);
+                    self.compressor_data.output_level += out_coef * (comp_data.output_level - self.compressor_data.output_level);
+                    self.compressor_data.gain_reduction_db += gr_coef * (comp_data.gain_reduction_db - self.compressor_data.gain_reduction_db);
+                    crate::eval::audio::store_compressor(&self.compressor_data);
+                }
+            }
+            MetroEvent::LoudnessUpdate(block) => {
+                if let Some(gain_db) = self.loudness.push(block) {
+                    let gain = crate::loudness::db_to_amp(gain_db);
+                    let _ = self.metro_tx.send(MetroCommand::SendParam("out_gain".to_string(), rosc::OscType::Float(gain)));
+                }
+            }
+            MetroEvent::SpectrogramUpdate(frame) => {
+                self.spectrogram.push(&frame);
+            }
+            MetroEvent::Onset => {
+                if self.onset_settings.script > 0 {
+                    self.execute_script(self.onset_settings.script - 1);
+                }
+            }
+            MetroEvent::ScReady => {
+                if self.awaiting_audio_restart {
+                    self.add_output("AUDIO ENGINE ONLINE".to_string());
+                    self.awaiting_audio_restart = false;
+                }
+            }
+            MetroEvent::AudioDeviceList { current, devices } => {
+                // Store for numbered selection
+                self.audio_device_current = current.clone();
+                self.audio_devices = devices.clone();
+
+                self.add_output(format!("CURRENT: {}", current.to_uppercase()));
+                self.add_output("AVAILABLE DEVICES:".to_string());
+                for (i, device) in devices.iter().enumerate() {
+                    self.add_output(format!("  {}: {}", i + 1, device));
+                }
+            }
+            MetroEvent::RestartScWithDevice(device) => {
+                let Some(sc_process) = sc_process else {
+                    self.add_output("ERROR: NO AUDIO ENGINE RUNNING".to_string());
+                    return;
+                };
+                let mut sc = sc_process.lock().unwrap();
+                if let Err(e) = sc.restart_with_device(&device) {
+                    if self.should_output(crate::types::OutputCategory::Error) {
+                        self.add_output(format!("ERROR: {}", e));
+                    }
+                } else {
+                    if let Err(e) = crate::config::save_audio_out_device(Some(device.clone())) {
+                        if self.should_output(crate::types::OutputCategory::Error) {
+                            self.add_output(format!("ERROR SAVING CONFIG: {}", e));
+                        }
+                    }
+                    self.awaiting_audio_restart = true;
+                }
+            }
+            MetroEvent::RestartScWithChannels(channels) => {
+                if let Err(e) = crate::config::save_output_channels(channels) {
+                    if self.should_output(crate::types::OutputCategory::Error) {
+                        self.add_output(format!("ERROR SAVING CONFIG: {}", e));
+                    }
+                }
+                self.output_channels = channels;
+                // A fresh engine starts with every voice on the master
+                let mixer = &mut self.mixer_data;
+                for route in [&mut mixer.route_osc, &mut mixer.route_pla, &mut mixer.route_nos, &mut mixer.route_smp] {
+                    *route = crate::types::OutRoute::default();
+                }
+                let Some(sc_process) = sc_process else {
+                    self.add_output(format!("OUT.CH: {} ON NEXT START", channels));
+                    return;
+                };
+                let device = crate::config::load_config().ok().and_then(|c| c.display.audio_out_device);
+                let mut sc = sc_process.lock().unwrap();
+                if let Err(e) = sc.restart(device.as_deref()) {
+                    if self.should_output(crate::types::OutputCategory::Error) {
+                        self.add_output(format!("ERROR: {}", e));
+                    }
+                } else {
+                    self.add_output("RESTARTING AUDIO ENGINE...".to_string());
+                    self.awaiting_audio_restart = true;
+                }
+            }
+            MetroEvent::Error(msg) => {
+                if self.should_output(crate::types::OutputCategory::Error) {
+                    self.add_output(msg);
+                }
+            }
+            MetroEvent::StartRecordingDirect(dir) => {
+                #[cfg(feature = "scsynth-direct")]
+                if let Some(sc_process) = sc_process {
+                    let mut sc = sc_process.lock().unwrap();
+                    match sc.start_recording(&dir, None) {
+                        Ok(_) => {
+                            if self.should_output(crate::types::OutputCategory::Confirm) {
+                                self.add_output("RECORDING STARTED".to_string());
+                            }
+                        }
+                        Err(e) => {
+                            if self.should_output(crate::types::OutputCategory::Error) {
+                                self.add_output(format!("ERROR: {}", e));
+                            }
+                        }
+                    }
+                }
+                #[cfg(not(feature = "scsynth-direct"))]
+                {
+                    let _ = dir;
+                }
+            }
+            MetroEvent::StopRecordingDirect => {
+                #[cfg(feature = "scsynth-direct")]
+                if let Some(sc_process) = sc_process {
+                    let mut sc = sc_process.lock().unwrap();
+                    match sc.stop_recording() {
+                        Ok(_) => {
+                            if self.should_output(crate::types::OutputCategory::Confirm) {
+                                self.add_output("RECORDING STOPPED".to_string());
+                            }
+                        }
+                        Err(e) => {
+                            if self.should_output(crate::types::OutputCategory::Error) {
+                                self.add_output(format!("ERROR: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            MetroEvent::StartStemRecordingDirect(dir, bpm) => {
+                #[cfg(feature = "scsynth-direct")]
+                if let Some(sc_process) = sc_process {
+                    let folder = crate::stems::session_folder(std::path::Path::new(&dir));
+                    let scene = self.current_scene_name.as_deref().filter(|name| *name != "[unsaved]");
+                    let started = std::fs::create_dir_all(&folder)
+                        .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))
+                        .and_then(|_| sc_process.lock().unwrap().start_stem_recording(&folder))
+                        .and_then(|_| crate::stems::write_sidecar(&folder, bpm, scene));
+                    match started {
+                        Ok(()) => {
+                            if self.should_output(crate::types::OutputCategory::Confirm) {
+                                self.add_output(format!("STEMS STARTED: {}", folder.display()));
+                            }
+                            self.stem_folder = Some(folder);
+                            self.stem_start = Some(std::time::Instant::now());
+                        }
+                        Err(e) => {
+                            if self.should_output(crate::types::OutputCategory::Error) {
+                                self.add_output(format!("ERROR: {}", e));
+                            }
+                        }
+                    }
+                }
+                #[cfg(not(feature = "scsynth-direct"))]
+                {
+                    let _ = (dir, bpm);
+                    self.add_output("ERROR: REC.STEMS NEEDS SCSYNTH-DIRECT MODE".to_string());
+                }
+            }
+            MetroEvent::StopStemRecordingDirect => {
+                #[cfg(feature = "scsynth-direct")]
+                if let Some(sc_process) = sc_process {
+                    let mut sc = sc_process.lock().unwrap();
+                    match sc.stop_stem_recording() {
+                        Ok(_) => {
+                            if self.should_output(crate::types::OutputCategory::Confirm) {
+                                self.add_output("STEMS STOPPED".to_string());
+                            }
+                            self.stem_folder = None;
+                            self.stem_start = None;
+                        }
+                        Err(e) => {
+                            if self.should_output(crate::types::OutputCategory::Error) {
+                                self.add_output(format!("ERROR: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            MetroEvent::SetRecordingPathDirect(path) => {
+                #[cfg(feature = "scsynth-direct")]
+                if let Some(sc_process) = sc_process {
+                    let mut sc = sc_process.lock().unwrap();
+                    sc.set_recording_path_prefix(path);
+                }
+                #[cfg(not(feature = "scsynth-direct"))]
+                {
+                    let _ = path;
+                }
+            }
+        }
+    }
+}
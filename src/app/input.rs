@@ -1,5 +1,5 @@
 use super::{App, EditAction};
-use crate::commands::registry::validate::validate_from_registry;
+use crate::commands::registry::validate::{error_token, validate_from_registry};
 
 impl App {
     pub fn insert_char(&mut self, c: char) {
@@ -81,8 +81,9 @@ impl App {
 
     pub fn select_line_up(&mut self) {
         if let Some(script_idx) = self.current_script_index() {
+            let last = self.script_rows(script_idx) - 1;
             let new_selection = match self.selected_line {
-                None => 7,
+                None => last,
                 Some(0) => 0,
                 Some(n) => n - 1,
             };
@@ -95,10 +96,10 @@ impl App {
 
     pub fn select_line_down(&mut self) {
         if let Some(script_idx) = self.current_script_index() {
+            let last = self.script_rows(script_idx) - 1;
             let new_selection = match self.selected_line {
                 None => 0,
-                Some(7) => 7,
-                Some(n) => n + 1,
+                Some(n) => (n + 1).min(last),
             };
             self.selected_line = Some(new_selection);
             let script = self.scripts.get_script(script_idx);
@@ -109,24 +110,26 @@ impl App {
 
     pub fn save_line(&mut self) {
         if let Some(script_idx) = self.current_script_index() {
+            let last = self.script_rows(script_idx) - 1;
             let line_idx = if let Some(selected) = self.selected_line {
                 selected
             } else {
                 let script = self.scripts.get_script(script_idx);
                 let mut first_empty = None;
-                for i in 0..8 {
+                for i in 0..=last {
                     if script.lines[i].is_empty() {
                         first_empty = Some(i);
                         break;
                     }
                 }
-                first_empty.unwrap_or(7)
+                first_empty.unwrap_or(last)
             };
 
             if !self.input.trim().is_empty() {
                 if let Err(e) = validate_from_registry(&self.input) {
                     self.script_error = Some(format!("{}", e));
                     self.script_error_time = Some(std::time::Instant::now());
+                    self.script_error_token = error_token(&e);
                     return;
                 }
             }
@@ -143,25 +146,27 @@ impl App {
             let script = self.scripts.get_script_mut(script_idx);
             script.lines[line_idx] = self.input.clone();
             self.scene_modified = true;
-            let next_line = if line_idx < 7 { line_idx + 1 } else { 0 };
+            let next_line = if line_idx < last { line_idx + 1 } else { 0 };
             self.selected_line = Some(next_line);
             self.input.clear();
             self.cursor_position = 0;
             // Clear any previous error on successful save
             self.script_error = None;
             self.script_error_time = None;
+            self.script_error_token = None;
         }
     }
 
     pub fn duplicate_line(&mut self) {
         if let Some(script_idx) = self.current_script_index() {
+            let last = self.script_rows(script_idx) - 1;
             if let Some(selected) = self.selected_line {
-                if selected < 7 {
+                if selected < last {
                     let script = self.scripts.get_script(script_idx);
                     let line_content = script.lines[selected].clone();
 
                     // Record lines that will be shifted for undo
-                    let shifted_lines: Vec<String> = (selected + 1..=7)
+                    let shifted_lines: Vec<String> = (selected + 1..=last)
                         .map(|i| script.lines[i].clone())
                         .collect();
                     self.script_undo_stacks[script_idx].push(EditAction::DuplicateLine {
@@ -170,7 +175,7 @@ impl App {
                     });
 
                     let script = self.scripts.get_script_mut(script_idx);
-                    for i in (selected + 2..=7).rev() {
+                    for i in (selected + 2..=last).rev() {
                         script.lines[i] = script.lines[i - 1].clone();
                     }
                     script.lines[selected + 1] = line_content;
@@ -280,6 +285,7 @@ impl App {
                     if let Err(e) = validate_from_registry(&self.clipboard) {
                         self.script_error = Some(format!("{}", e));
                         self.script_error_time = Some(std::time::Instant::now());
+                        self.script_error_token = None;
                         return;
                     }
                 }
@@ -300,6 +306,7 @@ impl App {
                 self.cursor_position = self.input.len();
                 self.script_error = None;
                 self.script_error_time = None;
+                self.script_error_token = None;
             }
         }
     }
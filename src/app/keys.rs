@@ -0,0 +1,54 @@
+use super::App;
+use crate::output::OutputDecider;
+use crate::types::OutputCategory;
+use crate::ui::keymap::{KeyAction, KeyMap};
+use std::collections::BTreeMap;
+
+impl App {
+    /// Replace the key bindings with the `[keys]` table from config.toml
+    pub fn load_keys(&mut self, keys: &BTreeMap<String, String>) {
+        let (keymap, errors) = KeyMap::from_config(keys);
+        self.keymap = keymap;
+        if self.should_output(OutputCategory::Error) {
+            for error in errors {
+                self.add_output(error);
+            }
+        }
+    }
+
+    /// KEYS: re-read config.toml and list the active bindings
+    pub fn reload_keys(&mut self) {
+        match crate::config::load_config() {
+            Ok(config) => self.load_keys(&config.keys),
+            Err(e) => {
+                if self.should_output(OutputCategory::Error) {
+                    self.add_output(format!("KEYS: {}", e.to_string().to_uppercase()));
+                }
+                return;
+            }
+        }
+
+        if self.should_output(OutputCategory::Query) {
+            if self.keymap.is_empty() {
+                self.add_output("KEYS: NO BINDINGS ([KEYS] IN CONFIG.TOML)".to_string());
+            } else {
+                self.add_output(format!("KEYS: {} BINDINGS", self.keymap.len()));
+                for line in self.keymap.describe() {
+                    self.add_output(line);
+                }
+            }
+        }
+    }
+
+    pub fn run_key_action(&mut self, action: KeyAction) {
+        match action {
+            KeyAction::Undo => self.undo(),
+            KeyAction::Redo => self.redo(),
+            KeyAction::NextPage => self.next_page(),
+            KeyAction::PrevPage => self.prev_page(),
+            KeyAction::ToggleHelp => self.toggle_help(),
+            KeyAction::Ignore => {}
+            KeyAction::Command(cmd) => self.execute_delayed_command(&cmd, 10),
+        }
+    }
+}
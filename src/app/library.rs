@@ -0,0 +1,149 @@
+use super::App;
+use crate::output::OutputDecider;
+use crate::sampler::library::LibraryInput;
+use crate::types::{MetroCommand, OutputCategory, Page};
+
+impl App {
+    /// Scan the sample library the first time the page is shown and keep
+    /// header info of the selected file cached for the renderer.
+    pub fn refresh_library(&mut self) {
+        if self.current_page != Page::Library {
+            return;
+        }
+
+        if self.library.root.is_none() {
+            self.rescan_library();
+        }
+
+        if let Some(rel) = self.library.selected_node().filter(|n| !n.is_dir).map(|n| n.rel.clone()) {
+            self.library.info(&rel);
+        }
+    }
+
+    pub fn rescan_library(&mut self) {
+        match crate::config::monokit_config_dir() {
+            Ok(config_dir) => self.library.scan(&config_dir.join("samples")),
+            Err(_) => {
+                if self.should_output(OutputCategory::Error) {
+                    self.add_output("LIBRARY: CONFIG DIR NOT FOUND".to_string());
+                }
+            }
+        }
+    }
+
+    /// Load the selection: folders load as a kit, files as a slice source
+    pub fn library_load(&mut self) {
+        let Some(path) = self.library.selected_path() else {
+            return;
+        };
+        let cmd = format!("KIT {}", path.to_string_lossy());
+        self.execute_delayed_command(&cmd, 10);
+        self.scene_modified = true;
+    }
+
+    /// Load the folder containing the selected file as a kit
+    pub fn library_load_folder(&mut self) {
+        let Some(folder) = self.library.selected_path().and_then(|p| p.parent().map(|p| p.to_path_buf())) else {
+            return;
+        };
+        let cmd = format!("KIT {}", folder.to_string_lossy());
+        self.execute_delayed_command(&cmd, 10);
+        self.scene_modified = true;
+    }
+
+    /// Audition the selected file on the preview voice
+    pub fn library_preview(&mut self) {
+        let Some(node) = self.library.selected_node().filter(|n| !n.is_dir).cloned() else {
+            return;
+        };
+        let Some(path) = self.library.selected_path() else {
+            return;
+        };
+
+        let channels = self.library.info(&node.rel).map(|i| i.channels).unwrap_or(2);
+        let _ = self.metro_tx.send(MetroCommand::StartPreview(path.to_string_lossy().to_string(), channels));
+    }
+
+    pub fn library_stop_preview(&mut self) {
+        let _ = self.metro_tx.send(MetroCommand::StopPreview);
+    }
+
+    /// Detect the BPM of the selected file and store it in the library index
+    pub fn library_detect_bpm(&mut self) {
+        let Some(rel) = self.library.selected_node().filter(|n| !n.is_dir).map(|n| n.rel.clone()) else {
+            return;
+        };
+
+        let message = match self.library.detect_bpm(&rel) {
+            Ok(Some(bpm)) => format!("LIBRARY: {:.1} BPM", bpm),
+            Ok(None) => "LIBRARY: NO TEMPO DETECTED".to_string(),
+            Err(e) => format!("LIBRARY: {}", e.to_string().to_uppercase()),
+        };
+        if self.should_output(OutputCategory::Confirm) {
+            self.add_output(message);
+        }
+    }
+
+    pub fn library_begin_search(&mut self) {
+        self.library.input = LibraryInput::Search;
+    }
+
+    /// Start editing the tags of the selected file, prefilled with its current tags
+    pub fn library_begin_tag(&mut self) {
+        let Some(rel) = self.library.selected_node().filter(|n| !n.is_dir).map(|n| n.rel.clone()) else {
+            return;
+        };
+        self.library.tag_buffer = self.library.index.tags(&rel).join(" ");
+        self.library.input = LibraryInput::Tag;
+    }
+
+    pub fn library_input_char(&mut self, c: char) {
+        match self.library.input {
+            LibraryInput::Search => {
+                let mut query = self.library.query.clone();
+                query.push(c);
+                self.library.set_query(query);
+            }
+            LibraryInput::Tag => self.library.tag_buffer.push(c),
+            LibraryInput::None => {}
+        }
+    }
+
+    pub fn library_input_backspace(&mut self) {
+        match self.library.input {
+            LibraryInput::Search => {
+                let mut query = self.library.query.clone();
+                query.pop();
+                self.library.set_query(query);
+            }
+            LibraryInput::Tag => {
+                self.library.tag_buffer.pop();
+            }
+            LibraryInput::None => {}
+        }
+    }
+
+    /// Finish the input line; search keeps its query, tags are written to the index
+    pub fn library_input_commit(&mut self) {
+        if self.library.input == LibraryInput::Tag {
+            if let Some(rel) = self.library.selected_node().map(|n| n.rel.clone()) {
+                let input = std::mem::take(&mut self.library.tag_buffer);
+                if let Err(e) = self.library.set_tags(&rel, &input) {
+                    if self.should_output(OutputCategory::Error) {
+                        self.add_output(format!("LIBRARY: {}", e.to_string().to_uppercase()));
+                    }
+                }
+            }
+        }
+        self.library.input = LibraryInput::None;
+    }
+
+    /// Abandon the input line; cancelling a search also clears its query
+    pub fn library_input_cancel(&mut self) {
+        if self.library.input == LibraryInput::Search {
+            self.library.set_query(String::new());
+        }
+        self.library.tag_buffer.clear();
+        self.library.input = LibraryInput::None;
+    }
+}
@@ -3,15 +3,21 @@ use crate::output::OutputDecider;
 use crate::terminal::TerminalCapabilities;
 use crate::theme::Theme;
 use crate::types::{
-    ColorMode, ConfirmAction, Counters, CpuData, FxMixState, LineSegmentActivity, MeterData, MetroCommand, MetroState, MixerData, NotesStorage, Page, ParamActivity, PatternStorage, SamplerState, ScaleState, ScopeData, ScriptMutes, ScriptStorage, SearchMatch, SpectrumData, SyncMode, Variables, VoiceMeterData,
+    ColorMode, ConfirmAction, Counters, CpuData, DEFAULT_SCRIPT_LINES, MAX_SCRIPT_LINES, FxMixState, LineSegmentActivity, MeterData, MetroCommand, MetroState, MixerData, NotesStorage, Page, ParamActivity, PatternStorage, SamplerState, ScaleState, ScopeData, ScriptMutes, ScriptStorage, SearchMatch, SpectrumData, SyncMode, Variables, VoiceMeterData,
 };
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+pub mod completion;
+mod events;
 mod history;
 mod input;
+mod keys;
+mod library;
+mod mouse;
 mod script_exec;
+mod waveform;
 
 pub use history::{EditAction, UndoStack};
 
@@ -41,11 +47,17 @@ pub struct App {
     pub should_quit: bool,
     pub script_error: Option<String>,
     pub script_error_time: Option<Instant>,
+    /// Token of the input the script error points at
+    pub script_error_token: Option<usize>,
     pub theme: Theme,
     pub color_mode: ColorMode,
     pub terminal_caps: TerminalCapabilities,
     pub recording: bool,
     pub recording_start: Option<Instant>,
+    /// Folder of the REC.STEMS session in progress, kept apart from REC so
+    /// either can be stopped on its own
+    pub stem_folder: Option<std::path::PathBuf>,
+    pub stem_start: Option<Instant>,
     pub rec_spinner_type: usize,
     pub debug_level: u8,
     pub br_len: usize,
@@ -69,6 +81,9 @@ pub struct App {
     pub cpu_data: CpuData,
     pub eq_state: crate::types::EqState,
     pub compressor_data: crate::types::CompressorData,
+    pub onset_settings: crate::types::OnsetSettings,
+    pub loudness: crate::loudness::LoudnessMeter,
+    pub spectrogram: crate::spectrogram::Spectrogram,
     pub mixer_data: MixerData,
     pub fx_mix_state: FxMixState,
     pub show_cpu: bool,
@@ -91,8 +106,9 @@ pub struct App {
     pub search_cursor: usize,
     pub search_matches: Vec<SearchMatch>,
     pub search_current_match: usize,
-    pub conditional_segments: [[LineSegmentActivity; 8]; 10],
+    pub conditional_segments: [[LineSegmentActivity; MAX_SCRIPT_LINES]; 10],
     pub show_conditional_highlight: bool,
+    pub show_syntax_highlight: bool,
     pub current_scene_name: Option<String>,
     pub title_mode: u8,
     pub title_timer_enabled: bool,
@@ -104,6 +120,7 @@ pub struct App {
     pub out_cfm: bool,
     pub audio_devices: Vec<String>,
     pub audio_device_current: String,
+    pub output_channels: u8,
     pub header_scramble: Option<crate::scramble::ScrambleAnimation>,
     pub scramble_enabled: bool,
     pub scramble_grid_enabled: bool,
@@ -113,6 +130,7 @@ pub struct App {
     pub ui_scrambles: Vec<(String, crate::scramble::ScrambleAnimation)>,
     pub grid_scrambles: Vec<crate::scramble::ScrambleAnimation>,
     pub ascii_meters: bool,
+    pub mouse: bool,
     pub awaiting_audio_restart: bool,
     pub script_break: bool,
     pub autoload: bool,
@@ -121,13 +139,27 @@ pub struct App {
     pub script_mutes: ScriptMutes,
     pub confirm_quit_unsaved: bool,
     pub confirm_overwrite_scene: bool,
+    /// Lines the script editor offers (8-32); longer scripts show all their lines
+    pub script_length: usize,
     pub scene_modified: bool,
     pub pending_confirmation: Option<ConfirmAction>,
     pub sampler_state: SamplerState,
+    pub waveform_view: crate::sampler::waveform::WaveformView,
+    pub library: crate::sampler::library::LibraryBrowser,
+    pub keymap: crate::ui::keymap::KeyMap,
+    pub grid_pages: Vec<crate::ui::grid_pages::GridPage>,
+    /// Grid bar held with the mouse and the last value sent for it
+    pub bar_drag: Option<(crate::ui::grid_pages::BarTarget, Option<i32>)>,
+    /// Tab completion being cycled through
+    pub completion: Option<completion::Completion>,
+    pub snapshots: crate::snapshot::SharedSnapshots,
+    pub rng: crate::rng::SharedRng,
 }
 
 impl App {
     pub fn new(metro_tx: Sender<MetroCommand>, metro_state: Arc<Mutex<MetroState>>, theme: Theme, color_mode: ColorMode, config: &crate::config::Config, terminal_caps: TerminalCapabilities) -> Self {
+        let snapshots = metro_state.lock().unwrap().snapshots.clone();
+        let rng = metro_state.lock().unwrap().rng.clone();
         Self {
             current_page: Page::Live,
             previous_page: Page::Live,
@@ -154,11 +186,14 @@ impl App {
             should_quit: false,
             script_error: None,
             script_error_time: None,
+            script_error_token: None,
             theme,
             color_mode,
             terminal_caps,
             recording: false,
             recording_start: None,
+            stem_folder: None,
+            stem_start: None,
             rec_spinner_type: 0,
             debug_level: config.display.debug_level,
             br_len: 2,
@@ -184,9 +219,18 @@ impl App {
                 display_mode: config.display.scope_display_mode,
                 unipolar: config.display.scope_unipolar,
                 gain: config.display.scope_gain,
+                trigger: crate::types::ScopeTrigger::Free,
+                trig_level: 0,
+                holdoff_ms: 0,
+                xy: false,
+                overlay: crate::types::ScopeOverlay::Off,
+                single_armed: false,
             },
             cpu_data: CpuData::default(),
             eq_state: crate::types::EqState::default(),
+            onset_settings: crate::types::OnsetSettings::default(),
+            loudness: crate::loudness::LoudnessMeter::default(),
+            spectrogram: crate::spectrogram::Spectrogram::default(),
             compressor_data: crate::types::CompressorData::default(),
             mixer_data: MixerData::default(),
             fx_mix_state: FxMixState::default(),
@@ -212,6 +256,7 @@ impl App {
             search_current_match: 0,
             conditional_segments: Default::default(),
             show_conditional_highlight: config.display.show_conditional_highlight,
+            show_syntax_highlight: config.display.show_syntax_highlight,
             current_scene_name: None,
             title_mode: config.display.title_mode,
             title_timer_enabled: config.display.title_timer_enabled,
@@ -238,6 +283,7 @@ impl App {
                 }
             },
             audio_device_current: config.display.audio_out_device.clone().unwrap_or_default(),
+            output_channels: crate::types::output_channel_count(config.display.output_channels),
             header_scramble: if config.display.scramble_enabled {
                 let mode = crate::scramble::ScrambleMode::from_u8(config.display.scramble_mode);
                 let curve = crate::scramble::ScrambleCurve::from_u8(config.display.scramble_curve);
@@ -262,6 +308,7 @@ impl App {
             },
             grid_scrambles: Vec::new(),
             ascii_meters: config.display.ascii_meters,
+            mouse: config.display.mouse,
             awaiting_audio_restart: false,
             script_break: false,
             autoload: config.display.autoload,
@@ -270,9 +317,18 @@ impl App {
             script_mutes: ScriptMutes::default(),
             confirm_quit_unsaved: config.display.confirm_quit_unsaved,
             confirm_overwrite_scene: config.display.confirm_overwrite_scene,
+            script_length: config.display.script_length.clamp(DEFAULT_SCRIPT_LINES, MAX_SCRIPT_LINES),
             scene_modified: false,
             pending_confirmation: None,
             sampler_state: SamplerState::default(),
+            waveform_view: crate::sampler::waveform::WaveformView::default(),
+            library: crate::sampler::library::LibraryBrowser::default(),
+            keymap: crate::ui::keymap::KeyMap::default(),
+            grid_pages: Vec::new(),
+            bar_drag: None,
+            completion: None,
+            snapshots,
+            rng,
         }
     }
 
@@ -282,6 +338,47 @@ impl App {
         }
     }
 
+    /// Set up the `[[grid_pages]]` from config.toml. A saved GRID.MODE for a
+    /// page that is no longer there falls back to the labels.
+    pub fn load_grid_pages(&mut self, config: &[crate::config::GridPageConfig]) {
+        let (pages, errors) = crate::ui::grid_pages::pages_from_config(config);
+        self.grid_pages = pages;
+        if self.grid_mode >= self.grid_mode_count() {
+            self.grid_mode = 0;
+        }
+        if self.should_output(crate::types::OutputCategory::Error) {
+            for error in errors {
+                self.add_output(error);
+            }
+        }
+    }
+
+    /// Startup shared by the TUI and headless mode once SuperCollider is up:
+    /// config keys and pages, the VCA mode, the last scene if autoload is on,
+    /// then the init script
+    pub fn start_session(&mut self, config: &crate::config::Config) {
+        self.load_keys(&config.keys);
+        self.load_grid_pages(&config.grid_pages);
+
+        let _ = self.metro_tx.send(MetroCommand::SendParam(
+            "vca_mode".to_string(),
+            rosc::OscType::Int(if config.display.vca_mode { 1 } else { 0 }),
+        ));
+
+        if config.display.autoload {
+            if let Some(last_scene) = &config.display.last_scene {
+                self.execute_delayed_command(&format!("LOAD {}", last_scene), 0);
+            }
+        }
+
+        self.execute_script(9);
+    }
+
+    /// Built-in grid modes plus the custom pages
+    pub fn grid_mode_count(&self) -> u8 {
+        crate::ui::grid_pages::FIRST_CUSTOM_MODE + self.grid_pages.len() as u8
+    }
+
     pub fn trigger_grid_scramble(&mut self) {
         use crate::types::{GRID_LABELS, GRID_ICONS};
         if !self.scramble_grid_enabled {
@@ -315,6 +412,16 @@ impl App {
         self.selected_line = None;
     }
 
+    /// Run the spectrogram synth only while its page is shown, sending
+    /// /n_run when the page is entered or left
+    pub fn refresh_spectrogram(&mut self) {
+        let shown = self.current_page == Page::Spectrogram;
+        if shown != self.spectrogram.running {
+            self.spectrogram.running = shown;
+            let _ = self.metro_tx.send(MetroCommand::SetSpectrogramRunning(shown));
+        }
+    }
+
     pub fn toggle_help(&mut self) {
         if self.current_page == Page::Help {
             self.current_page = self.previous_page;
@@ -382,6 +489,12 @@ impl App {
         }
     }
 
+    /// Lines the editor offers for a script: the configured length, or more if
+    /// the script already uses more (e.g. loaded from a scene)
+    pub fn script_rows(&self, script_idx: usize) -> usize {
+        self.script_length.max(self.scripts.get_script(script_idx).used_len())
+    }
+
     pub fn add_output(&mut self, msg: String) {
         self.output.push(msg);
         if self.output.len() > 100 {
@@ -434,11 +547,15 @@ impl App {
             show_grid_view: &mut self.show_grid_view,
             show_seq_highlight: &mut self.show_seq_highlight,
             grid_mode: &mut self.grid_mode,
+            grid_pages: &self.grid_pages,
             rec_spinner_type: &mut self.rec_spinner_type,
             eq_state: &mut self.eq_state,
             mixer_data: &mut self.mixer_data,
             fx_mix_state: &mut self.fx_mix_state,
             scope_settings: &mut self.scope_settings,
+            onset_settings: &mut self.onset_settings,
+            loudness: &mut self.loudness,
+            spectrogram: &mut self.spectrogram,
             current_page: &mut self.current_page,
             br_len: &mut self.br_len,
             sync_mode: &mut self.sync_mode,
@@ -449,12 +566,14 @@ impl App {
             load_clr: &mut self.load_clr,
             vca_mode: &mut self.vca_mode,
             show_conditional_highlight: &mut self.show_conditional_highlight,
+            show_syntax_highlight: &mut self.show_syntax_highlight,
             current_scene_name: &mut self.current_scene_name,
             title_mode: &mut self.title_mode,
             title_timer_enabled: &mut self.title_timer_enabled,
             title_timer_interval_secs: &mut self.title_timer_interval_secs,
             title_timer_last_toggle: &mut self.title_timer_last_toggle,
             audio_devices: &self.audio_devices,
+            output_channels: self.output_channels,
             header_scramble: &mut self.header_scramble,
             scramble_enabled: &mut self.scramble_enabled,
             scramble_grid_enabled: &mut self.scramble_grid_enabled,
@@ -462,6 +581,7 @@ impl App {
             scramble_speed: &mut self.scramble_speed,
             scramble_curve: &mut self.scramble_curve,
             ascii_meters: &mut self.ascii_meters,
+            mouse: &mut self.mouse,
             autoload: &mut self.autoload,
             terminal_caps: &self.terminal_caps,
             color_mode: self.color_mode,
@@ -470,9 +590,12 @@ impl App {
             script_mutes: &mut self.script_mutes,
             confirm_quit_unsaved: &mut self.confirm_quit_unsaved,
             confirm_overwrite_scene: &mut self.confirm_overwrite_scene,
+            script_length: &mut self.script_length,
             scene_modified: &mut self.scene_modified,
             pending_confirmation: &mut self.pending_confirmation,
             sampler_state: &mut self.sampler_state,
+            snapshots: &self.snapshots,
+            rng: &self.rng,
         };
 
         let result = crate::commands::process_command(
@@ -506,6 +629,7 @@ impl App {
             if time.elapsed().as_secs() >= 3 {
                 self.script_error = None;
                 self.script_error_time = None;
+                self.script_error_token = None;
             }
         }
     }
@@ -650,7 +774,7 @@ impl App {
                         // Clear the duplicated line and restore shifted lines
                         script.lines[*line_idx + 1].clear();
                         for (i, content) in shifted_lines.iter().enumerate() {
-                            if *line_idx + 2 + i <= 7 {
+                            if *line_idx + 2 + i < MAX_SCRIPT_LINES {
                                 script.lines[*line_idx + 2 + i] = content.clone();
                             }
                         }
@@ -749,11 +873,11 @@ impl App {
                             self.cursor_position = 0;
                         }
                     }
-                    EditAction::DuplicateLine { line_idx, .. } => {
+                    EditAction::DuplicateLine { line_idx, shifted_lines } => {
                         let script = self.scripts.get_script(script_idx);
                         let line_content = script.lines[*line_idx].clone();
                         let script = self.scripts.get_script_mut(script_idx);
-                        for i in (*line_idx + 2..=7).rev() {
+                        for i in (*line_idx + 2..=*line_idx + shifted_lines.len()).rev() {
                             script.lines[i] = script.lines[i - 1].clone();
                         }
                         script.lines[*line_idx + 1] = line_content;
@@ -0,0 +1,118 @@
+use super::App;
+use crate::types::Page;
+use crate::ui::header::nav_page_at;
+use crate::ui::pages::live::grid_bar_at;
+use crate::ui::pages::script_renderer::visible_rows;
+use crate::ui::pages::HELP_CATEGORIES;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+/// Lines moved per wheel notch on help and the REPL output
+const SCROLL_LINES: usize = 3;
+
+impl App {
+    /// Handle a mouse event (MOUSE 1); `area` is the whole terminal
+    pub fn handle_mouse(&mut self, event: MouseEvent, area: Rect) {
+        if self.pending_confirmation.is_some() {
+            return;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => self.mouse_scroll(false),
+            MouseEventKind::ScrollDown => self.mouse_scroll(true),
+            MouseEventKind::Down(MouseButton::Left) => self.mouse_click(event.column, event.row, area),
+            MouseEventKind::Drag(MouseButton::Left) => self.drag_bar(event.column),
+            MouseEventKind::Up(MouseButton::Left) => self.bar_drag = None,
+            _ => {}
+        }
+    }
+
+    /// Wheel: help text, REPL output and pattern steps
+    fn mouse_scroll(&mut self, down: bool) {
+        match self.current_page {
+            Page::Help => {
+                let last = HELP_CATEGORIES[self.help_page].lines.len().saturating_sub(1);
+                self.help_scroll = if down {
+                    (self.help_scroll + SCROLL_LINES).min(last)
+                } else {
+                    self.help_scroll.saturating_sub(SCROLL_LINES)
+                };
+            }
+            Page::Live if !self.show_grid_view => {
+                // Scrolling up goes back through the output
+                self.output_scroll = if down {
+                    self.output_scroll.saturating_sub(SCROLL_LINES)
+                } else {
+                    (self.output_scroll + SCROLL_LINES).min(self.output.len())
+                };
+            }
+            Page::Pattern => {
+                // One step per notch, like the arrow keys
+                self.pattern_cursor.1 = if down {
+                    (self.pattern_cursor.1 + 1).min(63)
+                } else {
+                    self.pattern_cursor.1.saturating_sub(1)
+                };
+                self.pattern_input.clear();
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_click(&mut self, column: u16, row: u16, area: Rect) {
+        let chunks = crate::ui::page_layout(self, area);
+        let (header, content) = (chunks[0], chunks[1]);
+
+        // Page names in the header, after the border and a space
+        if row == header.y + 1 {
+            if let Some(page) = column.checked_sub(header.x + 2).and_then(|offset| nav_page_at(self, offset as usize)) {
+                self.go_to_page(page);
+            }
+            return;
+        }
+
+        // Input line, after the "> " prompt
+        if let Some(footer) = chunks.get(2) {
+            if row == footer.y + 1 && !self.search_mode {
+                self.cursor_position = (column.saturating_sub(footer.x + 3) as usize).min(self.input.chars().count());
+                return;
+            }
+        }
+
+        if let Some(bar) = grid_bar_at(self, content, column, row) {
+            self.bar_drag = Some((bar, None));
+            self.drag_bar(column);
+        } else if let Some(script_idx) = self.current_script_index() {
+            self.click_script_line(script_idx, column, row, content);
+        }
+    }
+
+    /// Select the clicked script line for editing, with the cursor where it was clicked
+    fn click_script_line(&mut self, script_idx: usize, column: u16, row: u16, content: Rect) {
+        // Script pages draw a border and a blank or status line above the script
+        let Some(offset) = row.checked_sub(content.y + 2) else {
+            return;
+        };
+        let rows = visible_rows(self.script_rows(script_idx), (content.height as usize).saturating_sub(3), self.selected_line);
+        let line = rows.start + offset as usize;
+        if !rows.contains(&line) {
+            return;
+        }
+        self.selected_line = Some(line);
+        self.input = self.scripts.get_script(script_idx).lines[line].clone();
+        // Lines start after the border and two spaces
+        self.cursor_position = (column.saturating_sub(content.x + 3) as usize).min(self.input.chars().count());
+    }
+
+    /// Send the dragged bar's command, as if typed, when the value under the mouse changes
+    fn drag_bar(&mut self, column: u16) {
+        let command = match &mut self.bar_drag {
+            Some((bar, last)) if *last != Some(bar.value_at(column)) => {
+                *last = Some(bar.value_at(column));
+                bar.command_at(column)
+            }
+            _ => return,
+        };
+        self.param_activity.mark(command.split(' ').next().unwrap_or_default());
+        self.execute_delayed_command(&command, 10);
+    }
+}
@@ -11,7 +11,7 @@ impl App {
             return;
         }
 
-        if script_index < 10 && line_num < 8 {
+        if script_index < 10 && line_num < crate::types::MAX_SCRIPT_LINES {
             let segment = ConditionalSegment {
                 start,
                 end,
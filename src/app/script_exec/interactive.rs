@@ -15,6 +15,11 @@ impl App {
         self.input.clear();
         self.cursor_position = 0;
 
+        let cmd = crate::utils::strip_comment(&cmd).trim().to_string();
+        if cmd.is_empty() {
+            return;
+        }
+
         let cmd_upper = cmd.to_uppercase();
         if cmd_upper == "Q" || cmd_upper == "QUIT" || cmd_upper == "EXIT" {
             // Check if quit confirmation is needed
@@ -38,6 +43,11 @@ impl App {
                 self.recording_start = None;
                 self.add_output("RECORDING STOPPED (AUTO)".to_string());
             }
+            if self.stem_folder.take().is_some() {
+                self.stem_start = None;
+                let _ = self.metro_tx.send(crate::types::MetroCommand::StopStemRecording(false));
+                self.add_output("STEMS STOPPED (AUTO)".to_string());
+            }
             self.should_quit = true;
             return;
         }
@@ -47,6 +57,11 @@ impl App {
             return;
         }
 
+        if cmd_upper == "KEYS" {
+            self.reload_keys();
+            return;
+        }
+
         if cmd_upper.starts_with("REPL.DUMP") {
             let parts: Vec<&str> = cmd_upper.split_whitespace().collect();
             let filename = if parts.len() > 1 {
@@ -163,7 +178,7 @@ impl App {
             }
 
             // Check if this is a LOAD command
-            let is_load_cmd = parts.get(0).map(|c| c.eq_ignore_ascii_case("LOAD")).unwrap_or(false);
+            let is_load_cmd = parts.first().map(|c| c.eq_ignore_ascii_case("LOAD") || c.eq_ignore_ascii_case("IMPORT.TT")).unwrap_or(false);
 
             // Interactive mode (script_index=10) doesn't need highlighting
             self.process_sub_command(sub_cmd, 10, &mut metro_interval, None, 0, 0);
@@ -6,7 +6,7 @@ use super::App;
 use crate::commands::process_command;
 use crate::eval::eval_expression;
 use crate::output::OutputDecider;
-use crate::utils::{split_respecting_quotes, split_whitespace_respecting_quotes};
+use crate::utils::{split_respecting_quotes, split_whitespace_respecting_quotes, strip_comment};
 use std::io::Write;
 
 impl App {
@@ -73,11 +73,15 @@ impl App {
             show_grid_view: &mut self.show_grid_view,
             show_seq_highlight: &mut self.show_seq_highlight,
             grid_mode: &mut self.grid_mode,
+            grid_pages: &self.grid_pages,
             rec_spinner_type: &mut self.rec_spinner_type,
             eq_state: &mut self.eq_state,
             mixer_data: &mut self.mixer_data,
             fx_mix_state: &mut self.fx_mix_state,
             scope_settings: &mut self.scope_settings,
+            onset_settings: &mut self.onset_settings,
+            loudness: &mut self.loudness,
+            spectrogram: &mut self.spectrogram,
             current_page: &mut self.current_page,
             br_len: &mut self.br_len,
             sync_mode: &mut self.sync_mode,
@@ -88,12 +92,14 @@ impl App {
             load_clr: &mut self.load_clr,
             vca_mode: &mut self.vca_mode,
             show_conditional_highlight: &mut self.show_conditional_highlight,
+            show_syntax_highlight: &mut self.show_syntax_highlight,
             current_scene_name: &mut self.current_scene_name,
             title_mode: &mut self.title_mode,
             title_timer_enabled: &mut self.title_timer_enabled,
             title_timer_interval_secs: &mut self.title_timer_interval_secs,
             title_timer_last_toggle: &mut self.title_timer_last_toggle,
             audio_devices: &self.audio_devices,
+            output_channels: self.output_channels,
             header_scramble: &mut self.header_scramble,
             scramble_enabled: &mut self.scramble_enabled,
             scramble_grid_enabled: &mut self.scramble_grid_enabled,
@@ -101,6 +107,7 @@ impl App {
             scramble_speed: &mut self.scramble_speed,
             scramble_curve: &mut self.scramble_curve,
             ascii_meters: &mut self.ascii_meters,
+            mouse: &mut self.mouse,
             autoload: &mut self.autoload,
             terminal_caps: &self.terminal_caps,
             color_mode: self.color_mode,
@@ -109,9 +116,12 @@ impl App {
             script_mutes: &mut self.script_mutes,
             confirm_quit_unsaved: &mut self.confirm_quit_unsaved,
             confirm_overwrite_scene: &mut self.confirm_overwrite_scene,
+            script_length: &mut self.script_length,
             scene_modified: &mut self.scene_modified,
             pending_confirmation: &mut self.pending_confirmation,
             sampler_state: &mut self.sampler_state,
+            snapshots: &self.snapshots,
+            rng: &self.rng,
         };
 
         let result = process_command(
@@ -237,6 +247,7 @@ impl App {
         }
 
         self.script_break = false;
+        let _rng_stream = crate::rng::enter_script(&self.rng, script_index);
 
         let script = self.scripts.get_script(script_index);
         let lines: Vec<String> = script.lines.to_vec();
@@ -261,7 +272,7 @@ impl App {
                 break;
             }
 
-            let original_line = line.trim();
+            let original_line = strip_comment(line).trim();
             if original_line.is_empty() {
                 continue;
             }
@@ -0,0 +1,145 @@
+use super::App;
+use crate::sampler::slice::{frame_to_position, position_to_frame};
+use crate::sampler::stretch::{interval_to_bpm, tempo_ratio};
+use crate::sampler::waveform::{OverviewSource, WaveformOverview, OVERVIEW_BINS};
+use crate::types::{Page, SamplerMode};
+use std::path::Path;
+
+impl App {
+    /// Rebuild the waveform overview when a different file is loaded or the
+    /// file changes on disk, while the Waveform page is shown. Only slice mode has a single file to draw;
+    /// kits clear the overview.
+    pub fn refresh_waveform(&mut self) {
+        if self.current_page != Page::Waveform {
+            return;
+        }
+
+        let path = match (&self.sampler_state.mode, &self.sampler_state.kit_path) {
+            (SamplerMode::Slice, Some(path)) => path.clone(),
+            _ => {
+                self.waveform_view.overview = None;
+                self.waveform_view.source = None;
+                return;
+            }
+        };
+
+        // Failed builds count too, so an unreadable file isn't decoded every
+        // frame, but a rewrite of the file is picked up
+        let source = OverviewSource::of(&path);
+        if self.waveform_view.source.as_ref() == Some(&source) {
+            return;
+        }
+
+        let new_file = self.waveform_view.source.as_ref().map(|s| s.path.as_str()) != Some(path.as_str());
+        self.waveform_view.overview = WaveformOverview::build(Path::new(&path), OVERVIEW_BINS).ok();
+        self.waveform_view.source = Some(source);
+        if new_file {
+            self.waveform_view.zoom = 1;
+            self.waveform_view.scroll = 0;
+            self.waveform_view.cursor = 0;
+        }
+    }
+
+    /// Estimated playhead frame of the last sampler trigger.
+    /// scsynth doesn't report buffer position, so this is derived from the
+    /// trigger time, the slot bounds and the rate/pitch settings.
+    pub fn sampler_playhead_frame(&self) -> Option<usize> {
+        let triggered = self.sampler_trigger_activity?;
+        let overview = self.waveform_view.overview.as_ref()?;
+        let slot = self.sampler_state.slots.get(self.sampler_state.current_slot)?;
+        let length = slot.end_frame.saturating_sub(slot.start_frame);
+        if length == 0 {
+            return None;
+        }
+
+        let playback = &self.sampler_state.playback;
+        let speed = match (playback.stretch, self.sampler_state.source_bpm) {
+            // Stretched loops follow the metro tempo; pitch no longer moves the read position
+            (true, Some(source_bpm)) => {
+                let interval_ms = self.metro_state.lock().unwrap().interval_ms;
+                (playback.rate as f64 / 8192.0) * tempo_ratio(interval_to_bpm(interval_ms), source_bpm) as f64
+            }
+            _ => {
+                let semitones = playback.pitch as f64 + playback.fine as f64 / 100.0;
+                (playback.rate as f64 / 8192.0) * 2f64.powf(semitones / 12.0)
+            }
+        };
+        let elapsed = (triggered.elapsed().as_secs_f64() * overview.sample_rate as f64 * speed) as usize;
+
+        let offset = if playback.loop_mode {
+            elapsed % length
+        } else if elapsed < length {
+            elapsed
+        } else {
+            return None;
+        };
+
+        Some(if playback.direction {
+            slot.end_frame - 1 - offset
+        } else {
+            slot.start_frame + offset
+        })
+    }
+
+    /// Select a slot from the waveform page and move the cursor to its start
+    pub fn waveform_select_slot(&mut self, delta: i32) {
+        let count = self.sampler_state.slots.len();
+        if count == 0 {
+            return;
+        }
+
+        let slot = (self.sampler_state.current_slot as i32 + delta).clamp(0, count as i32 - 1) as usize;
+        self.sampler_state.current_slot = slot;
+
+        if let Some(total) = self.sampler_state.total_frames {
+            let position = frame_to_position(self.sampler_state.slots[slot].start_frame, total);
+            self.waveform_view.set_cursor(position);
+        }
+    }
+
+    /// Select the slot under the waveform cursor
+    pub fn waveform_select_at_cursor(&mut self) {
+        let Some(total) = self.sampler_state.total_frames else {
+            return;
+        };
+
+        let frame = position_to_frame(self.waveform_view.cursor, total);
+        if let Some(slot) = self
+            .sampler_state
+            .slots
+            .iter()
+            .position(|s| frame >= s.start_frame && frame < s.end_frame)
+        {
+            self.sampler_state.current_slot = slot;
+        }
+    }
+
+    /// Insert a marker at the waveform cursor (goes through S.MARK so snapping applies)
+    pub fn waveform_mark(&mut self) {
+        let cmd = format!("S.MARK {}", self.waveform_view.cursor);
+        self.execute_delayed_command(&cmd, 10);
+        self.waveform_select_at_cursor();
+        self.scene_modified = true;
+    }
+
+    /// Delete the marker at the start of the selected slot
+    pub fn waveform_unmark(&mut self) {
+        let cmd = format!("S.UNMARK {}", self.sampler_state.current_slot);
+        self.execute_delayed_command(&cmd, 10);
+        self.scene_modified = true;
+    }
+
+    /// Move the marker at the start of the selected slot to the cursor
+    pub fn waveform_move_marker(&mut self) {
+        let cmd = format!("S.MARK.MV {} {}", self.sampler_state.current_slot, self.waveform_view.cursor);
+        self.execute_delayed_command(&cmd, 10);
+        self.scene_modified = true;
+    }
+
+    /// Audition the selected slot
+    pub fn waveform_audition(&mut self) {
+        let cmd = format!("STR {}", self.sampler_state.current_slot);
+        self.sampler_trigger_activity = Some(std::time::Instant::now());
+        self.execute_delayed_command(&cmd, 10);
+    }
+}
@@ -77,3 +77,37 @@ pub fn find_device_by_name(name: &str) -> Result<Option<AudioDevice>, String> {
 
     Ok(None)
 }
+
+/// Most output channels a device offers (the default device if no name is given).
+/// None when cpal can't find the device.
+#[allow(deprecated)] // name() is deprecated in cpal 0.17 but still works
+pub fn max_output_channels(name: Option<&str>) -> Option<u16> {
+    let device = match name {
+        Some(name) => cpal::available_hosts()
+            .into_iter()
+            .filter_map(|host_id| cpal::host_from_id(host_id).ok())
+            .filter_map(|host| host.output_devices().ok())
+            .flatten()
+            .find(|device| device.name().map(|n| n == name).unwrap_or(false)),
+        None => cpal::default_host().default_output_device(),
+    }?;
+
+    device
+        .supported_output_configs()
+        .ok()?
+        .map(|config| config.channels())
+        .max()
+}
+
+/// Check a device has enough outputs before the engine is started on it.
+/// Devices cpal can't see are let through and left to scsynth.
+pub fn check_output_channels(name: Option<&str>, channels: u8) -> Result<(), String> {
+    match max_output_channels(name) {
+        Some(max) if max < channels as u16 => Err(format!(
+            "{} HAS {} OUTPUTS",
+            name.unwrap_or("DEFAULT DEVICE").to_uppercase(),
+            max
+        )),
+        _ => Ok(()),
+    }
+}
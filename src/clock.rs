@@ -0,0 +1,201 @@
+//! Time source for the metro and delay threads.
+//!
+//! `RealClock` is the wall clock. It blocks until shortly before a deadline
+//! and spin-sleeps the rest, since OS timers alone wake a thread 1-15 ms late
+//! depending on platform, which DEL and metro ticks would hear. `SimClock`
+//! stands still until it is advanced, so tests and `monokit --test` can run
+//! tick sequences, DEL schedules and MIDI clock following exactly and without
+//! sleeping.
+
+use spin_sleep::SpinSleeper;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, TryRecvError};
+use std::time::{Duration, Instant};
+
+pub trait Clock: Clone + Send + 'static {
+    fn now(&self) -> Instant;
+
+    /// Wait for a message on `rx`, giving up once `timeout` of this clock's time has passed
+    fn recv_timeout<T>(&self, rx: &Receiver<T>, timeout: Duration) -> Result<T, RecvTimeoutError>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn recv_timeout<T>(&self, rx: &Receiver<T>, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let sleeper = SpinSleeper::default();
+        let accuracy = Duration::from_nanos(sleeper.native_accuracy_ns() as u64);
+        if timeout > accuracy {
+            match rx.recv_timeout(timeout - accuracy) {
+                Err(RecvTimeoutError::Timeout) => {}
+                result => return result,
+            }
+        }
+
+        // spin_sleep the rest; a message arriving now waits at most the OS accuracy
+        sleeper.sleep(deadline.saturating_duration_since(Instant::now()));
+        match rx.try_recv() {
+            Ok(msg) => Ok(msg),
+            Err(TryRecvError::Disconnected) => Err(RecvTimeoutError::Disconnected),
+            Err(TryRecvError::Empty) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+}
+
+pub use sim::SimClock;
+
+mod sim {
+    use super::Clock;
+    use std::collections::HashMap;
+    use std::sync::mpsc::{Receiver, RecvTimeoutError, TryRecvError};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread::{self, ThreadId};
+    use std::time::{Duration, Instant};
+
+    /// How often a waiting thread looks at its channel, in real time
+    const POLL: Duration = Duration::from_millis(1);
+
+    /// Longest `settle` waits, in real time, for the threads to go idle
+    const SETTLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[derive(Default)]
+    struct State {
+        elapsed: Duration,
+        /// Bumped by each settle round; idle threads check in under the new value
+        epoch: u64,
+        /// Every thread that has waited on this clock, with the epoch it last went
+        /// idle in (None while it is busy)
+        threads: HashMap<ThreadId, Option<u64>>,
+        /// Times a waiting thread woke up to do something
+        wakeups: u64,
+        /// Set by `hold`: no thread acts until the next settle
+        held: bool,
+    }
+
+    /// A clock that only moves when `advance` is called
+    #[derive(Clone)]
+    pub struct SimClock {
+        start: Instant,
+        state: Arc<(Mutex<State>, Condvar)>,
+    }
+
+    impl SimClock {
+        pub fn new() -> Self {
+            Self { start: Instant::now(), state: Default::default() }
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            self.state.0.lock().unwrap().elapsed
+        }
+
+        /// Move time forward, then wait until every thread has dealt with it
+        pub fn advance(&self, by: Duration) {
+            self.state.0.lock().unwrap().elapsed += by;
+            self.settle();
+        }
+
+        /// Wait for `threads` threads to start using the clock, then settle
+        pub fn settle_threads(&self, threads: usize) {
+            let (lock, changed) = &*self.state;
+            let start = Instant::now();
+            let mut state = lock.lock().unwrap();
+            while state.threads.len() < threads && start.elapsed() < SETTLE_TIMEOUT {
+                state = changed.wait_timeout(state, POLL).unwrap().0;
+            }
+            drop(state);
+            self.settle();
+        }
+
+        /// Keep the threads from acting on anything until the next `settle`, so
+        /// messages the caller sends in between reach them all together
+        pub fn hold(&self) {
+            self.state.0.lock().unwrap().held = true;
+        }
+
+        /// Wait until every thread using the clock is idle, having seen all messages
+        /// sent and all time advanced before the call, including what the threads
+        /// sent each other in response
+        pub fn settle(&self) {
+            self.state.0.lock().unwrap().held = false;
+            let start = Instant::now();
+            while !self.quiet_round() && start.elapsed() < SETTLE_TIMEOUT {}
+        }
+
+        /// One check-in of every thread. Quiet if all were idle to begin with and
+        /// none woke up, so nothing can have been sent that is still unseen.
+        fn quiet_round(&self) -> bool {
+            let (lock, changed) = &*self.state;
+            let start = Instant::now();
+            let mut state = lock.lock().unwrap();
+            let all_idle = state.threads.values().all(Option::is_some);
+            let wakeups = state.wakeups;
+            state.epoch += 1;
+            let epoch = state.epoch;
+            changed.notify_all();
+            while !state.threads.values().all(|idle| *idle == Some(epoch)) && start.elapsed() < SETTLE_TIMEOUT {
+                state = changed.wait_timeout(state, POLL).unwrap().0;
+            }
+            all_idle && state.wakeups == wakeups
+        }
+    }
+
+    impl Default for SimClock {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Clock for SimClock {
+        fn now(&self) -> Instant {
+            self.start + self.elapsed()
+        }
+
+        fn recv_timeout<T>(&self, rx: &Receiver<T>, timeout: Duration) -> Result<T, RecvTimeoutError> {
+            let (lock, changed) = &*self.state;
+            let id = thread::current().id();
+            let mut state = lock.lock().unwrap();
+            let deadline = state.elapsed + timeout;
+            let mut pending = None;
+            loop {
+                if pending.is_none() {
+                    match rx.try_recv() {
+                        Ok(msg) => pending = Some(msg),
+                        Err(TryRecvError::Disconnected) => {
+                            // The thread is about to finish, so stop waiting for it
+                            state.threads.remove(&id);
+                            changed.notify_all();
+                            return Err(RecvTimeoutError::Disconnected);
+                        }
+                        Err(TryRecvError::Empty) => {}
+                    }
+                }
+                // One thread runs at a time, so what a thread sends in one go reaches
+                // the others all together, the same way on every run
+                let others_busy = state.held || state.threads.iter().any(|(thread, idle)| *thread != id && idle.is_none());
+                let result = if others_busy {
+                    None
+                } else if let Some(msg) = pending.take() {
+                    Some(Ok(msg))
+                } else if state.elapsed >= deadline {
+                    Some(Err(RecvTimeoutError::Timeout))
+                } else {
+                    None
+                };
+                if let Some(result) = result {
+                    state.threads.insert(id, None);
+                    state.wakeups += 1;
+                    return result;
+                }
+                let epoch = state.epoch;
+                state.threads.insert(id, Some(epoch));
+                changed.notify_all();
+                state = changed.wait_timeout(state, POLL).unwrap().0;
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::output::OutputDecider;
 use crate::terminal::TerminalCapabilities;
 use crate::theme::Theme;
 use crate::types::{
-    ColorMode, ConfirmAction, Counters, EqState, FxMixState, MetroCommand, MixerData, NotesStorage, Page, PatternStorage, SamplerState, ScaleState,
+    ColorMode, ConfirmAction, Counters, EqState, FxMixState, MetroCommand, MixerData, NotesStorage, OnsetSettings, Page, PatternStorage, SamplerState, ScaleState,
     ScriptMutes, ScriptStorage, ScopeSettings, SyncMode, Variables,
 };
 use std::sync::{mpsc::Sender, Arc};
@@ -44,11 +44,16 @@ pub struct ExecutionContext<'a> {
     pub show_grid_view: &'a mut bool,
     pub show_seq_highlight: &'a mut bool,
     pub grid_mode: &'a mut u8,
+    /// Custom grid pages from config, GRID.MODE 7 onwards
+    pub grid_pages: &'a [crate::ui::grid_pages::GridPage],
     pub rec_spinner_type: &'a mut usize,
     pub eq_state: &'a mut EqState,
     pub mixer_data: &'a mut MixerData,
     pub fx_mix_state: &'a mut FxMixState,
     pub scope_settings: &'a mut ScopeSettings,
+    pub onset_settings: &'a mut OnsetSettings,
+    pub loudness: &'a mut crate::loudness::LoudnessMeter,
+    pub spectrogram: &'a mut crate::spectrogram::Spectrogram,
     pub current_page: &'a mut Page,
 
     // System state
@@ -61,12 +66,15 @@ pub struct ExecutionContext<'a> {
     pub load_clr: &'a mut bool,
     pub vca_mode: &'a mut bool,
     pub show_conditional_highlight: &'a mut bool,
+    pub show_syntax_highlight: &'a mut bool,
     pub current_scene_name: &'a mut Option<String>,
     pub title_mode: &'a mut u8,
     pub title_timer_enabled: &'a mut bool,
     pub title_timer_interval_secs: &'a mut u16,
     pub title_timer_last_toggle: &'a mut Option<Instant>,
     pub audio_devices: &'a [String],
+    /// Hardware outputs the engine was started with
+    pub output_channels: u8,
     pub header_scramble: &'a mut Option<crate::scramble::ScrambleAnimation>,
     pub scramble_enabled: &'a mut bool,
     pub scramble_grid_enabled: &'a mut bool,
@@ -74,6 +82,7 @@ pub struct ExecutionContext<'a> {
     pub scramble_speed: &'a mut u8,
     pub scramble_curve: &'a mut u8,
     pub ascii_meters: &'a mut bool,
+    pub mouse: &'a mut bool,
     pub autoload: &'a mut bool,
     pub terminal_caps: &'a TerminalCapabilities,
     pub color_mode: ColorMode,
@@ -82,9 +91,12 @@ pub struct ExecutionContext<'a> {
     pub script_mutes: &'a mut ScriptMutes,
     pub confirm_quit_unsaved: &'a mut bool,
     pub confirm_overwrite_scene: &'a mut bool,
+    pub script_length: &'a mut usize,
     pub scene_modified: &'a mut bool,
     pub pending_confirmation: &'a mut Option<ConfirmAction>,
     pub sampler_state: &'a mut SamplerState,
+    pub snapshots: &'a crate::snapshot::SharedSnapshots,
+    pub rng: &'a crate::rng::SharedRng,
 }
 
 impl<'a> ExecutionContext<'a> {}
@@ -1,5 +1,8 @@
+use crate::commands::context::ExecutionContext;
+use crate::commands::system::utility::{parse_script_id, script_id_label};
 use crate::eval::eval_expression;
-use crate::types::{Counters, PatternStorage, ScaleState, ScriptStorage, Variables};
+use crate::output::OutputDecider;
+use crate::types::{Counters, OutputCategory, PatternStorage, ScaleState, ScriptStorage, Variables};
 use anyhow::{Context, Result};
 use rand::Rng;
 
@@ -20,7 +23,7 @@ where
     if max <= 0 {
         output("0".to_string());
     } else {
-        let result = rand::thread_rng().gen_range(0..=max);
+        let result = crate::rng::with_rng(|rng| rng.gen_range(0..=max));
         output(format!("{}", result));
     }
     Ok(())
@@ -46,7 +49,7 @@ where
     if min > max {
         std::mem::swap(&mut min, &mut max);
     }
-    let result = rand::thread_rng().gen_range(min..=max);
+    let result = crate::rng::with_rng(|rng| rng.gen_range(min..=max));
     output(format!("{}", result));
     Ok(())
 }
@@ -56,7 +59,7 @@ pub fn handle_toss<F>(
 ) where
     F: FnMut(String),
 {
-    let result = if rand::thread_rng().gen_bool(0.5) { 1 } else { 0 };
+    let result = if crate::rng::with_rng(|rng| rng.gen_bool(0.5)) { 1 } else { 0 };
     output(format!("{}", result));
 }
 
@@ -131,3 +134,88 @@ pub fn handle_tog<F>(
         output("ERROR: FAILED TO EVALUATE FIRST VALUE".to_string());
     }
 }
+
+/// Parse a seed: a plain number (any u64), -1 for "unseeded", or an expression
+fn parse_seed(parts: &[&str], idx: usize, ctx: &mut ExecutionContext) -> Option<Option<u64>> {
+    if let Ok(seed) = parts[idx].parse::<u64>() {
+        return Some(Some(seed));
+    }
+    let (value, _) = eval_expression(
+        parts, idx, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+    )?;
+    match value {
+        -1 => Some(None),
+        v if v >= 0 => Some(Some(v as u64)),
+        _ => None,
+    }
+}
+
+fn format_seed(seed: Option<u64>) -> String {
+    match seed {
+        Some(seed) => seed.to_string(),
+        None => "OFF".to_string(),
+    }
+}
+
+pub fn handle_seed<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let seed = format_seed(ctx.rng.current_seed());
+        ctx.output(OutputCategory::Query, format!("SEED: {}", seed), &mut output);
+        return Ok(());
+    }
+
+    let Some(seed) = parse_seed(parts, 1, ctx) else {
+        ctx.output(OutputCategory::Error, "SEED: USE 0+ (-1=OFF)".to_string(), &mut output);
+        return Ok(());
+    };
+
+    ctx.rng.seed(seed);
+    ctx.output(OutputCategory::Confirm, format!("SEED: {}", format_seed(seed)), &mut output);
+    Ok(())
+}
+
+pub fn handle_seed_sc<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let seeds: Vec<String> = (0..10)
+            .filter_map(|i| ctx.rng.script_seed(i).map(|seed| format!("{}={}", script_id_label(i), seed)))
+            .collect();
+        let message = if seeds.is_empty() {
+            "SEED.SC: NONE".to_string()
+        } else {
+            format!("SEED.SC: {}", seeds.join(" "))
+        };
+        ctx.output(OutputCategory::Query, message, &mut output);
+        return Ok(());
+    }
+
+    let Some(index) = parse_script_id(parts[1]) else {
+        ctx.output(OutputCategory::Error, "SEED.SC: INVALID SCRIPT ID (USE 1-8, M, I)".to_string(), &mut output);
+        return Ok(());
+    };
+    let label = script_id_label(index);
+
+    if parts.len() < 3 {
+        let seed = format_seed(ctx.rng.script_seed(index));
+        ctx.output(OutputCategory::Query, format!("SEED.SC {}: {}", label, seed), &mut output);
+        return Ok(());
+    }
+
+    let Some(seed) = parse_seed(parts, 2, ctx) else {
+        ctx.output(OutputCategory::Error, "SEED.SC: USE 0+ (-1=SHARED)".to_string(), &mut output);
+        return Ok(());
+    };
+
+    ctx.rng.seed_script(index, seed);
+    let message = match seed {
+        Some(seed) => format!("SEED.SC {}: {}", label, seed),
+        None => format!("SEED.SC {}: SHARED", label),
+    };
+    ctx.output(OutputCategory::Confirm, message, &mut output);
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::commands::context::ExecutionContext;
 use crate::commands::core::{counters, math_ops, random_ops, scale, scheduling as delay, variables};
-use crate::commands::{gate, patterns, randomization, slew};
+use crate::commands::{gate, mutation, patterns, randomization, slew};
 use anyhow::Result;
 
 pub fn dispatch_core_commands<F>(
@@ -171,6 +171,8 @@ where
             random_ops::handle_toss(output);
             Some(Ok(vec![]))
         }
+        "SEED" => Some(random_ops::handle_seed(parts, ctx, output).map(|_| vec![])),
+        "SEED.SC" => Some(random_ops::handle_seed_sc(parts, ctx, output).map(|_| vec![])),
         "EITH" => {
             random_ops::handle_eith(parts, variables, patterns, counters, scripts, script_index, scale, output);
             Some(Ok(vec![]))
@@ -192,6 +194,11 @@ where
         "RND.FILT" => { Some(randomization::handle_rnd_filt(metro_tx, debug_level, output).map(|_| vec![])) }
         "RND.DLY" => { Some(randomization::handle_rnd_dly(metro_tx, debug_level, output).map(|_| vec![])) }
         "RND.VERB" => { Some(randomization::handle_rnd_verb(metro_tx, debug_level, output).map(|_| vec![])) }
+        "MUT" => Some(mutation::handle_mut(parts, ctx, output).map(|_| vec![])),
+        "BREED" => Some(mutation::handle_breed(parts, ctx, output).map(|_| vec![])),
+        "MUT.KEEP" => Some(mutation::handle_mut_keep(ctx, output).map(|_| vec![])),
+        "MUT.NO" => Some(mutation::handle_mut_no(ctx, output).map(|_| vec![])),
+        "MUT.BACK" => Some(mutation::handle_mut_back(ctx, output).map(|_| vec![])),
         "ADD" | "+" => {
             math_ops::handle_add(parts, variables, patterns, counters, scripts, script_index, scale, output);
             Some(Ok(vec![]))
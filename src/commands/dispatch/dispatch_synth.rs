@@ -40,6 +40,10 @@ where
         "PLTR" => Some(triggers::handle_pltr(ctx, output).map(|_| vec![])),
         "PLV" => Some(synth_params::handle_plv(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, out_cfm, output).map(|_| vec![])),
         "TR" => Some(triggers::handle_tr(ctx, output).map(|_| vec![])),
+        "SNAP.SAVE" => Some(synth_params::handle_snap_save(parts, ctx, output).map(|_| vec![])),
+        "SNAP.LOAD" => Some(synth_params::handle_snap_load(parts, ctx, output).map(|_| vec![])),
+        "SNAP.MORPH" => Some(synth_params::handle_snap_morph(parts, ctx, output).map(|_| vec![])),
+        "SNAP.GLIDE" => Some(synth_params::handle_snap_glide(parts, ctx, output).map(|_| vec![])),
         "VOL" | "OUT.VOL" => Some(triggers::handle_vol(parts, ctx, output).map(|_| vec![])),
         "PF" | "POSC.FREQ" => Some(synth_params::handle_pf(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, out_cfm, output).map(|_| vec![])),
         "PW" | "POSC.WAVE" => Some(synth_params::handle_pw(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, out_cfm, output).map(|_| vec![])),
@@ -151,6 +155,13 @@ where
         "S.SLICE" | "SSLC" => Some(synth_params::handle_s_slice(parts, variables, patterns, counters, scripts, script_index, debug_level, scale, &mut *ctx.sampler_state, out_cfm, output).map(|_| vec![])),
         "S.ONSET" | "SONS" => Some(synth_params::handle_s_onset(parts, &mut *ctx.sampler_state, debug_level, out_cfm, output).map(|_| vec![])),
         "S.ONSET.MIN" | "SOMIN" => Some(synth_params::handle_s_onset_min(parts, &mut *ctx.sampler_state, debug_level, out_cfm, output).map(|_| vec![])),
+        "S.MARK" | "SMK" => Some(synth_params::handle_s_mark(parts, ctx, output).map(|_| vec![])),
+        "S.MARK.MV" | "SMMV" => Some(synth_params::handle_s_mark_mv(parts, ctx, output).map(|_| vec![])),
+        "S.UNMARK" | "SUMK" => Some(synth_params::handle_s_unmark(parts, ctx, output).map(|_| vec![])),
+        "S.SNAP" | "SSNP" => Some(synth_params::handle_s_snap(parts, ctx, output).map(|_| vec![])),
+        "S.EXPORT" | "SEXP" => Some(synth_params::handle_s_export(parts, ctx, output).map(|_| vec![])),
+        "S.STRETCH" | "SSTR" => Some(synth_params::handle_s_stretch(parts, ctx, output).map(|_| vec![])),
+        "S.BPM" | "SBPM" => Some(synth_params::handle_s_bpm(parts, ctx, output).map(|_| vec![])),
         "S.ATK" | "SA" => Some(synth_params::handle_s_atk(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, &mut *ctx.sampler_state, out_cfm, output).map(|_| vec![])),
         "S.DEC" | "SDC" => Some(synth_params::handle_s_dec(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, &mut *ctx.sampler_state, out_cfm, output).map(|_| vec![])),
         "S.REL" | "SRE" => Some(synth_params::handle_s_rel(parts, variables, patterns, counters, scripts, script_index, metro_tx, debug_level, scale, &mut *ctx.sampler_state, out_cfm, output).map(|_| vec![])),
@@ -2,7 +2,7 @@ use crate::commands::context::ExecutionContext;
 use crate::commands::logging::log_command;
 use crate::commands::system::{
     config as config_cmds, display, metro as metro_cmds, midi as midi_cmds,
-    preset as preset_cmds, recording, scene as scene_cmds, sc as sc_cmds, triggers, utility,
+    patch as patch_cmds, preset as preset_cmds, react, recording, scene as scene_cmds, sc as sc_cmds, triggers, utility,
 };
 use crate::config;
 use anyhow::Result;
@@ -44,7 +44,9 @@ where
     let show_grid = &mut *ctx.show_grid;
     let show_grid_view = &mut *ctx.show_grid_view;
     let show_seq_highlight = &mut *ctx.show_seq_highlight;
+    let show_syntax_highlight = &mut *ctx.show_syntax_highlight;
     let grid_mode = &mut *ctx.grid_mode;
+    let grid_pages = ctx.grid_pages;
     let rec_spinner_type = &mut *ctx.rec_spinner_type;
     let current_scene_name = &mut *ctx.current_scene_name;
     let title_mode = &mut *ctx.title_mode;
@@ -62,6 +64,7 @@ where
     let scramble_speed = &mut *ctx.scramble_speed;
     let scramble_curve = &mut *ctx.scramble_curve;
     let ascii_meters = &mut *ctx.ascii_meters;
+    let mouse = &mut *ctx.mouse;
     let autoload = &mut *ctx.autoload;
     let terminal_caps = ctx.terminal_caps;
     let color_mode = ctx.color_mode;
@@ -79,15 +82,24 @@ where
         "M.SYNC" => Some(metro_cmds::handle_m_sync(parts, ctx, output).map(|_| vec![])),
         "MIDI.IN" | "MIDI" => Some(midi_cmds::handle_midi_in(parts, ctx, output).map(|_| vec![])),
         "MIDI.DIAG" => Some(midi_cmds::handle_midi_diag(parts, ctx, output).map(|_| vec![])),
+        "LVL" | "LVL.IN" | "LVL.OSC" | "LVL.PLA" | "LVL.NOS" | "LVL.SMP" | "BAND" | "GR" => Some(react::handle_level_query(parts, ctx, output).map(|_| vec![])),
+        "ON.SCRIPT" => Some(react::handle_on_script(parts, ctx, output).map(|_| vec![])),
+        "ON.SRC" => Some(react::handle_on_src(parts, ctx, output).map(|_| vec![])),
+        "ON.THR" => Some(react::handle_on_thr(parts, ctx, output).map(|_| vec![])),
         "SC.DIAG" => Some(sc_cmds::handle_sc_diag(parts, ctx, output).map(|_| vec![])),
         "AUDIO.OUT" | "AUDIO" => Some(crate::commands::system::handle_audio_out(parts, metro_tx, audio_devices, output).map(|_| vec![])),
+        "OUT.CH" => Some(crate::commands::system::handle_out_ch(parts, ctx, output).map(|_| vec![])),
+        "OUT.ROUTE" => Some(crate::commands::system::handle_out_route(parts, ctx, output).map(|_| vec![])),
+        "LUFS" => Some(crate::commands::system::handle_lufs(parts, ctx, output).map(|_| vec![])),
+        "LUFS.RST" => Some(crate::commands::system::handle_lufs_rst(parts, ctx, output).map(|_| vec![])),
+        "LUFS.TARGET" => Some(crate::commands::system::handle_lufs_target(parts, ctx, output).map(|_| vec![])),
         "RST" => Some(triggers::handle_rst(ctx, output, 0).map(|_| vec![])),
         "SCRIPT" | "$" => {
             log_command(&format!("CMD: {} → DISPATCHED", input.trim()));
             Some(utility::handle_script(parts, variables, patterns, counters, scripts, script_index, scale))
         }
         "SAVE" => {
-            scene_cmds::handle_save(parts, scripts, patterns, notes, current_scene_name, *scramble_enabled, *scramble_mode, *scramble_speed, *scramble_curve, header_scramble, *debug_level, *out_ess, &*ctx.script_mutes, *ctx.confirm_overwrite_scene, &mut *ctx.pending_confirmation, &mut *ctx.scene_modified, &*ctx.sampler_state, output);
+            scene_cmds::handle_save(parts, scripts, patterns, notes, current_scene_name, *scramble_enabled, *scramble_mode, *scramble_speed, *scramble_curve, header_scramble, *debug_level, *out_ess, &*ctx.script_mutes, *ctx.confirm_overwrite_scene, &mut *ctx.pending_confirmation, &mut *ctx.scene_modified, &*ctx.sampler_state, ctx.snapshots, ctx.rng, output);
             Some(Ok(vec![]))
         }
         "LOAD" => {
@@ -97,12 +109,14 @@ where
                 }
                 std::thread::sleep(std::time::Duration::from_millis(160));
             }
-            if scene_cmds::handle_load(parts, &mut *ctx.variables, &mut *ctx.scripts, &mut *ctx.patterns, &mut *ctx.notes, &mut *ctx.current_scene_name, *ctx.scramble_enabled, *ctx.scramble_mode, *ctx.scramble_speed, *ctx.scramble_curve, &mut *ctx.header_scramble, *ctx.debug_level, *ctx.out_ess, &mut *ctx.script_mutes, &mut *ctx.scene_modified, &mut *ctx.sampler_state, output) {
+            if scene_cmds::handle_load(parts, &mut *ctx.variables, &mut *ctx.scripts, &mut *ctx.patterns, &mut *ctx.notes, &mut *ctx.current_scene_name, *ctx.scramble_enabled, *ctx.scramble_mode, *ctx.scramble_speed, *ctx.scramble_curve, &mut *ctx.header_scramble, *ctx.debug_level, *ctx.out_ess, &mut *ctx.script_mutes, &mut *ctx.scene_modified, &mut *ctx.sampler_state, ctx.snapshots, ctx.rng, output) {
                 log_command(&format!("CMD: {} → DISPATCHED", input.trim()));
                 return Some(Ok(vec![9]));
             }
             Some(Ok(vec![]))
         }
+        "IMPORT.TT" => Some(scene_cmds::handle_import_tt(parts, ctx, output).map(|loaded| if loaded { vec![9] } else { vec![] })),
+        "EXPORT.TT" => Some(scene_cmds::handle_export_tt(parts, ctx, output).map(|_| vec![])),
         "LOAD.RST" => {
             config_cmds::handle_load_rst(parts, load_rst, *debug_level, output);
             Some(Ok(vec![]))
@@ -139,6 +153,13 @@ where
             preset_cmds::handle_psets(*debug_level, *out_qry, output);
             Some(Ok(vec![]))
         }
+        "PATCH" => Some(patch_cmds::handle_patch(parts, ctx, output).map(|_| vec![])),
+        "PATCH.SAVE" => Some(patch_cmds::handle_patch_save(parts, ctx, output).map(|_| vec![])),
+        "PATCH.DIFF" => Some(patch_cmds::handle_patch_diff(parts, ctx, output).map(|_| vec![])),
+        "PATCH.DEL" => Some(patch_cmds::handle_patch_del(parts, ctx, output).map(|_| vec![])),
+        "PATCH.EXPORT" => Some(patch_cmds::handle_patch_export(parts, ctx, output).map(|_| vec![])),
+        "PATCH.IMPORT" => Some(patch_cmds::handle_patch_import(parts, ctx, output).map(|_| vec![])),
+        "PATCHES" => Some(patch_cmds::handle_patches(ctx, output).map(|_| vec![])),
         "THEME" => {
             utility::handle_theme(parts, theme, color_mode, output);
             Some(Ok(vec![]))
@@ -154,6 +175,8 @@ where
         "REC" => Some(recording::handle_rec(metro_tx).map(|_| vec![])),
         "REC.STOP" => Some(recording::handle_rec_stop(metro_tx).map(|_| vec![])),
         "REC.PATH" => Some(recording::handle_rec_path(parts, metro_tx, *debug_level, *out_cfm, output).map(|_| vec![])),
+        "REC.STEMS" => Some(recording::handle_rec_stems(parts, ctx, output).map(|_| vec![])),
+        "REC.STEMS.STOP" => Some(recording::handle_rec_stems_stop(parts, ctx, output).map(|_| vec![])),
         "PRINT" => {
             utility::handle_print(parts, variables, patterns, counters, scripts, script_index, scale, *debug_level, *out_ess, output);
             Some(Ok(vec![]))
@@ -190,6 +213,19 @@ where
         }
         "SCOPE.GAIN" | "SCG" => Some(display::handle_scope_gain(parts, scope_settings, metro_tx, variables, patterns, counters, scripts, script_index, scale, output).map(|_| vec![])),
         "SCOPE.RST" | "SCR" => Some(display::handle_scope_rst(scope_settings, metro_tx, output).map(|_| vec![])),
+        "SCOPE.TRIG" => Some(display::handle_scope_trig(parts, ctx, output).map(|_| vec![])),
+        "SCOPE.LVL" => Some(display::handle_scope_lvl(parts, ctx, output).map(|_| vec![])),
+        "SCOPE.HOLD" => Some(display::handle_scope_hold(parts, ctx, output).map(|_| vec![])),
+        "SCOPE.ARM" => Some(display::handle_scope_arm(parts, ctx, output).map(|_| vec![])),
+        "SCOPE.XY" => Some(display::handle_scope_xy(parts, ctx, output).map(|_| vec![])),
+        "SCOPE.SRC" => Some(display::handle_scope_src(parts, ctx, output).map(|_| vec![])),
+        "SPEC.BANDS" => Some(display::handle_spec_bands(parts, ctx, output).map(|_| vec![])),
+        "SPEC.SCALE" => Some(display::handle_spec_scale(parts, ctx, output).map(|_| vec![])),
+        "SPEC.MODE" => Some(display::handle_spec_mode(parts, ctx, output).map(|_| vec![])),
+        "SPEC.DISP" => Some(display::handle_spec_disp(parts, ctx, output).map(|_| vec![])),
+        "SPEC.FREEZE" => Some(display::handle_spec_freeze(parts, ctx, output).map(|_| vec![])),
+        "SPEC.SNAP" => Some(display::handle_spec_snap(parts, ctx, output).map(|_| vec![])),
+        "SPEC.SNAP.CLR" => Some(display::handle_spec_snap_clr(parts, ctx, output).map(|_| vec![])),
         "NOTE" => {
             display::handle_note(parts, notes, *debug_level, *out_cfm, output);
             Some(Ok(vec![]))
@@ -198,6 +234,7 @@ where
             display::handle_note_clr(notes, *debug_level, *out_cfm, output);
             Some(Ok(vec![]))
         }
+        "SCRIPT.LEN" => Some(config_cmds::handle_script_len(parts, ctx, output).map(|_| vec![])),
         "FLASH" => {
             if parts.len() < 2 {
                 output(format!("FLASH HOLD: {}ms", *activity_hold_ms as u32));
@@ -236,6 +273,10 @@ where
             display::handle_hl_seq(parts, show_seq_highlight, *debug_level, output);
             Some(Ok(vec![]))
         }
+        "HL.SYN" => {
+            display::handle_hl_syn(parts, show_syntax_highlight, *debug_level, output);
+            Some(Ok(vec![]))
+        }
         "METER.HDR" => {
             display::handle_meter_hdr(parts, show_meters_header, *debug_level, output);
             Some(Ok(vec![]))
@@ -248,6 +289,10 @@ where
             display::handle_meter_ascii(parts, ascii_meters, *debug_level, output);
             Some(Ok(vec![]))
         }
+        "MOUSE" => {
+            display::handle_mouse(parts, mouse, *debug_level, output);
+            Some(Ok(vec![]))
+        }
         "SPECTRUM" => {
             display::handle_spectrum(parts, show_spectrum, *debug_level, output);
             Some(Ok(vec![]))
@@ -265,7 +310,7 @@ where
             Some(Ok(vec![]))
         }
         "GRID.MODE" => {
-            display::handle_grid_mode(parts, grid_mode, *debug_level, output);
+            display::handle_grid_mode(parts, grid_mode, grid_pages, *debug_level, output);
             Some(Ok(vec![]))
         }
         "REC.SPINNER" => {
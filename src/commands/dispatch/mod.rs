@@ -23,6 +23,8 @@ where
         return Ok(vec![]);
     }
 
+    let _rng_stream = crate::rng::enter_script(ctx.rng, ctx.script_index);
+
     let parts_owned = split_whitespace_respecting_quotes(trimmed);
     let parts: Vec<&str> = parts_owned.iter().map(|s| s.as_str()).collect();
     let cmd = parts[0].to_uppercase();
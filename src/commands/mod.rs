@@ -5,6 +5,7 @@ mod core;
 mod dispatch;
 mod gate;
 mod logging;
+pub mod mutation;
 mod patterns;
 pub mod randomization;
 pub mod registry;
@@ -22,7 +23,7 @@ pub use core::scale;
 pub use core::scheduling as delay;
 
 // Re-export public API
-pub use aliases::resolve_alias;
+pub use aliases::{resolve_alias, resolve_to_short};
 pub use dispatch::process_command;
 
 #[cfg(test)]
@@ -0,0 +1,115 @@
+//! MUT / BREED: evolve the current sound instead of rolling a new one like RND.*.
+//! The work happens in the metro thread (see `SnapshotBank::mutate`) so it sees
+//! params set earlier on the same line; the seed is drawn here so SEED and
+//! SEED.SC make a mutation reproducible.
+
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::evolve::MUT_MAX;
+use crate::output::OutputDecider;
+use crate::preset::patch::PatchGroup;
+use crate::snapshot::{slot_in_range, SNAP_SLOTS};
+use crate::types::{MetroCommand, OutputCategory};
+use anyhow::{Context, Result};
+use rand::Rng;
+
+fn draw_seed() -> u64 {
+    crate::rng::with_rng(|rng| rng.gen())
+}
+
+/// MUT <amount 0-100> [OSC|PL|SMP|FX|ALL]
+pub fn handle_mut<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some((amount, consumed)) = eval_expression(
+        parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+    ) else {
+        ctx.output(OutputCategory::Error, "MUT: NEEDS AMOUNT 0-100".to_string(), &mut output);
+        return Ok(());
+    };
+    let amount = amount as i32;
+    if !(0..=MUT_MAX).contains(&amount) {
+        ctx.output(OutputCategory::Error, format!("MUT: AMOUNT 0-{}", MUT_MAX), &mut output);
+        return Ok(());
+    }
+    let group = match parts.get(1 + consumed.max(1)) {
+        Some(text) => match PatchGroup::parse(text) {
+            Some(group) => group,
+            None => {
+                ctx.output(OutputCategory::Error, "MUT: GROUP MUST BE OSC, PL, SMP, FX OR ALL".to_string(), &mut output);
+                return Ok(());
+            }
+        },
+        None => PatchGroup::All,
+    };
+
+    ctx.metro_tx
+        .send(MetroCommand::Mutate(amount, group, draw_seed()))
+        .context("Failed to send mutation to metro thread")?;
+    ctx.output(OutputCategory::Confirm, format!("MUT {} {}", amount, group.label()), &mut output);
+    Ok(())
+}
+
+/// BREED <a> <b> - cross two snapshot slots into a new candidate
+pub fn handle_breed<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let mut slots = Vec::with_capacity(2);
+    let mut idx = 1;
+    for _ in 0..2 {
+        let Some((slot, consumed)) = eval_expression(
+            parts, idx, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+        ) else {
+            ctx.output(OutputCategory::Error, "BREED: NEEDS TWO SLOTS".to_string(), &mut output);
+            return Ok(());
+        };
+        slots.push(slot as i32);
+        idx += consumed.max(1);
+    }
+    let (a, b) = (slots[0], slots[1]);
+    if !slot_in_range(a) || !slot_in_range(b) {
+        ctx.output(OutputCategory::Error, format!("BREED: SLOT 1-{}", SNAP_SLOTS), &mut output);
+        return Ok(());
+    }
+
+    ctx.metro_tx
+        .send(MetroCommand::Breed(a as u8, b as u8, draw_seed()))
+        .context("Failed to send breed to metro thread")?;
+    ctx.output(OutputCategory::Confirm, format!("BREED {} X {}", a, b), &mut output);
+    Ok(())
+}
+
+pub fn handle_mut_keep<F>(ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.metro_tx
+        .send(MetroCommand::MutKeep)
+        .context("Failed to send keep to metro thread")?;
+    ctx.output(OutputCategory::Confirm, "MUT KEPT".to_string(), &mut output);
+    Ok(())
+}
+
+pub fn handle_mut_no<F>(ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.metro_tx
+        .send(MetroCommand::MutDiscard)
+        .context("Failed to send discard to metro thread")?;
+    ctx.output(OutputCategory::Confirm, "MUT DISCARDED".to_string(), &mut output);
+    Ok(())
+}
+
+pub fn handle_mut_back<F>(ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.metro_tx
+        .send(MetroCommand::MutBack)
+        .context("Failed to send undo to metro thread")?;
+    ctx.output(OutputCategory::Confirm, "MUT BACK".to_string(), &mut output);
+    Ok(())
+}
@@ -190,7 +190,7 @@ pub fn pattern_shuf_impl(pat_ref: PatternRef, patterns: &mut PatternStorage) ->
         return Err("CANNOT OPERATE ON EMPTY PAT");
     }
     let len = pattern.length;
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
     pattern.data[..len].shuffle(&mut rng);
     Ok(pat_idx)
 }
@@ -206,7 +206,7 @@ pub fn pattern_sort_impl(pat_ref: PatternRef, patterns: &mut PatternStorage) ->
 pub fn pattern_rnd_impl(pat_ref: PatternRef, min: i16, max: i16, patterns: &mut PatternStorage) -> usize {
     let pat_idx = pat_ref.index(patterns);
     let pattern = &mut patterns.patterns[pat_idx];
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
     for i in 0..pattern.length {
         pattern.data[i] = rng.gen_range(min..=max);
     }
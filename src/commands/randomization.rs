@@ -13,7 +13,7 @@ pub fn handle_rnd_voice<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let pf = rng.gen_range(50.0..=2000.0);
     let pw = rng.gen_range(0..=2);
@@ -48,7 +48,7 @@ pub fn handle_rnd_osc<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let pf = rng.gen_range(50.0..=2000.0);
     let pw = rng.gen_range(0..=2);
@@ -75,7 +75,7 @@ pub fn handle_rnd_fm<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let fm = rng.gen_range(0..=8000);
     let fb = rng.gen_range(0..=4000);
@@ -102,7 +102,7 @@ pub fn handle_rnd_mod<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let mb = rng.gen_range(0..=8000);
     let tk = rng.gen_range(0..=8000);
@@ -133,7 +133,7 @@ pub fn handle_rnd_env<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let env_atk = rng.gen_range(5..=2000);
     let env_dec = rng.gen_range(5..=2000);
@@ -199,7 +199,7 @@ where
         std::mem::swap(&mut min, &mut max);
     }
     let pattern = &mut patterns.patterns[patterns.working];
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
     for i in 0..pattern.length {
         pattern.data[i] = rng.gen_range(min..=max);
     }
@@ -267,7 +267,7 @@ where
         std::mem::swap(&mut min, &mut max);
     }
     let pattern = &mut patterns.patterns[pat];
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
     for i in 0..pattern.length {
         pattern.data[i] = rng.gen_range(min..=max);
     }
@@ -316,7 +316,7 @@ where
     if min > max {
         std::mem::swap(&mut min, &mut max);
     }
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
     for pat_idx in 0..6 {
         let pattern = &mut patterns.patterns[pat_idx];
         for i in 0..pattern.length {
@@ -340,7 +340,7 @@ pub fn handle_rnd_fx<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     // Filter
     let fc = rng.gen_range(200.0..=8000.0);
@@ -444,7 +444,7 @@ pub fn handle_rnd_filt<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let fc = rng.gen_range(200.0..=8000.0);
     metro_tx.send(MetroCommand::SendParam("fc".to_string(), OscType::Float(fc)))?;
@@ -474,7 +474,7 @@ pub fn handle_rnd_dly<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let dt = rng.gen_range(50..=500);
     metro_tx.send(MetroCommand::SendParam("dt".to_string(), OscType::Int(dt)))?;
@@ -504,7 +504,7 @@ pub fn handle_rnd_verb<F>(
 where
     F: FnMut(String),
 {
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let rv = rng.gen_range(0..=10000);
     metro_tx.send(MetroCommand::SendParam("rv".to_string(), OscType::Int(rv)))?;
@@ -535,7 +535,7 @@ where
     F: FnMut(String),
 {
     use crate::types::PLAITS_NODE_ID;
-    let mut rng = rand::thread_rng();
+    let mut rng = crate::rng::fork();
 
     let engine = rng.gen_range(0..=15);
     let harmonics = rng.gen_range(0..=16383);
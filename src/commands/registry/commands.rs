@@ -30,6 +30,7 @@ pub static COMMAND_REGISTRY: Lazy<HashMap<&'static str, CommandDef>> = Lazy::new
                 args: def.args.clone(),
                 help: def.help,
                 special_validation: def.special_validation,
+                range: def.range,
             };
             // Only insert if not already present (avoid overwriting explicit entries)
             m.entry(canonical).or_insert(canonical_def);
@@ -38,3 +39,20 @@ pub static COMMAND_REGISTRY: Lazy<HashMap<&'static str, CommandDef>> = Lazy::new
 
     m
 });
+
+/// Synth parameter ranges from the registry, keyed by OSC parameter name
+static PARAM_RANGES: Lazy<HashMap<&'static str, (f32, f32)>> = Lazy::new(|| {
+    let mut ranges: HashMap<&'static str, (f32, f32)> = HashMap::new();
+    for range in COMMAND_REGISTRY.values().filter_map(|def| def.range) {
+        // Two commands can set the same param (MC and MFF both set mf_f); keep the widest range
+        let entry = ranges.entry(range.param).or_insert((range.min, range.max));
+        entry.0 = entry.0.min(range.min);
+        entry.1 = entry.1.max(range.max);
+    }
+    ranges
+});
+
+/// Valid (min, max) of a synth parameter, if a registry command declares one
+pub fn param_range(param: &str) -> Option<(f32, f32)> {
+    PARAM_RANGES.get(param).copied()
+}
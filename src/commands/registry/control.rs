@@ -35,6 +35,8 @@ pub fn register_control(m: &mut std::collections::HashMap<&'static str, CommandD
     m.insert("TOSS", CommandDef::new("TOSS", None, ArgCount::None, "Random 0 or 1"));
     m.insert("EITH", CommandDef::new("EITH", None, ArgCount::AtLeast(2), "Either value"));
     m.insert("TOG", CommandDef::new("TOG", None, ArgCount::AtLeast(2), "Toggle between values"));
+    m.insert("SEED", CommandDef::new("SEED", None, ArgCount::AtLeast(0), "Seed random ops (-1=off)"));
+    m.insert("SEED.SC", CommandDef::new("SEED.SC", None, ArgCount::AtLeast(0), "Per-script random stream"));
 
     // Randomization
     m.insert("RND.VOICE", CommandDef::new("RND.VOICE", None, ArgCount::None, "Randomize voice"));
@@ -51,6 +53,13 @@ pub fn register_control(m: &mut std::collections::HashMap<&'static str, CommandD
     m.insert("RND.DLY", CommandDef::new("RND.DLY", None, ArgCount::None, "Randomize delay"));
     m.insert("RND.VERB", CommandDef::new("RND.VERB", None, ArgCount::None, "Randomize reverb"));
 
+    // Evolution
+    m.insert("MUT", CommandDef::new("MUT", None, ArgCount::AtLeast(1), "Mutate sound 0-100 [OSC|PL|SMP|FX]"));
+    m.insert("BREED", CommandDef::new("BREED", None, ArgCount::AtLeast(2), "Cross snapshot slots A and B"));
+    m.insert("MUT.KEEP", CommandDef::new("MUT.KEEP", None, ArgCount::None, "Keep mutated sound"));
+    m.insert("MUT.NO", CommandDef::new("MUT.NO", None, ArgCount::None, "Discard mutation, back to parent"));
+    m.insert("MUT.BACK", CommandDef::new("MUT.BACK", None, ArgCount::None, "Undo last MUT.KEEP"));
+
     // Comparisons
     m.insert("EZ", CommandDef::new("EZ", None, ArgCount::AtLeast(1), "Equal to zero"));
     m.insert("NZ", CommandDef::new("NZ", None, ArgCount::AtLeast(1), "Not zero"));
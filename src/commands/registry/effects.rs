@@ -5,50 +5,50 @@ use super::{ArgCount, CommandDef};
 pub fn register_effects(m: &mut std::collections::HashMap<&'static str, CommandDef>) {
     // Delay
     m.insert("DT", CommandDef::new("DT", Some("DLY.TIME"), ArgCount::AtLeast(1), "Delay time"));
-    m.insert("DF", CommandDef::new("DF", Some("DLY.FB"), ArgCount::AtLeast(1), "Delay feedback"));
-    m.insert("DLP", CommandDef::new("DLP", Some("DLY.LP"), ArgCount::AtLeast(1), "Delay lowpass"));
-    m.insert("DW", CommandDef::new("DW", Some("DLY.WET"), ArgCount::AtLeast(1), "Delay wet"));
+    m.insert("DF", CommandDef::new("DF", Some("DLY.FB"), ArgCount::AtLeast(1), "Delay feedback").with_range("df", 0.0, 16383.0));
+    m.insert("DLP", CommandDef::new("DLP", Some("DLY.LP"), ArgCount::AtLeast(1), "Delay lowpass").with_range("dlp", 100.0, 20000.0));
+    m.insert("DW", CommandDef::new("DW", Some("DLY.WET"), ArgCount::AtLeast(1), "Delay wet").with_range("dw", 0.0, 16383.0));
     m.insert("DS", CommandDef::new("DS", Some("DLY.SYN"), ArgCount::AtLeast(1), "Delay sync"));
-    m.insert("D.MODE", CommandDef::new("D.MODE", Some("DLY.MODE"), ArgCount::AtLeast(1), "Delay mode (0-2)"));
-    m.insert("D.TAIL", CommandDef::new("D.TAIL", Some("DLY.TAIL"), ArgCount::AtLeast(1), "Delay tail (0-2)"));
+    m.insert("D.MODE", CommandDef::new("D.MODE", Some("DLY.MODE"), ArgCount::AtLeast(1), "Delay mode (0-2)").with_range("dmode", 0.0, 2.0));
+    m.insert("D.TAIL", CommandDef::new("D.TAIL", Some("DLY.TAIL"), ArgCount::AtLeast(1), "Delay tail (0-2)").with_range("dtail", 0.0, 2.0));
 
     // Reverb
-    m.insert("RV", CommandDef::new("RV", Some("REV.DEC"), ArgCount::AtLeast(1), "Reverb decay"));
-    m.insert("RP", CommandDef::new("RP", Some("REV.PRE"), ArgCount::AtLeast(1), "Reverb predelay"));
-    m.insert("RH", CommandDef::new("RH", Some("REV.DMP"), ArgCount::AtLeast(1), "Reverb damping"));
-    m.insert("RW", CommandDef::new("RW", Some("REV.WET"), ArgCount::AtLeast(1), "Reverb wet"));
-    m.insert("R.MODE", CommandDef::new("R.MODE", Some("REV.MODE"), ArgCount::AtLeast(1), "Reverb mode (0-2)"));
-    m.insert("R.TAIL", CommandDef::new("R.TAIL", Some("REV.TAIL"), ArgCount::AtLeast(1), "Reverb tail (0-2)"));
+    m.insert("RV", CommandDef::new("RV", Some("REV.DEC"), ArgCount::AtLeast(1), "Reverb decay").with_range("rv", 0.0, 16383.0));
+    m.insert("RP", CommandDef::new("RP", Some("REV.PRE"), ArgCount::AtLeast(1), "Reverb predelay").with_range("rp", 0.0, 100.0));
+    m.insert("RH", CommandDef::new("RH", Some("REV.DMP"), ArgCount::AtLeast(1), "Reverb damping").with_range("rh", 0.0, 16383.0));
+    m.insert("RW", CommandDef::new("RW", Some("REV.WET"), ArgCount::AtLeast(1), "Reverb wet").with_range("rw", 0.0, 16383.0));
+    m.insert("R.MODE", CommandDef::new("R.MODE", Some("REV.MODE"), ArgCount::AtLeast(1), "Reverb mode (0-2)").with_range("rmode", 0.0, 2.0));
+    m.insert("R.TAIL", CommandDef::new("R.TAIL", Some("REV.TAIL"), ArgCount::AtLeast(1), "Reverb tail (0-2)").with_range("rtail", 0.0, 2.0));
 
     // Lo-Fi
-    m.insert("LB", CommandDef::new("LB", Some("LOFI.BIT"), ArgCount::AtLeast(1), "Lo-fi bit depth"));
-    m.insert("LS", CommandDef::new("LS", Some("LOFI.SMP"), ArgCount::AtLeast(1), "Lo-fi sample rate"));
-    m.insert("LM", CommandDef::new("LM", Some("LOFI.MIX"), ArgCount::AtLeast(1), "Lo-fi mix"));
+    m.insert("LB", CommandDef::new("LB", Some("LOFI.BIT"), ArgCount::AtLeast(1), "Lo-fi bit depth").with_range("lb", 1.0, 16.0));
+    m.insert("LS", CommandDef::new("LS", Some("LOFI.SMP"), ArgCount::AtLeast(1), "Lo-fi sample rate").with_range("ls", 100.0, 48000.0));
+    m.insert("LM", CommandDef::new("LM", Some("LOFI.MIX"), ArgCount::AtLeast(1), "Lo-fi mix").with_range("lm", 0.0, 16383.0));
 
     // Ring Modulator
-    m.insert("RGF", CommandDef::new("RGF", Some("RING.FRQ"), ArgCount::AtLeast(1), "Ring mod frequency"));
-    m.insert("RGW", CommandDef::new("RGW", Some("RING.WAV"), ArgCount::AtLeast(1), "Ring mod waveform"));
-    m.insert("RGM", CommandDef::new("RGM", Some("RING.MIX"), ArgCount::AtLeast(1), "Ring mod mix"));
+    m.insert("RGF", CommandDef::new("RGF", Some("RING.FRQ"), ArgCount::AtLeast(1), "Ring mod frequency").with_range("rgf", 20.0, 2000.0));
+    m.insert("RGW", CommandDef::new("RGW", Some("RING.WAV"), ArgCount::AtLeast(1), "Ring mod waveform").with_range("rgw", 0.0, 3.0));
+    m.insert("RGM", CommandDef::new("RGM", Some("RING.MIX"), ArgCount::AtLeast(1), "Ring mod mix").with_range("rgm", 0.0, 16383.0));
 
     // Compressor
-    m.insert("CT", CommandDef::new("CT", Some("COMP.THR"), ArgCount::AtLeast(1), "Compressor threshold"));
-    m.insert("CR", CommandDef::new("CR", Some("COMP.RAT"), ArgCount::AtLeast(1), "Compressor ratio"));
-    m.insert("CA", CommandDef::new("CA", Some("COMP.ATK"), ArgCount::AtLeast(1), "Compressor attack"));
-    m.insert("CL", CommandDef::new("CL", Some("COMP.REL"), ArgCount::AtLeast(1), "Compressor release"));
-    m.insert("CM", CommandDef::new("CM", Some("COMP.MKP"), ArgCount::AtLeast(1), "Compressor makeup"));
-    m.insert("CR.MIX", CommandDef::new("CR.MIX", Some("COMP.MIX"), ArgCount::AtLeast(1), "Compressor mix"));
+    m.insert("CT", CommandDef::new("CT", Some("COMP.THR"), ArgCount::AtLeast(1), "Compressor threshold").with_range("ct", 0.0, 16383.0));
+    m.insert("CR", CommandDef::new("CR", Some("COMP.RAT"), ArgCount::AtLeast(1), "Compressor ratio").with_range("cr", 1.0, 20.0));
+    m.insert("CA", CommandDef::new("CA", Some("COMP.ATK"), ArgCount::AtLeast(1), "Compressor attack").with_range("ca", 1.0, 500.0));
+    m.insert("CL", CommandDef::new("CL", Some("COMP.REL"), ArgCount::AtLeast(1), "Compressor release").with_range("cl", 10.0, 2000.0));
+    m.insert("CM", CommandDef::new("CM", Some("COMP.MKP"), ArgCount::AtLeast(1), "Compressor makeup").with_range("cm", 0.0, 16383.0));
+    m.insert("CR.MIX", CommandDef::new("CR.MIX", Some("COMP.MIX"), ArgCount::AtLeast(1), "Compressor mix").with_range("cr_mix", 0.0, 16383.0));
     m.insert("CRMIX", CommandDef::new("CRMIX", Some("COMP.MIX"), ArgCount::AtLeast(1), "Compressor mix"));
     m.insert("COMP.AUTO", CommandDef::new("COMP.AUTO", Some("CAU"), ArgCount::Range(0, 1), "Compressor auto-makeup"));
     m.insert("CAU", CommandDef::new("CAU", Some("COMP.AUTO"), ArgCount::Range(0, 1), "Compressor auto-makeup"));
 
     // EQ
-    m.insert("EL", CommandDef::new("EL", Some("EQ.LOW"), ArgCount::AtLeast(1), "EQ low band"));
-    m.insert("ELF", CommandDef::new("ELF", Some("EQ.LF"), ArgCount::AtLeast(1), "EQ low shelf freq"));
-    m.insert("EM", CommandDef::new("EM", Some("EQ.MID"), ArgCount::AtLeast(1), "EQ mid band"));
-    m.insert("EH", CommandDef::new("EH", Some("EQ.HI"), ArgCount::AtLeast(1), "EQ high band"));
-    m.insert("EHF", CommandDef::new("EHF", Some("EQ.HF"), ArgCount::AtLeast(1), "EQ high shelf freq"));
-    m.insert("EF", CommandDef::new("EF", Some("EQ.FRQ"), ArgCount::AtLeast(1), "EQ mid frequency"));
-    m.insert("EQ", CommandDef::new("EQ", None, ArgCount::AtLeast(1), "EQ mid Q"));
+    m.insert("EL", CommandDef::new("EL", Some("EQ.LOW"), ArgCount::AtLeast(1), "EQ low band").with_range("el", -24.0, 24.0));
+    m.insert("ELF", CommandDef::new("ELF", Some("EQ.LF"), ArgCount::AtLeast(1), "EQ low shelf freq").with_range("elf", 20.0, 2000.0));
+    m.insert("EM", CommandDef::new("EM", Some("EQ.MID"), ArgCount::AtLeast(1), "EQ mid band").with_range("em", -24.0, 24.0));
+    m.insert("EH", CommandDef::new("EH", Some("EQ.HI"), ArgCount::AtLeast(1), "EQ high band").with_range("eh", -24.0, 24.0));
+    m.insert("EHF", CommandDef::new("EHF", Some("EQ.HF"), ArgCount::AtLeast(1), "EQ high shelf freq").with_range("ehf", 1000.0, 20000.0));
+    m.insert("EF", CommandDef::new("EF", Some("EQ.FRQ"), ArgCount::AtLeast(1), "EQ mid frequency").with_range("ef", 200.0, 8000.0));
+    m.insert("EQ", CommandDef::new("EQ", None, ArgCount::AtLeast(1), "EQ mid Q").with_range("eq", 0.1, 10.0));
 
     // Beat Repeat
     m.insert("BRL", CommandDef::new("BRL", Some("BR.LEN"), ArgCount::AtLeast(1), "Beat repeat length"));
@@ -58,7 +58,7 @@ pub fn register_effects(m: &mut std::collections::HashMap<&'static str, CommandD
     m.insert("BR.LEN", CommandDef::new("BR.LEN", None, ArgCount::AtLeast(1), "Beat repeat length"));
     m.insert("BR.REV", CommandDef::new("BR.REV", None, ArgCount::AtLeast(1), "Beat repeat reverse"));
     m.insert("BR.WIN", CommandDef::new("BR.WIN", None, ArgCount::AtLeast(1), "Beat repeat window"));
-    m.insert("BR.MIX", CommandDef::new("BR.MIX", None, ArgCount::AtLeast(1), "Beat repeat mix"));
+    m.insert("BR.MIX", CommandDef::new("BR.MIX", None, ArgCount::AtLeast(1), "Beat repeat mix").with_range("br_mix", 0.0, 16383.0));
 
     // Pitch Shift
     m.insert("PSM", CommandDef::new("PSM", Some("PS.MODE"), ArgCount::AtLeast(1), "Pitch shift mode"));
@@ -69,7 +69,7 @@ pub fn register_effects(m: &mut std::collections::HashMap<&'static str, CommandD
     m.insert("PS.MODE", CommandDef::new("PS.MODE", None, ArgCount::AtLeast(1), "Pitch shift mode"));
     m.insert("PS.SEMI", CommandDef::new("PS.SEMI", None, ArgCount::AtLeast(1), "Pitch shift semitones"));
     m.insert("PS.GRAIN", CommandDef::new("PS.GRAIN", None, ArgCount::AtLeast(1), "Pitch shift grain size"));
-    m.insert("PS.MIX", CommandDef::new("PS.MIX", None, ArgCount::AtLeast(1), "Pitch shift mix"));
+    m.insert("PS.MIX", CommandDef::new("PS.MIX", None, ArgCount::AtLeast(1), "Pitch shift mix").with_range("ps_mix", 0.0, 16383.0));
     m.insert("PS.TARG", CommandDef::new("PS.TARG", None, ArgCount::AtLeast(1), "Pitch shift target"));
 
     // Clouds Granular
@@ -93,17 +93,17 @@ pub fn register_effects(m: &mut std::collections::HashMap<&'static str, CommandD
     m.insert("CLM", CommandDef::new("CLM", Some("CL.MODE"), ArgCount::AtLeast(1), "Clouds mode"));
     m.insert("CLLO", CommandDef::new("CLLO", Some("CL.LOFI"), ArgCount::AtLeast(1), "Clouds lo-fi"));
     m.insert("CL.TRIG", CommandDef::new("CL.TRIG", None, ArgCount::None, "Trigger clouds"));
-    m.insert("CL.PITCH", CommandDef::new("CL.PITCH", None, ArgCount::AtLeast(1), "Clouds pitch"));
-    m.insert("CL.POS", CommandDef::new("CL.POS", None, ArgCount::AtLeast(1), "Clouds position"));
-    m.insert("CL.SIZE", CommandDef::new("CL.SIZE", None, ArgCount::AtLeast(1), "Clouds size"));
-    m.insert("CL.DENS", CommandDef::new("CL.DENS", None, ArgCount::AtLeast(1), "Clouds density"));
-    m.insert("CL.TEX", CommandDef::new("CL.TEX", None, ArgCount::AtLeast(1), "Clouds texture"));
-    m.insert("CL.WET", CommandDef::new("CL.WET", None, ArgCount::AtLeast(1), "Clouds wet"));
-    m.insert("CL.GAIN", CommandDef::new("CL.GAIN", None, ArgCount::AtLeast(1), "Clouds gain"));
-    m.insert("CL.SPREAD", CommandDef::new("CL.SPREAD", None, ArgCount::AtLeast(1), "Clouds spread"));
-    m.insert("CL.RVB", CommandDef::new("CL.RVB", None, ArgCount::AtLeast(1), "Clouds reverb"));
+    m.insert("CL.PITCH", CommandDef::new("CL.PITCH", None, ArgCount::AtLeast(1), "Clouds pitch").with_range("cl_pitch", 0.0, 16383.0));
+    m.insert("CL.POS", CommandDef::new("CL.POS", None, ArgCount::AtLeast(1), "Clouds position").with_range("cl_pos", 0.0, 16383.0));
+    m.insert("CL.SIZE", CommandDef::new("CL.SIZE", None, ArgCount::AtLeast(1), "Clouds size").with_range("cl_size", 0.0, 16383.0));
+    m.insert("CL.DENS", CommandDef::new("CL.DENS", None, ArgCount::AtLeast(1), "Clouds density").with_range("cl_dens", 0.0, 16383.0));
+    m.insert("CL.TEX", CommandDef::new("CL.TEX", None, ArgCount::AtLeast(1), "Clouds texture").with_range("cl_tex", 0.0, 16383.0));
+    m.insert("CL.WET", CommandDef::new("CL.WET", None, ArgCount::AtLeast(1), "Clouds wet").with_range("cl_wet", 0.0, 16383.0));
+    m.insert("CL.GAIN", CommandDef::new("CL.GAIN", None, ArgCount::AtLeast(1), "Clouds gain").with_range("cl_gain", 0.0, 16383.0));
+    m.insert("CL.SPREAD", CommandDef::new("CL.SPREAD", None, ArgCount::AtLeast(1), "Clouds spread").with_range("cl_spread", 0.0, 16383.0));
+    m.insert("CL.RVB", CommandDef::new("CL.RVB", None, ArgCount::AtLeast(1), "Clouds reverb").with_range("cl_rvb", 0.0, 16383.0));
     m.insert("CL.FB", CommandDef::new("CL.FB", None, ArgCount::AtLeast(1), "Clouds feedback"));
-    m.insert("CL.FREEZE", CommandDef::new("CL.FREEZE", None, ArgCount::AtLeast(1), "Clouds freeze"));
-    m.insert("CL.MODE", CommandDef::new("CL.MODE", None, ArgCount::AtLeast(1), "Clouds mode"));
-    m.insert("CL.LOFI", CommandDef::new("CL.LOFI", None, ArgCount::AtLeast(1), "Clouds lo-fi"));
+    m.insert("CL.FREEZE", CommandDef::new("CL.FREEZE", None, ArgCount::AtLeast(1), "Clouds freeze").with_range("cl_freeze", 0.0, 1.0));
+    m.insert("CL.MODE", CommandDef::new("CL.MODE", None, ArgCount::AtLeast(1), "Clouds mode").with_range("cl_mode", 0.0, 3.0));
+    m.insert("CL.LOFI", CommandDef::new("CL.LOFI", None, ArgCount::AtLeast(1), "Clouds lo-fi").with_range("cl_lofi", 0.0, 16383.0));
 }
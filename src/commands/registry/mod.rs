@@ -18,6 +18,25 @@ pub enum ArgCount {
     Custom,
 }
 
+/// Synth parameter a command sets and the range it accepts, in the units sent over OSC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamRange {
+    pub param: &'static str,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ParamRange {
+    /// Range as typed on the command line: continuous 0-1 params (Plaits) take 0-16383
+    pub fn typed(&self) -> (f32, f32) {
+        if self.max <= 1.0 && !crate::snapshot::is_discrete(self.param) {
+            (0.0, 16383.0)
+        } else {
+            (self.min, self.max)
+        }
+    }
+}
+
 /// Command definition - single source of truth
 #[derive(Debug, Clone)]
 pub struct CommandDef {
@@ -31,6 +50,8 @@ pub struct CommandDef {
     pub help: &'static str,
     /// Whether command requires specialized validation (SEQ, loops, etc.)
     pub special_validation: bool,
+    /// Parameter range for commands that set one synth parameter (used by MUT)
+    pub range: Option<ParamRange>,
 }
 
 impl CommandDef {
@@ -47,6 +68,24 @@ impl CommandDef {
             args,
             help,
             special_validation: false,
+            range: None,
+        }
+    }
+
+    /// Argument summary for the editor hint, e.g. "1 ARG 20-20000"
+    pub fn usage(&self) -> String {
+        let args = match self.args {
+            ArgCount::None => "NO ARGS".to_string(),
+            ArgCount::Exactly(1) => "1 ARG".to_string(),
+            ArgCount::Exactly(n) => format!("{} ARGS", n),
+            ArgCount::AtLeast(0) => "ANY ARGS".to_string(),
+            ArgCount::AtLeast(n) => format!("{}+ ARGS", n),
+            ArgCount::Range(min, max) => format!("{}-{} ARGS", min, max),
+            ArgCount::Custom => String::new(),
+        };
+        match self.range.map(|range| range.typed()) {
+            Some((min, max)) => format!("{} {}-{}", args, min, max).trim_start().to_string(),
+            None => args,
         }
     }
 
@@ -55,6 +94,12 @@ impl CommandDef {
         self.special_validation = true;
         self
     }
+
+    /// Attach the synth parameter this command sets and its valid range
+    pub const fn with_range(mut self, param: &'static str, min: f32, max: f32) -> Self {
+        self.range = Some(ParamRange { param, min, max });
+        self
+    }
 }
 
 // Category modules
@@ -73,4 +118,4 @@ mod commands;
 // Validation
 pub mod validate;
 
-pub use commands::COMMAND_REGISTRY;
+pub use commands::{param_range, COMMAND_REGISTRY};
@@ -4,38 +4,38 @@ use super::{ArgCount, CommandDef};
 
 pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef>) {
     // Primary Oscillator
-    m.insert("PF", CommandDef::new("PF", Some("POSC.FREQ"), ArgCount::AtLeast(1), "Primary osc frequency"));
-    m.insert("PW", CommandDef::new("PW", Some("POSC.WAVE"), ArgCount::AtLeast(1), "Primary osc waveform"));
-    m.insert("PV", CommandDef::new("PV", Some("PRI.VOL"), ArgCount::AtLeast(1), "Primary osc volume"));
+    m.insert("PF", CommandDef::new("PF", Some("POSC.FREQ"), ArgCount::AtLeast(1), "Primary osc frequency").with_range("pf", 20.0, 20000.0));
+    m.insert("PW", CommandDef::new("PW", Some("POSC.WAVE"), ArgCount::AtLeast(1), "Primary osc waveform").with_range("pw", 0.0, 2.0));
+    m.insert("PV", CommandDef::new("PV", Some("PRI.VOL"), ArgCount::AtLeast(1), "Primary osc volume").with_range("pv", 0.0, 16383.0));
 
     // Modulation Oscillator
-    m.insert("MF", CommandDef::new("MF", Some("MOSC.FREQ"), ArgCount::AtLeast(1), "Mod osc frequency"));
-    m.insert("MW", CommandDef::new("MW", Some("MOSC.WAVE"), ArgCount::AtLeast(1), "Mod osc waveform"));
-    m.insert("MV", CommandDef::new("MV", Some("MOD.VOL"), ArgCount::AtLeast(1), "Mod osc volume"));
-    m.insert("FB", CommandDef::new("FB", Some("MOSC.FB"), ArgCount::AtLeast(1), "Mod osc feedback"));
-    m.insert("FBA", CommandDef::new("FBA", Some("MOSC.FBA"), ArgCount::AtLeast(1), "Mod osc feedback amt"));
+    m.insert("MF", CommandDef::new("MF", Some("MOSC.FREQ"), ArgCount::AtLeast(1), "Mod osc frequency").with_range("mf", 20.0, 20000.0));
+    m.insert("MW", CommandDef::new("MW", Some("MOSC.WAVE"), ArgCount::AtLeast(1), "Mod osc waveform").with_range("mw", 0.0, 3.0));
+    m.insert("MV", CommandDef::new("MV", Some("MOD.VOL"), ArgCount::AtLeast(1), "Mod osc volume").with_range("mv", 0.0, 16383.0));
+    m.insert("FB", CommandDef::new("FB", Some("MOSC.FB"), ArgCount::AtLeast(1), "Mod osc feedback").with_range("fb", 0.0, 16383.0));
+    m.insert("FBA", CommandDef::new("FBA", Some("MOSC.FBA"), ArgCount::AtLeast(1), "Mod osc feedback amt").with_range("fba", 0.0, 16383.0));
 
     // Discontinuity
-    m.insert("DC", CommandDef::new("DC", Some("DISC.AMT"), ArgCount::AtLeast(1), "Discontinuity amount"));
-    m.insert("DM", CommandDef::new("DM", Some("DISC.MODE"), ArgCount::AtLeast(1), "Discontinuity mode"));
+    m.insert("DC", CommandDef::new("DC", Some("DISC.AMT"), ArgCount::AtLeast(1), "Discontinuity amount").with_range("dc", 0.0, 16383.0));
+    m.insert("DM", CommandDef::new("DM", Some("DISC.MODE"), ArgCount::AtLeast(1), "Discontinuity mode").with_range("dm", 0.0, 6.0));
 
     // Modulation Bus
-    m.insert("TK", CommandDef::new("TK", Some("MBUS.TRK"), ArgCount::AtLeast(1), "Modulation bus track"));
-    m.insert("MB", CommandDef::new("MB", Some("MBUS.AMT"), ArgCount::AtLeast(1), "Modulation bus amount"));
-    m.insert("MBA", CommandDef::new("MBA", Some("MBEV.AMT"), ArgCount::AtLeast(1), "Mod bus envelope amt"));
-    m.insert("MBD", CommandDef::new("MBD", Some("MBEV.DEC"), ArgCount::AtLeast(1), "Mod bus envelope dec"));
+    m.insert("TK", CommandDef::new("TK", Some("MBUS.TRK"), ArgCount::AtLeast(1), "Modulation bus track").with_range("tk", 0.0, 16383.0));
+    m.insert("MB", CommandDef::new("MB", Some("MBUS.AMT"), ArgCount::AtLeast(1), "Modulation bus amount").with_range("mb", 0.0, 16383.0));
+    m.insert("MBA", CommandDef::new("MBA", Some("MBEV.AMT"), ArgCount::AtLeast(1), "Mod bus envelope amt").with_range("mba", 0.0, 16383.0));
+    m.insert("MBD", CommandDef::new("MBD", Some("MBEV.DEC"), ArgCount::AtLeast(1), "Mod bus envelope dec").with_range("mbd", 1.0, 10000.0));
 
     // Routing
-    m.insert("FM", CommandDef::new("FM", Some("MBUS.FM"), ArgCount::AtLeast(1), "FM routing"));
-    m.insert("MX", CommandDef::new("MX", Some("MBUS.MIX"), ArgCount::AtLeast(1), "Mix routing"));
-    m.insert("MM", CommandDef::new("MM", Some("MBUS.MMX"), ArgCount::AtLeast(1), "Mod mix routing"));
-    m.insert("ME", CommandDef::new("ME", Some("MBUS.EMX"), ArgCount::AtLeast(1), "Effect mix routing"));
-    m.insert("MP", CommandDef::new("MP", Some("ROUT.MP"), ArgCount::AtLeast(1), "Route to pitch"));
-    m.insert("MD", CommandDef::new("MD", Some("ROUT.MD"), ArgCount::AtLeast(1), "Route to discontinuity"));
-    m.insert("MT", CommandDef::new("MT", Some("ROUT.MT"), ArgCount::AtLeast(1), "Route to timbre"));
-    m.insert("MA", CommandDef::new("MA", Some("ROUT.MA"), ArgCount::AtLeast(1), "Route to amplitude"));
-    m.insert("MC", CommandDef::new("MC", Some("ROUT.MC"), ArgCount::AtLeast(1), "Route to filter cutoff"));
-    m.insert("MQ", CommandDef::new("MQ", Some("ROUT.MQ"), ArgCount::AtLeast(1), "Route to filter Q"));
+    m.insert("FM", CommandDef::new("FM", Some("MBUS.FM"), ArgCount::AtLeast(1), "FM routing").with_range("fm", 0.0, 16383.0));
+    m.insert("MX", CommandDef::new("MX", Some("MBUS.MIX"), ArgCount::AtLeast(1), "Mix routing").with_range("mx", 0.0, 16383.0));
+    m.insert("MM", CommandDef::new("MM", Some("MBUS.MMX"), ArgCount::AtLeast(1), "Mod mix routing").with_range("mm", 0.0, 1.0));
+    m.insert("ME", CommandDef::new("ME", Some("MBUS.EMX"), ArgCount::AtLeast(1), "Effect mix routing").with_range("me", 0.0, 1.0));
+    m.insert("MP", CommandDef::new("MP", Some("ROUT.MP"), ArgCount::AtLeast(1), "Route to pitch").with_range("mp", 0.0, 1.0));
+    m.insert("MD", CommandDef::new("MD", Some("ROUT.MD"), ArgCount::AtLeast(1), "Route to discontinuity").with_range("md", 0.0, 1.0));
+    m.insert("MT", CommandDef::new("MT", Some("ROUT.MT"), ArgCount::AtLeast(1), "Route to timbre").with_range("mt", 0.0, 1.0));
+    m.insert("MA", CommandDef::new("MA", Some("ROUT.MA"), ArgCount::AtLeast(1), "Route to amplitude").with_range("ma", 0.0, 1.0));
+    m.insert("MC", CommandDef::new("MC", Some("ROUT.MC"), ArgCount::AtLeast(1), "Route to filter cutoff").with_range("mf_f", 0.0, 1.0));
+    m.insert("MQ", CommandDef::new("MQ", Some("ROUT.MQ"), ArgCount::AtLeast(1), "Route to filter Q").with_range("mf_q", 0.0, 1.0));
 
     // Envelopes - Global Controls
     m.insert("ENV.ATK", CommandDef::new("ENV.ATK", None, ArgCount::Exactly(1), "Global envelope attack"));
@@ -43,23 +43,23 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("ENV.CRV", CommandDef::new("ENV.CRV", None, ArgCount::Exactly(1), "Global envelope curve"));
 
     // Envelopes - Decay
-    m.insert("AD", CommandDef::new("AD", Some("AENV.DEC"), ArgCount::AtLeast(1), "Amplitude env decay"));
-    m.insert("PD", CommandDef::new("PD", Some("PENV.DEC"), ArgCount::AtLeast(1), "Pitch env decay"));
-    m.insert("FD", CommandDef::new("FD", Some("FMEV.DEC"), ArgCount::AtLeast(1), "FM env decay"));
-    m.insert("DD", CommandDef::new("DD", Some("DENV.DEC"), ArgCount::AtLeast(1), "Discontinuity env decay"));
+    m.insert("AD", CommandDef::new("AD", Some("AENV.DEC"), ArgCount::AtLeast(1), "Amplitude env decay").with_range("ad", 1.0, 10000.0));
+    m.insert("PD", CommandDef::new("PD", Some("PENV.DEC"), ArgCount::AtLeast(1), "Pitch env decay").with_range("pd", 1.0, 10000.0));
+    m.insert("FD", CommandDef::new("FD", Some("FMEV.DEC"), ArgCount::AtLeast(1), "FM env decay").with_range("fd", 1.0, 10000.0));
+    m.insert("DD", CommandDef::new("DD", Some("DENV.DEC"), ArgCount::AtLeast(1), "Discontinuity env decay").with_range("dd", 1.0, 10000.0));
     m.insert("AENV.DEC", CommandDef::new("AENV.DEC", None, ArgCount::AtLeast(1), "Amplitude env decay"));
     m.insert("PENV.DEC", CommandDef::new("PENV.DEC", None, ArgCount::AtLeast(1), "Pitch env decay"));
     m.insert("FMEV.DEC", CommandDef::new("FMEV.DEC", None, ArgCount::AtLeast(1), "FM env decay"));
     m.insert("DENV.DEC", CommandDef::new("DENV.DEC", None, ArgCount::AtLeast(1), "Discontinuity env decay"));
-    m.insert("FBD", CommandDef::new("FBD", Some("FBEV.DEC"), ArgCount::AtLeast(1), "FB env decay"));
+    m.insert("FBD", CommandDef::new("FBD", Some("FBEV.DEC"), ArgCount::AtLeast(1), "FB env decay").with_range("fbd", 1.0, 10000.0));
     m.insert("FBEV.DEC", CommandDef::new("FBEV.DEC", None, ArgCount::AtLeast(1), "FB envelope decay"));
-    m.insert("FED", CommandDef::new("FED", Some("FLEV.DEC"), ArgCount::AtLeast(1), "Filter env decay"));
+    m.insert("FED", CommandDef::new("FED", Some("FLEV.DEC"), ArgCount::AtLeast(1), "Filter env decay").with_range("fed", 1.0, 10000.0));
     m.insert("FLEV.DEC", CommandDef::new("FLEV.DEC", None, ArgCount::AtLeast(1), "Filter env decay"));
 
     // Envelopes - Amount
-    m.insert("PA", CommandDef::new("PA", Some("PENV.AMT"), ArgCount::AtLeast(1), "Pitch env amount"));
-    m.insert("FA", CommandDef::new("FA", Some("FMEV.AMT"), ArgCount::AtLeast(1), "FM env amount"));
-    m.insert("DA", CommandDef::new("DA", Some("DENV.AMT"), ArgCount::AtLeast(1), "Discontinuity env amt"));
+    m.insert("PA", CommandDef::new("PA", Some("PENV.AMT"), ArgCount::AtLeast(1), "Pitch env amount").with_range("pa", 0.0, 16.0));
+    m.insert("FA", CommandDef::new("FA", Some("FMEV.AMT"), ArgCount::AtLeast(1), "FM env amount").with_range("fa", 0.0, 16383.0));
+    m.insert("DA", CommandDef::new("DA", Some("DENV.AMT"), ArgCount::AtLeast(1), "Discontinuity env amt").with_range("da", 0.0, 16383.0));
     m.insert("PENV.AMT", CommandDef::new("PENV.AMT", None, ArgCount::AtLeast(1), "Pitch env amount"));
     m.insert("FMEV.AMT", CommandDef::new("FMEV.AMT", None, ArgCount::AtLeast(1), "FM env amount"));
     m.insert("DENV.AMT", CommandDef::new("DENV.AMT", None, ArgCount::AtLeast(1), "Discontinuity env amt"));
@@ -71,29 +71,29 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("DAA", CommandDef::new("DAA", Some("DENV.ATK"), ArgCount::Exactly(1), "Discontinuity env attack"));
     m.insert("FBAA", CommandDef::new("FBAA", Some("FBEV.ATK"), ArgCount::Exactly(1), "FB env attack"));
     m.insert("FLAA", CommandDef::new("FLAA", Some("FLEV.ATK"), ArgCount::Exactly(1), "Filter env attack"));
-    m.insert("AENV.ATK", CommandDef::new("AENV.ATK", None, ArgCount::Exactly(1), "Amplitude env attack"));
-    m.insert("PENV.ATK", CommandDef::new("PENV.ATK", None, ArgCount::Exactly(1), "Pitch env attack"));
-    m.insert("FMEV.ATK", CommandDef::new("FMEV.ATK", None, ArgCount::Exactly(1), "FM env attack"));
-    m.insert("DENV.ATK", CommandDef::new("DENV.ATK", None, ArgCount::Exactly(1), "Discontinuity env attack"));
-    m.insert("FBEV.ATK", CommandDef::new("FBEV.ATK", None, ArgCount::Exactly(1), "FB env attack"));
-    m.insert("FLEV.ATK", CommandDef::new("FLEV.ATK", None, ArgCount::Exactly(1), "Filter env attack"));
+    m.insert("AENV.ATK", CommandDef::new("AENV.ATK", None, ArgCount::Exactly(1), "Amplitude env attack").with_range("aenv_atk", 1.0, 10000.0));
+    m.insert("PENV.ATK", CommandDef::new("PENV.ATK", None, ArgCount::Exactly(1), "Pitch env attack").with_range("penv_atk", 1.0, 10000.0));
+    m.insert("FMEV.ATK", CommandDef::new("FMEV.ATK", None, ArgCount::Exactly(1), "FM env attack").with_range("fmev_atk", 1.0, 10000.0));
+    m.insert("DENV.ATK", CommandDef::new("DENV.ATK", None, ArgCount::Exactly(1), "Discontinuity env attack").with_range("denv_atk", 1.0, 10000.0));
+    m.insert("FBEV.ATK", CommandDef::new("FBEV.ATK", None, ArgCount::Exactly(1), "FB env attack").with_range("fbev_atk", 1.0, 10000.0));
+    m.insert("FLEV.ATK", CommandDef::new("FLEV.ATK", None, ArgCount::Exactly(1), "Filter env attack").with_range("flev_atk", 1.0, 10000.0));
 
     // Envelopes - Curve
     m.insert("AC", CommandDef::new("AC", Some("AENV.CRV"), ArgCount::Exactly(1), "Amplitude env curve"));
     m.insert("PC", CommandDef::new("PC", Some("PENV.CRV"), ArgCount::Exactly(1), "Pitch env curve"));
     m.insert("FBC", CommandDef::new("FBC", Some("FBEV.CRV"), ArgCount::Exactly(1), "FB env curve"));
     m.insert("FLC", CommandDef::new("FLC", Some("FLEV.CRV"), ArgCount::Exactly(1), "Filter env curve"));
-    m.insert("AENV.CRV", CommandDef::new("AENV.CRV", None, ArgCount::Exactly(1), "Amplitude env curve"));
-    m.insert("PENV.CRV", CommandDef::new("PENV.CRV", None, ArgCount::Exactly(1), "Pitch env curve"));
-    m.insert("FMEV.CRV", CommandDef::new("FMEV.CRV", None, ArgCount::Exactly(1), "FM env curve"));
-    m.insert("DENV.CRV", CommandDef::new("DENV.CRV", None, ArgCount::Exactly(1), "Discontinuity env curve"));
-    m.insert("FBEV.CRV", CommandDef::new("FBEV.CRV", None, ArgCount::Exactly(1), "FB env curve"));
-    m.insert("FLEV.CRV", CommandDef::new("FLEV.CRV", None, ArgCount::Exactly(1), "Filter env curve"));
+    m.insert("AENV.CRV", CommandDef::new("AENV.CRV", None, ArgCount::Exactly(1), "Amplitude env curve").with_range("aenv_crv", -8.0, 8.0));
+    m.insert("PENV.CRV", CommandDef::new("PENV.CRV", None, ArgCount::Exactly(1), "Pitch env curve").with_range("penv_crv", -8.0, 8.0));
+    m.insert("FMEV.CRV", CommandDef::new("FMEV.CRV", None, ArgCount::Exactly(1), "FM env curve").with_range("fmev_crv", -8.0, 8.0));
+    m.insert("DENV.CRV", CommandDef::new("DENV.CRV", None, ArgCount::Exactly(1), "Discontinuity env curve").with_range("denv_crv", -8.0, 8.0));
+    m.insert("FBEV.CRV", CommandDef::new("FBEV.CRV", None, ArgCount::Exactly(1), "FB env curve").with_range("fbev_crv", -8.0, 8.0));
+    m.insert("FLEV.CRV", CommandDef::new("FLEV.CRV", None, ArgCount::Exactly(1), "Filter env curve").with_range("flev_crv", -8.0, 8.0));
 
     // Envelopes - Unvalidated (has handler but no validation)
     m.insert("FBEV.AMT", CommandDef::new("FBEV.AMT", None, ArgCount::AtLeast(1), "FB envelope amount"));
     m.insert("FLEV.AMT", CommandDef::new("FLEV.AMT", None, ArgCount::AtLeast(1), "Filter env amount"));
-    m.insert("FE", CommandDef::new("FE", Some("FLEV.AMT"), ArgCount::AtLeast(1), "Filter envelope amt"));
+    m.insert("FE", CommandDef::new("FE", Some("FLEV.AMT"), ArgCount::AtLeast(1), "Filter envelope amt").with_range("fe", 0.0, 16383.0));
 
     // Envelopes - Gate
     m.insert("GATE", CommandDef::new("GATE", None, ArgCount::Exactly(1), "Trigger all gates"));
@@ -105,14 +105,14 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("FLEV.GATE", CommandDef::new("FLEV.GATE", None, ArgCount::Exactly(1), "Filter env gate"));
 
     // Noise
-    m.insert("NW", CommandDef::new("NW", Some("NOISE.WAV"), ArgCount::AtLeast(1), "Noise waveform"));
-    m.insert("NV", CommandDef::new("NV", Some("NOISE.VOL"), ArgCount::AtLeast(1), "Noise volume"));
+    m.insert("NW", CommandDef::new("NW", Some("NOISE.WAV"), ArgCount::AtLeast(1), "Noise waveform").with_range("nw", 0.0, 2.0));
+    m.insert("NV", CommandDef::new("NV", Some("NOISE.VOL"), ArgCount::AtLeast(1), "Noise volume").with_range("nv", 0.0, 16383.0));
     m.insert("NP", CommandDef::new("NP", Some("NOISE.PRI"), ArgCount::AtLeast(1), "Noise priority"));
     m.insert("NM", CommandDef::new("NM", Some("NOISE.MOD"), ArgCount::AtLeast(1), "Noise modulation"));
 
     // Plaits
-    m.insert("PLV", CommandDef::new("PLV", None, ArgCount::AtLeast(1), "Plaits voice level"));
-    m.insert("PAV", CommandDef::new("PAV", None, ArgCount::AtLeast(1), "Plaits aux level"));
+    m.insert("PLV", CommandDef::new("PLV", None, ArgCount::AtLeast(1), "Plaits voice level").with_range("plv", 0.0, 16383.0));
+    m.insert("PAV", CommandDef::new("PAV", None, ArgCount::AtLeast(1), "Plaits aux level").with_range("pav", 0.0, 16383.0));
     m.insert("PLE", CommandDef::new("PLE", Some("PL.ENG"), ArgCount::AtLeast(1), "Plaits engine"));
     m.insert("PLF", CommandDef::new("PLF", Some("PL.FREQ"), ArgCount::AtLeast(1), "Plaits frequency"));
     m.insert("PLH", CommandDef::new("PLH", Some("PL.HARM"), ArgCount::AtLeast(1), "Plaits harmonics"));
@@ -120,21 +120,21 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("PLM", CommandDef::new("PLM", Some("PL.MORPH"), ArgCount::AtLeast(1), "Plaits morph"));
     m.insert("PLD", CommandDef::new("PLD", Some("PL.DEC"), ArgCount::AtLeast(1), "Plaits decay"));
     m.insert("PLL", CommandDef::new("PLL", Some("PL.LPG"), ArgCount::AtLeast(1), "Plaits LPG"));
-    m.insert("PL.ENG", CommandDef::new("PL.ENG", None, ArgCount::AtLeast(1), "Plaits engine"));
+    m.insert("PL.ENG", CommandDef::new("PL.ENG", None, ArgCount::AtLeast(1), "Plaits engine").with_range("engine", 0.0, 15.0));
     m.insert("PL.FREQ", CommandDef::new("PL.FREQ", None, ArgCount::AtLeast(1), "Plaits frequency"));
-    m.insert("PL.HARM", CommandDef::new("PL.HARM", None, ArgCount::AtLeast(1), "Plaits harmonics"));
-    m.insert("PL.TIMB", CommandDef::new("PL.TIMB", None, ArgCount::AtLeast(1), "Plaits timbre"));
-    m.insert("PL.MORPH", CommandDef::new("PL.MORPH", None, ArgCount::AtLeast(1), "Plaits morph"));
-    m.insert("PL.DEC", CommandDef::new("PL.DEC", None, ArgCount::AtLeast(1), "Plaits decay"));
-    m.insert("PL.LPG", CommandDef::new("PL.LPG", None, ArgCount::AtLeast(1), "Plaits LPG"));
+    m.insert("PL.HARM", CommandDef::new("PL.HARM", None, ArgCount::AtLeast(1), "Plaits harmonics").with_range("harmonics", 0.0, 1.0));
+    m.insert("PL.TIMB", CommandDef::new("PL.TIMB", None, ArgCount::AtLeast(1), "Plaits timbre").with_range("timbre", 0.0, 1.0));
+    m.insert("PL.MORPH", CommandDef::new("PL.MORPH", None, ArgCount::AtLeast(1), "Plaits morph").with_range("morph", 0.0, 1.0));
+    m.insert("PL.DEC", CommandDef::new("PL.DEC", None, ArgCount::AtLeast(1), "Plaits decay").with_range("decay", 0.0, 1.0));
+    m.insert("PL.LPG", CommandDef::new("PL.LPG", None, ArgCount::AtLeast(1), "Plaits LPG").with_range("lpg", 0.0, 1.0));
 
     // Filter
-    m.insert("FC", CommandDef::new("FC", Some("FILT.CUT"), ArgCount::AtLeast(1), "Filter cutoff"));
-    m.insert("FQ", CommandDef::new("FQ", Some("FILT.RES"), ArgCount::AtLeast(1), "Filter resonance"));
-    m.insert("FT", CommandDef::new("FT", Some("FILT.TYP"), ArgCount::AtLeast(1), "Filter type"));
-    m.insert("FK", CommandDef::new("FK", Some("FILT.KEY"), ArgCount::AtLeast(1), "Filter key tracking"));
-    m.insert("MFF", CommandDef::new("MFF", Some("MODF.CUT"), ArgCount::AtLeast(1), "Mod filter cutoff"));
-    m.insert("MFQ", CommandDef::new("MFQ", Some("MODF.RES"), ArgCount::AtLeast(1), "Mod filter resonance"));
+    m.insert("FC", CommandDef::new("FC", Some("FILT.CUT"), ArgCount::AtLeast(1), "Filter cutoff").with_range("fc", 20.0, 20000.0));
+    m.insert("FQ", CommandDef::new("FQ", Some("FILT.RES"), ArgCount::AtLeast(1), "Filter resonance").with_range("fq", 0.0, 16383.0));
+    m.insert("FT", CommandDef::new("FT", Some("FILT.TYP"), ArgCount::AtLeast(1), "Filter type").with_range("ft", 0.0, 13.0));
+    m.insert("FK", CommandDef::new("FK", Some("FILT.KEY"), ArgCount::AtLeast(1), "Filter key tracking").with_range("fk", 0.0, 16383.0));
+    m.insert("MFF", CommandDef::new("MFF", Some("MODF.CUT"), ArgCount::AtLeast(1), "Mod filter cutoff").with_range("mf_f", 0.0, 16383.0));
+    m.insert("MFQ", CommandDef::new("MFQ", Some("MODF.RES"), ArgCount::AtLeast(1), "Mod filter resonance").with_range("mf_q", 0.0, 16383.0));
 
     // Output
     m.insert("VOL", CommandDef::new("VOL", Some("OUT.VOL"), ArgCount::Exactly(1), "Output volume"));
@@ -206,6 +206,20 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("SONS", CommandDef::new("SONS", Some("S.ONSET"), ArgCount::Range(0, 1), "Auto-detect slices"));
     m.insert("S.ONSET.MIN", CommandDef::new("S.ONSET.MIN", None, ArgCount::Exactly(1), "Min onset spacing ms"));
     m.insert("SOMIN", CommandDef::new("SOMIN", Some("S.ONSET.MIN"), ArgCount::Exactly(1), "Min onset spacing ms"));
+    m.insert("S.MARK", CommandDef::new("S.MARK", None, ArgCount::AtLeast(1), "Insert slice marker at position"));
+    m.insert("SMK", CommandDef::new("SMK", Some("S.MARK"), ArgCount::AtLeast(1), "Insert slice marker at position"));
+    m.insert("S.MARK.MV", CommandDef::new("S.MARK.MV", None, ArgCount::AtLeast(2), "Move slice marker"));
+    m.insert("SMMV", CommandDef::new("SMMV", Some("S.MARK.MV"), ArgCount::AtLeast(2), "Move slice marker"));
+    m.insert("S.UNMARK", CommandDef::new("S.UNMARK", None, ArgCount::AtLeast(1), "Delete slice marker"));
+    m.insert("SUMK", CommandDef::new("SUMK", Some("S.UNMARK"), ArgCount::AtLeast(1), "Delete slice marker"));
+    m.insert("S.SNAP", CommandDef::new("S.SNAP", None, ArgCount::Range(0, 1), "Marker snap: 0=off 1=zero 2=transient"));
+    m.insert("SSNP", CommandDef::new("SSNP", Some("S.SNAP"), ArgCount::Range(0, 1), "Marker snap: 0=off 1=zero 2=transient"));
+    m.insert("S.STRETCH", CommandDef::new("S.STRETCH", None, ArgCount::AtLeast(0), "Time-stretch loop to metro BPM"));
+    m.insert("SSTR", CommandDef::new("SSTR", Some("S.STRETCH"), ArgCount::AtLeast(0), "Time-stretch loop to metro BPM"));
+    m.insert("S.BPM", CommandDef::new("S.BPM", None, ArgCount::AtLeast(0), "Loop source BPM (0=detect)"));
+    m.insert("SBPM", CommandDef::new("SBPM", Some("S.BPM"), ArgCount::AtLeast(0), "Loop source BPM (0=detect)"));
+    m.insert("S.EXPORT", CommandDef::new("S.EXPORT", None, ArgCount::AtLeast(1), "Export slices as WAV kit"));
+    m.insert("SEXP", CommandDef::new("SEXP", Some("S.EXPORT"), ArgCount::AtLeast(1), "Export slices as WAV kit"));
 
     // Sampler - Envelope Parameters
     m.insert("S.ATK", CommandDef::new("S.ATK", None, ArgCount::AtLeast(1), "Sample attack time"));
@@ -260,4 +274,10 @@ pub fn register_synth(m: &mut std::collections::HashMap<&'static str, CommandDef
     m.insert("SFCM", CommandDef::new("SFCM", Some("SF.CUTMOD"), ArgCount::AtLeast(1), "Sampler cutoff modulation"));
     m.insert("SF.RESMOD", CommandDef::new("SF.RESMOD", None, ArgCount::AtLeast(1), "Sampler resonance modulation"));
     m.insert("SFQM", CommandDef::new("SFQM", Some("SF.RESMOD"), ArgCount::AtLeast(1), "Sampler resonance modulation"));
+
+    // Snapshots
+    m.insert("SNAP.SAVE", CommandDef::new("SNAP.SAVE", None, ArgCount::AtLeast(1), "Save params to snapshot"));
+    m.insert("SNAP.LOAD", CommandDef::new("SNAP.LOAD", None, ArgCount::AtLeast(1), "Load snapshot params"));
+    m.insert("SNAP.MORPH", CommandDef::new("SNAP.MORPH", None, ArgCount::AtLeast(3), "Morph between snapshots"));
+    m.insert("SNAP.GLIDE", CommandDef::new("SNAP.GLIDE", None, ArgCount::AtLeast(2), "Glide to snapshot"));
 }
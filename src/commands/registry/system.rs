@@ -18,6 +18,21 @@ pub fn register_system(m: &mut std::collections::HashMap<&'static str, CommandDe
     // Audio
     m.insert("AUDIO", CommandDef::new("AUDIO", None, ArgCount::Custom, "Audio output config"));
     m.insert("AUDIO.OUT", CommandDef::new("AUDIO.OUT", None, ArgCount::Custom, "Audio output config"));
+    m.insert("OUT.CH", CommandDef::new("OUT.CH", None, ArgCount::Range(0, 1), "Hardware output channels"));
+    m.insert("OUT.ROUTE", CommandDef::new("OUT.ROUTE", None, ArgCount::AtLeast(1), "Voice to output pair"));
+
+    // Audio-reactive
+    m.insert("LVL", CommandDef::new("LVL", None, ArgCount::None, "Output level 0-16383"));
+    m.insert("LVL.IN", CommandDef::new("LVL.IN", None, ArgCount::None, "Input level 0-16383"));
+    m.insert("LVL.OSC", CommandDef::new("LVL.OSC", None, ArgCount::None, "Oscillator level 0-16383"));
+    m.insert("LVL.PLA", CommandDef::new("LVL.PLA", None, ArgCount::None, "Plaits level 0-16383"));
+    m.insert("LVL.NOS", CommandDef::new("LVL.NOS", None, ArgCount::None, "Noise level 0-16383"));
+    m.insert("LVL.SMP", CommandDef::new("LVL.SMP", None, ArgCount::None, "Sampler level 0-16383"));
+    m.insert("BAND", CommandDef::new("BAND", None, ArgCount::AtLeast(1), "Spectrum band 1-15 level"));
+    m.insert("GR", CommandDef::new("GR", None, ArgCount::None, "Compressor gain reduction"));
+    m.insert("ON.SCRIPT", CommandDef::new("ON.SCRIPT", None, ArgCount::Range(0, 1), "Script run on onset (0=off)"));
+    m.insert("ON.SRC", CommandDef::new("ON.SRC", None, ArgCount::Range(0, 1), "Onset source (0=out 1=in)"));
+    m.insert("ON.THR", CommandDef::new("ON.THR", None, ArgCount::Range(0, 1), "Onset threshold (1-100)"));
 
     // SuperCollider
     m.insert("SC.DIAG", CommandDef::new("SC.DIAG", None, ArgCount::Range(1, 2), "SC diagnostics"));
@@ -25,6 +40,8 @@ pub fn register_system(m: &mut std::collections::HashMap<&'static str, CommandDe
     // Scene Management
     m.insert("SAVE", CommandDef::new("SAVE", None, ArgCount::Exactly(1), "Save scene"));
     m.insert("LOAD", CommandDef::new("LOAD", None, ArgCount::Exactly(1), "Load scene"));
+    m.insert("IMPORT.TT", CommandDef::new("IMPORT.TT", None, ArgCount::AtLeast(1), "Import Teletype scene file"));
+    m.insert("EXPORT.TT", CommandDef::new("EXPORT.TT", None, ArgCount::AtLeast(1), "Export scene as Teletype file"));
     m.insert("DELETE", CommandDef::new("DELETE", None, ArgCount::Exactly(1), "Delete scene"));
     m.insert("SCENES", CommandDef::new("SCENES", None, ArgCount::None, "List scenes"));
     m.insert("LOAD.RST", CommandDef::new("LOAD.RST", None, ArgCount::Range(0, 1), "RST on load"));
@@ -36,11 +53,20 @@ pub fn register_system(m: &mut std::collections::HashMap<&'static str, CommandDe
     m.insert("PSET.SAVE", CommandDef::new("PSET.SAVE", None, ArgCount::AtLeast(2), "Save preset"));
     m.insert("PSET.DEL", CommandDef::new("PSET.DEL", None, ArgCount::AtLeast(1), "Delete preset"));
     m.insert("PSETS", CommandDef::new("PSETS", None, ArgCount::None, "List presets"));
+    m.insert("PATCH", CommandDef::new("PATCH", None, ArgCount::AtLeast(1), "Load voice patch"));
+    m.insert("PATCH.SAVE", CommandDef::new("PATCH.SAVE", None, ArgCount::AtLeast(2), "Save voice patch"));
+    m.insert("PATCH.DIFF", CommandDef::new("PATCH.DIFF", None, ArgCount::AtLeast(1), "Compare patch to sound"));
+    m.insert("PATCH.DEL", CommandDef::new("PATCH.DEL", None, ArgCount::AtLeast(1), "Delete voice patch"));
+    m.insert("PATCH.EXPORT", CommandDef::new("PATCH.EXPORT", None, ArgCount::AtLeast(1), "Export patch TOML"));
+    m.insert("PATCH.IMPORT", CommandDef::new("PATCH.IMPORT", None, ArgCount::AtLeast(1), "Import patch TOML"));
+    m.insert("PATCHES", CommandDef::new("PATCHES", None, ArgCount::None, "List voice patches"));
 
     // Recording
     m.insert("REC", CommandDef::new("REC", None, ArgCount::None, "Start recording"));
     m.insert("REC.STOP", CommandDef::new("REC.STOP", None, ArgCount::None, "Stop recording"));
     m.insert("REC.PATH", CommandDef::new("REC.PATH", None, ArgCount::Exactly(1), "Set recording path"));
+    m.insert("REC.STEMS", CommandDef::new("REC.STEMS", None, ArgCount::Range(0, 1), "Record master and stems"));
+    m.insert("REC.STEMS.STOP", CommandDef::new("REC.STEMS.STOP", None, ArgCount::Range(0, 1), "Stop stem recording"));
 
     // Quantization
     m.insert("Q.ROOT", CommandDef::new("Q.ROOT", None, ArgCount::Range(0, 1), "Quantize root note"));
@@ -89,6 +115,9 @@ pub fn register_system(m: &mut std::collections::HashMap<&'static str, CommandDe
 
     // Output control
     m.insert("LIMIT", CommandDef::new("LIMIT", None, ArgCount::Range(0, 1), "Output limiter"));
+    m.insert("LUFS", CommandDef::new("LUFS", None, ArgCount::None, "Loudness and true peak"));
+    m.insert("LUFS.RST", CommandDef::new("LUFS.RST", None, ArgCount::None, "Reset integrated loudness"));
+    m.insert("LUFS.TARGET", CommandDef::new("LUFS.TARGET", None, ArgCount::AtLeast(0), "Target loudness for output gain"));
 
     // Confirmation dialogs
     m.insert("CFM.QUIT", CommandDef::new("CFM.QUIT", None, ArgCount::Range(0, 1), "Confirm quit w/ unsaved"));
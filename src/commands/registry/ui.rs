@@ -16,12 +16,16 @@ pub fn register_ui(m: &mut std::collections::HashMap<&'static str, CommandDef>)
     m.insert("OUT.QRY", CommandDef::new("OUT.QRY", None, ArgCount::Range(0, 1), "Output queries"));
     m.insert("OUT.CFM", CommandDef::new("OUT.CFM", None, ArgCount::Range(0, 1), "Output confirmations"));
     m.insert("REPL.DUMP", CommandDef::new("REPL.DUMP", None, ArgCount::Range(0, 1), "REPL dump mode"));
+    m.insert("KEYS", CommandDef::new("KEYS", None, ArgCount::None, "Reload and list key bindings"));
 
     // Meters
     m.insert("METER.HDR", CommandDef::new("METER.HDR", None, ArgCount::Range(0, 1), "Show meters in header"));
     m.insert("METER.GRID", CommandDef::new("METER.GRID", None, ArgCount::Range(0, 1), "Show meters in grid"));
     m.insert("METER.ASCII", CommandDef::new("METER.ASCII", None, ArgCount::Range(0, 1), "ASCII meters"));
 
+    // Mouse
+    m.insert("MOUSE", CommandDef::new("MOUSE", None, ArgCount::Range(0, 1), "Mouse capture"));
+
     // Grid
     m.insert("GRID", CommandDef::new("GRID", None, ArgCount::Range(0, 1), "Grid visibility"));
     m.insert("GRID.DEF", CommandDef::new("GRID.DEF", None, ArgCount::Range(0, 1), "Grid default view"));
@@ -33,9 +37,11 @@ pub fn register_ui(m: &mut std::collections::HashMap<&'static str, CommandDef>)
     // Highlighting
     m.insert("HL.COND", CommandDef::new("HL.COND", None, ArgCount::Range(0, 1), "Highlight conditionals"));
     m.insert("HL.SEQ", CommandDef::new("HL.SEQ", None, ArgCount::Range(0, 1), "Highlight sequences"));
+    m.insert("HL.SYN", CommandDef::new("HL.SYN", None, ArgCount::Range(0, 1), "Syntax highlighting"));
 
     // Activity
     m.insert("ACTIVITY", CommandDef::new("ACTIVITY", None, ArgCount::Range(0, 1), "Activity indicator"));
+    m.insert("SCRIPT.LEN", CommandDef::new("SCRIPT.LEN", None, ArgCount::Range(0, 1), "Script editor length (8-32)"));
     m.insert("FLASH", CommandDef::new("FLASH", None, ArgCount::Range(0, 1), "Activity flash duration"));
 
     // Spectrum and Scope
@@ -48,6 +54,21 @@ pub fn register_ui(m: &mut std::collections::HashMap<&'static str, CommandDef>)
     m.insert("SCG", CommandDef::new("SCG", None, ArgCount::Range(0, 1), "Scope input gain"));
     m.insert("SCOPE.RST", CommandDef::new("SCOPE.RST", Some("SCR"), ArgCount::None, "Reset scope settings"));
     m.insert("SCR", CommandDef::new("SCR", None, ArgCount::None, "Reset scope settings"));
+    m.insert("SCOPE.TRIG", CommandDef::new("SCOPE.TRIG", None, ArgCount::Range(0, 1), "Scope trigger mode"));
+    m.insert("SCOPE.LVL", CommandDef::new("SCOPE.LVL", None, ArgCount::Range(0, 1), "Scope trigger level"));
+    m.insert("SCOPE.HOLD", CommandDef::new("SCOPE.HOLD", None, ArgCount::Range(0, 1), "Scope trigger holdoff"));
+    m.insert("SCOPE.ARM", CommandDef::new("SCOPE.ARM", None, ArgCount::None, "Arm single-shot capture"));
+    m.insert("SCOPE.XY", CommandDef::new("SCOPE.XY", None, ArgCount::Range(0, 1), "Scope X/Y mode"));
+    m.insert("SCOPE.SRC", CommandDef::new("SCOPE.SRC", None, ArgCount::Range(0, 1), "Scope overlay voice"));
+
+    // Spectrogram page
+    m.insert("SPEC.BANDS", CommandDef::new("SPEC.BANDS", None, ArgCount::Range(0, 1), "Spectrogram band count (8-64)"));
+    m.insert("SPEC.SCALE", CommandDef::new("SPEC.SCALE", None, ArgCount::Range(0, 1), "Spectrogram log/linear scaling"));
+    m.insert("SPEC.MODE", CommandDef::new("SPEC.MODE", None, ArgCount::Range(0, 1), "Spectrogram peak/average"));
+    m.insert("SPEC.DISP", CommandDef::new("SPEC.DISP", None, ArgCount::Range(0, 1), "Spectrogram braille/block"));
+    m.insert("SPEC.FREEZE", CommandDef::new("SPEC.FREEZE", None, ArgCount::Range(0, 1), "Freeze spectrogram"));
+    m.insert("SPEC.SNAP", CommandDef::new("SPEC.SNAP", None, ArgCount::None, "Snapshot spectrum to compare"));
+    m.insert("SPEC.SNAP.CLR", CommandDef::new("SPEC.SNAP.CLR", None, ArgCount::None, "Clear spectrum snapshot"));
 
     // Notes
     m.insert("NOTE", CommandDef::new("NOTE", None, ArgCount::AtLeast(1), "Add note"));
@@ -11,6 +11,38 @@ use crate::commands::validate::{
     validate_all_pn_expressions,
 };
 use crate::commands::validate_expr::validate_expression;
+use std::fmt;
+
+/// A validation error that points at one token of the line, so the editor can
+/// highlight it. Displays as the plain message.
+#[derive(Debug)]
+pub struct TokenError {
+    pub message: String,
+    /// Index of the offending whitespace-separated token
+    pub token: usize,
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// The token a validation error points at, if it points at one
+pub fn error_token(error: &anyhow::Error) -> Option<usize> {
+    error.downcast_ref::<TokenError>().map(|e| e.token)
+}
+
+fn token_error(token: usize, message: String) -> anyhow::Error {
+    anyhow::Error::new(TokenError { message, token })
+}
+
+/// Attach the token index to an error from a value check
+fn at_token(token: usize) -> impl FnOnce(anyhow::Error) -> anyhow::Error {
+    move |error| token_error(token, error.to_string())
+}
 
 /// Validates that a pattern number is in range 0-5.
 fn validate_pattern_number(val: &str) -> Result<()> {
@@ -52,7 +84,7 @@ fn validate_script_reference(val: &str) -> Result<()> {
 /// Validate a script command using the command registry
 /// Returns Ok(()) if valid, Err with message if invalid
 pub fn validate_from_registry(line: &str) -> Result<()> {
-    let trimmed = line.trim();
+    let trimmed = crate::utils::strip_comment(line).trim();
     if trimmed.is_empty() {
         return Ok(());
     }
@@ -162,7 +194,7 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
     let cmd_def = COMMAND_REGISTRY.get(command.as_str());
 
     if cmd_def.is_none() {
-        return Err(anyhow::anyhow!("UNKNOWN COMMAND: {}", command));
+        return Err(token_error(0, format!("UNKNOWN COMMAND: {}", command)));
     }
 
     let cmd_def = cmd_def.unwrap();
@@ -171,29 +203,31 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
     match &cmd_def.args {
         ArgCount::None => {
             if argc > 0 {
-                return Err(anyhow::anyhow!("{} TAKES NO ARGUMENTS", command));
+                return Err(token_error(1, format!("{} TAKES NO ARGUMENTS", command)));
             }
         }
         ArgCount::Exactly(n) => {
             if argc != *n {
-                return Err(anyhow::anyhow!("{} TAKES EXACTLY {} ARGUMENT{}",
-                    command, n, if *n == 1 { "" } else { "S" }));
+                return Err(token_error(if argc > *n { n + 1 } else { 0 }, format!("{} TAKES EXACTLY {} ARGUMENT{}",
+                    command, n, if *n == 1 { "" } else { "S" })));
             }
         }
         ArgCount::AtLeast(n) => {
             if argc < *n {
-                return Err(anyhow::anyhow!("{} REQUIRES AT LEAST {} ARGUMENT{}",
-                    command, n, if *n == 1 { "" } else { "S" }));
+                return Err(token_error(0, format!("{} REQUIRES AT LEAST {} ARGUMENT{}",
+                    command, n, if *n == 1 { "" } else { "S" })));
             }
         }
         ArgCount::Range(min, max) => {
             if argc < *min || argc > *max {
+                // Too many points at the first extra argument, too few at the command
+                let token = if argc > *max { max + 1 } else { 0 };
                 if min == max {
-                    return Err(anyhow::anyhow!("{} TAKES EXACTLY {} ARGUMENT{}",
-                        command, min, if *min == 1 { "" } else { "S" }));
+                    return Err(token_error(token, format!("{} TAKES EXACTLY {} ARGUMENT{}",
+                        command, min, if *min == 1 { "" } else { "S" })));
                 } else {
-                    return Err(anyhow::anyhow!("{} TAKES {}-{} ARGUMENTS",
-                        command, min, max));
+                    return Err(token_error(token, format!("{} TAKES {}-{} ARGUMENTS",
+                        command, min, max)));
                 }
             }
         }
@@ -209,7 +243,7 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
             if argc > 0 {
                 let consumed = validate_expression(&parts, 1)?;
                 if consumed != argc {
-                    return Err(anyhow::anyhow!("EXTRA TOKENS AFTER EXPRESSION"));
+                    return Err(token_error(consumed + 1, "EXTRA TOKENS AFTER EXPRESSION".to_string()));
                 }
             }
         }
@@ -220,22 +254,22 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
         "P" => {
             // Validate pattern index (0-63)
             if argc >= 1 {
-                validate_pattern_index(parts[1])?;
+                validate_pattern_index(parts[1]).map_err(at_token(1))?;
             }
         }
         "P.N" => {
             // Validate pattern number (0-5)
             if argc >= 1 {
-                validate_pattern_number(parts[1])?;
+                validate_pattern_number(parts[1]).map_err(at_token(1))?;
             }
         }
         "PN" => {
             // Validate pattern number and index
             if argc >= 1 {
-                validate_pattern_number(parts[1])?;
+                validate_pattern_number(parts[1]).map_err(at_token(1))?;
             }
             if argc >= 2 {
-                validate_pattern_index(parts[2])?;
+                validate_pattern_index(parts[2]).map_err(at_token(2))?;
             }
         }
         "PN.HERE" | "PN.NEXT" | "PN.PREV" | "PN.POP" | "PN.REV" | "PN.SHUF" | "PN.SORT" |
@@ -243,44 +277,49 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
         "PN.L" | "PN.I" => {
             // Validate pattern number (first arg)
             if argc >= 1 {
-                validate_pattern_number(parts[1])?;
+                validate_pattern_number(parts[1]).map_err(at_token(1))?;
             }
         }
         "PN.PUSH" | "PN.ADD" | "PN.SUB" | "PN.MUL" | "PN.DIV" | "PN.MOD" | "PN.FND" |
         "PN.RM" | "PN.ROT" => {
             // Validate pattern number (first arg)
             if argc >= 1 {
-                validate_pattern_number(parts[1])?;
+                validate_pattern_number(parts[1]).map_err(at_token(1))?;
             }
         }
         "PN.INS" | "PN.SCALE" => {
             // Validate pattern number (first arg)
             if argc >= 1 {
-                validate_pattern_number(parts[1])?;
+                validate_pattern_number(parts[1]).map_err(at_token(1))?;
             }
         }
         "SCRIPT" | "$" => {
             // Validate script reference (1-8, M, or I)
             if argc == 1 {
-                validate_script_reference(parts[1])?;
+                validate_script_reference(parts[1]).map_err(at_token(1))?;
             }
         }
         "M.SCRIPT" => {
             // Validate script reference
             if argc >= 1 {
-                validate_script_reference(parts[1])?;
+                validate_script_reference(parts[1]).map_err(at_token(1))?;
             }
         }
         "MUTE" => {
             // Validate script reference for MUTE <script>
             if argc == 1 {
-                validate_script_reference(parts[1])?;
+                validate_script_reference(parts[1]).map_err(at_token(1))?;
+            }
+        }
+        "SEED.SC" => {
+            if argc >= 1 {
+                validate_script_reference(parts[1]).map_err(at_token(1))?;
             }
         }
         "TOG" => {
             // Validate that TOG has two different values
             if argc >= 2 && parts[1] == parts[2] {
-                return Err(anyhow::anyhow!("TOG REQUIRES TWO DIFFERENT VALUES"));
+                return Err(token_error(2, "TOG REQUIRES TWO DIFFERENT VALUES".to_string()));
             }
         }
         "PAGE" | "PG" => {
@@ -290,8 +329,8 @@ pub fn validate_from_registry(line: &str) -> Result<()> {
                 match page_arg.as_str() {
                     "LIVE" | "L" | "HELP" | "H" | "GRID" | "G" |
                     "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" |
-                    "M" | "I" | "P" | "V" | "N" | "S" => {}
-                    _ => return Err(anyhow::anyhow!("INVALID PAGE \"{}\"", page_arg))
+                    "M" | "I" | "P" | "V" | "N" | "S" | "W" | "B" => {}
+                    _ => return Err(token_error(1, format!("INVALID PAGE \"{}\"", page_arg)))
                 }
             }
         }
@@ -11,6 +11,7 @@ pub mod source_levels;
 pub mod plaits;
 pub mod mixer;
 pub mod sampler;
+pub mod snapshot;
 
 pub use oscillator::*;
 pub use modulation::*;
@@ -24,3 +25,4 @@ pub use source_levels::*;
 pub use plaits::*;
 pub use mixer::*;
 pub use sampler::*;
+pub use snapshot::*;
@@ -145,6 +145,7 @@ where
     let sampler = &mut *ctx.sampler_state;
     sampler.mode = mode;
     sampler.kit_path = Some(resolved_path_str.to_string());
+    sampler.snap_source = None;
     sampler.num_slots = num_slots;
     sampler.current_slot = 0;
 
@@ -164,6 +165,11 @@ where
 
     crate::eval::KIT_SLOTS.store(num_slots as u16, std::sync::atomic::Ordering::Relaxed);
 
+    sampler.source_bpm = crate::sampler::stretch::known_source_bpm(&resolved_path);
+    if sampler.playback.stretch {
+        super::stretch::send_stretch_tempo(ctx)?;
+    }
+
     if *ctx.debug_level >= TIER_CONFIRMS || *ctx.out_cfm {
         let mode_str = match mode {
             SamplerMode::Slice => "SLICE",
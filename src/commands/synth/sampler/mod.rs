@@ -2,8 +2,12 @@ mod macros;
 mod utils;
 mod commands;
 mod params;
+mod slicing;
+mod stretch;
 
 pub use commands::{handle_kit, handle_str, handle_kit_len, handle_kit_info};
+pub use slicing::{handle_s_mark, handle_s_mark_mv, handle_s_unmark, handle_s_snap, handle_s_export};
+pub use stretch::{handle_s_stretch, handle_s_bpm};
 pub use params::{
     handle_s_rate, handle_s_pitch, handle_s_fine, handle_s_dir, handle_s_loop,
     handle_s_start, handle_s_len, handle_s_atk, handle_s_dec, handle_s_rel,
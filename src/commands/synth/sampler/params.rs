@@ -400,6 +400,7 @@ where
 
     let slice_len = total_frames / n;
 
+    sampler_state.snap_source = None;
     sampler_state.slots.clear();
     for i in 0..n {
         let start_frame = i * slice_len;
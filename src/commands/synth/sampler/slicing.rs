@@ -0,0 +1,304 @@
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::output::OutputDecider;
+use crate::sampler::slice::{
+    export_slices, frame_to_position, insert_marker, move_marker, position_to_frame,
+    remove_marker, snap_to_transient, snap_to_zero_crossing, whole_file_slot, SnapMode, SnapSource,
+    TRANSIENT_SNAP_MS, ZERO_CROSSING_RADIUS,
+};
+use crate::types::{OutputCategory, SamplerMode};
+use anyhow::Result;
+use std::path::Path;
+
+fn eval_arg(parts: &[&str], idx: usize, ctx: &mut ExecutionContext) -> Option<i16> {
+    if let Some((expr_val, _)) = eval_expression(
+        parts, idx, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+    ) {
+        Some(expr_val)
+    } else {
+        parts.get(idx).and_then(|p| p.parse().ok())
+    }
+}
+
+/// Checks slice mode with a loaded file, returning the total frame count
+fn require_slice_file<F>(name: &str, ctx: &mut ExecutionContext, output: &mut F) -> Option<usize>
+where
+    F: FnMut(String),
+{
+    if ctx.sampler_state.mode != SamplerMode::Slice {
+        ctx.output(OutputCategory::Error, format!("{}: SLICE MODE ONLY", name), &mut *output);
+        return None;
+    }
+
+    match ctx.sampler_state.total_frames {
+        Some(frames) if frames > 0 => {
+            if ctx.sampler_state.slots.is_empty() {
+                ctx.sampler_state.slots.push(whole_file_slot(frames));
+            }
+            Some(frames)
+        }
+        _ => {
+            ctx.output(OutputCategory::Error, format!("{}: NO FILE LOADED", name), &mut *output);
+            None
+        }
+    }
+}
+
+/// Applies the current snap mode to a marker frame. The file is decoded
+/// once and kept in the sampler state until the next KIT or S.SLICE.
+fn snap_frame(ctx: &mut ExecutionContext, frame: usize) -> usize {
+    let sampler = &mut *ctx.sampler_state;
+    let mode = SnapMode::from_u8(sampler.snap_mode);
+    if mode == SnapMode::Off {
+        return frame;
+    }
+
+    let Some(path) = sampler.kit_path.as_deref() else {
+        return frame;
+    };
+    if sampler.snap_source.as_ref().map(|source| source.path.as_str()) != Some(path) {
+        sampler.snap_source = SnapSource::load(path).ok();
+    }
+    let Some(source) = sampler.snap_source.as_mut() else {
+        return frame;
+    };
+
+    match mode {
+        SnapMode::ZeroCrossing => snap_to_zero_crossing(&source.mono, frame, ZERO_CROSSING_RADIUS),
+        SnapMode::Transient => {
+            let onsets = source.onsets(sampler.onset_sensitivity, sampler.onset_min_spacing_ms);
+            let radius = (TRANSIENT_SNAP_MS / 1000.0 * source.sample_rate as f32) as usize;
+            snap_to_transient(&onsets, frame, radius)
+        }
+        SnapMode::Off => frame,
+    }
+}
+
+fn sync_slot_count(ctx: &mut ExecutionContext) {
+    let sampler = &mut *ctx.sampler_state;
+    let count = sampler.slots.len();
+    sampler.num_slots = count;
+    sampler.slice_count = Some(count);
+    sampler.current_slot = sampler.current_slot.min(count.saturating_sub(1));
+    crate::eval::KIT_SLOTS.store(count as u16, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn handle_s_mark<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "S.MARK: REQUIRES POSITION".to_string(), &mut output);
+        return Ok(());
+    }
+
+    let Some(total_frames) = require_slice_file("S.MARK", ctx, &mut output) else {
+        return Ok(());
+    };
+
+    let Some(position) = eval_arg(parts, 1, ctx) else {
+        ctx.output(OutputCategory::Error, "S.MARK: INVALID VALUE".to_string(), &mut output);
+        return Ok(());
+    };
+
+    if !(0..=16383).contains(&position) {
+        ctx.output(OutputCategory::Error, "S.MARK: RANGE 0-16383".to_string(), &mut output);
+        return Ok(());
+    }
+
+    if ctx.sampler_state.slots.len() >= 128 {
+        ctx.output(OutputCategory::Error, "S.MARK: MAX 128 SLICES".to_string(), &mut output);
+        return Ok(());
+    }
+
+    let frame = snap_frame(ctx, position_to_frame(position, total_frames));
+
+    match insert_marker(&mut ctx.sampler_state.slots, frame) {
+        Some(slot) => {
+            sync_slot_count(ctx);
+            ctx.output(
+                OutputCategory::Confirm,
+                format!("S.MARK: SLOT {} AT {}", slot, frame_to_position(frame, total_frames)),
+                &mut output,
+            );
+        }
+        None => {
+            ctx.output(OutputCategory::Error, "S.MARK: MARKER EXISTS".to_string(), &mut output);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_s_mark_mv<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 3 {
+        ctx.output(OutputCategory::Error, "S.MARK.MV: REQUIRES SLOT POSITION".to_string(), &mut output);
+        return Ok(());
+    }
+
+    let Some(total_frames) = require_slice_file("S.MARK.MV", ctx, &mut output) else {
+        return Ok(());
+    };
+
+    let (Some(slot), Some(position)) = (eval_arg(parts, 1, ctx), eval_arg(parts, 2, ctx)) else {
+        ctx.output(OutputCategory::Error, "S.MARK.MV: INVALID VALUE".to_string(), &mut output);
+        return Ok(());
+    };
+
+    let slot_count = ctx.sampler_state.slots.len();
+    if slot < 1 || slot as usize >= slot_count {
+        ctx.output(
+            OutputCategory::Error,
+            format!("S.MARK.MV: SLOT RANGE 1-{}", slot_count.saturating_sub(1)),
+            &mut output,
+        );
+        return Ok(());
+    }
+
+    if !(0..=16383).contains(&position) {
+        ctx.output(OutputCategory::Error, "S.MARK.MV: RANGE 0-16383".to_string(), &mut output);
+        return Ok(());
+    }
+
+    let frame = snap_frame(ctx, position_to_frame(position, total_frames));
+
+    match move_marker(&mut ctx.sampler_state.slots, slot as usize, frame) {
+        Some(frame) => {
+            ctx.output(
+                OutputCategory::Confirm,
+                format!("S.MARK.MV: SLOT {} AT {}", slot, frame_to_position(frame, total_frames)),
+                &mut output,
+            );
+        }
+        None => {
+            ctx.output(OutputCategory::Error, "S.MARK.MV: NO ROOM TO MOVE".to_string(), &mut output);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_s_unmark<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "S.UNMARK: REQUIRES SLOT".to_string(), &mut output);
+        return Ok(());
+    }
+
+    if require_slice_file("S.UNMARK", ctx, &mut output).is_none() {
+        return Ok(());
+    }
+
+    let Some(slot) = eval_arg(parts, 1, ctx) else {
+        ctx.output(OutputCategory::Error, "S.UNMARK: INVALID VALUE".to_string(), &mut output);
+        return Ok(());
+    };
+
+    let slot_count = ctx.sampler_state.slots.len();
+    if slot < 1 || !remove_marker(&mut ctx.sampler_state.slots, slot as usize) {
+        ctx.output(
+            OutputCategory::Error,
+            format!("S.UNMARK: SLOT RANGE 1-{}", slot_count.saturating_sub(1)),
+            &mut output,
+        );
+        return Ok(());
+    }
+
+    sync_slot_count(ctx);
+    ctx.output(OutputCategory::Confirm, format!("S.UNMARK: REMOVED SLOT {}", slot), &mut output);
+
+    Ok(())
+}
+
+pub fn handle_s_snap<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let mode = SnapMode::from_u8(ctx.sampler_state.snap_mode);
+        ctx.output(
+            OutputCategory::Query,
+            format!("S.SNAP: {} ({})", ctx.sampler_state.snap_mode, mode.label()),
+            &mut output,
+        );
+        return Ok(());
+    }
+
+    let Some(value) = eval_arg(parts, 1, ctx) else {
+        ctx.output(OutputCategory::Error, "S.SNAP: INVALID VALUE".to_string(), &mut output);
+        return Ok(());
+    };
+
+    if !(0..=2).contains(&value) {
+        ctx.output(OutputCategory::Error, "S.SNAP: RANGE 0-2".to_string(), &mut output);
+        return Ok(());
+    }
+
+    ctx.sampler_state.snap_mode = value as u8;
+    let mode = SnapMode::from_u8(value as u8);
+    ctx.output(OutputCategory::Confirm, format!("S.SNAP: {}", mode.label()), &mut output);
+
+    Ok(())
+}
+
+pub fn handle_s_export<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "S.EXPORT: REQUIRES KIT NAME".to_string(), &mut output);
+        return Ok(());
+    }
+
+    if require_slice_file("S.EXPORT", ctx, &mut output).is_none() {
+        return Ok(());
+    }
+
+    let Some(source) = ctx.sampler_state.kit_path.clone() else {
+        ctx.output(OutputCategory::Error, "S.EXPORT: NO FILE LOADED".to_string(), &mut output);
+        return Ok(());
+    };
+
+    let kit_name = crate::scene::sanitize_name(&parts[1..].join(" "));
+    if kit_name.is_empty() {
+        ctx.output(OutputCategory::Error, "S.EXPORT: INVALID KIT NAME".to_string(), &mut output);
+        return Ok(());
+    }
+
+    let dest_dir = match crate::config::monokit_config_dir() {
+        Ok(config_dir) => config_dir.join("samples").join(&kit_name),
+        Err(_) => {
+            ctx.output(OutputCategory::Error, "S.EXPORT: CONFIG DIR NOT FOUND".to_string(), &mut output);
+            return Ok(());
+        }
+    };
+
+    if dest_dir.exists() {
+        ctx.output(OutputCategory::Error, format!("S.EXPORT: KIT {} EXISTS", kit_name), &mut output);
+        return Ok(());
+    }
+
+    match export_slices(Path::new(&source), &ctx.sampler_state.slots, &dest_dir) {
+        Ok(files) => {
+            ctx.output(
+                OutputCategory::Confirm,
+                format!("S.EXPORT: {} SLICES TO {}", files.len(), kit_name),
+                &mut output,
+            );
+        }
+        Err(e) => {
+            ctx.output(
+                OutputCategory::Error,
+                format!("S.EXPORT: {}", e.to_string().to_uppercase()),
+                &mut output,
+            );
+        }
+    }
+
+    Ok(())
+}
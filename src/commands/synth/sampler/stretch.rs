@@ -0,0 +1,145 @@
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::output::OutputDecider;
+use crate::sampler::library::detect_bpm;
+use crate::sampler::stretch::interval_to_bpm;
+use crate::types::{MetroCommand, OutputCategory, SamplerMode, SyncMode};
+use anyhow::{Context, Result};
+use rosc::OscType;
+use std::path::Path;
+
+/// File the tempo belongs to: the slice source, or the current kit slot
+fn tempo_source_path(ctx: &ExecutionContext) -> Option<String> {
+    let sampler = &*ctx.sampler_state;
+    match sampler.mode {
+        SamplerMode::Slice => sampler.kit_path.clone(),
+        SamplerMode::Kit => sampler.slots.get(sampler.current_slot).and_then(|s| s.file_path.clone()),
+    }
+}
+
+/// Sends the loop tempo, plus the metro tempo when the internal clock is in charge
+/// (under MIDI clock the metro thread keeps the sampler's tempo up to date)
+pub(super) fn send_stretch_tempo(ctx: &mut ExecutionContext) -> Result<()> {
+    if let Some(bpm) = ctx.sampler_state.source_bpm {
+        ctx.metro_tx
+            .send(MetroCommand::SendParam("s_srcBpm".to_string(), OscType::Float(bpm)))
+            .context("Failed to send sampler source BPM")?;
+    }
+
+    if *ctx.sync_mode == SyncMode::Internal {
+        ctx.metro_tx
+            .send(MetroCommand::SendParam(
+                "s_tempoBpm".to_string(),
+                OscType::Float(interval_to_bpm(*ctx.metro_interval)),
+            ))
+            .context("Failed to send sampler tempo")?;
+    }
+
+    Ok(())
+}
+
+fn format_bpm(bpm: Option<f32>) -> String {
+    match bpm {
+        Some(bpm) => format!("{:.1} BPM", bpm),
+        None => "UNKNOWN BPM".to_string(),
+    }
+}
+
+pub fn handle_s_stretch<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let enabled = ctx.sampler_state.playback.stretch as u8;
+        let bpm = format_bpm(ctx.sampler_state.source_bpm);
+        ctx.output(OutputCategory::Query, format!("S.STRETCH: {} ({})", enabled, bpm), &mut output);
+        return Ok(());
+    }
+
+    let value = if let Some((expr_val, _)) = eval_expression(
+        parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+    ) {
+        expr_val
+    } else {
+        match parts[1].parse::<i16>() {
+            Ok(v) => v,
+            Err(_) => {
+                ctx.output(OutputCategory::Error, "S.STRETCH: INVALID VALUE".to_string(), &mut output);
+                return Ok(());
+            }
+        }
+    };
+
+    if !(0..=1).contains(&value) {
+        ctx.output(OutputCategory::Error, "S.STRETCH: RANGE 0-1".to_string(), &mut output);
+        return Ok(());
+    }
+
+    if value == 1 && ctx.sampler_state.source_bpm.is_none() {
+        ctx.sampler_state.source_bpm = tempo_source_path(ctx).and_then(|path| detect_bpm(Path::new(&path)));
+        if ctx.sampler_state.source_bpm.is_none() {
+            ctx.output(OutputCategory::Error, "S.STRETCH: NO BPM (SET S.BPM)".to_string(), &mut output);
+            return Ok(());
+        }
+    }
+
+    ctx.sampler_state.playback.stretch = value == 1;
+    if value == 1 {
+        send_stretch_tempo(ctx)?;
+    }
+    ctx.metro_tx
+        .send(MetroCommand::SendParam("s_stretch".to_string(), OscType::Int(value as i32)))
+        .context("Failed to send sampler stretch")?;
+
+    let message = if value == 1 {
+        format!("S.STRETCH: ON ({})", format_bpm(ctx.sampler_state.source_bpm))
+    } else {
+        "S.STRETCH: OFF".to_string()
+    };
+    ctx.output(OutputCategory::Confirm, message, &mut output);
+
+    Ok(())
+}
+
+pub fn handle_s_bpm<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let bpm = format_bpm(ctx.sampler_state.source_bpm);
+        ctx.output(OutputCategory::Query, format!("S.BPM: {}", bpm), &mut output);
+        return Ok(());
+    }
+
+    let value: f32 = if let Ok(v) = parts[1].parse::<f32>() {
+        v
+    } else if let Some((expr_val, _)) = eval_expression(
+        parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+    ) {
+        expr_val as f32
+    } else {
+        ctx.output(OutputCategory::Error, "S.BPM: INVALID VALUE".to_string(), &mut output);
+        return Ok(());
+    };
+
+    // 0 re-detects the tempo from the audio
+    let bpm = if value == 0.0 {
+        let detected = tempo_source_path(ctx).and_then(|path| detect_bpm(Path::new(&path)));
+        if detected.is_none() {
+            ctx.output(OutputCategory::Error, "S.BPM: NO TEMPO DETECTED".to_string(), &mut output);
+            return Ok(());
+        }
+        detected
+    } else if (20.0..=300.0).contains(&value) {
+        Some(value)
+    } else {
+        ctx.output(OutputCategory::Error, "S.BPM: RANGE 20-300 (0=DETECT)".to_string(), &mut output);
+        return Ok(());
+    };
+
+    ctx.sampler_state.source_bpm = bpm;
+    send_stretch_tempo(ctx)?;
+    ctx.output(OutputCategory::Confirm, format!("S.BPM: {}", format_bpm(bpm)), &mut output);
+
+    Ok(())
+}
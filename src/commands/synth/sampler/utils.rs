@@ -6,6 +6,8 @@ use std::path::{Path, PathBuf};
 
 use crate::types::OSC_ADDR;
 
+pub use crate::sampler::library::is_audio_file;
+
 pub const MAX_DISPLAY_WIDTH: usize = 46;
 pub const INDENT_WIDTH: usize = 2;
 
@@ -83,14 +85,6 @@ pub fn search_library_recursive(dir: &Path, target: &str) -> Option<PathBuf> {
     None
 }
 
-pub fn is_audio_file(path: &Path) -> bool {
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        matches!(ext, "wav" | "WAV" | "aif" | "AIF" | "aiff" | "AIFF" | "flac" | "FLAC")
-    } else {
-        false
-    }
-}
-
 pub fn read_wav_frame_count(path: &Path) -> Option<usize> {
     let file = File::open(path).ok()?;
     let reader = hound::WavReader::new(file).ok()?;
@@ -0,0 +1,111 @@
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::output::OutputDecider;
+use crate::snapshot::{slot_in_range, MORPH_MAX, SNAP_SLOTS};
+use crate::types::{MetroCommand, OutputCategory};
+use anyhow::{Context, Result};
+
+const GLIDE_MAX_MS: i32 = 30000;
+
+/// Evaluate `count` consecutive arguments, each of which may be an expression
+fn eval_args(parts: &[&str], count: usize, ctx: &mut ExecutionContext) -> Option<Vec<i32>> {
+    let mut values = Vec::with_capacity(count);
+    let mut idx = 1;
+    for _ in 0..count {
+        let (value, consumed) = eval_expression(
+            parts, idx, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale,
+        )?;
+        values.push(value as i32);
+        idx += consumed.max(1);
+    }
+    Some(values)
+}
+
+fn slot_error(cmd: &str) -> String {
+    format!("{}: SLOT 1-{}", cmd, SNAP_SLOTS)
+}
+
+pub fn handle_snap_save<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(&[slot]) = eval_args(parts, 1, ctx).as_deref() else {
+        ctx.output(OutputCategory::Error, "SNAP.SAVE: NEEDS SLOT".to_string(), &mut output);
+        return Ok(());
+    };
+    if !slot_in_range(slot) {
+        ctx.output(OutputCategory::Error, slot_error("SNAP.SAVE"), &mut output);
+        return Ok(());
+    }
+    ctx.metro_tx
+        .send(MetroCommand::SnapSave(slot as u8))
+        .context("Failed to send snapshot save to metro thread")?;
+    *ctx.scene_modified = true;
+    ctx.output(OutputCategory::Confirm, format!("SNAP SAVED TO {}", slot), &mut output);
+    Ok(())
+}
+
+pub fn handle_snap_load<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(&[slot]) = eval_args(parts, 1, ctx).as_deref() else {
+        ctx.output(OutputCategory::Error, "SNAP.LOAD: NEEDS SLOT".to_string(), &mut output);
+        return Ok(());
+    };
+    if !slot_in_range(slot) {
+        ctx.output(OutputCategory::Error, slot_error("SNAP.LOAD"), &mut output);
+        return Ok(());
+    }
+    ctx.metro_tx
+        .send(MetroCommand::SnapLoad(slot as u8))
+        .context("Failed to send snapshot load to metro thread")?;
+    ctx.output(OutputCategory::Confirm, format!("SNAP LOADED {}", slot), &mut output);
+    Ok(())
+}
+
+pub fn handle_snap_morph<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(&[a, b, amount]) = eval_args(parts, 3, ctx).as_deref() else {
+        ctx.output(OutputCategory::Error, "SNAP.MORPH: NEEDS A B 0-16383".to_string(), &mut output);
+        return Ok(());
+    };
+    if !slot_in_range(a) || !slot_in_range(b) {
+        ctx.output(OutputCategory::Error, slot_error("SNAP.MORPH"), &mut output);
+        return Ok(());
+    }
+    if !(0..=MORPH_MAX).contains(&amount) {
+        ctx.output(OutputCategory::Error, "SNAP.MORPH: POSITION 0-16383".to_string(), &mut output);
+        return Ok(());
+    }
+    ctx.metro_tx
+        .send(MetroCommand::SnapMorph(a as u8, b as u8, amount))
+        .context("Failed to send snapshot morph to metro thread")?;
+    ctx.output(OutputCategory::Confirm, format!("SNAP MORPH {}>{} AT {}", a, b, amount), &mut output);
+    Ok(())
+}
+
+pub fn handle_snap_glide<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(&[slot, ms]) = eval_args(parts, 2, ctx).as_deref() else {
+        ctx.output(OutputCategory::Error, "SNAP.GLIDE: NEEDS SLOT AND MS".to_string(), &mut output);
+        return Ok(());
+    };
+    if !slot_in_range(slot) {
+        ctx.output(OutputCategory::Error, slot_error("SNAP.GLIDE"), &mut output);
+        return Ok(());
+    }
+    if !(0..=GLIDE_MAX_MS).contains(&ms) {
+        ctx.output(OutputCategory::Error, format!("SNAP.GLIDE: TIME 0-{} MS", GLIDE_MAX_MS), &mut output);
+        return Ok(());
+    }
+    ctx.metro_tx
+        .send(MetroCommand::SnapGlide(slot as u8, ms as u64))
+        .context("Failed to send snapshot glide to metro thread")?;
+    ctx.output(OutputCategory::Confirm, format!("SNAP GLIDE TO {} OVER {} MS", slot, ms), &mut output);
+    Ok(())
+}
@@ -1,5 +1,10 @@
-use crate::types::MetroCommand;
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::loudness::{self, format_db, TARGET_CEILING_DB, TARGET_MAX, TARGET_MIN};
+use crate::output::OutputDecider;
+use crate::types::{MetroCommand, OutRoute, OutputCategory, MAX_OUTPUT_CHANNELS};
 use anyhow::Result;
+use rosc::OscType;
 use std::sync::mpsc::Sender;
 
 pub fn handle_audio_out<F>(
@@ -44,3 +49,172 @@ where
     }
     Ok(())
 }
+
+/// OUT.CH [2-12] - hardware outputs to open, in pairs. Checked against the
+/// device and applied by restarting the engine.
+pub fn handle_out_ch<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("OUT.CH: {}", ctx.output_channels), &mut output);
+        return Ok(());
+    }
+    match parts[1].parse::<u8>() {
+        Ok(channels) if (2..=MAX_OUTPUT_CHANNELS).contains(&channels) && channels % 2 == 0 => {
+            if channels == ctx.output_channels {
+                ctx.output(OutputCategory::Confirm, format!("OUT.CH: {}", channels), &mut output);
+            } else {
+                ctx.metro_tx.send(MetroCommand::SetOutputChannels(channels))?;
+                ctx.output(OutputCategory::Essential, format!("OUT.CH: {}", channels), &mut output);
+            }
+        }
+        _ => ctx.output(
+            OutputCategory::Error,
+            format!("ERROR: OUT.CH 2-{} IN PAIRS", MAX_OUTPUT_CHANNELS),
+            &mut output,
+        ),
+    }
+    Ok(())
+}
+
+fn voice_route<'a>(voice: &str, ctx: &'a mut ExecutionContext) -> Option<&'a mut OutRoute> {
+    match voice {
+        "OSC" => Some(&mut ctx.mixer_data.route_osc),
+        "PLA" => Some(&mut ctx.mixer_data.route_pla),
+        "NOS" => Some(&mut ctx.mixer_data.route_nos),
+        "SMP" => Some(&mut ctx.mixer_data.route_smp),
+        _ => None,
+    }
+}
+
+fn route_message(voice: &str, route: OutRoute) -> String {
+    match route.pair {
+        0 => format!("OUT.ROUTE {}: MASTER", voice),
+        pair => format!("OUT.ROUTE {}: {} {}", voice, pair, if route.post { "POST" } else { "PRE" }),
+    }
+}
+
+/// OUT.ROUTE <OSC|PLA|NOS|SMP> [PAIR] [PRE|POST] - send a voice to its own
+/// output pair instead of the master, or back to the master with pair 0.
+/// Pair 1 carries the master itself.
+pub fn handle_out_route<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let usage = "ERROR: OUT.ROUTE OSC|PLA|NOS|SMP PAIR [PRE|POST]".to_string();
+    let Some(voice) = parts.get(1).map(|v| v.to_uppercase()) else {
+        ctx.output(OutputCategory::Error, usage, &mut output);
+        return Ok(());
+    };
+    let Some(current) = voice_route(&voice, ctx).copied() else {
+        ctx.output(OutputCategory::Error, usage, &mut output);
+        return Ok(());
+    };
+    if parts.len() < 3 {
+        ctx.output(OutputCategory::Query, route_message(&voice, current), &mut output);
+        return Ok(());
+    }
+
+    let Some((pair, consumed)) = eval_expression(parts, 2, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale) else {
+        ctx.output(OutputCategory::Error, usage, &mut output);
+        return Ok(());
+    };
+    let pairs = ctx.output_channels / 2;
+    if pairs < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: OUT.ROUTE NEEDS OUT.CH 4 OR MORE".to_string(), &mut output);
+        return Ok(());
+    }
+    if pair != 0 && !(2..=pairs as i16).contains(&pair) {
+        ctx.output(OutputCategory::Error, format!("ERROR: OUT.ROUTE PAIR 0 OR 2-{} (OUT.CH {})", pairs, ctx.output_channels), &mut output);
+        return Ok(());
+    }
+    let post = match parts.get(2 + consumed).map(|m| m.to_uppercase()).as_deref() {
+        None | Some("PRE") => false,
+        Some("POST") => true,
+        Some(_) => {
+            ctx.output(OutputCategory::Error, usage, &mut output);
+            return Ok(());
+        }
+    };
+
+    let route = OutRoute { pair: pair as u8, post };
+    if let Some(slot) = voice_route(&voice, ctx) {
+        *slot = route;
+    }
+    let name = voice.to_lowercase();
+    ctx.metro_tx.send(MetroCommand::SendParam(format!("route_{}", name), OscType::Int(route.pair as i32)))?;
+    ctx.metro_tx.send(MetroCommand::SendParam(format!("route_{}_post", name), OscType::Int(route.post as i32)))?;
+    ctx.output(OutputCategory::Confirm, route_message(&voice, route), &mut output);
+    Ok(())
+}
+
+/// LUFS - momentary, short-term and integrated loudness with the true peak
+pub fn handle_lufs<F>(_parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let meter = &*ctx.loudness;
+    let msg = format!(
+        "LUFS M {} S {} I {} TP {} (MAX {})",
+        format_db(meter.momentary),
+        format_db(meter.short_term),
+        format_db(meter.integrated),
+        format_db(meter.true_peak),
+        format_db(meter.max_true_peak),
+    );
+    ctx.output(OutputCategory::Query, msg, &mut output);
+    Ok(())
+}
+
+/// LUFS.RST - start the integrated loudness and max true peak over
+pub fn handle_lufs_rst<F>(_parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.loudness.reset();
+    ctx.output(OutputCategory::Confirm, "LUFS: RESET".to_string(), &mut output);
+    Ok(())
+}
+
+fn target_message(target: Option<f32>) -> String {
+    match target {
+        Some(target) => format!("LUFS.TARGET: {} (CEILING {} DBTP)", target, TARGET_CEILING_DB),
+        None => "LUFS.TARGET: OFF".to_string(),
+    }
+}
+
+/// LUFS.TARGET [-36..-6|0] - ride the output gain towards a loudness, with the
+/// limiter on at a -1dBTP ceiling. 0 turns it off and restores LIMIT.
+pub fn handle_lufs_target<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, target_message(ctx.loudness.target), &mut output);
+        return Ok(());
+    }
+    let target = match eval_expression(parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale) {
+        Some((0, _)) => None,
+        Some((value, _)) if (TARGET_MIN..=TARGET_MAX).contains(&value) => Some(value as f32),
+        _ => {
+            ctx.output(
+                OutputCategory::Error,
+                format!("ERROR: LUFS.TARGET {} TO {}, OR 0 FOR OFF", TARGET_MIN, TARGET_MAX),
+                &mut output,
+            );
+            return Ok(());
+        }
+    };
+
+    ctx.loudness.set_target(target);
+    let (limit, ceiling) = match target {
+        Some(_) => (true, loudness::db_to_amp(TARGET_CEILING_DB)),
+        None => (*ctx.limiter_enabled, 1.0),
+    };
+    ctx.metro_tx.send(MetroCommand::SendParam("limit".to_string(), OscType::Int(limit as i32)))?;
+    ctx.metro_tx.send(MetroCommand::SendParam("limit_ceil".to_string(), OscType::Float(ceiling)))?;
+    ctx.metro_tx.send(MetroCommand::SendParam("out_gain".to_string(), OscType::Float(loudness::db_to_amp(ctx.loudness.gain_db))))?;
+    ctx.output(OutputCategory::Confirm, target_message(target), &mut output);
+    Ok(())
+}
@@ -1,4 +1,8 @@
+use crate::commands::context::ExecutionContext;
 use crate::config;
+use crate::output::OutputDecider;
+use crate::types::{OutputCategory, DEFAULT_SCRIPT_LINES, MAX_SCRIPT_LINES};
+use anyhow::Result;
 
 define_bool_toggle!(handle_cpu, "CPU", "CPU DISPLAY: {}", "CPU DISPLAY: OFF", "CPU DISPLAY: ON", config::save_show_cpu);
 
@@ -21,3 +25,27 @@ define_bool_toggle!(handle_out_cfm, "OUT.CFM", config::save_out_cfm);
 define_bool_toggle!(handle_cfm_quit, "CFM.QUIT", "CFM.QUIT: {}", "CFM.QUIT: OFF", "CFM.QUIT: ON (CONFIRM QUIT)", config::save_confirm_quit_unsaved);
 
 define_bool_toggle!(handle_cfm_save, "CFM.SAVE", "CFM.SAVE: {}", "CFM.SAVE: OFF", "CFM.SAVE: ON (CONFIRM OVERWRITE)", config::save_confirm_overwrite_scene);
+
+/// SCRIPT.LEN [8-32] - lines the script editor offers
+pub fn handle_script_len<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("SCRIPT.LEN: {}", *ctx.script_length), &mut output);
+        return Ok(());
+    }
+    match parts[1].parse::<usize>() {
+        Ok(lines) if (DEFAULT_SCRIPT_LINES..=MAX_SCRIPT_LINES).contains(&lines) => {
+            *ctx.script_length = lines;
+            let _ = config::save_script_length(lines);
+            ctx.output(OutputCategory::Confirm, format!("SCRIPT.LEN: {}", lines), &mut output);
+        }
+        _ => ctx.output(
+            OutputCategory::Error,
+            format!("ERROR: SCRIPT.LEN {}-{}", DEFAULT_SCRIPT_LINES, MAX_SCRIPT_LINES),
+            &mut output,
+        ),
+    }
+    Ok(())
+}
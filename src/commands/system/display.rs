@@ -1,6 +1,10 @@
+use crate::commands::context::ExecutionContext;
 use crate::config;
 use crate::eval::eval_expression;
-use crate::types::{Counters, MetroCommand, PatternStorage, ScaleState, ScriptStorage, Variables, TIER_CONFIRMS};
+use crate::output::OutputDecider;
+use crate::spectrogram::{SpectrumMode, SpectrumScale, MIN_BANDS};
+use crate::ui::grid_pages::GRID_MODE_NAMES;
+use crate::types::{Counters, MetroCommand, OutputCategory, PatternStorage, ScaleState, ScopeOverlay, ScopeTrigger, ScriptStorage, Variables, SPECTROGRAM_BANDS, TIER_CONFIRMS};
 use anyhow::{Context, Result};
 use rosc::OscType;
 use std::sync::mpsc::Sender;
@@ -332,6 +336,12 @@ where
     scope_settings.display_mode = 0;
     scope_settings.unipolar = false;
     scope_settings.gain = 8192;
+    scope_settings.trigger = ScopeTrigger::Free;
+    scope_settings.trig_level = 0;
+    scope_settings.holdoff_ms = 0;
+    scope_settings.xy = false;
+    scope_settings.overlay = ScopeOverlay::Off;
+    scope_settings.single_armed = false;
 
     metro_tx
         .send(MetroCommand::SendScopeRate(100.0))
@@ -341,12 +351,268 @@ where
         .send(MetroCommand::SendParam("scopeGain".to_string(), OscType::Float(1.0)))
         .context("Failed to send scope gain")?;
 
+    for param in ["scope_trig", "scope_xy", "scope_src"] {
+        metro_tx.send(MetroCommand::SendParam(param.to_string(), OscType::Int(0)))?;
+    }
+    for param in ["scope_level", "scope_hold"] {
+        metro_tx.send(MetroCommand::SendParam(param.to_string(), OscType::Float(0.0)))?;
+    }
+
     let _ = config::save_scope_settings(scope_settings);
 
     output("SCOPE RESET TO DEFAULTS".to_string());
     Ok(())
 }
 
+fn scope_value(parts: &[&str], ctx: &mut ExecutionContext) -> Option<i16> {
+    eval_expression(parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale).map(|(value, _)| value)
+}
+
+/// SCOPE.TRIG [0-3] - free-running, rising or falling edge, or single shot on TR
+pub fn handle_scope_trig<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let trigger = ctx.scope_settings.trigger;
+        ctx.output(OutputCategory::Query, format!("SCOPE.TRIG: {} ({})", trigger.to_u8(), trigger.name()), &mut output);
+        return Ok(());
+    }
+    let Some(trigger) = scope_value(parts, ctx).and_then(|v| u8::try_from(v).ok()).and_then(ScopeTrigger::from_u8) else {
+        ctx.output(OutputCategory::Error, "ERROR: SCOPE.TRIG TAKES 0-3".to_string(), &mut output);
+        return Ok(());
+    };
+    ctx.scope_settings.trigger = trigger;
+    ctx.scope_settings.single_armed = trigger == ScopeTrigger::Single;
+    ctx.metro_tx.send(MetroCommand::SendParam("scope_trig".to_string(), OscType::Int(trigger.to_u8() as i32)))?;
+    ctx.output(OutputCategory::Confirm, format!("SCOPE.TRIG: {} ({})", trigger.to_u8(), trigger.name()), &mut output);
+    Ok(())
+}
+
+/// SCOPE.LVL [-100..100] - trigger level in percent of full scale
+pub fn handle_scope_lvl<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("SCOPE.LVL: {}", ctx.scope_settings.trig_level), &mut output);
+        return Ok(());
+    }
+    match scope_value(parts, ctx) {
+        Some(level) if (-100..=100).contains(&level) => {
+            ctx.scope_settings.trig_level = level;
+            ctx.metro_tx.send(MetroCommand::SendParam("scope_level".to_string(), OscType::Float(level as f32 / 100.0)))?;
+            ctx.output(OutputCategory::Confirm, format!("SCOPE.LVL: {}", level), &mut output);
+        }
+        _ => ctx.output(OutputCategory::Error, "ERROR: SCOPE.LVL TAKES -100 TO 100".to_string(), &mut output),
+    }
+    Ok(())
+}
+
+/// SCOPE.HOLD [0-1000] - holdoff after each capture, in ms
+pub fn handle_scope_hold<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("SCOPE.HOLD: {}MS", ctx.scope_settings.holdoff_ms), &mut output);
+        return Ok(());
+    }
+    match scope_value(parts, ctx) {
+        Some(ms) if (0..=1000).contains(&ms) => {
+            ctx.scope_settings.holdoff_ms = ms as u32;
+            ctx.metro_tx.send(MetroCommand::SendParam("scope_hold".to_string(), OscType::Float(ms as f32 / 1000.0)))?;
+            ctx.output(OutputCategory::Confirm, format!("SCOPE.HOLD: {}MS", ms), &mut output);
+        }
+        _ => ctx.output(OutputCategory::Error, "ERROR: SCOPE.HOLD TAKES 0-1000 MS".to_string(), &mut output),
+    }
+    Ok(())
+}
+
+/// SCOPE.ARM - take another single-shot capture on the next TR
+pub fn handle_scope_arm<F>(_parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if ctx.scope_settings.trigger != ScopeTrigger::Single {
+        ctx.output(OutputCategory::Error, "ERROR: SCOPE.ARM NEEDS SCOPE.TRIG 3".to_string(), &mut output);
+        return Ok(());
+    }
+    ctx.scope_settings.single_armed = true;
+    ctx.output(OutputCategory::Confirm, "SCOPE: ARMED".to_string(), &mut output);
+    Ok(())
+}
+
+/// SCOPE.XY [0|1] - plot left against right
+pub fn handle_scope_xy<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let msg = format!("SCOPE.XY: {}", if ctx.scope_settings.xy { "ON" } else { "OFF" });
+        ctx.output(OutputCategory::Query, msg, &mut output);
+        return Ok(());
+    }
+    match scope_value(parts, ctx) {
+        Some(value @ (0 | 1)) => {
+            ctx.scope_settings.xy = value == 1;
+            ctx.metro_tx.send(MetroCommand::SendParam("scope_xy".to_string(), OscType::Int(value as i32)))?;
+            let msg = format!("SCOPE.XY: {}", if value == 1 { "ON" } else { "OFF" });
+            ctx.output(OutputCategory::Confirm, msg, &mut output);
+        }
+        _ => ctx.output(OutputCategory::Error, "ERROR: SCOPE.XY TAKES 0 (OFF) OR 1 (ON)".to_string(), &mut output),
+    }
+    Ok(())
+}
+
+/// SCOPE.SRC [0-4] - voice bus drawn over the master trace
+pub fn handle_scope_src<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let overlay = ctx.scope_settings.overlay;
+        ctx.output(OutputCategory::Query, format!("SCOPE.SRC: {} ({})", overlay.to_u8(), overlay.name()), &mut output);
+        return Ok(());
+    }
+    // The sclang server mixes the voices in one synth, so no voice busses exist to draw
+    if cfg!(not(feature = "scsynth-direct")) {
+        ctx.output(OutputCategory::Error, "ERROR: SCOPE.SRC NEEDS SCSYNTH-DIRECT MODE".to_string(), &mut output);
+        return Ok(());
+    }
+    let Some(overlay) = scope_value(parts, ctx).and_then(|v| u8::try_from(v).ok()).and_then(ScopeOverlay::from_u8) else {
+        ctx.output(OutputCategory::Error, "ERROR: SCOPE.SRC TAKES 0-4".to_string(), &mut output);
+        return Ok(());
+    };
+    ctx.scope_settings.overlay = overlay;
+    ctx.metro_tx.send(MetroCommand::SendParam("scope_src".to_string(), OscType::Int(overlay.to_u8() as i32)))?;
+    ctx.output(OutputCategory::Confirm, format!("SCOPE.SRC: {} ({})", overlay.to_u8(), overlay.name()), &mut output);
+    Ok(())
+}
+
+/// SPEC.BANDS [8-64] - bands shown on the spectrogram page
+pub fn handle_spec_bands<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("SPEC.BANDS: {}", ctx.spectrogram.bands), &mut output);
+        return Ok(());
+    }
+    match eval_expression(parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale) {
+        Some((value, _)) if (MIN_BANDS as i16..=SPECTROGRAM_BANDS as i16).contains(&value) => {
+            ctx.spectrogram.bands = value as usize;
+            ctx.output(OutputCategory::Confirm, format!("SPEC.BANDS: {}", value), &mut output);
+        }
+        _ => ctx.output(
+            OutputCategory::Error,
+            format!("ERROR: SPEC.BANDS TAKES {}-{}", MIN_BANDS, SPECTROGRAM_BANDS),
+            &mut output,
+        ),
+    }
+    Ok(())
+}
+
+/// Query or set a two-way spectrogram option. Returns the new choice when set.
+fn spec_option<F>(
+    parts: &[&str],
+    ctx: &mut ExecutionContext,
+    cmd: &str,
+    labels: [&str; 2],
+    current: usize,
+    output: &mut F,
+) -> Option<usize>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Query, format!("{}: {} ({})", cmd, current, labels[current]), output);
+        return None;
+    }
+    match eval_expression(parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale) {
+        Some((value, _)) if value == 0 || value == 1 => {
+            ctx.output(OutputCategory::Confirm, format!("{}: {} ({})", cmd, value, labels[value as usize]), output);
+            Some(value as usize)
+        }
+        _ => {
+            ctx.output(
+                OutputCategory::Error,
+                format!("ERROR: {} TAKES 0 ({}) OR 1 ({})", cmd, labels[0], labels[1]),
+                output,
+            );
+            None
+        }
+    }
+}
+
+/// SPEC.SCALE [0|1] - log (dB) or linear band levels
+pub fn handle_spec_scale<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let current = if ctx.spectrogram.scale == SpectrumScale::Log { 0 } else { 1 };
+    if let Some(choice) = spec_option(parts, ctx, "SPEC.SCALE", ["LOG", "LIN"], current, &mut output) {
+        ctx.spectrogram.scale = if choice == 0 { SpectrumScale::Log } else { SpectrumScale::Linear };
+    }
+    Ok(())
+}
+
+/// SPEC.MODE [0|1] - peak (fast attack, slow fall) or running average
+pub fn handle_spec_mode<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let current = if ctx.spectrogram.mode == SpectrumMode::Peak { 0 } else { 1 };
+    if let Some(choice) = spec_option(parts, ctx, "SPEC.MODE", ["PEAK", "AVG"], current, &mut output) {
+        ctx.spectrogram.mode = if choice == 0 { SpectrumMode::Peak } else { SpectrumMode::Average };
+    }
+    Ok(())
+}
+
+/// SPEC.DISP [0|1] - braille or block shade waterfall
+pub fn handle_spec_disp<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let current = ctx.spectrogram.display_mode as usize;
+    if let Some(choice) = spec_option(parts, ctx, "SPEC.DISP", ["BRAILLE", "BLOCK"], current, &mut output) {
+        ctx.spectrogram.display_mode = choice as u8;
+    }
+    Ok(())
+}
+
+/// SPEC.FREEZE [0|1] - stop the waterfall to look at it
+pub fn handle_spec_freeze<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let current = ctx.spectrogram.frozen as usize;
+    if let Some(choice) = spec_option(parts, ctx, "SPEC.FREEZE", ["OFF", "ON"], current, &mut output) {
+        ctx.spectrogram.frozen = choice == 1;
+    }
+    Ok(())
+}
+
+/// SPEC.SNAP - keep the current spectrum, drawn over the live one to compare
+pub fn handle_spec_snap<F>(_parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.spectrogram.take_snapshot();
+    ctx.output(OutputCategory::Confirm, "SPEC.SNAP: TAKEN".to_string(), &mut output);
+    Ok(())
+}
+
+/// SPEC.SNAP.CLR - drop the snapshot
+pub fn handle_spec_snap_clr<F>(_parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    ctx.spectrogram.clear_snapshot();
+    ctx.output(OutputCategory::Confirm, "SPEC.SNAP: CLEARED".to_string(), &mut output);
+    Ok(())
+}
+
 pub fn handle_note<F>(
     parts: &[&str],
     notes: &mut crate::types::NotesStorage,
@@ -414,6 +680,8 @@ define_bool_toggle!(handle_meter_grid, "METER.GRID", "GRID METERS: {}", "GRID ME
 
 define_bool_toggle!(handle_meter_ascii, "METER.ASCII", "ASCII METERS: {}", "ASCII METERS: OFF", "ASCII METERS: ON", config::save_ascii_meters);
 
+define_bool_toggle!(handle_mouse, "MOUSE", "MOUSE: {}", "MOUSE: OFF", "MOUSE: ON", config::save_mouse);
+
 define_bool_toggle!(handle_spectrum, "SPECTRUM", "SPECTRUM: {}", "SPECTRUM: OFF", "SPECTRUM: ON", config::save_show_spectrum);
 
 define_bool_toggle!(handle_activity, "ACTIVITY", "ACTIVITY: {}", "ACTIVITY: OFF", "ACTIVITY: ON", config::save_show_activity);
@@ -424,18 +692,38 @@ define_bool_toggle!(handle_grid_def, "GRID.DEF", "GRID.DEF: {}", "GRID.DEF: 0 (R
 
 define_bool_toggle!(handle_hl_seq, "HL.SEQ", "SEQ HIGHLIGHT: {}", "SEQ HIGHLIGHT: OFF", "SEQ HIGHLIGHT: ON", config::save_show_seq_highlight);
 
-define_enum_select!(
-    handle_grid_mode,
-    "GRID.MODE",
-    config::save_grid_mode,
-    "ERROR: GRID.MODE TAKES 0-5",
-    (0, "LABELS"),
-    (1, "ICONS"),
-    (2, "FX VIZ"),
-    (3, "MIXER"),
-    (4, "FX VIZ 2"),
-    (5, "SAMPLER"),
-);
+define_bool_toggle!(handle_hl_syn, "HL.SYN", "SYNTAX HIGHLIGHT: {}", "SYNTAX HIGHLIGHT: OFF", "SYNTAX HIGHLIGHT: ON", config::save_show_syntax_highlight);
+
+pub fn handle_grid_mode<F>(
+    parts: &[&str],
+    state: &mut u8,
+    pages: &[crate::ui::grid_pages::GridPage],
+    debug_level: u8,
+    mut output: F,
+) where
+    F: FnMut(String),
+{
+    if parts.len() == 1 {
+        if debug_level >= crate::types::TIER_QUERIES {
+            output(format!("GRID.MODE: {}", *state));
+        }
+        return;
+    }
+    let name = parts[1].parse::<usize>().ok().and_then(|mode| match mode.checked_sub(GRID_MODE_NAMES.len()) {
+        None => Some((mode, GRID_MODE_NAMES[mode].to_string())),
+        Some(page) => pages.get(page).map(|page| (mode, page.name.clone())),
+    });
+    match name {
+        Some((mode, name)) => {
+            *state = mode as u8;
+            let _ = config::save_grid_mode(*state);
+            if debug_level >= TIER_CONFIRMS {
+                output(format!("GRID.MODE: {} ({})", mode, name));
+            }
+        }
+        None => output(format!("ERROR: GRID.MODE TAKES 0-{}", GRID_MODE_NAMES.len() + pages.len() - 1)),
+    }
+}
 
 pub fn handle_rec_spinner<F>(
     parts: &[&str],
@@ -4,7 +4,9 @@ pub mod macros;
 pub mod audio;
 pub mod metro;
 pub mod midi;
+pub mod patch;
 pub mod preset;
+pub mod react;
 pub mod scene;
 pub mod sc;
 pub mod triggers;
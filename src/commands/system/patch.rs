@@ -0,0 +1,223 @@
+use crate::commands::context::ExecutionContext;
+use crate::output::OutputDecider;
+use crate::preset::patch::{self, Patch, PatchGroup};
+use crate::preset::PresetError;
+use crate::types::{MetroCommand, OutputCategory};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+fn patch_error(name: &str, e: PresetError) -> String {
+    match e {
+        PresetError::NotFound(_) => format!("ERROR: PATCH '{}' NOT FOUND", name),
+        PresetError::InvalidName(_) => format!("ERROR: INVALID PATCH NAME '{}'", name),
+        PresetError::IoError(e) => format!("ERROR: PATCH '{}': {}", name, e),
+        PresetError::ParseError(e) => format!("ERROR: PATCH '{}' IS NOT VALID: {}", name, e),
+    }
+}
+
+/// Send every param of a patch through the metro thread
+fn send_patch(patch: &Patch, ctx: &mut ExecutionContext) -> Result<()> {
+    for (param, value) in &patch.params {
+        ctx.metro_tx
+            .send(MetroCommand::SendParam(param.clone(), value.to_osc()))
+            .context("Failed to send patch param to metro thread")?;
+    }
+    Ok(())
+}
+
+pub fn handle_patch<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH REQUIRES A NAME".to_string(), &mut output);
+        return Ok(());
+    }
+    let name = parts[1..].join(" ").to_lowercase();
+
+    match patch::load_patch(&name) {
+        Ok(patch) => {
+            send_patch(&patch, ctx)?;
+            ctx.output(
+                OutputCategory::Essential,
+                format!("LOADED PATCH {} ({}, {} PARAMS)", name, patch.group.label(), patch.params.len()),
+                &mut output,
+            );
+        }
+        Err(e) => ctx.output(OutputCategory::Error, patch_error(&name, e), &mut output),
+    }
+    Ok(())
+}
+
+pub fn handle_patch_save<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 3 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH.SAVE REQUIRES GROUP AND NAME".to_string(), &mut output);
+        return Ok(());
+    }
+    let Some(group) = PatchGroup::parse(parts[1]) else {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH GROUP MUST BE OSC, PL, SMP, FX OR ALL".to_string(), &mut output);
+        return Ok(());
+    };
+    let name = parts[2..].join(" ").to_lowercase();
+
+    let live = match crate::metro::live_params(ctx.metro_tx) {
+        Ok(live) => live,
+        Err(e) => {
+            ctx.output(OutputCategory::Error, e, &mut output);
+            return Ok(());
+        }
+    };
+    let patch = Patch::capture(&name, group, &live);
+
+    match patch::save_patch(&patch) {
+        Ok(()) => ctx.output(
+            OutputCategory::Essential,
+            format!("SAVED PATCH: {} ({}, {} PARAMS)", name, group.label(), patch.params.len()),
+            &mut output,
+        ),
+        Err(e) => ctx.output(OutputCategory::Error, patch_error(&name, e), &mut output),
+    }
+    Ok(())
+}
+
+pub fn handle_patch_diff<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH.DIFF REQUIRES A NAME".to_string(), &mut output);
+        return Ok(());
+    }
+    let name = parts[1..].join(" ").to_lowercase();
+
+    let patch = match patch::load_patch(&name) {
+        Ok(patch) => patch,
+        Err(e) => {
+            ctx.output(OutputCategory::Error, patch_error(&name, e), &mut output);
+            return Ok(());
+        }
+    };
+
+    let live = match crate::metro::live_params(ctx.metro_tx) {
+        Ok(live) => live,
+        Err(e) => {
+            ctx.output(OutputCategory::Error, e, &mut output);
+            return Ok(());
+        }
+    };
+    let diff = patch.diff(&live);
+    if diff.is_empty() {
+        ctx.output(OutputCategory::Query, format!("PATCH {}: SAME AS CURRENT", name), &mut output);
+        return Ok(());
+    }
+    ctx.output(OutputCategory::Query, format!("PATCH {}: {} DIFFER (NOW > PATCH)", name, diff.len()), &mut output);
+    for (param, current, value) in diff {
+        let current = current.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+        ctx.output(
+            OutputCategory::Query,
+            format!("  {:<10} {} > {}", param.to_uppercase(), current, value),
+            &mut output,
+        );
+    }
+    Ok(())
+}
+
+pub fn handle_patch_del<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH.DEL REQUIRES A NAME".to_string(), &mut output);
+        return Ok(());
+    }
+    let name = parts[1..].join(" ").to_lowercase();
+
+    match patch::delete_patch(&name) {
+        Ok(()) => ctx.output(OutputCategory::Essential, format!("DELETED PATCH: {}", name), &mut output),
+        Err(e) => ctx.output(OutputCategory::Error, patch_error(&name, e), &mut output),
+    }
+    Ok(())
+}
+
+pub fn handle_patches<F>(ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let names = patch::list_patches().unwrap_or_default();
+    if names.is_empty() {
+        ctx.output(OutputCategory::Query, "NO PATCHES SAVED".to_string(), &mut output);
+        return Ok(());
+    }
+    ctx.output(OutputCategory::Query, "PATCHES:".to_string(), &mut output);
+    for name in names {
+        let group = patch::load_patch(&name).map(|p| p.group.label()).unwrap_or("?");
+        ctx.output(OutputCategory::Query, format!("  {} [{}]", name, group), &mut output);
+    }
+    Ok(())
+}
+
+/// PATCH.EXPORT <name> [file.toml] - write the patch TOML elsewhere (default ./<name>.toml).
+/// The name may have spaces like everywhere else; a last word ending in .toml is the file.
+pub fn handle_patch_export<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH.EXPORT REQUIRES A NAME".to_string(), &mut output);
+        return Ok(());
+    }
+    let (name_parts, file) = match parts[1..].split_last() {
+        Some((last, rest)) if !rest.is_empty() && last.to_lowercase().ends_with(".toml") => (rest, Some(*last)),
+        _ => (&parts[1..], None),
+    };
+    let name = name_parts.join(" ").to_lowercase();
+    let path = match file {
+        Some(file) => PathBuf::from(file),
+        None => PathBuf::from(format!("{}.toml", crate::preset::sanitize_name(&name))),
+    };
+
+    let result = patch::load_patch(&name)
+        .and_then(|patch| patch.to_toml())
+        .and_then(|text| std::fs::write(&path, text).map_err(PresetError::IoError));
+    match result {
+        Ok(()) => ctx.output(OutputCategory::Essential, format!("EXPORTED PATCH {} TO {}", name, path.display()), &mut output),
+        Err(e) => ctx.output(OutputCategory::Error, patch_error(&name, e), &mut output),
+    }
+    Ok(())
+}
+
+/// PATCH.IMPORT <path> - copy a shared patch file into the patch library
+pub fn handle_patch_import<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: PATCH.IMPORT REQUIRES A FILE PATH".to_string(), &mut output);
+        return Ok(());
+    }
+    let path = PathBuf::from(parts[1..].join(" "));
+
+    let mut patch = match patch::read_patch_file(&path) {
+        Ok(patch) => patch,
+        Err(e) => {
+            ctx.output(OutputCategory::Error, patch_error(&path.display().to_string(), e), &mut output);
+            return Ok(());
+        }
+    };
+    // Drop anything a patch can't set (mixer levels, triggers) from hand-edited files
+    patch.params.retain(|param, _| crate::snapshot::is_tracked(param) && PatchGroup::All.contains(param));
+    patch.name = patch.name.to_lowercase();
+
+    match patch::save_patch(&patch) {
+        Ok(()) => ctx.output(
+            OutputCategory::Essential,
+            format!("IMPORTED PATCH: {} ({}, {} PARAMS)", patch.name, patch.group.label(), patch.params.len()),
+            &mut output,
+        ),
+        Err(e) => ctx.output(OutputCategory::Error, patch_error(&patch.name, e), &mut output),
+    }
+    Ok(())
+}
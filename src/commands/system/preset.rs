@@ -29,14 +29,8 @@ pub fn handle_pset<F>(
         Ok((preset, preset_type)) => {
             let script = &mut scripts.scripts[script_num];
 
-            for (i, line) in preset.lines.iter().enumerate() {
-                if i < 8 {
-                    script.lines[i] = line.clone();
-                }
-            }
-
-            for i in preset.lines.len()..8 {
-                script.lines[i] = String::new();
+            for (i, line) in script.lines.iter_mut().enumerate() {
+                *line = preset.lines.get(i).cloned().unwrap_or_default();
             }
 
             script.j = preset.j;
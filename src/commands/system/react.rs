@@ -0,0 +1,84 @@
+use crate::commands::context::ExecutionContext;
+use crate::eval::eval_expression;
+use crate::output::OutputDecider;
+use crate::types::{MetroCommand, OnsetSource, OutputCategory};
+use anyhow::Result;
+use rosc::OscType;
+
+fn eval_arg(parts: &[&str], ctx: &mut ExecutionContext) -> Option<i16> {
+    eval_expression(parts, 1, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale).map(|(value, _)| value)
+}
+
+/// LVL, LVL.OSC, BAND <N>, GR... typed on their own print the current reading
+pub fn handle_level_query<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if let Some((value, _)) = eval_expression(parts, 0, ctx.variables, ctx.patterns, ctx.counters, ctx.scripts, ctx.script_index, ctx.scale) {
+        ctx.output(OutputCategory::Query, value.to_string(), &mut output);
+    }
+    Ok(())
+}
+
+/// ON.SCRIPT [0-8] - script run on each onset, 0 for none
+pub fn handle_on_script<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let script = ctx.onset_settings.script;
+        let msg = if script == 0 { "ON.SCRIPT: OFF".to_string() } else { format!("ON.SCRIPT: {}", script) };
+        ctx.output(OutputCategory::Query, msg, &mut output);
+        return Ok(());
+    }
+    match eval_arg(parts, ctx) {
+        Some(script @ 0..=8) => {
+            ctx.onset_settings.script = script as usize;
+            let msg = if script == 0 { "ON.SCRIPT: OFF".to_string() } else { format!("ON.SCRIPT: {}", script) };
+            ctx.output(OutputCategory::Confirm, msg, &mut output);
+        }
+        _ => ctx.output(OutputCategory::Error, "ERROR: ON.SCRIPT 0-8".to_string(), &mut output),
+    }
+    Ok(())
+}
+
+/// ON.SRC [0|1] - detect onsets on the output (0) or the live input (1)
+pub fn handle_on_src<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let source = ctx.onset_settings.source;
+        ctx.output(OutputCategory::Query, format!("ON.SRC: {}", source.name()), &mut output);
+        return Ok(());
+    }
+    let Some(source) = eval_arg(parts, ctx).and_then(|n| u8::try_from(n).ok()).and_then(OnsetSource::from_u8) else {
+        ctx.output(OutputCategory::Error, "ERROR: ON.SRC 0=OUT 1=IN".to_string(), &mut output);
+        return Ok(());
+    };
+    ctx.onset_settings.source = source;
+    ctx.metro_tx.send(MetroCommand::SendParam("onset_src".to_string(), OscType::Int(source.to_u8() as i32)))?;
+    ctx.output(OutputCategory::Confirm, format!("ON.SRC: {}", source.name()), &mut output);
+    Ok(())
+}
+
+/// ON.THR [1-100] - onset detection threshold
+pub fn handle_on_thr<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        let threshold = ctx.onset_settings.threshold;
+        ctx.output(OutputCategory::Query, format!("ON.THR: {}", threshold), &mut output);
+        return Ok(());
+    }
+    match eval_arg(parts, ctx) {
+        Some(threshold @ 1..=100) => {
+            ctx.onset_settings.threshold = threshold as u8;
+            ctx.metro_tx.send(MetroCommand::SendParam("onset_thresh".to_string(), OscType::Float(threshold as f32 / 100.0)))?;
+            ctx.output(OutputCategory::Confirm, format!("ON.THR: {}", threshold), &mut output);
+        }
+        _ => ctx.output(OutputCategory::Error, "ERROR: ON.THR 1-100".to_string(), &mut output),
+    }
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use crate::types::{MetroCommand, TIER_CONFIRMS};
+use crate::commands::context::ExecutionContext;
+use crate::output::OutputDecider;
+use crate::types::{MetroCommand, OutputCategory, TIER_CONFIRMS};
 use anyhow::{Context, Result};
 use std::sync::mpsc::Sender;
 
@@ -51,3 +53,53 @@ where
     }
     Ok(())
 }
+
+/// Optional Q argument: wait for the next metro tick. None if it's something else.
+fn quantize_arg(parts: &[&str]) -> Option<bool> {
+    match parts.get(1).map(|arg| arg.to_uppercase()).as_deref() {
+        None => Some(false),
+        Some("Q") => Some(true),
+        Some(_) => None,
+    }
+}
+
+/// REC.STEMS [Q] - record the master and each voice and FX return to its own file
+pub fn handle_rec_stems<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(quantized) = quantize_arg(parts) else {
+        ctx.output(OutputCategory::Error, "ERROR: REC.STEMS [Q]".to_string(), &mut output);
+        return Ok(());
+    };
+    let cwd = std::env::current_dir()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| ".".to_string());
+
+    ctx.metro_tx
+        .send(MetroCommand::StartStemRecording(cwd, quantized))
+        .context("Failed to send stem recording command")?;
+    if quantized {
+        ctx.output(OutputCategory::Confirm, "STEMS START ON NEXT TICK".to_string(), &mut output);
+    }
+    // Otherwise output is handled by the UI event handler when recording starts
+    Ok(())
+}
+
+/// REC.STEMS.STOP [Q] - stop stem recording, Q on the next metro tick
+pub fn handle_rec_stems_stop<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    let Some(quantized) = quantize_arg(parts) else {
+        ctx.output(OutputCategory::Error, "ERROR: REC.STEMS.STOP [Q]".to_string(), &mut output);
+        return Ok(());
+    };
+    ctx.metro_tx
+        .send(MetroCommand::StopStemRecording(quantized))
+        .context("Failed to send stop stem recording command")?;
+    if quantized {
+        ctx.output(OutputCategory::Confirm, "STEMS STOP ON NEXT TICK".to_string(), &mut output);
+    }
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use crate::types::{ConfirmAction, NotesStorage, PatternStorage, SamplerState, ScriptMutes, ScriptStorage, Variables, TIER_ERRORS, TIER_ESSENTIAL, TIER_QUERIES, TIER_CONFIRMS};
+use crate::commands::context::ExecutionContext;
+use crate::output::OutputDecider;
+use crate::types::{ConfirmAction, NotesStorage, OutputCategory, PatternStorage, SamplerState, ScriptMutes, ScriptStorage, Variables, TIER_ERRORS, TIER_ESSENTIAL, TIER_QUERIES, TIER_CONFIRMS};
+use anyhow::Result;
+use std::path::PathBuf;
 
 pub fn handle_save<F>(
     parts: &[&str],
@@ -18,6 +22,8 @@ pub fn handle_save<F>(
     pending_confirmation: &mut Option<ConfirmAction>,
     scene_modified: &mut bool,
     sampler_state: &SamplerState,
+    snapshots: &crate::snapshot::SharedSnapshots,
+    rng: &crate::rng::SharedRng,
     mut output: F,
 ) where
     F: FnMut(String),
@@ -35,7 +41,7 @@ pub fn handle_save<F>(
         return;
     }
 
-    let scene = crate::scene::Scene::from_app_state(scripts, patterns, notes, script_mutes, sampler_state);
+    let scene = crate::scene::Scene::from_app_state(scripts, patterns, notes, script_mutes, sampler_state, &snapshots.lock().unwrap(), rng);
     match crate::scene::save_scene(&name, &scene) {
         Ok(()) => {
             *current_scene_name = Some(name.clone());
@@ -72,6 +78,8 @@ pub fn handle_load<F>(
     script_mutes: &mut ScriptMutes,
     scene_modified: &mut bool,
     sampler_state: &mut SamplerState,
+    snapshots: &crate::snapshot::SharedSnapshots,
+    rng: &crate::rng::SharedRng,
     mut output: F,
 ) -> bool
 where
@@ -84,7 +92,7 @@ where
     let name = parts[1..].join(" ");
     match crate::scene::load_scene(&name) {
         Ok(scene) => {
-            scene.apply_to_app_state(scripts, patterns, notes, script_mutes, sampler_state);
+            scene.apply_to_app_state(scripts, patterns, notes, script_mutes, sampler_state, &mut snapshots.lock().unwrap(), rng);
             *variables = crate::types::Variables::default();
             *current_scene_name = Some(name.clone());
             *scene_modified = false;
@@ -162,3 +170,79 @@ pub fn handle_delete<F>(
         Err(e) => output(format!("ERROR: {:?}", e)),
     }
 }
+
+fn teletype_map() -> crate::teletype::OpMap {
+    let overrides = crate::config::load_config().map(|c| c.teletype).unwrap_or_default();
+    crate::teletype::OpMap::new(&overrides)
+}
+
+/// IMPORT.TT <file> - replace the scene with a Teletype text scene. Returns true
+/// when a scene was loaded, so its I script runs as with LOAD.
+pub fn handle_import_tt<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<bool>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: IMPORT.TT REQUIRES A FILE PATH".to_string(), &mut output);
+        return Ok(false);
+    }
+    let path = PathBuf::from(parts[1..].join(" "));
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            ctx.output(OutputCategory::Error, format!("ERROR: {}: {}", path.display(), e), &mut output);
+            return Ok(false);
+        }
+    };
+
+    let (scene, report) = crate::teletype::parse_scene(&text, &teletype_map());
+    scene.apply_to_app_state(ctx.scripts, ctx.patterns, ctx.notes, ctx.script_mutes, ctx.sampler_state, &mut ctx.snapshots.lock().unwrap(), ctx.rng);
+    *ctx.variables = Variables::default();
+    let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    *ctx.current_scene_name = Some(name.clone());
+    // Not saved as a monokit scene yet
+    *ctx.scene_modified = true;
+
+    ctx.output(
+        OutputCategory::Essential,
+        format!("IMPORTED TT SCENE: {} ({} LINES)", name, report.translated),
+        &mut output,
+    );
+    if !report.untranslated.is_empty() {
+        ctx.output(
+            OutputCategory::Essential,
+            format!("KEPT AS # TT: COMMENTS: {}", report.untranslated.join(" ")),
+            &mut output,
+        );
+    }
+    Ok(true)
+}
+
+/// EXPORT.TT <file> - write the current scene in Teletype's text format
+pub fn handle_export_tt<F>(parts: &[&str], ctx: &mut ExecutionContext, mut output: F) -> Result<()>
+where
+    F: FnMut(String),
+{
+    if parts.len() < 2 {
+        ctx.output(OutputCategory::Error, "ERROR: EXPORT.TT REQUIRES A FILE PATH".to_string(), &mut output);
+        return Ok(());
+    }
+    let path = PathBuf::from(parts[1..].join(" "));
+
+    let scene = crate::scene::Scene::from_app_state(ctx.scripts, ctx.patterns, ctx.notes, ctx.script_mutes, ctx.sampler_state, &ctx.snapshots.lock().unwrap(), ctx.rng);
+    let (text, skipped) = crate::teletype::export_scene(&scene, &teletype_map());
+    if let Err(e) = std::fs::write(&path, text) {
+        ctx.output(OutputCategory::Error, format!("ERROR: {}: {}", path.display(), e), &mut output);
+        return Ok(());
+    }
+
+    ctx.output(OutputCategory::Essential, format!("EXPORTED TT SCENE TO {}", path.display()), &mut output);
+    for skip in skipped {
+        ctx.output(
+            OutputCategory::Essential,
+            format!("  SKIPPED {}: {} ({})", skip.script, skip.line, skip.reason),
+            &mut output,
+        );
+    }
+    Ok(())
+}
@@ -490,7 +490,7 @@ pub fn handle_compat_mode<F>(
     }
 }
 
-fn parse_script_id(id_str: &str) -> Option<usize> {
+pub(crate) fn parse_script_id(id_str: &str) -> Option<usize> {
     let upper = id_str.to_uppercase();
     match upper.as_str() {
         "M" => Some(8),
@@ -509,7 +509,7 @@ fn parse_script_id(id_str: &str) -> Option<usize> {
     }
 }
 
-fn script_id_label(index: usize) -> String {
+pub(crate) fn script_id_label(index: usize) -> String {
     match index {
         0..=7 => format!("{}", index + 1),
         8 => "M".to_string(),
@@ -615,6 +615,9 @@ pub fn handle_page<F>(
         "V" => Page::Variables,
         "N" => Page::Notes,
         "S" => Page::Scope,
+        "F" => Page::Spectrogram,
+        "W" => Page::Waveform,
+        "B" => Page::Library,
         _ => {
             output(format!("ERROR: INVALID PAGE \"{}\"", page_arg));
             return;
@@ -80,6 +80,14 @@ pub fn validate_expression(parts: &[&str], start: usize) -> Result<usize> {
             Ok(1 + consumed1 + consumed2 + consumed3 + consumed4)
         }
         "TOSS" => Ok(1),
+        "LVL" | "LVL.IN" | "LVL.OSC" | "LVL.PLA" | "LVL.NOS" | "LVL.SMP" | "GR" => Ok(1),
+        "BAND" => {
+            if start + 1 >= parts.len() {
+                return Err(anyhow::anyhow!("BAND REQUIRES 1 ARGUMENT"));
+            }
+            let consumed = validate_expression(parts, start + 1)?;
+            Ok(1 + consumed)
+        }
         "MAP" => {
             if start + 5 >= parts.len() {
                 return Err(anyhow::anyhow!("MAP REQUIRES 5 ARGUMENTS"));
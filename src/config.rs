@@ -2,9 +2,10 @@ use crate::theme::Theme;
 use anyhow::{Context, Result};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 const BUNDLED_THEMES_TOML: &str = include_str!("../themes/themes.toml");
 
@@ -14,6 +15,39 @@ pub struct Config {
     pub display: DisplayConfig,
     #[serde(default)]
     pub themes: HashMap<String, CustomTheme>,
+    /// Key chord -> action or command line, e.g. `"ctrl+1" = "MUTE 1"`
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+    /// Teletype op -> monokit op for IMPORT.TT/EXPORT.TT, e.g. `"CV 3" = "PW"`
+    #[serde(default)]
+    pub teletype: BTreeMap<String, String>,
+    /// `[[grid_pages]]`: custom GRID.MODE pages after the built-in ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grid_pages: Vec<GridPageConfig>,
+}
+
+/// A custom grid page: up to 12 parameters, shown in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridPageConfig {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<GridParamConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridParamConfig {
+    /// Command that sets the parameter, e.g. "PF" or "POSC.FREQ"
+    pub cmd: String,
+    /// Up to 4 chars; defaults to the command name
+    #[serde(default)]
+    pub label: Option<String>,
+    /// "bar", "number" or "icon"
+    #[serde(default = "default_grid_format")]
+    pub format: String,
+}
+
+fn default_grid_format() -> String {
+    "bar".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +82,8 @@ pub struct DisplayConfig {
     pub show_seq_highlight: bool,
     #[serde(default = "default_show_conditional_highlight")]
     pub show_conditional_highlight: bool,
+    #[serde(default = "default_true")]
+    pub show_syntax_highlight: bool,
     #[serde(default = "default_grid_mode")]
     pub grid_mode: u8,
     #[serde(default = "default_limiter_enabled")]
@@ -80,6 +116,12 @@ pub struct DisplayConfig {
     pub out_cfm: bool,
     #[serde(default)]
     pub audio_out_device: Option<String>,
+    /// Open two input channels for LVL.IN and input onsets
+    #[serde(default)]
+    pub audio_in: bool,
+    /// Hardware outputs opened on the device, in pairs for OUT.ROUTE
+    #[serde(default = "default_output_channels")]
+    pub output_channels: u8,
     #[serde(default = "default_true")]
     pub scramble_enabled: bool,
     #[serde(default = "default_true")]
@@ -94,6 +136,9 @@ pub struct DisplayConfig {
     pub vca_mode: bool,
     #[serde(default)]
     pub ascii_meters: bool,
+    /// Capture the mouse: click pages and script lines, scroll, drag grid bars
+    #[serde(default)]
+    pub mouse: bool,
     #[serde(default)]
     pub autoload: bool,
     #[serde(default)]
@@ -102,6 +147,8 @@ pub struct DisplayConfig {
     pub confirm_quit_unsaved: bool,
     #[serde(default = "default_true")]
     pub confirm_overwrite_scene: bool,
+    #[serde(default = "default_script_length")]
+    pub script_length: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,6 +166,23 @@ pub struct CustomTheme {
     pub success: String,
     #[serde(default = "default_label")]
     pub label: String,
+    /// Syntax colors; any left out are blended from the colors above
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_alias: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_variable: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_string: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_keyword: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syn_separator: Option<String>,
     #[serde(default)]
     pub font: Option<String>,
 }
@@ -187,6 +251,14 @@ fn default_limiter_enabled() -> bool {
     true
 }
 
+fn default_script_length() -> usize {
+    crate::types::DEFAULT_SCRIPT_LINES
+}
+
+fn default_output_channels() -> u8 {
+    2
+}
+
 fn default_activity_hold_ms() -> u32 {
     200
 }
@@ -229,6 +301,7 @@ impl Default for DisplayConfig {
             show_grid_view: false,
             show_seq_highlight: default_show_seq_highlight(),
             show_conditional_highlight: default_show_conditional_highlight(),
+            show_syntax_highlight: true,
             grid_mode: default_grid_mode(),
             limiter_enabled: default_limiter_enabled(),
             activity_hold_ms: default_activity_hold_ms(),
@@ -245,6 +318,8 @@ impl Default for DisplayConfig {
             out_qry: false,
             out_cfm: false,
             audio_out_device: None,
+            audio_in: false,
+            output_channels: default_output_channels(),
             scramble_enabled: default_true(),
             scramble_grid_enabled: default_true(),
             scramble_mode: default_scramble_mode(),
@@ -252,10 +327,12 @@ impl Default for DisplayConfig {
             scramble_curve: 0,
             vca_mode: false,
             ascii_meters: false,
+            mouse: false,
             autoload: false,
             last_scene: None,
             confirm_quit_unsaved: default_true(),
             confirm_overwrite_scene: default_true(),
+            script_length: default_script_length(),
         }
     }
 }
@@ -265,6 +342,9 @@ impl Default for Config {
         Self {
             display: DisplayConfig::default(),
             themes: HashMap::new(),
+            keys: BTreeMap::new(),
+            teletype: BTreeMap::new(),
+            grid_pages: Vec::new(),
         }
     }
 }
@@ -289,16 +369,32 @@ fn config_path() -> Result<PathBuf> {
     Ok(config_dir.join("config.toml"))
 }
 
+/// Set while golden tests run, so a scene's DEBUG or HEADER can't change the
+/// user's settings and the user's settings can't change a scene's output
+static DETACHED: AtomicBool = AtomicBool::new(false);
+
+/// From now on load first-run defaults and drop saves instead of touching config.toml
+pub fn detach_from_disk() {
+    DETACHED.store(true, Ordering::Relaxed);
+}
+
 pub fn load_config() -> Result<Config> {
+    let first_run = || Config {
+        display: DisplayConfig::default(),
+        themes: load_bundled_themes().unwrap_or_default(),
+        keys: BTreeMap::new(),
+        teletype: BTreeMap::new(),
+        grid_pages: Vec::new(),
+    };
+    if DETACHED.load(Ordering::Relaxed) {
+        return Ok(first_run());
+    }
+
     let path = config_path()?;
 
     if !path.exists() {
         // First run: create config with bundled themes populated
-        let bundled_themes = load_bundled_themes().unwrap_or_default();
-        let config = Config {
-            display: DisplayConfig::default(),
-            themes: bundled_themes,
-        };
+        let config = first_run();
         // Save to create the config file with themes for user customization
         if let Err(e) = save_config(&config) {
             // Log but don't fail - themes still work from bundled source
@@ -314,6 +410,9 @@ pub fn load_config() -> Result<Config> {
 }
 
 pub fn save_config(config: &Config) -> Result<()> {
+    if DETACHED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
     let path = config_path()?;
     let contents = toml::to_string_pretty(config).context("Failed to serialize config")?;
     fs::write(&path, contents).context("Failed to write config file")?;
@@ -365,20 +464,7 @@ pub fn load_theme_by_name(name: &str, config: &Config) -> Result<Theme> {
     if let Some(custom) = config.themes.get(&name_lower)
         .or_else(|| config.themes.iter().find(|(k, _)| k.to_lowercase() == name_lower).map(|(_, v)| v))
     {
-        return Ok(Theme {
-            name: name.to_string(),
-            background: parse_hex_color(&custom.background)?,
-            foreground: parse_hex_color(&custom.foreground)?,
-            secondary: parse_hex_color(&custom.secondary)?,
-            highlight_bg: parse_hex_color(&custom.highlight_bg)?,
-            highlight_fg: parse_hex_color(&custom.highlight_fg)?,
-            border: parse_hex_color(&custom.border)?,
-            error: parse_hex_color(&custom.error)?,
-            accent: parse_hex_color(&custom.accent)?,
-            success: parse_hex_color(&custom.success)?,
-            label: parse_hex_color(&custom.label)?,
-            font: custom.font.clone(),
-        });
+        return theme_from_custom(name, custom);
     }
 
     // Check bundled themes as fallback
@@ -386,33 +472,58 @@ pub fn load_theme_by_name(name: &str, config: &Config) -> Result<Theme> {
     if let Some(custom) = bundled.get(&name_lower)
         .or_else(|| bundled.iter().find(|(k, _)| k.to_lowercase() == name_lower).map(|(_, v)| v))
     {
-        return Ok(Theme {
-            name: name.to_string(),
-            background: parse_hex_color(&custom.background)?,
-            foreground: parse_hex_color(&custom.foreground)?,
-            secondary: parse_hex_color(&custom.secondary)?,
-            highlight_bg: parse_hex_color(&custom.highlight_bg)?,
-            highlight_fg: parse_hex_color(&custom.highlight_fg)?,
-            border: parse_hex_color(&custom.border)?,
-            error: parse_hex_color(&custom.error)?,
-            accent: parse_hex_color(&custom.accent)?,
-            success: parse_hex_color(&custom.success)?,
-            label: parse_hex_color(&custom.label)?,
-            font: custom.font.clone(),
-        });
+        return theme_from_custom(name, custom);
     }
 
     anyhow::bail!("Unknown theme: {}. Use list_themes command to see available themes", name)
 }
 
+fn theme_from_custom(name: &str, custom: &CustomTheme) -> Result<Theme> {
+    let foreground = parse_hex_color(&custom.foreground)?;
+    let secondary = parse_hex_color(&custom.secondary)?;
+    let accent = parse_hex_color(&custom.accent)?;
+    let success = parse_hex_color(&custom.success)?;
+    let label = parse_hex_color(&custom.label)?;
+    // Syntax colors the theme doesn't set: blends of its own palette
+    let syntax = |color: &Option<String>, default: Color| color.as_deref().map_or(Ok(default), parse_hex_color);
+    let blend = |from, to| Theme::lerp_color(from, to, 0.5);
+
+    Ok(Theme {
+        name: name.to_string(),
+        background: parse_hex_color(&custom.background)?,
+        foreground,
+        secondary,
+        highlight_bg: parse_hex_color(&custom.highlight_bg)?,
+        highlight_fg: parse_hex_color(&custom.highlight_fg)?,
+        border: parse_hex_color(&custom.border)?,
+        error: parse_hex_color(&custom.error)?,
+        accent,
+        success,
+        label,
+        syn_command: syntax(&custom.syn_command, foreground)?,
+        syn_alias: syntax(&custom.syn_alias, label)?,
+        syn_variable: syntax(&custom.syn_variable, blend(accent, foreground))?,
+        syn_number: syntax(&custom.syn_number, success)?,
+        syn_note: syntax(&custom.syn_note, blend(success, accent))?,
+        syn_string: syntax(&custom.syn_string, blend(success, foreground))?,
+        syn_keyword: syntax(&custom.syn_keyword, accent)?,
+        syn_separator: syntax(&custom.syn_separator, secondary)?,
+        font: custom.font.clone(),
+    })
+}
+
 pub fn list_themes(config: &Config) -> Vec<String> {
     let mut themes = vec!["dark".to_string(), "light".to_string(), "system".to_string()];
 
+    // Bundled themes are also copied into the config on first run
+    let mut named: Vec<String> = config.themes.keys().cloned().collect();
     if let Ok(bundled) = load_bundled_themes() {
-        themes.extend(bundled.keys().cloned());
+        named.extend(bundled.keys().cloned());
     }
+    named.sort();
+    named.dedup();
 
-    themes.extend(config.themes.keys().cloned());
+    themes.extend(named);
     themes
 }
 
@@ -514,6 +625,13 @@ pub fn save_show_seq_highlight(enabled: bool) -> Result<()> {
     Ok(())
 }
 
+pub fn save_show_syntax_highlight(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.display.show_syntax_highlight = enabled;
+    save_config(&config)?;
+    Ok(())
+}
+
 pub fn save_show_conditional_highlight(enabled: bool) -> Result<()> {
     let mut config = load_config()?;
     config.display.show_conditional_highlight = enabled;
@@ -542,6 +660,13 @@ pub fn save_activity_hold_ms(ms: u32) -> Result<()> {
     Ok(())
 }
 
+pub fn save_script_length(lines: usize) -> Result<()> {
+    let mut config = load_config()?;
+    config.display.script_length = lines;
+    save_config(&config)?;
+    Ok(())
+}
+
 pub fn save_title_mode(mode: u8) -> Result<()> {
     let mut config = load_config()?;
     config.display.title_mode = mode;
@@ -634,6 +759,13 @@ pub fn save_audio_out_device(device: Option<String>) -> Result<()> {
     Ok(())
 }
 
+pub fn save_output_channels(channels: u8) -> Result<()> {
+    let mut config = load_config()?;
+    config.display.output_channels = channels;
+    save_config(&config)?;
+    Ok(())
+}
+
 pub fn save_scramble_enabled(enabled: bool) -> Result<()> {
     let mut config = load_config()?;
     config.display.scramble_enabled = enabled;
@@ -697,6 +829,13 @@ pub fn save_ascii_meters(enabled: bool) -> Result<()> {
     Ok(())
 }
 
+pub fn save_mouse(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.display.mouse = enabled;
+    save_config(&config)?;
+    Ok(())
+}
+
 pub fn save_autoload(enabled: bool) -> Result<()> {
     let mut config = load_config()?;
     config.display.autoload = enabled;
@@ -0,0 +1,76 @@
+//! Meter, spectrum and compressor readings as expression terms.
+//!
+//! The app stores every update from the meter thread here, so scripts read the
+//! latest values whichever thread runs them. All terms are scaled to 0-16383.
+
+use crate::types::{CompressorData, MeterData, SpectrumData, VoiceMeterData, SPECTRUM_BANDS};
+use std::sync::atomic::{AtomicU16, Ordering};
+
+/// Value of a term at full scale
+pub const LEVEL_MAX: i16 = 16383;
+
+/// Gain reduction that reads as full scale, in dB
+const GR_RANGE_DB: f32 = 40.0;
+
+static OUT_LEVEL: AtomicU16 = AtomicU16::new(0);
+static IN_LEVEL: AtomicU16 = AtomicU16::new(0);
+static OSC_LEVEL: AtomicU16 = AtomicU16::new(0);
+static PLA_LEVEL: AtomicU16 = AtomicU16::new(0);
+static NOS_LEVEL: AtomicU16 = AtomicU16::new(0);
+static SMP_LEVEL: AtomicU16 = AtomicU16::new(0);
+static BANDS: [AtomicU16; SPECTRUM_BANDS] = [const { AtomicU16::new(0) }; SPECTRUM_BANDS];
+static GAIN_REDUCTION: AtomicU16 = AtomicU16::new(0);
+
+/// 0.0-1.0 to 0-16383
+pub fn scale_level(value: f32) -> u16 {
+    if !value.is_finite() {
+        return 0;
+    }
+    (value.clamp(0.0, 1.0) * LEVEL_MAX as f32).round() as u16
+}
+
+pub fn store_meter(data: &MeterData) {
+    OUT_LEVEL.store(scale_level(data.peak_l.max(data.peak_r)), Ordering::Relaxed);
+    IN_LEVEL.store(scale_level(data.in_peak), Ordering::Relaxed);
+}
+
+pub fn store_voice_meter(data: &VoiceMeterData) {
+    OSC_LEVEL.store(scale_level(data.osc_l.max(data.osc_r)), Ordering::Relaxed);
+    PLA_LEVEL.store(scale_level(data.pla_l.max(data.pla_r)), Ordering::Relaxed);
+    NOS_LEVEL.store(scale_level(data.nos_l.max(data.nos_r)), Ordering::Relaxed);
+    SMP_LEVEL.store(scale_level(data.smp_l.max(data.smp_r)), Ordering::Relaxed);
+}
+
+pub fn store_spectrum(data: &SpectrumData) {
+    for (band, value) in BANDS.iter().zip(data.bands.iter()) {
+        band.store(scale_level(*value), Ordering::Relaxed);
+    }
+}
+
+pub fn store_compressor(data: &CompressorData) {
+    GAIN_REDUCTION.store(scale_level(-data.gain_reduction_db / GR_RANGE_DB), Ordering::Relaxed);
+}
+
+fn load(level: &AtomicU16) -> i16 {
+    level.load(Ordering::Relaxed) as i16
+}
+
+/// Reading for a level term such as LVL or LVL.OSC, None if `expr` is not one
+pub fn level(expr: &str) -> Option<i16> {
+    let level = match expr {
+        "LVL" => &OUT_LEVEL,
+        "LVL.IN" => &IN_LEVEL,
+        "LVL.OSC" => &OSC_LEVEL,
+        "LVL.PLA" => &PLA_LEVEL,
+        "LVL.NOS" => &NOS_LEVEL,
+        "LVL.SMP" => &SMP_LEVEL,
+        "GR" => &GAIN_REDUCTION,
+        _ => return None,
+    };
+    Some(load(level))
+}
+
+/// Level of spectrum band 1-15, lowest first. Out of range numbers clamp.
+pub fn band(band: i16) -> i16 {
+    load(&BANDS[(band.clamp(1, SPECTRUM_BANDS as i16) - 1) as usize])
+}
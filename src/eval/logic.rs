@@ -22,7 +22,7 @@ pub fn eval_logic_expression(
                 if max <= 0 {
                     return Some((0, 1 + consumed));
                 }
-                let result = rand::thread_rng().gen_range(0..=max);
+                let result = crate::rng::with_rng(|rng| rng.gen_range(0..=max));
                 return Some((result, 1 + consumed));
             }
             None
@@ -36,14 +36,14 @@ pub fn eval_logic_expression(
                     if min > max {
                         std::mem::swap(&mut min, &mut max);
                     }
-                    let result = rand::thread_rng().gen_range(min..=max);
+                    let result = crate::rng::with_rng(|rng| rng.gen_range(min..=max));
                     return Some((result, 1 + min_consumed + max_consumed));
                 }
             }
             None
         }
         "TOSS" => {
-            let result = if rand::thread_rng().gen_bool(0.5) { 1 } else { 0 };
+            let result = if crate::rng::with_rng(|rng| rng.gen_bool(0.5)) { 1 } else { 0 };
             Some((result, 1))
         }
         "EITH" => {
@@ -57,7 +57,7 @@ pub fn eval_logic_expression(
                 if let Some((b, b_consumed)) = eval_expr_fn(parts, start_idx + 1 + a_consumed, variables, patterns, counters, scripts, script_index, scale) {
                     let next_idx = start_idx + 1 + a_consumed + b_consumed;
                     let key = format!("{}_{}", script_index, parts[start_idx..next_idx].join("_"));
-                    let selected_index = if rand::thread_rng().gen_bool(0.5) { 0 } else { 1 };
+                    let selected_index = if crate::rng::with_rng(|rng| rng.gen_bool(0.5)) { 0 } else { 1 };
                     patterns.toggle_state.insert(key.clone(), selected_index);
                     let result = if selected_index == 0 { a } else { b };
                     patterns.toggle_last_value.insert(key, result);
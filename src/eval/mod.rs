@@ -1,6 +1,7 @@
 mod patterns;
 mod math;
 mod logic;
+pub mod audio;
 pub mod rhythm;
 pub mod seq;
 
@@ -50,6 +51,15 @@ pub fn eval_expression(parts: &[&str], start_idx: usize, variables: &Variables,
         return Some((KIT_SLOTS.load(Ordering::Relaxed) as i16, 1));
     }
 
+    if let Some(level) = audio::level(&expr) {
+        return Some((level, 1));
+    }
+
+    if expr == "BAND" {
+        let (band, consumed) = eval_expression(parts, start_idx + 1, variables, patterns, counters, scripts, script_index, scale)?;
+        return Some((audio::band(band), 1 + consumed));
+    }
+
     if let Some(result) = patterns::eval_pattern_expression(
         &expr, parts, start_idx, variables, patterns, counters, scripts, script_index, scale, &eval_expression
     ) {
@@ -118,7 +128,7 @@ pub fn eval_condition(cond: &str, variables: &Variables, patterns: &mut PatternS
         let parts: Vec<&str> = pct_str.split_whitespace().collect();
         if let Some((pct_val, _)) = eval_expression(&parts, 0, variables, patterns, counters, scripts, script_index, scale) {
             let pct = (pct_val as u8).min(100);
-            let roll: u8 = rand::thread_rng().gen_range(0..100);
+            let roll: u8 = crate::rng::with_rng(|rng| rng.gen_range(0..100));
             return roll < pct;
         }
         return false;
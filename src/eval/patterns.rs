@@ -224,7 +224,7 @@ pub fn eval_pattern_expression(
             }
             let len = pattern.length;
             use rand::seq::SliceRandom;
-            let mut rng = rand::thread_rng();
+            let mut rng = crate::rng::fork();
             pattern.data[..len].shuffle(&mut rng);
             Some((len as i16, 1))
         }
@@ -401,7 +401,7 @@ pub fn eval_pattern_expression(
             let working = patterns.working;
             let pattern = &mut patterns.patterns[working];
             use rand::Rng;
-            let mut rng = rand::thread_rng();
+            let mut rng = crate::rng::fork();
             for i in 0..pattern.length {
                 pattern.data[i] = rng.gen_range(min..=max);
             }
@@ -607,7 +607,7 @@ pub fn eval_pattern_expression(
                 }
                 let len = pattern.length;
                 use rand::seq::SliceRandom;
-                let mut rng = rand::thread_rng();
+                let mut rng = crate::rng::fork();
                 pattern.data[..len].shuffle(&mut rng);
                 return Some((len as i16, 1 + consumed));
             }
@@ -842,7 +842,7 @@ pub fn eval_pattern_expression(
                 };
                 let pattern = &mut patterns.patterns[pat];
                 use rand::Rng;
-                let mut rng = rand::thread_rng();
+                let mut rng = crate::rng::fork();
                 for i in 0..pattern.length {
                     pattern.data[i] = rng.gen_range(min..=max);
                 }
@@ -278,7 +278,7 @@ fn eval_simple_step(step: &SeqStep) -> i16 {
         SeqStep::Value(v) => *v,
         SeqStep::Rest => 0,
         SeqStep::RandomTrigger => {
-            if rand::thread_rng().gen_bool(0.5) { 1 } else { 0 }
+            if crate::rng::with_rng(|rng| rng.gen_bool(0.5)) { 1 } else { 0 }
         }
         SeqStep::Alternation(_) | SeqStep::RandomChoice(_) => unreachable!(),
     }
@@ -295,7 +295,7 @@ fn eval_step(
         SeqStep::Value(v) => *v,
         SeqStep::Rest => 0,
         SeqStep::RandomTrigger => {
-            if rand::thread_rng().gen_bool(0.5) { 1 } else { 0 }
+            if crate::rng::with_rng(|rng| rng.gen_bool(0.5)) { 1 } else { 0 }
         }
         SeqStep::Alternation(options) => {
             let alt_key = format!("seq_alt_{}_{}_{}", script_index, pattern, step_index);
@@ -307,7 +307,7 @@ fn eval_step(
             result
         }
         SeqStep::RandomChoice(options) => {
-            let idx = rand::thread_rng().gen_range(0..options.len());
+            let idx = crate::rng::with_rng(|rng| rng.gen_range(0..options.len()));
             let rnd_key = format!("seq_rnd_{}_{}_{}", script_index, pattern, step_index);
             patterns.toggle_state.insert(rnd_key.clone(), idx);
             let result = eval_simple_step(&options[idx]);
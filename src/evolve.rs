@@ -0,0 +1,97 @@
+//! Sound evolution: MUT nudges the live parameters around their current values,
+//! BREED crosses two snapshot slots, and the performer keeps or discards each
+//! candidate by ear.
+//!
+//! Ranges come from the command registry, so a param only mutates if a command
+//! declares what values it accepts.
+
+use crate::commands::registry::param_range;
+use crate::preset::patch::PatchGroup;
+use crate::snapshot::{is_discrete, ParamValue, Snapshot};
+use rand::Rng;
+
+pub const MUT_MAX: i32 = 100;
+
+/// Kept candidates remembered for MUT.BACK
+pub const HISTORY_MAX: usize = 32;
+
+/// Ranges this wide (20-20000 Hz, 1-10000 ms) are mutated on a log scale so a
+/// low kick frequency moves by musical amounts rather than hundreds of Hz
+const LOG_RATIO: f32 = 100.0;
+
+/// Move a continuous value by up to `amount` (0.0-1.0) of half its range
+fn nudge<R: Rng>(value: f32, min: f32, max: f32, amount: f32, rng: &mut R) -> f32 {
+    let offset = rng.gen_range(-0.5..=0.5) * amount;
+    if min > 0.0 && max / min >= LOG_RATIO {
+        let (lo, hi) = (min.ln(), max.ln());
+        (value.clamp(min, max).ln() + offset * (hi - lo)).clamp(lo, hi).exp()
+    } else {
+        (value + offset * (max - min)).clamp(min, max)
+    }
+}
+
+/// Mutated copy of `base`. Continuous params drift, discrete ones (waveforms,
+/// modes, switches) jump to a random setting with a chance that grows with `amount`.
+pub fn mutate<R: Rng>(base: &Snapshot, amount: i32, group: PatchGroup, rng: &mut R) -> Snapshot {
+    let mut candidate = base.clone();
+    if amount <= 0 {
+        return candidate;
+    }
+    let amount = amount.min(MUT_MAX) as f32 / MUT_MAX as f32;
+    for (name, value) in candidate.iter_mut() {
+        let Some((min, max)) = param_range(name) else {
+            continue;
+        };
+        // Values outside the range are settings like -1 (follow the global envelope)
+        if !group.contains(name) || !(min..=max).contains(&value.as_f32()) {
+            continue;
+        }
+        *value = if is_discrete(name) {
+            if rng.gen::<f32>() < amount * 0.5 {
+                ParamValue::Int(rng.gen_range(min as i32..=max as i32))
+            } else {
+                *value
+            }
+        } else {
+            match *value {
+                ParamValue::Int(v) => ParamValue::Int(nudge(v as f32, min, max, amount, rng).round() as i32),
+                ParamValue::Float(v) => ParamValue::Float(nudge(v, min, max, amount, rng)),
+            }
+        };
+    }
+    candidate
+}
+
+/// Uniform crossover: each param comes from `a` or `b` with equal chance.
+/// A param only one parent has is inherited as is.
+pub fn breed<R: Rng>(a: &Snapshot, b: &Snapshot, rng: &mut R) -> Snapshot {
+    let mut child = a.clone();
+    for (name, &value) in b {
+        if !a.contains_key(name) || rng.gen::<bool>() {
+            child.insert(name.clone(), value);
+        }
+    }
+    child
+}
+
+/// The sound a candidate was made from, plus the sounds kept before it
+#[derive(Debug, Clone, Default)]
+pub struct Evolution {
+    /// Values before the pending candidate; None when nothing awaits a decision
+    pub parent: Option<Snapshot>,
+    pub history: Vec<Snapshot>,
+}
+
+impl Evolution {
+    /// Accept the pending candidate, remembering its parent for MUT.BACK
+    pub fn keep(&mut self) -> bool {
+        let Some(parent) = self.parent.take() else {
+            return false;
+        };
+        if self.history.len() == HISTORY_MAX {
+            self.history.remove(0);
+        }
+        self.history.push(parent);
+        true
+    }
+}
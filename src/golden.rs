@@ -0,0 +1,290 @@
+//! Golden tests: `monokit --test <dir>` runs every scene in a directory and
+//! compares what it did with `<scene>.expected` next to it. `--bless` writes the
+//! expected files from the current run instead.
+//!
+//! The scene drives the real metro and delay threads on a `SimClock` that moves
+//! in 1ms steps, with OSC going to a local socket instead of SuperCollider.
+//! Ticks, DEL commands and glides fall due exactly on time, so a scene produces
+//! the same REPL output, variables, patterns and OSC on every run, in no time.
+
+use anyhow::{anyhow, Context, Result};
+use rosc::OscPacket;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::fs;
+use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::app::App;
+use crate::clock::SimClock;
+use crate::config::Config;
+use crate::metro::{format_osc_args, metro_thread_with_clock};
+use crate::rng;
+use crate::scene::Scene;
+use crate::terminal::TerminalCapabilities;
+use crate::theme::Theme;
+use crate::types::{ColorMode, MetroCommand, MetroEvent, MetroState};
+
+/// Virtual time each scene runs for, as long as the old batch runner waited
+pub const SCENE_DURATION_MS: u64 = 3000;
+
+/// Seed for scenes that don't save one, so RND gives the same numbers every run
+const DEFAULT_SEED: u64 = 0;
+
+/// Bail out of scenes that keep scheduling `DEL 0` from themselves
+const MAX_EVENTS: usize = 100_000;
+
+/// Diff lines shown per failing scene
+const MAX_DIFF_LINES: usize = 40;
+
+/// Room for the OSC a scene sends within one millisecond, such as RST's
+const OSC_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Local socket standing in for SuperCollider
+fn osc_receiver() -> Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_recv_buffer_size(OSC_BUFFER_SIZE)?;
+    let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+    socket.bind(&addr.into())?;
+    let socket: UdpSocket = socket.into();
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+/// Every message the metro has sent so far, bundles unpacked, stamped with `ms`
+fn drain_osc(socket: &UdpSocket, ms: u64, osc: &mut Vec<String>) {
+    fn flatten(packet: OscPacket, ms: u64, osc: &mut Vec<String>) {
+        match packet {
+            OscPacket::Message(msg) => osc.push(format!("{:>6} {} {}", ms, msg.addr, format_osc_args(&msg.args))),
+            OscPacket::Bundle(bundle) => bundle.content.into_iter().for_each(|p| flatten(p, ms, osc)),
+        }
+    }
+    let mut buf = [0u8; rosc::decoder::MTU];
+    while let Ok(len) = socket.recv(&mut buf) {
+        if let Ok((_, packet)) = rosc::decoder::decode_udp(&buf[..len]) {
+            flatten(packet, ms, osc);
+        }
+    }
+}
+
+/// Events the threads sent at the same moment, in the order the live app
+/// usually sees them: delayed commands before the tick
+fn take_events(events: &Receiver<MetroEvent>) -> Vec<MetroEvent> {
+    let mut batch: Vec<MetroEvent> = events.try_iter().collect();
+    batch.sort_by_key(|event| match event {
+        MetroEvent::ExecuteDelayed(..) => 0,
+        MetroEvent::ExecuteScript(_) => 1,
+        _ => 2,
+    });
+    batch
+}
+
+/// Run a scene the way LOAD does (init script, then the metro) for
+/// `SCENE_DURATION_MS` of virtual time and describe the outcome
+pub fn run_scene(scene: &Scene) -> String {
+    crate::config::detach_from_disk();
+    let mut seeds = scene.rng.clone().unwrap_or_default();
+    seeds.seed.get_or_insert(DEFAULT_SEED);
+
+    let socket = match osc_receiver() {
+        Ok(socket) => socket,
+        Err(e) => return format!("[golden] CANNOT OPEN OSC SOCKET: {}\n", e),
+    };
+    let osc_addr = socket.local_addr().ok();
+
+    let metro_state = Arc::new(Mutex::new(MetroState::default()));
+    let (metro_tx, metro_rx) = mpsc::channel();
+    let (event_tx, events) = mpsc::channel();
+    let caps = TerminalCapabilities { true_color: true, term_program: None };
+    let mut app = App::new(metro_tx.clone(), metro_state.clone(), Theme::default(), ColorMode::TrueColor, &Config::default(), caps);
+    let _rng = rng::install(&app.rng);
+    app.rng.restore(&seeds);
+    scene.apply_to_app_state(
+        &mut app.scripts,
+        &mut app.patterns,
+        &mut app.notes,
+        &mut app.script_mutes,
+        &mut app.sampler_state,
+        &mut app.snapshots.lock().unwrap(),
+        &app.rng,
+    );
+
+    let clock = SimClock::new();
+    let thread_clock = clock.clone();
+    let metro = thread::spawn(move || metro_thread_with_clock(metro_rx, metro_state, event_tx, osc_addr, thread_clock));
+    // Metro and delay thread
+    clock.settle_threads(2);
+
+    let mut output = Vec::new();
+    let mut osc = Vec::new();
+    clock.hold();
+    app.execute_script(9);
+
+    let mut handled = 0;
+    'run: loop {
+        let ms = clock.elapsed().as_millis() as u64;
+        // Let the threads act on what the app sent, then hand back what they sent
+        // in turn, until neither side has anything left for this millisecond
+        loop {
+            clock.settle();
+            let batch = take_events(&events);
+            if batch.is_empty() {
+                break;
+            }
+            clock.hold();
+            for event in batch {
+                handled += 1;
+                if handled > MAX_EVENTS {
+                    output.push(format!("{:>6} [golden] STOPPED AFTER {} EVENTS", ms, MAX_EVENTS));
+                    break 'run;
+                }
+                app.handle_metro_event(event, None);
+            }
+        }
+        for line in std::mem::take(&mut app.output) {
+            output.push(format!("{:>6} {}", ms, line));
+        }
+        drain_osc(&socket, ms, &mut osc);
+        if ms >= SCENE_DURATION_MS {
+            break;
+        }
+        clock.advance(Duration::from_millis(1));
+    }
+
+    let _ = metro_tx.send(MetroCommand::Shutdown);
+    let _ = metro.join();
+    report(&app, &output, &osc)
+}
+
+fn report(app: &App, output: &[String], osc: &[String]) -> String {
+    let mut lines = vec!["== OUTPUT ==".to_string()];
+    lines.extend(output.iter().cloned());
+
+    lines.push("== VARIABLES ==".to_string());
+    let v = &app.variables;
+    for (name, value) in [("A", v.a), ("B", v.b), ("C", v.c), ("D", v.d), ("I", v.i), ("X", v.x), ("Y", v.y), ("Z", v.z), ("T", v.t)] {
+        lines.push(format!("{} {}", name, value));
+    }
+    let per_script = |f: fn(&crate::types::Script) -> i16| {
+        app.scripts.scripts.iter().map(|s| f(s).to_string()).collect::<Vec<_>>().join(" ")
+    };
+    lines.push(format!("J {}", per_script(|s| s.j)));
+    lines.push(format!("K {}", per_script(|s| s.k)));
+    let counters: Vec<String> = app.counters.values.iter().map(|n| n.to_string()).collect();
+    lines.push(format!("N {}", counters.join(" ")));
+
+    lines.push("== PATTERNS ==".to_string());
+    lines.push(format!("P.N {}", app.patterns.working));
+    for (i, pattern) in app.patterns.patterns.iter().enumerate() {
+        // Values past the length still matter to P.L and P.INS, so show up to the last one set
+        let used = pattern.data.iter().rposition(|&v| v != 0).map_or(0, |last| last + 1);
+        let values: Vec<String> = pattern.data[..used.max(pattern.length)].iter().map(|v| v.to_string()).collect();
+        lines.push(format!("P{} L {} I {}: {}", i, pattern.length, pattern.index, values.join(" ")).trim_end().to_string());
+    }
+
+    lines.push("== OSC ==".to_string());
+    lines.extend(osc.iter().cloned());
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+fn expected_path(scene_path: &Path) -> PathBuf {
+    scene_path.with_extension("expected")
+}
+
+/// Run (or with `bless`, record) every scene in `dir`. Returns whether all passed.
+pub fn run_golden_tests(dir: &Path, bless: bool) -> Result<bool> {
+    let mut scenes: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Cannot read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    scenes.sort();
+    if scenes.is_empty() {
+        return Err(anyhow!("No scenes (*.json) in {}", dir.display()));
+    }
+
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+    for path in &scenes {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let scene: Scene = match fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string())) {
+            Ok(scene) => scene,
+            Err(e) => {
+                println!("SKIP  {} (not a scene: {})", name, e);
+                skipped += 1;
+                continue;
+            }
+        };
+
+        let actual = run_scene(&scene);
+        let expected_path = expected_path(path);
+        if bless {
+            fs::write(&expected_path, &actual).with_context(|| format!("Cannot write {}", expected_path.display()))?;
+            println!("BLESS {}", name);
+            passed += 1;
+            continue;
+        }
+
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected == actual => {
+                println!("PASS  {}", name);
+                passed += 1;
+            }
+            Ok(expected) => {
+                println!("FAIL  {}", name);
+                for line in diff(&expected, &actual).iter().take(MAX_DIFF_LINES) {
+                    println!("      {}", line);
+                }
+                failed += 1;
+            }
+            Err(_) => {
+                println!("FAIL  {} (no {}, run with --bless)", name, expected_path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+    Ok(failed == 0)
+}
+
+/// Line diff of two reports: `-` lines only in `expected`, `+` lines only in `actual`,
+/// each prefixed with the section it belongs to
+pub fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut section = "";
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            if a[i].starts_with("== ") {
+                section = a[i];
+            }
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("{} -{}", section, a[i]));
+            i += 1;
+        } else {
+            out.push(format!("{} +{}", section, b[j]));
+            j += 1;
+        }
+    }
+    out
+}
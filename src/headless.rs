@@ -0,0 +1,248 @@
+//! Headless mode: the REPL without the TUI, driven over stdin/stdout and
+//! optionally a Unix domain socket.
+//!
+//! Metro, MIDI and SuperCollider keep running as in the TUI. Each incoming line is
+//! executed like a line typed at the prompt; its output goes back to whoever sent
+//! it. Output from running scripts goes to stdout and to every socket client.
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::app::App;
+use crate::config;
+use crate::meter::meter_thread;
+use crate::metro::metro_thread;
+use crate::sc_process::ScProcess;
+use crate::terminal;
+use crate::types::{self, ConfirmAction, MetroCommand, MetroEvent, MetroState};
+
+/// How long to wait for input before checking metro events again
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+const SC_BOOT_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Stdin,
+    Socket(usize),
+}
+
+enum Input {
+    Line(Source, String),
+    Connected(usize, Box<dyn Write + Send>),
+    Closed(Source),
+}
+
+/// Run headless until QUIT, or until stdin closes when no socket is listening
+pub fn run_headless_mode(socket_path: Option<&str>, dry_run: bool) -> Result<()> {
+    let config = config::load_config().unwrap_or_default();
+
+    let sc_process = if dry_run {
+        None
+    } else {
+        let mut sc = ScProcess::new().map_err(|e| anyhow!("{} (install SuperCollider or use --dry-run)", e))?;
+        sc.start(config.display.audio_out_device.as_deref())
+            .map_err(|e| anyhow!("Failed to start SuperCollider: {}", e))?;
+        Some(sc)
+    };
+
+    let metro_state = Arc::new(Mutex::new(MetroState::default()));
+    let (metro_tx, metro_rx) = mpsc::channel();
+    let (metro_event_tx, metro_event_rx) = mpsc::channel::<MetroEvent>();
+
+    let metro_state_clone = metro_state.clone();
+    let meter_event_tx = metro_event_tx.clone();
+    let metro_handle = thread::spawn(move || {
+        metro_thread(metro_rx, metro_state_clone, metro_event_tx, dry_run);
+    });
+
+    if let Some(sc) = &sc_process {
+        thread::spawn(move || {
+            meter_thread(meter_event_tx);
+        });
+        let ready_rx = sc.spawn_ready_sender();
+        eprintln!("Waiting for SuperCollider server...");
+        wait_for_sc(&metro_event_rx)?;
+        if let Some(rx) = ready_rx {
+            let _ = rx.recv_timeout(Duration::from_secs(3));
+        }
+    }
+    let sc_process = sc_process.map(Mutex::new);
+
+    let caps = terminal::detect_capabilities();
+    let theme = config::load_theme(&config).unwrap_or_default();
+    let mut app = App::new(metro_tx.clone(), metro_state, theme, types::ColorMode::TrueColor, &config, caps);
+    let _rng = crate::rng::install(&app.rng);
+    // Nobody is there to answer Y/N
+    app.confirm_quit_unsaved = false;
+    app.confirm_overwrite_scene = false;
+    app.start_session(&config);
+
+    let (input_tx, input_rx) = mpsc::channel();
+    spawn_stdin_reader(input_tx.clone());
+    if let Some(path) = socket_path {
+        listen(path, input_tx)?;
+    }
+
+    let mut clients: HashMap<usize, Box<dyn Write + Send>> = HashMap::new();
+    broadcast(&mut app, &mut clients);
+
+    loop {
+        match input_rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Input::Line(source, line)) => {
+                // Script output from before this line isn't part of its reply
+                broadcast(&mut app, &mut clients);
+                app.input = line;
+                app.execute_command();
+                if matches!(app.pending_confirmation, Some(ConfirmAction::Quit)) {
+                    app.should_quit = true;
+                }
+                let lines = std::mem::take(&mut app.output);
+                reply(source, &lines, &mut clients);
+            }
+            Ok(Input::Connected(id, writer)) => {
+                clients.insert(id, writer);
+            }
+            Ok(Input::Closed(Source::Stdin)) => {
+                if socket_path.is_none() {
+                    break;
+                }
+            }
+            Ok(Input::Closed(Source::Socket(id))) => {
+                clients.remove(&id);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        while let Ok(event) = metro_event_rx.try_recv() {
+            app.handle_metro_event(event, sc_process.as_ref());
+        }
+        broadcast(&mut app, &mut clients);
+
+        if app.should_quit {
+            break;
+        }
+    }
+
+    if app.recording {
+        let _ = app.metro_tx.send(MetroCommand::StopRecording);
+    }
+    if app.stem_folder.is_some() {
+        let _ = app.metro_tx.send(MetroCommand::StopStemRecording(false));
+    }
+    let _ = app.metro_tx.send(MetroCommand::Shutdown);
+    let _ = metro_handle.join();
+    if let Some(sc) = &sc_process {
+        sc.lock().unwrap().stop();
+    }
+    if let Some(path) = socket_path {
+        let _ = std::fs::remove_file(path);
+    }
+    Ok(())
+}
+
+fn wait_for_sc(metro_event_rx: &mpsc::Receiver<MetroEvent>) -> Result<()> {
+    let start = std::time::Instant::now();
+    while start.elapsed() < SC_BOOT_TIMEOUT {
+        match metro_event_rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(MetroEvent::ScReady) => {
+                eprintln!("SuperCollider ready!");
+                return Ok(());
+            }
+            Ok(_) | Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    Err(anyhow!("SuperCollider failed to start within {} seconds", SC_BOOT_TIMEOUT.as_secs()))
+}
+
+fn spawn_stdin_reader(input_tx: Sender<Input>) {
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if input_tx.send(Input::Line(Source::Stdin, line)).is_err() {
+                return;
+            }
+        }
+        let _ = input_tx.send(Input::Closed(Source::Stdin));
+    });
+}
+
+#[cfg(unix)]
+fn listen(path: &str, input_tx: Sender<Input>) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    // A socket left behind by a previous run would make bind fail
+    if std::fs::metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path).map_err(|e| anyhow!("Cannot listen on {}: {}", path, e))?;
+    eprintln!("Listening on {}", path);
+
+    thread::spawn(move || {
+        for (id, stream) in listener.incoming().flatten().enumerate() {
+            let Ok(writer) = stream.try_clone() else { continue };
+            if input_tx.send(Input::Connected(id, Box::new(writer))).is_err() {
+                return;
+            }
+            let tx = input_tx.clone();
+            thread::spawn(move || {
+                for line in io::BufReader::new(stream).lines() {
+                    let Ok(line) = line else { break };
+                    if tx.send(Input::Line(Source::Socket(id), line)).is_err() {
+                        return;
+                    }
+                }
+                let _ = tx.send(Input::Closed(Source::Socket(id)));
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen(_path: &str, _input_tx: Sender<Input>) -> Result<()> {
+    Err(anyhow!("--socket is only available on Unix systems"))
+}
+
+/// Send a line's output back to whoever sent it
+fn reply(source: Source, lines: &[String], clients: &mut HashMap<usize, Box<dyn Write + Send>>) {
+    match source {
+        Source::Stdin => print_lines(lines),
+        Source::Socket(id) => {
+            if let Some(writer) = clients.get_mut(&id) {
+                if write_lines(writer, lines).is_err() {
+                    clients.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+/// Send output nobody asked for (scripts, metro errors) to stdout and every client
+fn broadcast(app: &mut App, clients: &mut HashMap<usize, Box<dyn Write + Send>>) {
+    if app.output.is_empty() {
+        return;
+    }
+    let lines = std::mem::take(&mut app.output);
+    print_lines(&lines);
+    clients.retain(|_, writer| write_lines(writer, &lines).is_ok());
+}
+
+fn print_lines(lines: &[String]) {
+    let mut stdout = io::stdout().lock();
+    let _ = write_lines(&mut stdout, lines);
+}
+
+fn write_lines(writer: &mut dyn Write, lines: &[String]) -> io::Result<()> {
+    for line in lines {
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()
+}
@@ -0,0 +1,190 @@
+//! Loudness to ITU-R BS.1770 / EBU R128, from the 100ms blocks the
+//! monokit_loudness synth sends.
+//!
+//! The synth does the K-weighting and true-peak oversampling; this turns its
+//! blocks into momentary (400ms), short-term (3s) and gated integrated LUFS,
+//! and drives the output gain when a target loudness is set.
+
+use crate::types::LoudnessBlock;
+use std::collections::VecDeque;
+
+const MOMENTARY_BLOCKS: usize = 4;
+const SHORT_TERM_BLOCKS: usize = 30;
+
+/// Blocks quieter than this never count towards the integrated loudness
+const ABSOLUTE_GATE: f32 = -70.0;
+/// ...nor do blocks this far below the ungated integrated loudness
+const RELATIVE_GATE: f32 = -10.0;
+
+/// Integrated gating histogram: 0.1 LU bins from the absolute gate to +10 LUFS
+const BIN_LU: f32 = 0.1;
+const BINS: usize = 800;
+
+/// Range of LUFS.TARGET
+pub const TARGET_MIN: i16 = -36;
+pub const TARGET_MAX: i16 = -6;
+
+/// Limiter ceiling while a target is set, in dBTP
+pub const TARGET_CEILING_DB: f32 = -1.0;
+
+/// Output gain the target mode may apply, in dB
+const GAIN_MIN_DB: f32 = -24.0;
+const GAIN_MAX_DB: f32 = 12.0;
+/// Share of the short-term error corrected per block, and the most per block
+const GAIN_RATE: f32 = 0.02;
+const GAIN_STEP_MAX_DB: f32 = 1.0;
+/// Smallest gain change worth sending to the server
+const GAIN_RESOLUTION_DB: f32 = 0.1;
+
+/// Mean square power to LUFS (L and R weighted 1.0)
+pub fn power_to_lufs(power: f32) -> f32 {
+    if power > 0.0 {
+        -0.691 + 10.0 * power.log10()
+    } else {
+        f32::NEG_INFINITY
+    }
+}
+
+pub fn amp_to_db(amp: f32) -> f32 {
+    if amp > 0.0 {
+        20.0 * amp.log10()
+    } else {
+        f32::NEG_INFINITY
+    }
+}
+
+pub fn db_to_amp(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// "-14.2", or "-INF" for silence
+pub fn format_db(db: f32) -> String {
+    if db.is_finite() {
+        format!("{:.1}", db)
+    } else {
+        "-INF".to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    /// Power of the last SHORT_TERM_BLOCKS blocks, newest last
+    recent: VecDeque<f32>,
+    /// Gated momentary blocks: count and power sum per bin
+    histogram: Vec<(u32, f64)>,
+    pub momentary: f32,
+    pub short_term: f32,
+    pub integrated: f32,
+    /// True peak of the last block, and the highest since LUFS.RST, in dBTP
+    pub true_peak: f32,
+    pub max_true_peak: f32,
+    /// Target loudness for the output gain, None when off
+    pub target: Option<f32>,
+    /// Output gain the target mode has set, in dB
+    pub gain_db: f32,
+    /// Gain the target mode is heading for, sent once it differs enough
+    wanted_db: f32,
+}
+
+impl Default for LoudnessMeter {
+    fn default() -> Self {
+        Self {
+            recent: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+            histogram: vec![(0, 0.0); BINS],
+            momentary: f32::NEG_INFINITY,
+            short_term: f32::NEG_INFINITY,
+            integrated: f32::NEG_INFINITY,
+            true_peak: f32::NEG_INFINITY,
+            max_true_peak: f32::NEG_INFINITY,
+            target: None,
+            gain_db: 0.0,
+            wanted_db: 0.0,
+        }
+    }
+}
+
+impl LoudnessMeter {
+    /// Take one block. Returns the new output gain in dB when the target mode
+    /// has moved it far enough to be sent.
+    pub fn push(&mut self, block: LoudnessBlock) -> Option<f32> {
+        let power = if block.power.is_finite() { block.power.max(0.0) } else { 0.0 };
+        if self.recent.len() == SHORT_TERM_BLOCKS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(power);
+
+        self.true_peak = amp_to_db(block.true_peak);
+        self.max_true_peak = self.max_true_peak.max(self.true_peak);
+
+        if self.recent.len() >= MOMENTARY_BLOCKS {
+            let momentary_power = self.mean_power(MOMENTARY_BLOCKS);
+            self.momentary = power_to_lufs(momentary_power);
+            // Momentary windows overlap by 75%, as the standard asks
+            if self.momentary > ABSOLUTE_GATE {
+                let bin = &mut self.histogram[bin_of(self.momentary)];
+                bin.0 += 1;
+                bin.1 += momentary_power as f64;
+            }
+            self.integrated = self.gated_loudness();
+        }
+        if self.recent.len() == SHORT_TERM_BLOCKS {
+            self.short_term = power_to_lufs(self.mean_power(SHORT_TERM_BLOCKS));
+        }
+
+        self.follow_target()
+    }
+
+    /// Clear the integrated loudness and max true peak, keeping the target
+    pub fn reset(&mut self) {
+        *self = Self { target: self.target, gain_db: self.gain_db, wanted_db: self.wanted_db, ..Self::default() };
+    }
+
+    fn mean_power(&self, blocks: usize) -> f32 {
+        self.recent.iter().rev().take(blocks).sum::<f32>() / blocks as f32
+    }
+
+    fn gated_loudness(&self) -> f32 {
+        let (count, sum) = self.histogram.iter().fold((0u32, 0.0f64), |(c, s), bin| (c + bin.0, s + bin.1));
+        if count == 0 {
+            return f32::NEG_INFINITY;
+        }
+        let threshold = power_to_lufs((sum / count as f64) as f32) + RELATIVE_GATE;
+        let first = if threshold > ABSOLUTE_GATE { bin_of(threshold) } else { 0 };
+        let (count, sum) = self.histogram[first..].iter().fold((0u32, 0.0f64), |(c, s), bin| (c + bin.0, s + bin.1));
+        if count == 0 {
+            return f32::NEG_INFINITY;
+        }
+        power_to_lufs((sum / count as f64) as f32)
+    }
+
+    /// Nudge the gain towards the target from the short-term loudness. Holds
+    /// through silence and sudden drops, so the gain doesn't run up between songs.
+    fn follow_target(&mut self) -> Option<f32> {
+        let target = self.target?;
+        if self.short_term <= ABSOLUTE_GATE || self.momentary <= self.short_term + RELATIVE_GATE {
+            return None;
+        }
+        // The reading already includes the gain sent so far
+        let error = target - self.short_term - (self.wanted_db - self.gain_db);
+        let step = (error * GAIN_RATE).clamp(-GAIN_STEP_MAX_DB, GAIN_STEP_MAX_DB);
+        self.wanted_db = (self.wanted_db + step).clamp(GAIN_MIN_DB, GAIN_MAX_DB);
+        if (self.wanted_db - self.gain_db).abs() < GAIN_RESOLUTION_DB {
+            return None;
+        }
+        self.gain_db = self.wanted_db;
+        Some(self.gain_db)
+    }
+
+    /// Set or clear the target. Clearing returns the gain to 0dB.
+    pub fn set_target(&mut self, target: Option<f32>) {
+        self.target = target;
+        if target.is_none() {
+            self.gain_db = 0.0;
+            self.wanted_db = 0.0;
+        }
+    }
+}
+
+fn bin_of(lufs: f32) -> usize {
+    (((lufs - ABSOLUTE_GATE) / BIN_LU) as usize).min(BINS - 1)
+}
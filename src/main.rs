@@ -1,13 +1,21 @@
 mod app;
+mod clock;
 mod commands;
 mod config;
 mod eval;
+mod evolve;
+mod golden;
+mod headless;
+mod loudness;
 mod meter;
 mod metro;
 mod midi;
 mod osc_utils;
 mod output;
 mod preset;
+mod rng;
+mod snapshot;
+mod spectrogram;
 mod sc_process;
 
 #[cfg(feature = "scsynth-direct")]
@@ -16,9 +24,13 @@ mod audio_devices;
 #[cfg(feature = "scsynth-direct")]
 mod scsynth_direct;
 
+#[cfg(feature = "scsynth-direct")]
+mod stems;
+
 mod sampler;
 mod scramble;
 mod scene;
+mod teletype;
 mod terminal;
 mod theme;
 mod types;
@@ -28,6 +40,7 @@ mod utils;
 pub use anyhow;
 use anyhow::Result;
 use crossterm::{
+    event::DisableMouseCapture,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -61,6 +74,26 @@ fn main() -> Result<()> {
         }
     }
 
+    // Check for --test <dir> [--bless] mode
+    let test_idx = args.iter().position(|arg| arg == "--test");
+    if let Some(dir) = test_idx.and_then(|idx| args.get(idx + 1)) {
+        let bless = args.iter().any(|arg| arg == "--bless");
+        if !golden::run_golden_tests(std::path::Path::new(dir), bless)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Check for --headless [--socket <path>] mode
+    if args.iter().any(|arg| arg == "--headless") {
+        let socket_path = args
+            .iter()
+            .position(|arg| arg == "--socket")
+            .and_then(|idx| args.get(idx + 1))
+            .map(|s| s.as_str());
+        return headless::run_headless_mode(socket_path, dry_run);
+    }
+
     // Normal TUI mode
     run_tui_mode()
 }
@@ -96,6 +129,7 @@ fn run_batch_mode(scene_name: &str, wait_ms: Option<&str>, dry_run: bool) -> Res
     };
 
     let mut app = App::new(metro_tx, metro_state, theme, color_mode, &config, caps);
+    let _rng = rng::install(&app.rng);
 
     // Override debug level for batch mode to show output
     app.debug_level = 2; // ESSENTIAL level for batch output
@@ -260,25 +294,10 @@ fn run_tui_mode() -> Result<()> {
     };
 
     let mut app = App::new(metro_tx.clone(), metro_state, theme, color_mode, &config, caps);
+    let _rng = rng::install(&app.rng);
     app.add_output("MONOKIT - SCRIPTING FOR COMPLEX OSCILLATOR".to_string());
     app.add_output("ENTER CMDS. [ ] NAV PAGES. ESC FOR HELP.".to_string());
-
-    // Send initial VCA mode to SuperCollider
-    let _ = metro_tx.send(MetroCommand::SendParam(
-        "vca_mode".to_string(),
-        rosc::OscType::Int(if config.display.vca_mode { 1 } else { 0 })
-    ));
-
-    // Autoload last scene if enabled
-    if config.display.autoload {
-        if let Some(last_scene) = &config.display.last_scene {
-            app.input = format!("LOAD {}", last_scene);
-            app.execute_delayed_command(&app.input.clone(), 0);
-            app.input.clear();
-        }
-    }
-
-    app.execute_script(9);
+    app.start_session(&config);
 
     // Wrap sc_process in Arc<Mutex> to share with run_app
     let sc_process_shared = Arc::new(Mutex::new(sc_process));
@@ -298,6 +317,7 @@ fn run_tui_mode() -> Result<()> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
+        DisableMouseCapture,
         LeaveAlternateScreen
     )?;
     terminal.show_cursor()?;
@@ -1,4 +1,4 @@
-use crate::types::{CompressorData, CpuData, MeterData, MetroEvent, ScopeData, SpectrumData, VoiceMeterData, SCOPE_SAMPLES, SPECTRUM_BANDS};
+use crate::types::{CompressorData, CpuData, LoudnessBlock, MeterData, MetroEvent, ScopeData, SpectrumData, VoiceMeterData, SCOPE_SAMPLES, SPECTROGRAM_BANDS, SPECTRUM_BANDS};
 use rosc::{decoder, encoder, OscMessage, OscPacket, OscType};
 use socket2::{Domain, Protocol, Socket, Type};
 use std::net::{SocketAddr, UdpSocket};
@@ -41,7 +41,7 @@ pub fn meter_thread(event_tx: mpsc::Sender<MetroEvent>) {
     let mut voice_meter_data = VoiceMeterData::default();
     let mut spectrum_data = SpectrumData::default();
     let mut compressor_data = CompressorData::default();
-    let mut buf = [0u8; 1024];
+    let mut buf = [0u8; 4096];
 
     // Track if we've completed initial boot - used to detect restart vs initial boot
     // We only send /notify on RESTART (when we receive /monokit/ready after initial boot)
@@ -80,6 +80,19 @@ pub fn meter_thread(event_tx: mpsc::Sender<MetroEvent>) {
                                     return;
                                 }
                             }
+                        } else if msg.addr == "/monokit/input" {
+                            if let Some(update) = parse_meter_message(&msg.args) {
+                                apply_input_update(&mut meter_data, update);
+
+                                if let Err(e) = event_tx.send(MetroEvent::MeterUpdate(meter_data.clone())) {
+                                    let _ = event_tx.send(MetroEvent::Error(format!("ERROR: METER UPDATE SEND FAIL: {}", e)));
+                                    return;
+                                }
+                            }
+                        } else if msg.addr == "/monokit/onset" {
+                            if event_tx.send(MetroEvent::Onset).is_err() {
+                                return;
+                            }
                         } else if msg.addr == "/monokit/spectrum" {
                             if let Some(update) = parse_spectrum_message(&msg.args) {
                                 apply_spectrum_update(&mut spectrum_data, update);
@@ -122,6 +135,18 @@ pub fn meter_thread(event_tx: mpsc::Sender<MetroEvent>) {
                                     eprintln!("Failed to send compressor update: {}", e);
                                 }
                             }
+                        } else if msg.addr == "/monokit/loudness" {
+                            if let Some(block) = parse_loudness_message(&msg.args) {
+                                if event_tx.send(MetroEvent::LoudnessUpdate(block)).is_err() {
+                                    return;
+                                }
+                            }
+                        } else if msg.addr == "/monokit/spectrogram" {
+                            if let Some(frame) = parse_spectrogram_message(&msg.args) {
+                                if event_tx.send(MetroEvent::SpectrogramUpdate(frame)).is_err() {
+                                    return;
+                                }
+                            }
                         } else if msg.addr == "/monokit/voice_osc" {
                             if let Some(update) = parse_voice_meter_message(&msg.args) {
                                 apply_voice_meter_update(&mut voice_meter_data, "osc", update);
@@ -289,6 +314,14 @@ fn apply_meter_update(meter_data: &mut MeterData, update: MeterUpdate) {
     }
 }
 
+/// The input meter sends one mono channel
+fn apply_input_update(meter_data: &mut MeterData, update: MeterUpdate) {
+    if update.channel == 1 {
+        meter_data.in_peak = update.peak.clamp(0.0, 1.0);
+        meter_data.in_rms = update.rms.clamp(0.0, 1.0);
+    }
+}
+
 fn decay_peak_holds(meter_data: &mut MeterData) {
     meter_data.peak_hold_l *= PEAK_HOLD_DECAY_RATE;
     meter_data.peak_hold_r *= PEAK_HOLD_DECAY_RATE;
@@ -440,31 +473,29 @@ fn parse_status_reply_cpu(args: &[OscType]) -> Option<CpuData> {
 }
 
 fn parse_scope_message(args: &[OscType]) -> Option<ScopeData> {
-    // Handle both formats:
-    // - sclang forwarded: [sample0...sample127] (128 args)
-    // - scsynth direct (SendReply): [nodeID, replyID, sample0...sample127] (130 args)
+    // Handle both formats, each with one trace or two (master then overlay):
+    // - sclang forwarded: [samples...] (128 or 256 args)
+    // - scsynth direct (SendReply): [nodeID, replyID, samples...] (130 or 258 args)
 
     let data_start = match args.len() {
-        128 => 0,   // sclang mode (SCOPE_SAMPLES)
-        130 => 2,   // scsynth-direct mode (skip nodeID and replyID)
+        n if n == SCOPE_SAMPLES || n == SCOPE_SAMPLES * 2 => 0,
+        n if n == SCOPE_SAMPLES + 2 || n == SCOPE_SAMPLES * 2 + 2 => 2,
         _ => return None,
     };
 
-    let mut samples = [0.0f32; SCOPE_SAMPLES];
+    let mut scope_data = ScopeData::default();
     for (i, arg) in args.iter().skip(data_start).enumerate() {
-        if i >= SCOPE_SAMPLES {
-            break;
-        }
         let value = match arg {
             OscType::Float(f) => *f,
             OscType::Double(d) => *d as f32,
             OscType::Int(n) => *n as f32,
             _ => return None,
         };
-        samples[i] = value.clamp(-1.0, 1.0);
+        let trace = if i < SCOPE_SAMPLES { &mut scope_data.samples } else { &mut scope_data.overlay };
+        trace[i % SCOPE_SAMPLES] = value.clamp(-1.0, 1.0);
     }
 
-    Some(ScopeData { samples })
+    Some(scope_data)
 }
 
 fn parse_compressor_message(args: &[OscType]) -> Option<CompressorData> {
@@ -500,6 +531,43 @@ fn parse_compressor_message(args: &[OscType]) -> Option<CompressorData> {
     })
 }
 
+fn parse_loudness_message(args: &[OscType]) -> Option<LoudnessBlock> {
+    // - sclang forwarded: [power, true_peak] (2 args)
+    // - scsynth-direct SendReply: [nodeID, replyID, power, true_peak] (4 args)
+    let start = match args.len() {
+        2 => 0,
+        4 => 2,
+        _ => return None,
+    };
+    let value = |arg: &OscType| match arg {
+        OscType::Float(f) => Some(*f),
+        OscType::Double(d) => Some(*d as f32),
+        _ => None,
+    };
+    Some(LoudnessBlock {
+        power: value(&args[start])?,
+        true_peak: value(&args[start + 1])?,
+    })
+}
+
+fn parse_spectrogram_message(args: &[OscType]) -> Option<Vec<f32>> {
+    // - sclang forwarded: [bands...] (SPECTROGRAM_BANDS args)
+    // - scsynth-direct SendReply: [nodeID, replyID, bands...]
+    let start = match args.len() {
+        n if n == SPECTROGRAM_BANDS => 0,
+        n if n == SPECTROGRAM_BANDS + 2 => 2,
+        _ => return None,
+    };
+    args[start..]
+        .iter()
+        .map(|arg| match arg {
+            OscType::Float(f) => Some(*f),
+            OscType::Double(d) => Some(*d as f32),
+            _ => None,
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 struct VoiceMeterUpdate {
     channel: i32,
@@ -1,23 +1,28 @@
+use crate::clock::{Clock, RealClock};
 use crate::osc_utils::{create_bundle, OSC_LATENCY_MS};
+use crate::sampler::stretch::{interval_to_bpm, MidiTempoTracker};
+use crate::snapshot;
 use crate::types::{DelayedCommand, DelayThreadCommand, MetroCommand, MetroEvent, MetroState, SyncMode, OSC_ADDR, MONOKIT_NODE_ID, route_param_to_node, route_param_to_nodes, NOISE_NODE_ID, MOD_NODE_ID, PRIMARY_NODE_ID, MAIN_NODE_ID, PLAITS_NODE_ID};
 use rosc::{encoder, OscMessage, OscPacket, OscType};
-use spin_sleep::SpinSleeper;
 use audio_thread_priority::promote_current_thread_to_real_time;
 use socket2::{Domain, Protocol, Socket, Type};
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::net::{SocketAddr, UdpSocket};
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 const OSC_BUFFER_SIZE: usize = 4 * 1024 * 1024; // 4MB buffer to prevent packet loss
+/// How long `live_params` waits for the metro thread before giving up
+const FLUSH_TIMEOUT: Duration = Duration::from_millis(100);
 
 fn get_osc_log_path() -> std::path::PathBuf {
     std::env::temp_dir().join("monokit_osc.log")
 }
 
-fn format_osc_args(args: &[OscType]) -> String {
+pub fn format_osc_args(args: &[OscType]) -> String {
     args.iter()
         .map(|arg| match arg {
             OscType::Int(i) => i.to_string(),
@@ -126,6 +131,27 @@ fn create_trigger_message() -> OscMessage {
     msg
 }
 
+#[cfg(feature = "scsynth-direct")]
+fn create_plaits_trigger_message() -> OscMessage {
+    let msg = OscMessage {
+        addr: "/n_set".to_string(),
+        args: vec![OscType::Int(PLAITS_NODE_ID), OscType::String("t_gate".to_string()), OscType::Int(1)],
+    };
+    log_osc_message(&msg, "CREATE_PLAITS_TRIGGER");
+    msg
+}
+
+#[cfg(not(feature = "scsynth-direct"))]
+fn create_plaits_trigger_message() -> OscMessage {
+    // For sclang mode, send via /monokit/param with plaits-specific parameter
+    let msg = OscMessage {
+        addr: "/monokit/param".to_string(),
+        args: vec![OscType::String("t_gate_plaits".to_string()), OscType::Int(1)],
+    };
+    log_osc_message(&msg, "CREATE_PLAITS_TRIGGER");
+    msg
+}
+
 #[cfg(feature = "scsynth-direct")]
 fn create_volume_message(value: f32) -> OscMessage {
     OscMessage {
@@ -234,7 +260,7 @@ fn create_scope_rate_message(time_ms: f32) -> OscMessage {
     OscMessage {
         addr: "/n_set".to_string(),
         args: vec![
-            OscType::Int(1002),
+            OscType::Int(crate::types::SCOPE_NODE_ID),
             OscType::String("scopeRate".to_string()),
             OscType::Float(rate),
         ],
@@ -249,6 +275,25 @@ fn create_scope_rate_message(time_ms: f32) -> OscMessage {
     }
 }
 
+#[cfg(feature = "scsynth-direct")]
+fn create_spectrogram_run_message(running: bool) -> OscMessage {
+    OscMessage {
+        addr: "/n_run".to_string(),
+        args: vec![
+            OscType::Int(crate::types::SPECTROGRAM_NODE_ID),
+            OscType::Int(running as i32),
+        ],
+    }
+}
+
+#[cfg(not(feature = "scsynth-direct"))]
+fn create_spectrogram_run_message(running: bool) -> OscMessage {
+    OscMessage {
+        addr: "/monokit/spectrogram/run".to_string(),
+        args: vec![OscType::Int(running as i32)],
+    }
+}
+
 // Metro thread timing diagnostics
 struct MetroTimingStats {
     last_process_time: Option<Instant>,
@@ -396,6 +441,73 @@ impl MetroTimingStats {
     }
 }
 
+/// Messages setting a parameter on every node it is routed to
+fn param_messages(name: &str, value: OscType) -> Vec<OscMessage> {
+    // Some parameters need multiple destinations
+    #[cfg(feature = "scsynth-direct")]
+    return create_param_messages(name, value);
+
+    #[cfg(not(feature = "scsynth-direct"))]
+    return vec![create_param_message(name, value)];
+}
+
+/// The live param values once the metro thread has handled everything sent
+/// before this call, so they include params set just before reading them
+/// (`PF 440; PATCH.SAVE OSC KICK`)
+pub fn live_params(metro_tx: &mpsc::Sender<MetroCommand>) -> Result<snapshot::Snapshot, String> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    metro_tx
+        .send(MetroCommand::Flush(reply_tx))
+        .map_err(|_| "ERROR: METRO THREAD NOT RUNNING".to_string())?;
+    reply_rx
+        .recv_timeout(FLUSH_TIMEOUT)
+        .map_err(|_| "ERROR: METRO THREAD NOT RESPONDING".to_string())
+}
+
+/// The OSC a command sends by itself. Empty for commands that change metro
+/// state, go through the snapshot bank or the delay thread, or send nothing.
+pub fn command_messages(cmd: &MetroCommand) -> Vec<OscMessage> {
+    match cmd {
+        MetroCommand::SendParam(name, value) => param_messages(name, value.clone()),
+        #[cfg(feature = "scsynth-direct")]
+        MetroCommand::SendTrigger => create_trigger_messages(),
+        #[cfg(not(feature = "scsynth-direct"))]
+        MetroCommand::SendTrigger => vec![create_trigger_message()],
+        MetroCommand::SendPlaitsTrigger => vec![create_plaits_trigger_message()],
+        MetroCommand::SendVolume(value) => vec![create_volume_message(*value)],
+        MetroCommand::SetSlewTime(time_sec) => vec![create_slew_message(*time_sec)],
+        MetroCommand::SetParamSlew(param, time_sec) => vec![create_param_slew_message(param, *time_sec)],
+        MetroCommand::SetGate(time_sec) => vec![create_gate_message(*time_sec)],
+        MetroCommand::SetEnvGate(env_name, time_sec) => vec![create_env_gate_message(env_name, *time_sec)],
+        MetroCommand::SendScopeRate(time_ms) => vec![create_scope_rate_message(*time_ms)],
+        MetroCommand::SetSpectrogramRunning(running) => vec![create_spectrogram_run_message(*running)],
+        MetroCommand::StartPreview(path, channels) => crate::sampler::library::preview_messages(path, *channels),
+        MetroCommand::StopPreview => vec![crate::sampler::library::stop_preview_message()],
+        _ => Vec::new(),
+    }
+}
+
+/// Send a parameter to every node it is routed to
+fn send_param(socket: Option<&UdpSocket>, name: &str, value: OscType, use_timestamp: bool) {
+    for msg in param_messages(name, value) {
+        send_osc(socket, msg, use_timestamp);
+    }
+}
+
+/// Send the params produced by a snapshot op, or report why there were none
+fn send_snapshot_params(socket: Option<&UdpSocket>, result: Result<Vec<(String, OscType)>, String>, event_tx: &mpsc::Sender<MetroEvent>, use_timestamp: bool) {
+    match result {
+        Ok(params) => {
+            for (name, value) in params {
+                send_param(socket, &name, value, use_timestamp);
+            }
+        }
+        Err(e) => {
+            let _ = event_tx.send(MetroEvent::Error(format!("ERROR: {}", e)));
+        }
+    }
+}
+
 /// Send OSC - uses timestamps for internal timing, immediate for MIDI sync
 fn send_osc(socket: Option<&UdpSocket>, msg: OscMessage, use_timestamp: bool) {
     log_osc_message(&msg, "SEND");
@@ -412,19 +524,34 @@ fn send_osc(socket: Option<&UdpSocket>, msg: OscMessage, use_timestamp: bool) {
     }
 }
 
+/// How long the metro and delay threads wait for commands when nothing is due.
+/// Commands wake them at once, so this only bounds how long they sleep.
+const IDLE_WAIT: Duration = Duration::from_secs(1);
+
 /// Separate thread for delay command execution - runs independently of metro timing
-fn delay_thread(rx: mpsc::Receiver<DelayThreadCommand>, event_tx: mpsc::Sender<MetroEvent>) {
-    let spinner = SpinSleeper::default();
+fn delay_thread<C: Clock>(rx: mpsc::Receiver<DelayThreadCommand>, event_tx: mpsc::Sender<MetroEvent>, clock: C) {
     let mut delayed_commands: Vec<DelayedCommand> = Vec::new();
-    let start_time = Instant::now();
+    let start_time = clock.now();
+    let elapsed_ms = || clock.now().duration_since(start_time).as_millis() as u64;
 
     loop {
-        // Non-blocking receive of delay commands
-        while let Ok(cmd) = rx.try_recv() {
+        // Wake for the next command or when the next delayed command is due
+        let wait = match delayed_commands.first() {
+            Some(next) => Duration::from_millis(next.due_at_ms.saturating_sub(elapsed_ms())),
+            None => IDLE_WAIT,
+        };
+        let mut commands = Vec::new();
+        match clock.recv_timeout(&rx, wait) {
+            Ok(cmd) => commands.push(cmd),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+        commands.extend(rx.try_iter());
+
+        for cmd in commands {
             match cmd {
                 DelayThreadCommand::Schedule(command, delay_ms, script_index) => {
-                    let elapsed_ms = start_time.elapsed().as_millis() as u64;
-                    let due_at_ms = elapsed_ms + delay_ms;
+                    let due_at_ms = elapsed_ms() + delay_ms;
                     delayed_commands.push(DelayedCommand {
                         due_at_ms,
                         command,
@@ -433,7 +560,7 @@ fn delay_thread(rx: mpsc::Receiver<DelayThreadCommand>, event_tx: mpsc::Sender<M
                     delayed_commands.sort_by_key(|dc| dc.due_at_ms);
                 }
                 DelayThreadCommand::ScheduleRepeated(command, count, interval_ms, script_index) => {
-                    let elapsed_ms = start_time.elapsed().as_millis() as u64;
+                    let elapsed_ms = elapsed_ms();
                     for i in 0..count {
                         let due_at_ms = elapsed_ms + (i as u64 * interval_ms);
                         delayed_commands.push(DelayedCommand {
@@ -454,7 +581,7 @@ fn delay_thread(rx: mpsc::Receiver<DelayThreadCommand>, event_tx: mpsc::Sender<M
         }
 
         // Execute due delayed commands
-        let elapsed_ms = start_time.elapsed().as_millis() as u64;
+        let elapsed_ms = elapsed_ms();
         while !delayed_commands.is_empty() && delayed_commands[0].due_at_ms <= elapsed_ms {
             let delayed_cmd = delayed_commands.remove(0);
             let _ = event_tx.send(MetroEvent::ExecuteDelayed(
@@ -462,22 +589,29 @@ fn delay_thread(rx: mpsc::Receiver<DelayThreadCommand>, event_tx: mpsc::Sender<M
                 delayed_cmd.script_index,
             ));
         }
-
-        // Sleep until next delayed command or fixed tick (1ms max)
-        let sleep_duration = if let Some(next) = delayed_commands.first() {
-            let time_to_next = next.due_at_ms.saturating_sub(elapsed_ms);
-            Duration::from_millis(time_to_next.min(1))
-        } else {
-            Duration::from_millis(10) // Idle sleep when no delays pending
-        };
-        spinner.sleep(sleep_duration);
     }
 }
 
+/// Device output check before a restart. sclang picks its devices itself, so
+/// without scsynth-direct there's nothing to check against.
+#[cfg(feature = "scsynth-direct")]
+fn check_output_channels(device: Option<&str>, channels: u8) -> Result<(), String> {
+    crate::audio_devices::check_output_channels(device, channels)
+}
+
+#[cfg(not(feature = "scsynth-direct"))]
+fn check_output_channels(_device: Option<&str>, _channels: u8) -> Result<(), String> {
+    Ok(())
+}
+
 pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroState>>, event_tx: mpsc::Sender<MetroEvent>, dry_run: bool) {
-    let _rt_handle = promote_current_thread_to_real_time(512, 48000).ok();
+    let osc_addr = (!dry_run).then(|| OSC_ADDR.parse().unwrap());
+    metro_thread_to(rx, state, event_tx, osc_addr);
+}
 
-    if dry_run {
+/// Metro thread sending OSC to `osc_addr` instead of SuperCollider's port (None = dry run)
+pub fn metro_thread_to(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroState>>, event_tx: mpsc::Sender<MetroEvent>, osc_addr: Option<SocketAddr>) {
+    if osc_addr.is_none() {
         eprintln!("[monokit] Metro thread: DRY-RUN mode (no OSC)");
     } else {
         #[cfg(feature = "scsynth-direct")]
@@ -486,12 +620,14 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
         #[cfg(not(feature = "scsynth-direct"))]
         eprintln!("[monokit] Metro thread: SCLANG mode (port 57120, /monokit/* format)");
     }
+    metro_thread_with_clock(rx, state, event_tx, osc_addr, RealClock);
+}
 
-    let _spinner = SpinSleeper::default(); // Kept for potential future use
+/// Metro thread timing ticks, delays and MIDI clock against `clock`
+pub fn metro_thread_with_clock<C: Clock>(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroState>>, event_tx: mpsc::Sender<MetroEvent>, osc_addr: Option<SocketAddr>, clock: C) {
+    let _rt_handle = promote_current_thread_to_real_time(512, 48000).ok();
 
-    let socket: Option<UdpSocket> = if dry_run {
-        None
-    } else {
+    let socket: Option<UdpSocket> = if let Some(osc_addr) = osc_addr {
         // Create socket with large buffers to prevent UDP packet loss
         let socket = match Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)) {
             Ok(s) => s,
@@ -514,7 +650,6 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
         }
 
         // Connect to SuperCollider
-        let osc_addr: SocketAddr = OSC_ADDR.parse().unwrap();
         if let Err(e) = socket.connect(&osc_addr.into()) {
             let _ = event_tx.send(MetroEvent::Error(format!("ERROR: OSC CONNECT FAIL: {}", e)));
             return;
@@ -522,39 +657,57 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
 
         // Convert to std UdpSocket for compatibility
         Some(socket.into())
+    } else {
+        None
     };
 
     let mut interval_ms: u64 = 500;
     let mut active = false;
     let mut sync_mode = SyncMode::Internal;
-    let mut next_tick = Instant::now();
+    let mut next_tick = clock.now();
     let mut metro_timing = MetroTimingStats::new();
+    let mut midi_tempo = MidiTempoTracker::default();
+    // REC.STEMS start/stop waiting for the next tick
+    let mut pending_stems: Option<MetroEvent> = None;
 
     // Spawn separate delay thread for independent timing
     let (delay_tx, delay_rx) = mpsc::channel::<DelayThreadCommand>();
     let delay_event_tx = event_tx.clone();
+    let delay_clock = clock.clone();
+    let rng = state.lock().unwrap().rng.clone();
+    let delay_rng = rng.clone();
     thread::spawn(move || {
-        delay_thread(delay_rx, delay_event_tx);
+        let _rng = crate::rng::install(&delay_rng);
+        delay_thread(delay_rx, delay_event_tx, delay_clock);
     });
 
+    let snapshots = state.lock().unwrap().snapshots.clone();
+    let _rng = crate::rng::install(&rng);
+
     loop {
         let mut interval_changed = false;
 
         // Calculate time until next metro tick for recv timeout
         let wait_duration = if sync_mode == SyncMode::Internal && active {
-            let now = Instant::now();
+            let now = clock.now();
             if next_tick > now {
                 next_tick - now
             } else {
                 Duration::ZERO
             }
         } else {
-            Duration::from_millis(10)
+            IDLE_WAIT
+        };
+        // Wake up for the next step of a running SNAP.GLIDE
+        let wait_duration = if snapshots.lock().unwrap().is_gliding() {
+            wait_duration.min(snapshot::GLIDE_STEP)
+        } else {
+            wait_duration
         };
 
         // Wait for commands OR timeout at next tick time
         // This ensures we wake immediately on incoming commands (e.g., from delay thread)
-        let commands: Vec<MetroCommand> = match rx.recv_timeout(wait_duration) {
+        let commands: Vec<MetroCommand> = match clock.recv_timeout(&rx, wait_duration) {
             Ok(cmd) => {
                 let mut cmds = vec![cmd];
                 while let Ok(c) = rx.try_recv() {
@@ -570,6 +723,10 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                 MetroCommand::SetInterval(ms) => {
                     interval_ms = ms;
                     interval_changed = true;
+                    // Keep stretched sampler loops locked to the metro tempo
+                    if sync_mode == SyncMode::Internal {
+                        send_param(socket.as_ref(), "s_tempoBpm", OscType::Float(interval_to_bpm(ms)), false);
+                    }
                 }
                 MetroCommand::SetActive(act) => {
                     active = act;
@@ -579,59 +736,30 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                     state.script_index = idx;
                 }
                 MetroCommand::SendParam(name, value) => {
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        // Send to all target nodes (some parameters need multiple destinations)
-                        for msg in create_param_messages(&name, value.clone()) {
-                            send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                        }
-                    }
-                    #[cfg(not(feature = "scsynth-direct"))]
-                    {
-                        let msg = create_param_message(&name, value);
-                        send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                    }
+                    snapshots.lock().unwrap().record(&name, &value);
+                    send_param(socket.as_ref(), &name, value, sync_mode == SyncMode::Internal);
                 }
                 MetroCommand::SendTrigger => {
                     // Send t_gate to all 4 synths in multi-synth architecture
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        for msg in create_trigger_messages() {
-                            send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                        }
-                    }
-                    #[cfg(not(feature = "scsynth-direct"))]
-                    {
-                        let msg = create_trigger_message();
+                    for msg in command_messages(&MetroCommand::SendTrigger) {
                         send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
                     }
                     metro_timing.trigger_count += 1;
                 }
-                MetroCommand::SendPlaitsTrigger => {
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        let msg = OscMessage {
-                            addr: "/n_set".to_string(),
-                            args: vec![OscType::Int(PLAITS_NODE_ID), OscType::String("t_gate".to_string()), OscType::Int(1)],
-                        };
-                        log_osc_message(&msg, "CREATE_PLAITS_TRIGGER");
-                        send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                    }
-                    #[cfg(not(feature = "scsynth-direct"))]
-                    {
-                        // For sclang mode, send via /monokit/param with plaits-specific parameter
-                        let msg = OscMessage {
-                            addr: "/monokit/param".to_string(),
-                            args: vec![OscType::String("t_gate_plaits".to_string()), OscType::Int(1)],
-                        };
-                        log_osc_message(&msg, "CREATE_PLAITS_TRIGGER");
+                cmd @ (MetroCommand::SendPlaitsTrigger
+                | MetroCommand::SendVolume(_)
+                | MetroCommand::SetSlewTime(_)
+                | MetroCommand::SetParamSlew(..)
+                | MetroCommand::SetGate(_)
+                | MetroCommand::SetEnvGate(..)
+                | MetroCommand::SendScopeRate(_)
+                | MetroCommand::SetSpectrogramRunning(_)
+                | MetroCommand::StartPreview(..)
+                | MetroCommand::StopPreview) => {
+                    for msg in command_messages(&cmd) {
                         send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
                     }
                 }
-                MetroCommand::SendVolume(value) => {
-                    let msg = create_volume_message(value);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                }
                 MetroCommand::StartRecording(dir) => {
                     #[cfg(not(feature = "scsynth-direct"))]
                     {
@@ -660,6 +788,26 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                         let _ = event_tx.send(MetroEvent::StopRecordingDirect);
                     }
                 }
+                MetroCommand::StartStemRecording(dir, quantized) => {
+                    let bpm = match sync_mode {
+                        SyncMode::MidiClock => midi_tempo.bpm().unwrap_or(interval_to_bpm(interval_ms)),
+                        SyncMode::Internal => interval_to_bpm(interval_ms),
+                    };
+                    let event = MetroEvent::StartStemRecordingDirect(dir, bpm);
+                    if quantized && active {
+                        pending_stems = Some(event);
+                    } else {
+                        let _ = event_tx.send(event);
+                    }
+                }
+                MetroCommand::StopStemRecording(quantized) => {
+                    if quantized && active {
+                        pending_stems = Some(MetroEvent::StopStemRecordingDirect);
+                    } else {
+                        pending_stems = None;
+                        let _ = event_tx.send(MetroEvent::StopStemRecordingDirect);
+                    }
+                }
                 MetroCommand::SetRecordingPath(path) => {
                     #[cfg(not(feature = "scsynth-direct"))]
                     {
@@ -674,21 +822,43 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                         let _ = event_tx.send(MetroEvent::SetRecordingPathDirect(path));
                     }
                 }
-                MetroCommand::SetSlewTime(time_sec) => {
-                    let msg = create_slew_message(time_sec);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
+                MetroCommand::SnapSave(slot) => {
+                    snapshots.lock().unwrap().save(slot);
+                }
+                MetroCommand::SnapLoad(slot) => {
+                    let result = snapshots.lock().unwrap().load(slot);
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
+                }
+                MetroCommand::SnapMorph(a, b, amount) => {
+                    let result = snapshots.lock().unwrap().morph(a, b, amount);
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
                 }
-                MetroCommand::SetParamSlew(param, time_sec) => {
-                    let msg = create_param_slew_message(&param, time_sec);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
+                MetroCommand::SnapGlide(slot, ms) => {
+                    let result = snapshots.lock().unwrap().glide(slot, ms, clock.now());
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
                 }
-                MetroCommand::SetGate(time_sec) => {
-                    let msg = create_gate_message(time_sec);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
+                MetroCommand::Mutate(amount, group, seed) => {
+                    let params = snapshots.lock().unwrap().mutate(amount, group, seed);
+                    send_snapshot_params(socket.as_ref(), Ok(params), &event_tx, sync_mode == SyncMode::Internal);
                 }
-                MetroCommand::SetEnvGate(env_name, time_sec) => {
-                    let msg = create_env_gate_message(&env_name, time_sec);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
+                MetroCommand::Breed(a, b, seed) => {
+                    let result = snapshots.lock().unwrap().breed(a, b, seed);
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
+                }
+                MetroCommand::MutKeep => {
+                    let result = snapshots.lock().unwrap().keep().map(|_| Vec::new());
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
+                }
+                MetroCommand::MutDiscard => {
+                    let result = snapshots.lock().unwrap().discard();
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
+                }
+                MetroCommand::MutBack => {
+                    let result = snapshots.lock().unwrap().back();
+                    send_snapshot_params(socket.as_ref(), result, &event_tx, sync_mode == SyncMode::Internal);
+                }
+                MetroCommand::Flush(reply) => {
+                    let _ = reply.send(snapshots.lock().unwrap().live().clone());
                 }
                 MetroCommand::ScheduleDelayed(cmd, delay_ms, script_idx) => {
                     // Forward to delay thread
@@ -704,8 +874,17 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                 }
                 MetroCommand::SetSyncMode(mode) => {
                     sync_mode = mode;
+                    midi_tempo.reset();
+                    if sync_mode == SyncMode::Internal {
+                        send_param(socket.as_ref(), "s_tempoBpm", OscType::Float(interval_to_bpm(interval_ms)), false);
+                    }
                 }
                 MetroCommand::MidiClockTick => {
+                    if sync_mode == SyncMode::MidiClock {
+                        if let Some(bpm) = midi_tempo.tick(clock.now()) {
+                            send_param(socket.as_ref(), "s_tempoBpm", OscType::Float(bpm), false);
+                        }
+                    }
                     if sync_mode == SyncMode::MidiClock && active {
                         let recv_time = Instant::now();
 
@@ -713,6 +892,9 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                             let st = state.lock().unwrap();
                             st.script_index
                         };
+                        if let Some(event) = pending_stems.take() {
+                            let _ = event_tx.send(event);
+                        }
                         let _ = event_tx.send(MetroEvent::ExecuteScript(script_index));
 
                         let send_time = Instant::now();
@@ -729,6 +911,7 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                 MetroCommand::MidiTransportStart => {
                     if sync_mode == SyncMode::MidiClock {
                         active = true;
+                        midi_tempo.reset();
                     }
                 }
                 MetroCommand::MidiTransportStop => {
@@ -776,10 +959,6 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                     metro_timing.trigger_count = 0;
                     let _ = event_tx.send(MetroEvent::Error("TRIGGER COUNTER RESET".to_string()));
                 }
-                MetroCommand::SendScopeRate(time_ms) => {
-                    let msg = create_scope_rate_message(time_ms);
-                    send_osc(socket.as_ref(), msg, sync_mode == SyncMode::Internal);
-                }
                 MetroCommand::QueryAudioOutDevices => {
                     #[cfg(not(feature = "scsynth-direct"))]
                     {
@@ -819,7 +998,26 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
                     }
                 }
                 MetroCommand::SetAudioOutDevice(device) => {
-                    let _ = event_tx.send(MetroEvent::RestartScWithDevice(device));
+                    let channels = crate::config::load_config().map(|c| c.display.output_channels).unwrap_or(2);
+                    match check_output_channels(Some(&device), channels) {
+                        Ok(()) => {
+                            let _ = event_tx.send(MetroEvent::RestartScWithDevice(device));
+                        }
+                        Err(e) => {
+                            let _ = event_tx.send(MetroEvent::Error(format!("ERROR: {}, OUT.CH IS {}", e, channels)));
+                        }
+                    }
+                }
+                MetroCommand::SetOutputChannels(channels) => {
+                    let device = crate::config::load_config().ok().and_then(|c| c.display.audio_out_device);
+                    match check_output_channels(device.as_deref(), channels) {
+                        Ok(()) => {
+                            let _ = event_tx.send(MetroEvent::RestartScWithChannels(channels));
+                        }
+                        Err(e) => {
+                            let _ = event_tx.send(MetroEvent::Error(format!("ERROR: {}", e)));
+                        }
+                    }
                 }
                 MetroCommand::Error(msg) => {
                     let _ = event_tx.send(MetroEvent::Error(msg));
@@ -831,20 +1029,35 @@ pub fn metro_thread(rx: mpsc::Receiver<MetroCommand>, state: Arc<Mutex<MetroStat
         }
 
         if interval_changed {
-            next_tick = Instant::now();
+            next_tick = clock.now();
+        }
+
+        let glide_params = {
+            let mut bank = snapshots.lock().unwrap();
+            if bank.is_gliding() {
+                bank.glide_step(clock.now())
+            } else {
+                Vec::new()
+            }
+        };
+        for (name, value) in glide_params {
+            send_param(socket.as_ref(), &name, value, false);
         }
 
         // Check if it's time for metro tick (recv_timeout handles the waiting)
         match sync_mode {
             SyncMode::Internal => {
                 if active {
-                    let now = Instant::now();
+                    let now = clock.now();
                     if now >= next_tick {
                         let script_index = {
                             let st = state.lock().unwrap();
                             st.script_index
                         };
 
+                        if let Some(event) = pending_stems.take() {
+                            let _ = event_tx.send(event);
+                        }
                         let _ = event_tx.send(MetroEvent::ExecuteScript(script_index));
                         next_tick += Duration::from_millis(interval_ms);
 
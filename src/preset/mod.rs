@@ -1,4 +1,5 @@
 pub mod factory;
+pub mod patch;
 
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -0,0 +1,183 @@
+//! Voice patches: resolved parameter values for one voice group, stored as TOML.
+//!
+//! Unlike script presets a patch is loaded straight into the synth with `PATCH <name>`
+//! and doesn't take up a script slot. The files are plain TOML so they can be shared.
+
+use super::{sanitize_name, PresetError};
+use crate::snapshot::{ParamValue, Snapshot};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PLAITS_PARAMS: [&str; 9] = ["engine", "harmonics", "timbre", "morph", "decay", "lpg", "pitch", "plv", "pav"];
+const SAMPLER_PREFIXES: [&str; 3] = ["s_", "sf_", "srings_"];
+/// Clouds, beat repeat, pitch shift and compressor params share a prefix
+const FX_PREFIXES: [&str; 4] = ["cl_", "br_", "ps_", "cr_"];
+const FX_PARAMS: [&str; 41] = [
+    "fc", "fq", "ft", "fk", "fe", "fed", "flev_atk", "flev_crv", "mf_f", "mf_q",
+    "rgf", "rgm", "rgw", "lb", "ls", "lm", "ct", "cr", "ca", "cl", "cm",
+    "dt", "df", "dlp", "dw", "dmode", "dtail", "ds",
+    "rv", "rp", "rh", "rw", "rmode", "rtail",
+    "el", "elf", "em", "ef", "eq", "eh", "ehf",
+];
+/// Mixer levels and pans belong to the mix, not the sound
+const MIXER_PREFIXES: [&str; 2] = ["vol_", "pan_"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchGroup {
+    /// Complex oscillator voice: oscillators, FM, discontinuity, mod bus, noise, envelopes
+    Osc,
+    Plaits,
+    Sampler,
+    Fx,
+    All,
+}
+
+impl PatchGroup {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.to_uppercase().as_str() {
+            "OSC" => Some(PatchGroup::Osc),
+            "PL" | "PLAITS" => Some(PatchGroup::Plaits),
+            "SMP" | "SAMPLER" => Some(PatchGroup::Sampler),
+            "FX" => Some(PatchGroup::Fx),
+            "ALL" => Some(PatchGroup::All),
+            _ => None,
+        }
+    }
+
+    /// Group a parameter belongs to, or None for params no patch stores
+    pub fn of(param: &str) -> Option<Self> {
+        if MIXER_PREFIXES.iter().any(|p| param.starts_with(p)) {
+            return None;
+        }
+        if PLAITS_PARAMS.contains(&param) {
+            Some(PatchGroup::Plaits)
+        } else if SAMPLER_PREFIXES.iter().any(|p| param.starts_with(p)) {
+            Some(PatchGroup::Sampler)
+        } else if FX_PARAMS.contains(&param) || FX_PREFIXES.iter().any(|p| param.starts_with(p)) {
+            Some(PatchGroup::Fx)
+        } else {
+            Some(PatchGroup::Osc)
+        }
+    }
+
+    pub fn contains(self, param: &str) -> bool {
+        match PatchGroup::of(param) {
+            Some(group) => self == PatchGroup::All || self == group,
+            None => false,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PatchGroup::Osc => "OSC",
+            PatchGroup::Plaits => "PLAITS",
+            PatchGroup::Sampler => "SAMPLER",
+            PatchGroup::Fx => "FX",
+            PatchGroup::All => "ALL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    pub version: u32,
+    pub name: String,
+    pub group: PatchGroup,
+    #[serde(default)]
+    pub description: String,
+    pub params: Snapshot,
+}
+
+impl Patch {
+    /// Capture every param of `group`: the current value of those set since
+    /// launch or RST, the default of the rest
+    pub fn capture(name: &str, group: PatchGroup, live: &Snapshot) -> Self {
+        let mut params = crate::snapshot::defaults();
+        params.extend(live.iter().map(|(param, value)| (param.clone(), *value)));
+        params.retain(|param, _| group.contains(param));
+        Patch {
+            version: 1,
+            name: name.to_string(),
+            group,
+            description: String::new(),
+            params,
+        }
+    }
+
+    /// Params whose current value differs from the patch: (param, current, patch)
+    pub fn diff(&self, live: &Snapshot) -> Vec<(String, Option<ParamValue>, ParamValue)> {
+        self.params
+            .iter()
+            .filter(|(param, value)| live.get(*param) != Some(value))
+            .map(|(param, value)| (param.clone(), live.get(param).copied(), *value))
+            .collect()
+    }
+
+    pub fn to_toml(&self) -> Result<String, PresetError> {
+        toml::to_string_pretty(self).map_err(|e| PresetError::ParseError(e.to_string()))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, PresetError> {
+        toml::from_str(text).map_err(|e| PresetError::ParseError(e.to_string()))
+    }
+}
+
+pub fn get_patches_dir() -> PathBuf {
+    crate::config::monokit_config_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("patches")
+}
+
+pub fn patch_path(name: &str) -> PathBuf {
+    get_patches_dir().join(format!("{}.toml", sanitize_name(name)))
+}
+
+pub fn save_patch(patch: &Patch) -> Result<(), PresetError> {
+    if sanitize_name(&patch.name).is_empty() {
+        return Err(PresetError::InvalidName(patch.name.clone()));
+    }
+    fs::create_dir_all(get_patches_dir()).map_err(PresetError::IoError)?;
+    fs::write(patch_path(&patch.name), patch.to_toml()?).map_err(PresetError::IoError)
+}
+
+pub fn load_patch(name: &str) -> Result<Patch, PresetError> {
+    let path = patch_path(name);
+    if !path.exists() {
+        return Err(PresetError::NotFound(name.to_string()));
+    }
+    read_patch_file(&path)
+}
+
+pub fn read_patch_file(path: &Path) -> Result<Patch, PresetError> {
+    let text = fs::read_to_string(path).map_err(PresetError::IoError)?;
+    Patch::from_toml(&text)
+}
+
+pub fn list_patches() -> Result<Vec<String>, PresetError> {
+    let dir = get_patches_dir();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(PresetError::IoError)? {
+        let path = entry.map_err(PresetError::IoError)?.path();
+        if path.extension().is_some_and(|e| e == "toml") {
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+pub fn delete_patch(name: &str) -> Result<(), PresetError> {
+    let path = patch_path(name);
+    if !path.exists() {
+        return Err(PresetError::NotFound(name.to_string()));
+    }
+    fs::remove_file(&path).map_err(PresetError::IoError)
+}
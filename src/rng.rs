@@ -0,0 +1,179 @@
+//! Shared random number generator for every random op (RND, TOSS, PROB, SEQ, RND.* ...).
+//!
+//! Unseeded it draws from OS entropy. `SEED <n>` makes the sequence reproducible,
+//! and a script can get its own stream with `SEED.SC` so its randomness doesn't
+//! shift when other scripts consume numbers.
+//!
+//! The state belongs to the App as a [`SharedRng`]. Any thread that runs
+//! commands enters it with [`enter_script`] (or [`install`] for a whole
+//! session), so a seed set on one thread holds on every other.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::sync::{Arc, Mutex};
+
+const SCRIPT_STREAMS: usize = 10;
+
+/// Mixed into the seed for the display stream, so header scrambles replay
+/// with the seed without taking numbers from the scripts
+const DISPLAY_STREAM: u64 = 0x5c4a_3b1e;
+
+/// Seeds saved with a scene so a generative performance replays exactly
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RngSeeds {
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub script_seeds: Vec<Option<u64>>,
+}
+
+struct RngState {
+    seed: Option<u64>,
+    global: StdRng,
+    display: StdRng,
+    scripts: [Option<(u64, StdRng)>; SCRIPT_STREAMS],
+}
+
+impl Default for RngState {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            global: StdRng::from_entropy(),
+            display: StdRng::from_entropy(),
+            scripts: Default::default(),
+        }
+    }
+}
+
+/// Random state of one App, cloned to every thread that runs its commands
+#[derive(Clone, Default)]
+pub struct SharedRng(Arc<Mutex<RngState>>);
+
+impl std::fmt::Debug for SharedRng {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedRng").field("seed", &self.current_seed()).finish()
+    }
+}
+
+impl SharedRng {
+    /// Reseed the shared stream (None returns to entropy) and restart every script stream
+    pub fn seed(&self, seed: Option<u64>) {
+        let mut state = self.0.lock().unwrap();
+        state.seed = seed;
+        (state.global, state.display) = match seed {
+            Some(seed) => (StdRng::seed_from_u64(seed), StdRng::seed_from_u64(seed ^ DISPLAY_STREAM)),
+            None => (StdRng::from_entropy(), StdRng::from_entropy()),
+        };
+        for stream in state.scripts.iter_mut().flatten() {
+            stream.1 = StdRng::seed_from_u64(stream.0);
+        }
+    }
+
+    /// Give a script its own seeded stream, or None to share the global one again
+    pub fn seed_script(&self, script_index: usize, seed: Option<u64>) {
+        if script_index >= SCRIPT_STREAMS {
+            return;
+        }
+        self.0.lock().unwrap().scripts[script_index] = seed.map(|seed| (seed, StdRng::seed_from_u64(seed)));
+    }
+
+    pub fn current_seed(&self) -> Option<u64> {
+        self.0.lock().unwrap().seed
+    }
+
+    pub fn script_seed(&self, script_index: usize) -> Option<u64> {
+        self.0.lock().unwrap().scripts.get(script_index).and_then(|s| s.as_ref().map(|(seed, _)| *seed))
+    }
+
+    pub fn seeds(&self) -> RngSeeds {
+        RngSeeds {
+            seed: self.current_seed(),
+            script_seeds: (0..SCRIPT_STREAMS).map(|i| self.script_seed(i)).collect(),
+        }
+    }
+
+    /// Restore saved seeds, restarting all streams from the beginning
+    pub fn restore(&self, seeds: &RngSeeds) {
+        for i in 0..SCRIPT_STREAMS {
+            self.seed_script(i, seeds.script_seeds.get(i).copied().flatten());
+        }
+        self.seed(seeds.seed);
+    }
+}
+
+thread_local! {
+    /// The App state random ops on this thread draw from; a private one
+    /// until a thread enters an App's
+    static CURRENT: RefCell<SharedRng> = RefCell::new(SharedRng::default());
+    /// Script whose stream random ops on this thread draw from
+    static ACTIVE_SCRIPT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Run `f` with the stream of the script currently executing, or the shared stream
+pub fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    let shared = CURRENT.with(|current| current.borrow().clone());
+    let active = ACTIVE_SCRIPT.with(Cell::get);
+    let mut state = shared.0.lock().unwrap();
+    let state = &mut *state;
+    match active.and_then(|i| state.scripts[i].as_mut()) {
+        Some((_, rng)) => f(rng),
+        None => f(&mut state.global),
+    }
+}
+
+/// Child generator drawn from the active stream, for ops that take many values
+pub fn fork() -> StdRng {
+    with_rng(|rng| StdRng::from_rng(rng).expect("StdRng seeding from StdRng cannot fail"))
+}
+
+/// Run `f` with the display stream (header scrambles), kept apart from the scripts
+pub fn with_display_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    let shared = CURRENT.with(|current| current.borrow().clone());
+    let mut state = shared.0.lock().unwrap();
+    f(&mut state.display)
+}
+
+/// Child generator drawn from the display stream
+pub fn fork_display() -> StdRng {
+    with_display_rng(|rng| StdRng::from_rng(rng).expect("StdRng seeding from StdRng cannot fail"))
+}
+
+/// Keeps an App's state current on this thread until dropped
+pub struct Installed {
+    previous: SharedRng,
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Make `rng` the state random ops on this thread draw from while the guard lives
+pub fn install(rng: &SharedRng) -> Installed {
+    let previous = CURRENT.with(|current| std::mem::replace(&mut *current.borrow_mut(), rng.clone()));
+    Installed { previous }
+}
+
+/// Marks which script's stream random ops draw from until dropped
+pub struct ScriptStream {
+    previous: Option<usize>,
+    _installed: Installed,
+}
+
+impl Drop for ScriptStream {
+    fn drop(&mut self) {
+        ACTIVE_SCRIPT.with(|active| active.set(self.previous));
+    }
+}
+
+/// Enter `rng` and route random ops to `script_index`'s stream (if it has
+/// one) while the guard lives
+pub fn enter_script(rng: &SharedRng, script_index: usize) -> ScriptStream {
+    let active = (script_index < SCRIPT_STREAMS).then_some(script_index);
+    let previous = ACTIVE_SCRIPT.with(|current| current.replace(active));
+    ScriptStream { previous, _installed: install(rng) }
+}
@@ -0,0 +1,458 @@
+use anyhow::{Context, Result};
+use rosc::{OscMessage, OscType};
+#[cfg(feature = "scsynth-direct")]
+use rosc::{encoder, OscPacket};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use super::onset::{to_mono, OnsetDetector};
+use super::slice::{decode_audio, is_wav, symphonia_header};
+#[cfg(feature = "scsynth-direct")]
+use crate::types::{PREVIEW_BUFFER_ID, PREVIEW_NODE_ID};
+
+/// Tag/metadata index kept at the root of the sample library
+pub const INDEX_FILE_NAME: &str = ".index.toml";
+
+const MAX_SCAN_DEPTH: usize = 10;
+
+/// Detected tempos are folded into this range
+const BPM_MIN: f32 = 70.0;
+const BPM_MAX: f32 = 180.0;
+
+/// A directory or audio file in the library tree (stored in pre-order)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryNode {
+    /// Path relative to the library root, '/' separated
+    pub rel: String,
+    pub name: String,
+    pub depth: usize,
+    pub is_dir: bool,
+}
+
+/// Header metadata of an audio file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleInfo {
+    pub frames: usize,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+impl SampleInfo {
+    /// Header of a WAV, AIFF or FLAC file
+    pub fn read(path: &Path) -> Option<Self> {
+        if !is_wav(path) {
+            let (frames, channels, sample_rate) = symphonia_header(path).ok()?;
+            return Some(Self { frames, channels, sample_rate });
+        }
+        let reader = hound::WavReader::new(File::open(path).ok()?).ok()?;
+        let spec = reader.spec();
+        if spec.channels == 0 {
+            return None;
+        }
+        Some(Self {
+            frames: reader.len() as usize / spec.channels as usize,
+            channels: spec.channels,
+            sample_rate: spec.sample_rate,
+        })
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        if self.sample_rate == 0 {
+            return 0;
+        }
+        self.frames as u64 * 1000 / self.sample_rate as u64
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexEntry {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<f32>,
+}
+
+/// User tags and cached BPM per file, keyed by library-relative path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LibraryIndex {
+    #[serde(default)]
+    pub entries: BTreeMap<String, IndexEntry>,
+}
+
+impl LibraryIndex {
+    pub fn load(root: &Path) -> Self {
+        std::fs::read_to_string(root.join(INDEX_FILE_NAME))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).context("Failed to serialize library index")?;
+        std::fs::write(root.join(INDEX_FILE_NAME), content).context("Failed to write library index")?;
+        Ok(())
+    }
+
+    pub fn tags(&self, rel: &str) -> &[String] {
+        self.entries.get(rel).map(|e| e.tags.as_slice()).unwrap_or(&[])
+    }
+
+    /// Replace the tags of a file, dropping entries that no longer hold anything
+    pub fn set_tags(&mut self, rel: &str, tags: Vec<String>) {
+        let entry = self.entries.entry(rel.to_string()).or_default();
+        entry.tags = tags;
+        if entry.tags.is_empty() && entry.bpm.is_none() {
+            self.entries.remove(rel);
+        }
+    }
+}
+
+pub fn is_audio_file(path: &Path) -> bool {
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        matches!(ext, "wav" | "WAV" | "aif" | "AIF" | "aiff" | "AIFF" | "flac" | "FLAC")
+    } else {
+        false
+    }
+}
+
+/// Split user input into normalized tags (lowercase, no duplicates)
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(|c: char| c.is_whitespace() || c == ',') {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Scan `root` into a pre-order tree: directories first, then audio files, both sorted.
+/// Directories without audio anywhere below them are left out.
+pub fn scan_library(root: &Path) -> Vec<LibraryNode> {
+    let mut nodes = Vec::new();
+    scan_dir(root, "", 0, &mut nodes);
+    nodes
+}
+
+fn scan_dir(dir: &Path, rel: &str, depth: usize, nodes: &mut Vec<LibraryNode>) {
+    if depth >= MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() && path.read_link().is_err() {
+            dirs.push((name, path));
+        } else if path.is_file() && is_audio_file(&path) {
+            files.push(name);
+        }
+    }
+    dirs.sort();
+    files.sort();
+
+    let join = |name: &str| if rel.is_empty() { name.to_string() } else { format!("{}/{}", rel, name) };
+
+    for (name, path) in dirs {
+        let child_rel = join(&name);
+        let dir_idx = nodes.len();
+        nodes.push(LibraryNode { rel: child_rel.clone(), name, depth, is_dir: true });
+        scan_dir(&path, &child_rel, depth + 1, nodes);
+        if nodes.len() == dir_idx + 1 {
+            nodes.pop();
+        }
+    }
+
+    for name in files {
+        nodes.push(LibraryNode { rel: join(&name), name, depth, is_dir: false });
+    }
+}
+
+/// Estimate the tempo of a loop from its onsets.
+///
+/// Uses the median onset spacing folded into 70-180 BPM, then prefers the tempo
+/// implied by the file length (a whole number of beats) when the two agree.
+pub fn estimate_bpm(onsets: &[usize], sample_rate: u32, total_frames: usize) -> Option<f32> {
+    if onsets.len() < 4 || sample_rate == 0 {
+        return None;
+    }
+
+    let mut spacings: Vec<usize> = onsets.windows(2).map(|w| w[1].saturating_sub(w[0])).filter(|&d| d > 0).collect();
+    if spacings.is_empty() {
+        return None;
+    }
+    spacings.sort_unstable();
+    let median = spacings[spacings.len() / 2] as f32 / sample_rate as f32;
+
+    let mut bpm = 60.0 / median;
+    while bpm < BPM_MIN {
+        bpm *= 2.0;
+    }
+    while bpm > BPM_MAX {
+        bpm /= 2.0;
+    }
+
+    let seconds = total_frames as f32 / sample_rate as f32;
+    let beats = (seconds * bpm / 60.0).round();
+    if beats >= 1.0 {
+        let length_bpm = beats * 60.0 / seconds;
+        if (length_bpm - bpm).abs() / bpm < 0.04 {
+            bpm = length_bpm;
+        }
+    }
+
+    Some((bpm * 10.0).round() / 10.0)
+}
+
+/// Decode a file and estimate its tempo
+pub fn detect_bpm(path: &Path) -> Option<f32> {
+    let audio = decode_audio(path).ok()?;
+    let mono = to_mono(&audio.samples, audio.channels);
+    let onsets = OnsetDetector::new(audio.sample_rate).detect(&mono);
+    estimate_bpm(&onsets, audio.sample_rate, mono.len())
+}
+
+/// Input line currently owned by the library page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryInput {
+    None,
+    Search,
+    Tag,
+}
+
+/// State of the sample library browser page
+#[derive(Debug, Clone)]
+pub struct LibraryBrowser {
+    pub root: Option<PathBuf>,
+    pub nodes: Vec<LibraryNode>,
+    pub expanded: HashSet<String>,
+    pub index: LibraryIndex,
+    pub query: String,
+    pub input: LibraryInput,
+    pub tag_buffer: String,
+    pub selected: usize,
+    info_cache: HashMap<String, Option<SampleInfo>>,
+}
+
+impl Default for LibraryBrowser {
+    fn default() -> Self {
+        Self {
+            root: None,
+            nodes: Vec::new(),
+            expanded: HashSet::new(),
+            index: LibraryIndex::default(),
+            query: String::new(),
+            input: LibraryInput::None,
+            tag_buffer: String::new(),
+            selected: 0,
+            info_cache: HashMap::new(),
+        }
+    }
+}
+
+impl LibraryBrowser {
+    /// (Re)scan the library at `root`, keeping expansion state and the selected path
+    pub fn scan(&mut self, root: &Path) {
+        let selected_rel = self.selected_node().map(|n| n.rel.clone());
+
+        self.nodes = scan_library(root);
+        self.index = LibraryIndex::load(root);
+        self.info_cache.clear();
+        self.root = Some(root.to_path_buf());
+
+        self.selected = selected_rel
+            .and_then(|rel| self.visible().iter().position(|&i| self.nodes[i].rel == rel))
+            .unwrap_or(0);
+    }
+
+    /// Indices into `nodes` of the rows currently shown.
+    /// With a query the tree is flattened to matching files.
+    pub fn visible(&self) -> Vec<usize> {
+        if !self.query.trim().is_empty() {
+            let tokens: Vec<String> = self.query.split_whitespace().map(|t| t.to_lowercase()).collect();
+            return (0..self.nodes.len())
+                .filter(|&i| !self.nodes[i].is_dir && self.matches(&self.nodes[i], &tokens))
+                .collect();
+        }
+
+        let mut rows = Vec::new();
+        let mut collapsed_at: Option<usize> = None;
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Some(depth) = collapsed_at {
+                if node.depth > depth {
+                    continue;
+                }
+                collapsed_at = None;
+            }
+            rows.push(i);
+            if node.is_dir && !self.expanded.contains(&node.rel) {
+                collapsed_at = Some(node.depth);
+            }
+        }
+        rows
+    }
+
+    /// Every token must match: `#tag` matches tags only, other tokens match path or tags
+    fn matches(&self, node: &LibraryNode, tokens: &[String]) -> bool {
+        let rel = node.rel.to_lowercase();
+        let tags = self.index.tags(&node.rel);
+        tokens.iter().all(|token| match token.strip_prefix('#') {
+            Some(tag) => tags.iter().any(|t| t.starts_with(tag)),
+            None => rel.contains(token.as_str()) || tags.iter().any(|t| t.contains(token.as_str())),
+        })
+    }
+
+    pub fn selected_node(&self) -> Option<&LibraryNode> {
+        self.visible().get(self.selected).map(|&i| &self.nodes[i])
+    }
+
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        Some(self.root.as_ref()?.join(&self.selected_node()?.rel))
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        let count = self.visible().len();
+        if count == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected as i32 + delta).clamp(0, count as i32 - 1) as usize;
+    }
+
+    pub fn expand(&mut self) {
+        if let Some(node) = self.selected_node().filter(|n| n.is_dir).cloned() {
+            self.expanded.insert(node.rel);
+        }
+    }
+
+    /// Collapse the selected folder, or jump to the parent folder
+    pub fn collapse(&mut self) {
+        let Some(node) = self.selected_node().cloned() else {
+            return;
+        };
+
+        if node.is_dir && self.expanded.remove(&node.rel) {
+            return;
+        }
+
+        if let Some((parent, _)) = node.rel.rsplit_once('/') {
+            if let Some(row) = self.visible().iter().position(|&i| self.nodes[i].rel == parent) {
+                self.selected = row;
+            }
+        }
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+
+    /// Header metadata of a file, read once per scan
+    pub fn info(&mut self, rel: &str) -> Option<SampleInfo> {
+        if let Some(info) = self.info_cache.get(rel) {
+            return *info;
+        }
+        let info = self.root.as_ref().and_then(|root| SampleInfo::read(&root.join(rel)));
+        self.info_cache.insert(rel.to_string(), info);
+        info
+    }
+
+    pub fn cached_info(&self, rel: &str) -> Option<SampleInfo> {
+        self.info_cache.get(rel).copied().flatten()
+    }
+
+    pub fn bpm(&self, rel: &str) -> Option<f32> {
+        self.index.entries.get(rel).and_then(|e| e.bpm)
+    }
+
+    /// Detect and store the BPM of a file in the index
+    pub fn detect_bpm(&mut self, rel: &str) -> Result<Option<f32>> {
+        let root = self.root.clone().context("Library not scanned")?;
+        let bpm = detect_bpm(&root.join(rel));
+        if let Some(bpm) = bpm {
+            self.index.entries.entry(rel.to_string()).or_default().bpm = Some(bpm);
+            self.index.save(&root)?;
+        }
+        Ok(bpm)
+    }
+
+    pub fn set_tags(&mut self, rel: &str, input: &str) -> Result<()> {
+        let root = self.root.clone().context("Library not scanned")?;
+        self.index.set_tags(rel, parse_tags(input));
+        self.index.save(&root)
+    }
+}
+
+/// OSC that (re)starts the preview voice on `path`.
+/// The synth is created from the /b_allocRead completion message so it
+/// only starts once the file is in the buffer.
+#[cfg(feature = "scsynth-direct")]
+pub fn preview_messages(path: &str, channels: u16) -> Vec<OscMessage> {
+    let synthdef = if channels == 1 { "monokit_preview_mono" } else { "monokit_preview_stereo" };
+
+    let start = OscPacket::Message(OscMessage {
+        addr: "/s_new".to_string(),
+        args: vec![
+            OscType::String(synthdef.to_string()),
+            OscType::Int(PREVIEW_NODE_ID),
+            OscType::Int(1),
+            OscType::Int(0),
+            OscType::String("bufnum".to_string()),
+            OscType::Int(PREVIEW_BUFFER_ID as i32),
+        ],
+    });
+    let Ok(completion) = encoder::encode(&start) else {
+        return Vec::new();
+    };
+
+    vec![
+        stop_preview_message(),
+        OscMessage {
+            addr: "/b_allocRead".to_string(),
+            args: vec![
+                OscType::Int(PREVIEW_BUFFER_ID as i32),
+                OscType::String(path.to_string()),
+                OscType::Int(0),
+                OscType::Int(-1),
+                OscType::Blob(completion),
+            ],
+        },
+    ]
+}
+
+#[cfg(not(feature = "scsynth-direct"))]
+pub fn preview_messages(path: &str, channels: u16) -> Vec<OscMessage> {
+    vec![OscMessage {
+        addr: "/monokit/preview".to_string(),
+        args: vec![OscType::String(path.to_string()), OscType::Int(channels as i32)],
+    }]
+}
+
+#[cfg(feature = "scsynth-direct")]
+pub fn stop_preview_message() -> OscMessage {
+    OscMessage {
+        addr: "/n_free".to_string(),
+        args: vec![OscType::Int(PREVIEW_NODE_ID)],
+    }
+}
+
+#[cfg(not(feature = "scsynth-direct"))]
+pub fn stop_preview_message() -> OscMessage {
+    OscMessage {
+        addr: "/monokit/preview/stop".to_string(),
+        args: vec![],
+    }
+}
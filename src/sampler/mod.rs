@@ -1,4 +1,8 @@
 pub mod buffer_manager;
+pub mod library;
 pub mod onset;
+pub mod slice;
+pub mod stretch;
+pub mod waveform;
 
 pub use buffer_manager::{BufferAllocation, BufferError, BufferManager};
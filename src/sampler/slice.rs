@@ -0,0 +1,362 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::sampler::onset::{to_mono, OnsetDetector};
+use crate::types::{SampleSlot, SAMPLER_BUFFER_BASE};
+
+/// Search radius for zero crossing snap (frames either side of the marker)
+pub const ZERO_CROSSING_RADIUS: usize = 1024;
+
+/// Search radius for transient snap in milliseconds
+pub const TRANSIENT_SNAP_MS: f32 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapMode {
+    Off,
+    ZeroCrossing,
+    Transient,
+}
+
+impl SnapMode {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => SnapMode::ZeroCrossing,
+            2 => SnapMode::Transient,
+            _ => SnapMode::Off,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapMode::Off => "OFF",
+            SnapMode::ZeroCrossing => "ZERO",
+            SnapMode::Transient => "TRANSIENT",
+        }
+    }
+}
+
+/// Interleaved audio decoded to f32 in the -1.0..1.0 range
+pub struct DecodedAudio {
+    pub samples: Vec<f32>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+pub fn is_wav(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+}
+
+/// Decode an audio file to normalized f32 samples: WAV through hound,
+/// AIFF and FLAC through symphonia
+pub fn decode_audio(path: &Path) -> Result<DecodedAudio> {
+    if is_wav(path) {
+        decode_wav(path)
+    } else {
+        decode_symphonia(path)
+    }
+}
+
+/// Decode a WAV file to normalized f32 samples (int and float formats)
+fn decode_wav(path: &Path) -> Result<DecodedAudio> {
+    let file = File::open(path).context("Failed to open audio file")?;
+    let mut reader = hound::WavReader::new(file).context("Failed to read WAV file")?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(|s| s.ok()).collect(),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .filter_map(|s| s.ok())
+                .map(|s| s as f32 * scale)
+                .collect()
+        }
+    };
+
+    Ok(DecodedAudio {
+        samples,
+        channels: spec.channels,
+        sample_rate: spec.sample_rate,
+    })
+}
+
+/// Open the first audio track of a non-WAV file, returning its codec parameters
+fn probe_symphonia(
+    path: &Path,
+) -> Result<(Box<dyn symphonia::core::formats::FormatReader>, symphonia::core::codecs::CodecParameters)> {
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::probe::Hint;
+
+    let file = File::open(path).context("Failed to open audio file")?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &Default::default(), &Default::default())
+        .context("Failed to read audio file")?;
+    let params = probed
+        .format
+        .default_track()
+        .context("No audio track")?
+        .codec_params
+        .clone();
+    Ok((probed.format, params))
+}
+
+/// Frame count, channels and sample rate from the header of a non-WAV file
+pub fn symphonia_header(path: &Path) -> Result<(usize, u16, u32)> {
+    let (_, params) = probe_symphonia(path)?;
+    Ok((
+        params.n_frames.context("Unknown length")? as usize,
+        params.channels.context("Unknown channel count")?.count() as u16,
+        params.sample_rate.context("Unknown sample rate")?,
+    ))
+}
+
+fn decode_symphonia(path: &Path) -> Result<DecodedAudio> {
+    use symphonia::core::audio::SampleBuffer;
+
+    let (mut format, params) = probe_symphonia(path)?;
+    let channels = params.channels.context("Unknown channel count")?.count() as u16;
+    let sample_rate = params.sample_rate.context("Unknown sample rate")?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&params, &Default::default())
+        .context("Unsupported audio codec")?;
+
+    let mut samples = Vec::new();
+    while let Ok(packet) = format.next_packet() {
+        let decoded = decoder.decode(&packet).context("Failed to decode audio")?;
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(buffer.samples());
+    }
+
+    Ok(DecodedAudio {
+        samples,
+        channels,
+        sample_rate,
+    })
+}
+
+/// The slice file decoded for marker snapping. SamplerState keeps it until
+/// the next KIT or S.SLICE, so S.MARK and S.MARK.MV don't decode the file on
+/// every call.
+#[derive(Clone)]
+pub struct SnapSource {
+    pub path: String,
+    pub sample_rate: u32,
+    pub mono: Arc<Vec<f32>>,
+    /// Transients, found with the (sensitivity, min spacing) in `onset_settings`
+    onsets: Option<Arc<Vec<usize>>>,
+    onset_settings: (u32, f32),
+}
+
+impl std::fmt::Debug for SnapSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapSource")
+            .field("path", &self.path)
+            .field("frames", &self.mono.len())
+            .finish()
+    }
+}
+
+impl SnapSource {
+    pub fn load(path: &str) -> Result<Self> {
+        let audio = decode_audio(Path::new(path))?;
+        Ok(Self {
+            path: path.to_string(),
+            sample_rate: audio.sample_rate,
+            mono: Arc::new(to_mono(&audio.samples, audio.channels)),
+            onsets: None,
+            onset_settings: (0, 0.0),
+        })
+    }
+
+    /// Transients for these onset settings, detected the first time they are asked for
+    pub fn onsets(&mut self, sensitivity: u32, min_spacing_ms: f32) -> Arc<Vec<usize>> {
+        if let Some(onsets) = &self.onsets {
+            if self.onset_settings == (sensitivity, min_spacing_ms) {
+                return onsets.clone();
+            }
+        }
+        let onsets = Arc::new(
+            OnsetDetector::new(self.sample_rate)
+                .with_sensitivity(sensitivity)
+                .with_min_spacing(min_spacing_ms)
+                .detect(&self.mono),
+        );
+        self.onsets = Some(onsets.clone());
+        self.onset_settings = (sensitivity, min_spacing_ms);
+        onsets
+    }
+}
+
+/// Map a 0-16383 position onto a frame within the file
+pub fn position_to_frame(position: i16, total_frames: usize) -> usize {
+    let position = position.clamp(0, 16383) as u64;
+    (position * total_frames as u64 / 16383) as usize
+}
+
+/// Map a frame back onto the 0-16383 position range
+pub fn frame_to_position(frame: usize, total_frames: usize) -> i16 {
+    if total_frames == 0 {
+        return 0;
+    }
+    ((frame.min(total_frames) as u64 * 16383) / total_frames as u64) as i16
+}
+
+/// Split the slot containing `frame`, returning the index of the new slot
+pub fn insert_marker(slots: &mut Vec<SampleSlot>, frame: usize) -> Option<usize> {
+    let idx = slots
+        .iter()
+        .position(|slot| frame > slot.start_frame && frame < slot.end_frame)?;
+
+    let end_frame = slots[idx].end_frame;
+    slots[idx].end_frame = frame;
+    slots.insert(
+        idx + 1,
+        SampleSlot {
+            buffer_id: slots[idx].buffer_id,
+            start_frame: frame,
+            end_frame,
+            file_path: None,
+        },
+    );
+
+    Some(idx + 1)
+}
+
+/// Remove the marker at the start of slot `idx`, merging it into the previous slot
+pub fn remove_marker(slots: &mut Vec<SampleSlot>, idx: usize) -> bool {
+    if idx == 0 || idx >= slots.len() {
+        return false;
+    }
+
+    let removed = slots.remove(idx);
+    slots[idx - 1].end_frame = removed.end_frame;
+    true
+}
+
+/// Move the marker at the start of slot `idx`, keeping it between its neighbours.
+/// Returns the frame the marker ended up on.
+pub fn move_marker(slots: &mut [SampleSlot], idx: usize, frame: usize) -> Option<usize> {
+    if idx == 0 || idx >= slots.len() {
+        return None;
+    }
+
+    let lower = slots[idx - 1].start_frame + 1;
+    let upper = slots[idx].end_frame.saturating_sub(1);
+    if lower > upper {
+        return None;
+    }
+
+    let frame = frame.clamp(lower, upper);
+    slots[idx - 1].end_frame = frame;
+    slots[idx].start_frame = frame;
+    Some(frame)
+}
+
+/// Find the nearest zero crossing to `frame` within `radius` frames
+pub fn snap_to_zero_crossing(mono: &[f32], frame: usize, radius: usize) -> usize {
+    if mono.len() < 2 {
+        return frame;
+    }
+
+    let frame = frame.min(mono.len() - 1);
+    let is_crossing = |i: usize| {
+        i > 0 && i < mono.len() && (mono[i - 1] <= 0.0) != (mono[i] <= 0.0)
+    };
+
+    for offset in 0..=radius {
+        if is_crossing(frame + offset) {
+            return frame + offset;
+        }
+        if offset <= frame && is_crossing(frame - offset) {
+            return frame - offset;
+        }
+    }
+
+    frame
+}
+
+/// Find the nearest onset to `frame` within `radius` frames
+pub fn snap_to_transient(onsets: &[usize], frame: usize, radius: usize) -> usize {
+    onsets
+        .iter()
+        .copied()
+        .filter(|&onset| onset.abs_diff(frame) <= radius)
+        .min_by_key(|&onset| onset.abs_diff(frame))
+        .unwrap_or(frame)
+}
+
+/// Write each slot of `source` to its own WAV in `dest_dir`, keeping the source format
+pub fn export_slices(source: &Path, slots: &[SampleSlot], dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let file = File::open(source).context("Failed to open audio file")?;
+    let mut reader = hound::WavReader::new(file).context("Failed to read WAV file")?;
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+
+    std::fs::create_dir_all(dest_dir).context("Failed to create kit folder")?;
+
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("slice")
+        .to_string();
+
+    enum Frames {
+        Int(Vec<i32>),
+        Float(Vec<f32>),
+    }
+
+    let frames = match spec.sample_format {
+        hound::SampleFormat::Int => Frames::Int(reader.samples::<i32>().filter_map(|s| s.ok()).collect()),
+        hound::SampleFormat::Float => Frames::Float(reader.samples::<f32>().filter_map(|s| s.ok()).collect()),
+    };
+
+    let mut written = Vec::with_capacity(slots.len());
+    for (idx, slot) in slots.iter().enumerate() {
+        let path = dest_dir.join(format!("{}_{:03}.wav", stem, idx + 1));
+        let mut writer = hound::WavWriter::create(&path, spec).context("Failed to create slice WAV")?;
+
+        let start = slot.start_frame * channels;
+        let end = slot.end_frame * channels;
+
+        match &frames {
+            Frames::Int(data) => {
+                let end = end.min(data.len());
+                for &sample in &data[start.min(end)..end] {
+                    writer.write_sample(sample).context("Failed to write slice WAV")?;
+                }
+            }
+            Frames::Float(data) => {
+                let end = end.min(data.len());
+                for &sample in &data[start.min(end)..end] {
+                    writer.write_sample(sample).context("Failed to write slice WAV")?;
+                }
+            }
+        }
+
+        writer.finalize().context("Failed to finalize slice WAV")?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Build a single slot covering the whole file (used when no slices exist yet)
+pub fn whole_file_slot(total_frames: usize) -> SampleSlot {
+    SampleSlot {
+        buffer_id: SAMPLER_BUFFER_BASE,
+        start_frame: 0,
+        end_frame: total_frames,
+        file_path: None,
+    }
+}
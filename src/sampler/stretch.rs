@@ -0,0 +1,115 @@
+use std::path::Path;
+use std::time::Instant;
+
+use super::library::LibraryIndex;
+
+/// Plausible loop tempos when reading a bare number from a filename
+const FILENAME_BPM_MIN: f32 = 60.0;
+const FILENAME_BPM_MAX: f32 = 200.0;
+
+/// Smoothing factor for the MIDI clock tempo estimate
+const MIDI_TEMPO_SMOOTHING: f32 = 0.2;
+
+/// Minimum change before a new MIDI clock tempo is sent to the sampler
+const MIDI_TEMPO_THRESHOLD_BPM: f32 = 0.5;
+
+/// Read a loop tempo from a filename: "break_120bpm.wav", "amen 172 BPM.wav"
+/// or, failing that, a lone number in the 60-200 range ("loop-92.wav").
+pub fn bpm_from_filename(path: &str) -> Option<f32> {
+    let stem = Path::new(path).file_stem()?.to_str()?.to_lowercase();
+
+    let tokens: Vec<&str> = stem
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    // "120bpm" or "120" followed by "bpm"
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(number) = token.strip_suffix("bpm") {
+            if let Ok(bpm) = number.parse::<f32>() {
+                return Some(bpm).filter(|&b| b > 0.0);
+            }
+            if number.is_empty() && i > 0 {
+                if let Ok(bpm) = tokens[i - 1].parse::<f32>() {
+                    return Some(bpm).filter(|&b| b > 0.0);
+                }
+            }
+        }
+    }
+
+    let mut numbers = tokens
+        .iter()
+        .filter_map(|t| t.parse::<f32>().ok())
+        .filter(|bpm| (FILENAME_BPM_MIN..=FILENAME_BPM_MAX).contains(bpm));
+    match (numbers.next(), numbers.next()) {
+        (Some(bpm), None) => Some(bpm),
+        _ => None,
+    }
+}
+
+/// Tempo already known for a file: from its name, else the library index
+pub fn known_source_bpm(path: &Path) -> Option<f32> {
+    if let Some(bpm) = bpm_from_filename(&path.to_string_lossy()) {
+        return Some(bpm);
+    }
+
+    let root = crate::config::monokit_config_dir().ok()?.join("samples");
+    let rel = path.strip_prefix(&root).ok()?.to_str()?.replace('\\', "/");
+    LibraryIndex::load(&root).entries.get(&rel).and_then(|e| e.bpm)
+}
+
+/// Metro BPM for a 16th-note interval (as M.BPM sets it)
+pub fn interval_to_bpm(interval_ms: u64) -> f32 {
+    15000.0 / interval_ms.max(1) as f32
+}
+
+/// Playback speed that stretches a loop recorded at `source_bpm` to `tempo_bpm`
+pub fn tempo_ratio(tempo_bpm: f32, source_bpm: f32) -> f32 {
+    if source_bpm <= 0.0 {
+        return 1.0;
+    }
+    tempo_bpm / source_bpm
+}
+
+/// Tempo estimate from incoming MIDI clock 16th-note ticks
+#[derive(Debug, Default)]
+pub struct MidiTempoTracker {
+    last_tick: Option<Instant>,
+    interval_ms: Option<f32>,
+    sent_bpm: Option<f32>,
+}
+
+impl MidiTempoTracker {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Current tempo estimate, once two ticks have arrived
+    pub fn bpm(&self) -> Option<f32> {
+        self.interval_ms.map(|interval| 15000.0 / interval)
+    }
+
+    /// Record a tick, returning the new tempo when it moved enough to resend
+    pub fn tick(&mut self, now: Instant) -> Option<f32> {
+        let last = self.last_tick.replace(now)?;
+        let elapsed = now.duration_since(last).as_secs_f32() * 1000.0;
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        let interval = match self.interval_ms {
+            Some(prev) => prev + (elapsed - prev) * MIDI_TEMPO_SMOOTHING,
+            None => elapsed,
+        };
+        self.interval_ms = Some(interval);
+
+        let bpm = 15000.0 / interval;
+        match self.sent_bpm {
+            Some(sent) if (sent - bpm).abs() < MIDI_TEMPO_THRESHOLD_BPM => None,
+            _ => {
+                self.sent_bpm = Some(bpm);
+                Some(bpm)
+            }
+        }
+    }
+}
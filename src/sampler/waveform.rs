@@ -0,0 +1,156 @@
+use anyhow::Result;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::onset::to_mono;
+use super::slice::decode_audio;
+
+/// Number of min/max bins kept for the overview (matches the 0-16383 position range)
+pub const OVERVIEW_BINS: usize = 16384;
+
+/// Maximum zoom factor for the waveform page
+pub const MAX_ZOOM: u16 = 32;
+
+/// Min/max envelope of a sample file, decoded once per loaded file
+#[derive(Debug, Clone)]
+pub struct WaveformOverview {
+    pub total_frames: usize,
+    pub sample_rate: u32,
+    pub peaks: Vec<(f32, f32)>,
+}
+
+impl WaveformOverview {
+    pub fn build(path: &Path, bins: usize) -> Result<Self> {
+        let audio = decode_audio(path)?;
+        let mono = to_mono(&audio.samples, audio.channels);
+        let bins = bins.max(1).min(mono.len().max(1));
+
+        let mut peaks = Vec::with_capacity(bins);
+        for bin in 0..bins {
+            let start = bin * mono.len() / bins;
+            let end = ((bin + 1) * mono.len() / bins).max(start + 1).min(mono.len());
+            let chunk = &mono[start.min(end)..end];
+            let min = chunk.iter().copied().fold(0.0f32, f32::min);
+            let max = chunk.iter().copied().fold(0.0f32, f32::max);
+            peaks.push((min, max));
+        }
+
+        Ok(Self {
+            total_frames: mono.len(),
+            sample_rate: audio.sample_rate,
+            peaks,
+        })
+    }
+
+    /// Min/max per display column for the frame range `start..end`
+    pub fn columns(&self, start: usize, end: usize, width: usize) -> Vec<(f32, f32)> {
+        if width == 0 || self.total_frames == 0 || self.peaks.is_empty() {
+            return vec![(0.0, 0.0); width];
+        }
+
+        let bins = self.peaks.len();
+        let span = end.saturating_sub(start).max(1);
+        (0..width)
+            .map(|col| {
+                let col_start = start + col * span / width;
+                let col_end = start + (col + 1) * span / width;
+                let bin_start = (col_start * bins / self.total_frames).min(bins - 1);
+                let bin_end = (col_end * bins / self.total_frames).clamp(bin_start + 1, bins);
+                self.peaks[bin_start..bin_end]
+                    .iter()
+                    .fold((0.0f32, 0.0f32), |(lo, hi), &(min, max)| (lo.min(min), hi.max(max)))
+            })
+            .collect()
+    }
+}
+
+/// A file as it was on disk when an overview was built from it. A rewrite
+/// at the same path changes the length or modification time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverviewSource {
+    pub path: String,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl OverviewSource {
+    pub fn of(path: &str) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        Self {
+            path: path.to_string(),
+            modified: meta.as_ref().and_then(|m| m.modified().ok()),
+            len: meta.map(|m| m.len()).unwrap_or(0),
+        }
+    }
+}
+
+/// View state for the waveform page (zoom, scroll and edit cursor in 0-16383 units)
+#[derive(Debug, Clone)]
+pub struct WaveformView {
+    pub overview: Option<WaveformOverview>,
+    /// File the overview was last built from, kept when the build failed
+    pub source: Option<OverviewSource>,
+    pub zoom: u16,
+    pub scroll: i16,
+    pub cursor: i16,
+}
+
+impl Default for WaveformView {
+    fn default() -> Self {
+        Self {
+            overview: None,
+            source: None,
+            zoom: 1,
+            scroll: 0,
+            cursor: 0,
+        }
+    }
+}
+
+impl WaveformView {
+    /// Width of the visible window in position units
+    pub fn span(&self) -> i16 {
+        (16384 / self.zoom.max(1) as i32).max(1) as i16
+    }
+
+    /// Visible window as (start, end) position units
+    pub fn window(&self) -> (i16, i16) {
+        let start = self.scroll.clamp(0, 16384 - self.span());
+        (start, start + self.span())
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * 2).min(MAX_ZOOM);
+        self.center_on_cursor();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / 2).max(1);
+        self.center_on_cursor();
+    }
+
+    /// Move the cursor by `columns` display columns, scrolling to keep it visible
+    pub fn move_cursor(&mut self, columns: i32, width: usize) {
+        let step = (self.span() as i32 / width.max(1) as i32).max(1);
+        self.cursor = (self.cursor as i32 + columns * step).clamp(0, 16383) as i16;
+        self.follow_cursor();
+    }
+
+    pub fn set_cursor(&mut self, position: i16) {
+        self.cursor = position.clamp(0, 16383);
+        self.follow_cursor();
+    }
+
+    fn center_on_cursor(&mut self) {
+        self.scroll = (self.cursor - self.span() / 2).clamp(0, 16384 - self.span());
+    }
+
+    fn follow_cursor(&mut self) {
+        let (start, end) = self.window();
+        if self.cursor < start || self.cursor >= end {
+            self.center_on_cursor();
+        } else {
+            self.scroll = start;
+        }
+    }
+}
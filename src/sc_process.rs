@@ -66,6 +66,11 @@ impl ScProcess {
         if let Some(device) = audio_out_device {
             cmd.env("MONOKIT_AUDIO_OUT", device);
         }
+        let display = crate::config::load_config().map(|c| c.display).unwrap_or_default();
+        if display.audio_in {
+            cmd.env("MONOKIT_AUDIO_IN", "1");
+        }
+        cmd.env("MONOKIT_OUT_CHANNELS", crate::types::output_channel_count(display.output_channels).to_string());
 
         let mut child = cmd
             .spawn()
@@ -130,6 +135,20 @@ impl ScProcess {
         self.start(Some(device))
     }
 
+    #[cfg(feature = "scsynth-direct")]
+    pub fn restart(&mut self, audio_out_device: Option<&str>) -> Result<(), String> {
+        if let Some(ref mut scsynth) = self.scsynth_direct {
+            scsynth.restart(audio_out_device)
+        } else {
+            Err("scsynth not initialized".to_string())
+        }
+    }
+
+    #[cfg(not(feature = "scsynth-direct"))]
+    pub fn restart(&mut self, audio_out_device: Option<&str>) -> Result<(), String> {
+        self.start(audio_out_device)
+    }
+
     #[cfg(feature = "scsynth-direct")]
     pub fn spawn_ready_sender(&self) -> Option<std::sync::mpsc::Receiver<()>> {
         if let Some(ref scsynth) = self.scsynth_direct {
@@ -176,6 +195,26 @@ impl ScProcess {
         }
     }
 
+    /// Start REC.STEMS into `folder` (scsynth-direct mode only)
+    #[cfg(feature = "scsynth-direct")]
+    pub fn start_stem_recording(&mut self, folder: &std::path::Path) -> Result<(), String> {
+        if let Some(ref mut scsynth) = self.scsynth_direct {
+            scsynth.start_stem_recording(folder)
+        } else {
+            Err("scsynth not initialized".to_string())
+        }
+    }
+
+    /// Stop REC.STEMS (scsynth-direct mode only)
+    #[cfg(feature = "scsynth-direct")]
+    pub fn stop_stem_recording(&mut self) -> Result<(), String> {
+        if let Some(ref mut scsynth) = self.scsynth_direct {
+            scsynth.stop_stem_recording()
+        } else {
+            Err("scsynth not initialized".to_string())
+        }
+    }
+
     /// Set recording path prefix (scsynth-direct mode only)
     /// In sclang mode, recording path is handled via OSC messages to sclang
     #[cfg(feature = "scsynth-direct")]
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::types::{NotesStorage, PatternStorage, SamplerState, ScriptMutes, ScriptStorage};
+use crate::rng::RngSeeds;
+use crate::snapshot::{Snapshot, SnapshotBank};
+use crate::types::{NotesStorage, PatternStorage, SamplerState, ScriptMutes, ScriptStorage, DEFAULT_SCRIPT_LINES};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Scene {
@@ -16,6 +19,10 @@ pub struct Scene {
     pub script_mutes: Vec<bool>,
     #[serde(default)]
     pub sampler: Option<SamplerState>,
+    #[serde(default)]
+    pub rng: Option<RngSeeds>,
+    #[serde(default)]
+    pub snapshots: BTreeMap<u8, Snapshot>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,12 +124,13 @@ pub fn delete_scene(name: &str) -> Result<(), SceneError> {
 }
 
 impl Scene {
-    pub fn from_app_state(scripts: &ScriptStorage, patterns: &PatternStorage, notes: &NotesStorage, script_mutes: &ScriptMutes, sampler: &SamplerState) -> Self {
+    pub fn from_app_state(scripts: &ScriptStorage, patterns: &PatternStorage, notes: &NotesStorage, script_mutes: &ScriptMutes, sampler: &SamplerState, snapshots: &SnapshotBank, rng: &crate::rng::SharedRng) -> Self {
         let scene_scripts: Vec<SceneScript> = scripts
             .scripts
             .iter()
             .map(|s| SceneScript {
-                lines: s.lines.to_vec(),
+                // Never fewer than 8 lines, so short scripts save as they always have
+                lines: s.lines[..s.used_len().max(DEFAULT_SCRIPT_LINES)].to_vec(),
                 j: s.j,
                 k: s.k,
             })
@@ -146,16 +154,16 @@ impl Scene {
             notes: notes.lines.to_vec(),
             script_mutes: script_mutes.muted.to_vec(),
             sampler: Some(sampler.clone()),
+            rng: Some(rng.seeds()),
+            snapshots: snapshots.slots().clone(),
         }
     }
 
-    pub fn apply_to_app_state(&self, scripts: &mut ScriptStorage, patterns: &mut PatternStorage, notes: &mut NotesStorage, script_mutes: &mut ScriptMutes, sampler: &mut SamplerState) {
+    pub fn apply_to_app_state(&self, scripts: &mut ScriptStorage, patterns: &mut PatternStorage, notes: &mut NotesStorage, script_mutes: &mut ScriptMutes, sampler: &mut SamplerState, snapshots: &mut SnapshotBank, rng: &crate::rng::SharedRng) {
         for (i, scene_script) in self.scripts.iter().enumerate() {
             if i < scripts.scripts.len() {
-                for (j, line) in scene_script.lines.iter().enumerate() {
-                    if j < 8 {
-                        scripts.scripts[i].lines[j] = line.clone();
-                    }
+                for (j, line) in scripts.scripts[i].lines.iter_mut().enumerate() {
+                    *line = scene_script.lines.get(j).cloned().unwrap_or_default();
                 }
                 scripts.scripts[i].j = scene_script.j;
                 scripts.scripts[i].k = scene_script.k;
@@ -190,5 +198,13 @@ impl Scene {
         if let Some(scene_sampler) = &self.sampler {
             *sampler = scene_sampler.clone();
         }
+
+        // Restart the random streams so a seeded scene replays from the top
+        if let Some(seeds) = &self.rng {
+            rng.restore(seeds);
+        }
+
+        // Snapshot slots belong to the scene; one saved without any clears them
+        snapshots.set_slots(self.snapshots.clone());
     }
 }
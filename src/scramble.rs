@@ -72,7 +72,7 @@ impl ScrambleAnimation {
         let speed = speed.clamp(1, 10);
         let base_ms_per_char = 100;
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::fork_display();
         let target_chars: Vec<char> = text.chars().collect();
         let text_len = target_chars.len();
 
@@ -429,8 +429,7 @@ fn ease_out_expo(t: f32) -> f32 {
 
 fn random_char() -> char {
     const SCRAMBLE_CHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789?/\\(^)![]{}&^%$#";
-    let mut rng = rand::thread_rng();
-    let idx = rng.gen_range(0..SCRAMBLE_CHARS.len());
+    let idx = crate::rng::with_display_rng(|rng| rng.gen_range(0..SCRAMBLE_CHARS.len()));
     SCRAMBLE_CHARS.chars().nth(idx).unwrap()
 }
 
@@ -4,12 +4,13 @@ use std::env;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, Write};
 use std::net::UdpSocket;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use crate::types::{RECORDER_BUFFER_ID, STEM_BUFFER_BASE};
 #[cfg(target_os = "macos")]
 use crate::audio_devices;
 
@@ -18,8 +19,12 @@ pub struct ScsynthDirect {
     osc_socket: Option<UdpSocket>,
     recording_path_prefix: Option<String>,
     is_recording: bool,
+    is_recording_stems: bool,
 }
 
+/// REC.STEMS recorder node
+const STEM_RECORDER_NODE_ID: i32 = 2001;
+
 impl ScsynthDirect {
     pub fn new() -> Result<Self, String> {
         Ok(Self {
@@ -27,6 +32,7 @@ impl ScsynthDirect {
             osc_socket: None,
             recording_path_prefix: None,
             is_recording: false,
+            is_recording_stems: false,
         })
     }
 
@@ -54,8 +60,11 @@ impl ScsynthDirect {
         let mut cmd = Command::new(&scsynth_path);
 
         cmd.arg("-u").arg("57110");  // UDP port
-        cmd.arg("-o").arg("2");       // Output channels (stereo)
-        cmd.arg("-i").arg("0");       // No input channels (avoid sample rate mismatch)
+        let display = crate::config::load_config().map(|c| c.display).unwrap_or_default();
+        // Output channels: the master on 1-2, more pairs for OUT.ROUTE
+        cmd.arg("-o").arg(crate::types::output_channel_count(display.output_channels).to_string());
+        // No input channels unless asked for (avoid sample rate mismatch)
+        cmd.arg("-i").arg(if display.audio_in { "2" } else { "0" });
         cmd.arg("-R").arg("0");       // Don't load default synthdefs
         cmd.arg("-l").arg("4");       // Max logins (allow for restart re-registration)
 
@@ -414,10 +423,17 @@ impl ScsynthDirect {
             synthdefs_dir.join("monokit_primary.scsyndef"),
             synthdefs_dir.join("monokit_plaits.scsyndef"),
             synthdefs_dir.join("monokit_sampler.scsyndef"),
+            synthdefs_dir.join("monokit_preview_mono.scsyndef"),
+            synthdefs_dir.join("monokit_preview_stereo.scsyndef"),
             synthdefs_dir.join("monokit_main.scsyndef"),
+            synthdefs_dir.join("monokit_route_fx.scsyndef"),
             synthdefs_dir.join("monokit_spectrum.scsyndef"),
             synthdefs_dir.join("monokit_scope.scsyndef"),
+            synthdefs_dir.join("monokit_spectrogram.scsyndef"),
+            synthdefs_dir.join("monokit_onset.scsyndef"),
+            synthdefs_dir.join("monokit_loudness.scsyndef"),
             synthdefs_dir.join("monokit_recorder.scsyndef"),
+            synthdefs_dir.join("monokit_stem_recorder.scsyndef"),
         ];
 
         if !silent {
@@ -474,6 +490,54 @@ impl ScsynthDirect {
             eprintln!("[monokit]   Created monokit_scope (node {})", crate::types::SCOPE_NODE_ID);
         }
 
+        Self::send_osc_message_static(
+            socket,
+            "/s_new",
+            vec![
+                OscType::String("monokit_spectrogram".to_string()),
+                OscType::Int(crate::types::SPECTROGRAM_NODE_ID),
+                OscType::Int(1),
+                OscType::Int(0),
+            ],
+        )?;
+        // Paused until the Spectrogram page is shown (App::refresh_spectrogram)
+        Self::send_osc_message_static(
+            socket,
+            "/n_run",
+            vec![OscType::Int(crate::types::SPECTROGRAM_NODE_ID), OscType::Int(0)],
+        )?;
+        if !silent {
+            eprintln!("[monokit]   Created monokit_spectrogram (node {})", crate::types::SPECTROGRAM_NODE_ID);
+        }
+
+        Self::send_osc_message_static(
+            socket,
+            "/s_new",
+            vec![
+                OscType::String("monokit_onset".to_string()),
+                OscType::Int(crate::types::ONSET_NODE_ID),
+                OscType::Int(1),
+                OscType::Int(0),
+            ],
+        )?;
+        if !silent {
+            eprintln!("[monokit]   Created monokit_onset (node {})", crate::types::ONSET_NODE_ID);
+        }
+
+        Self::send_osc_message_static(
+            socket,
+            "/s_new",
+            vec![
+                OscType::String("monokit_loudness".to_string()),
+                OscType::Int(crate::types::LOUDNESS_NODE_ID),
+                OscType::Int(1),
+                OscType::Int(0),
+            ],
+        )?;
+        if !silent {
+            eprintln!("[monokit]   Created monokit_loudness (node {})", crate::types::LOUDNESS_NODE_ID);
+        }
+
         thread::sleep(Duration::from_millis(500));
 
         // Start CPU monitoring routine (2Hz)
@@ -716,6 +780,8 @@ impl ScsynthDirect {
                 OscType::Int(PLAITS_AUX_BUS),
                 OscType::String("samplerBus".to_string()),
                 OscType::Int(SAMPLER_BUS),
+                OscType::String("stemBus".to_string()),
+                OscType::Int(crate::stems::STEM_BUS),
                 // Envelope decay defaults (ensure synth starts with known values)
                 OscType::String("ad".to_string()),
                 OscType::Int(100),
@@ -729,6 +795,30 @@ impl ScsynthDirect {
             eprintln!("[monokit]   Created monokit_main (node {})", synths.main_node);
         }
 
+        // OUT.ROUTE POST: after monokit_main so they read this block's stems,
+        // paused until monokit_main routes their voice POST
+        for voice in 0..4 {
+            let node = crate::types::ROUTE_FX_NODE_ID + voice;
+            Self::send_osc_message_static(
+                socket,
+                "/s_new",
+                vec![
+                    OscType::String("monokit_route_fx".to_string()),
+                    OscType::Int(node),
+                    OscType::Int(1),  // addAction=1 (addToTail)
+                    OscType::Int(0),
+                    OscType::String("voice".to_string()),
+                    OscType::Int(voice),
+                    OscType::String("stemBus".to_string()),
+                    OscType::Int(crate::stems::STEM_BUS),
+                ],
+            )?;
+            Self::send_osc_message_static(socket, "/n_run", vec![OscType::Int(node), OscType::Int(0)])?;
+        }
+        if !silent {
+            eprintln!("[monokit]   Created monokit_route_fx (nodes {}-{})", crate::types::ROUTE_FX_NODE_ID, crate::types::ROUTE_FX_NODE_ID + 3);
+        }
+
         Ok(())
     }
 
@@ -792,6 +882,11 @@ impl ScsynthDirect {
         self.start_internal(Some(device), true)
     }
 
+    /// Restart quietly on the given device (or the default), picking up config changes
+    pub fn restart(&mut self, audio_out_device: Option<&str>) -> Result<(), String> {
+        self.start_internal(audio_out_device, true)
+    }
+
     pub fn is_running(&self) -> bool {
         self.child.is_some()
     }
@@ -828,12 +923,12 @@ impl ScsynthDirect {
         socket.set_read_timeout(Some(Duration::from_secs(5)))
             .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
 
-        // 1. Allocate buffer (bufnum=99, numFrames=16384, numChannels=2)
+        // 1. Allocate buffer (numFrames=16384, numChannels=2)
         Self::send_osc_message_static(
             &socket,
             "/b_alloc",
             vec![
-                OscType::Int(99),
+                OscType::Int(RECORDER_BUFFER_ID as i32),
                 OscType::Int(16384),
                 OscType::Int(2),
             ],
@@ -851,7 +946,7 @@ impl ScsynthDirect {
                 OscType::Int(1),  // addAction=1 (addToTail)
                 OscType::Int(0),  // targetID=0 (default group)
                 OscType::String("bufnum".to_string()),
-                OscType::Int(99),
+                OscType::Int(RECORDER_BUFFER_ID as i32),
             ],
         )?;
 
@@ -863,7 +958,7 @@ impl ScsynthDirect {
             &socket,
             "/b_write",
             vec![
-                OscType::Int(99),                           // buffer number
+                OscType::Int(RECORDER_BUFFER_ID as i32),    // buffer number
                 OscType::String(file_path),                 // path
                 OscType::String("wav".to_string()),         // header format
                 OscType::String("int24".to_string()),       // sample format
@@ -901,7 +996,7 @@ impl ScsynthDirect {
         Self::send_osc_message_static(
             &socket,
             "/b_close",
-            vec![OscType::Int(99)],
+            vec![OscType::Int(RECORDER_BUFFER_ID as i32)],
         )?;
 
         thread::sleep(Duration::from_millis(50));
@@ -910,7 +1005,7 @@ impl ScsynthDirect {
         Self::send_osc_message_static(
             &socket,
             "/b_free",
-            vec![OscType::Int(99)],
+            vec![OscType::Int(RECORDER_BUFFER_ID as i32)],
         )?;
 
         self.is_recording = false;
@@ -918,6 +1013,93 @@ impl ScsynthDirect {
         Ok(())
     }
 
+    /// Record the master and every stem bus into `folder`, one stereo WAV each.
+    /// A single recorder synth writes all files, so they start on the same sample.
+    pub fn start_stem_recording(&mut self, folder: &Path) -> Result<(), String> {
+        if self.is_recording_stems {
+            return Err("Already recording stems".to_string());
+        }
+
+        let socket = UdpSocket::bind("127.0.0.1:0")
+            .map_err(|e| format!("Failed to bind socket for recording: {}", e))?;
+
+        // Each /b_alloc opens its file when done and the last open starts the
+        // recorder. Async commands run in order, so every file is open by then.
+        let start = OscMessage {
+            addr: "/s_new".to_string(),
+            args: vec![
+                OscType::String("monokit_stem_recorder".to_string()),
+                OscType::Int(STEM_RECORDER_NODE_ID),
+                OscType::Int(1),  // addAction=1 (addToTail)
+                OscType::Int(0),
+                OscType::String("bufnum".to_string()),
+                OscType::Int(STEM_BUFFER_BASE as i32),
+                OscType::String("stemBus".to_string()),
+                OscType::Int(crate::stems::STEM_BUS),
+            ],
+        };
+
+        let stems = crate::stems::STEMS;
+        for (i, stem) in stems.iter().enumerate() {
+            let bufnum = STEM_BUFFER_BASE as i32 + i as i32;
+            let path = crate::stems::stem_path(folder, stem);
+            let mut write_args = vec![
+                OscType::Int(bufnum),
+                OscType::String(path.to_string_lossy().to_string()),
+                OscType::String("wav".to_string()),
+                OscType::String("int24".to_string()),
+                OscType::Int(-1),
+                OscType::Int(0),
+                OscType::Int(1),  // leaveOpen = 1 for DiskOut
+            ];
+            if i == stems.len() - 1 {
+                write_args.push(OscType::Blob(encode_message(start.clone())?));
+            }
+            let write = OscMessage { addr: "/b_write".to_string(), args: write_args };
+
+            Self::send_osc_message_static(
+                &socket,
+                "/b_alloc",
+                vec![
+                    OscType::Int(bufnum),
+                    OscType::Int(65536),
+                    OscType::Int(2),
+                    OscType::Blob(encode_message(write)?),
+                ],
+            )?;
+        }
+
+        self.is_recording_stems = true;
+
+        Ok(())
+    }
+
+    /// Stop stem recording and close every file
+    pub fn stop_stem_recording(&mut self) -> Result<(), String> {
+        if !self.is_recording_stems {
+            return Err("Not currently recording stems".to_string());
+        }
+
+        let socket = UdpSocket::bind("127.0.0.1:0")
+            .map_err(|e| format!("Failed to bind socket for recording: {}", e))?;
+
+        Self::send_osc_message_static(&socket, "/n_free", vec![OscType::Int(STEM_RECORDER_NODE_ID)])?;
+        thread::sleep(Duration::from_millis(50));
+
+        for i in 0..crate::stems::STEMS.len() as i32 {
+            Self::send_osc_message_static(&socket, "/b_close", vec![OscType::Int(STEM_BUFFER_BASE as i32 + i)])?;
+        }
+        thread::sleep(Duration::from_millis(50));
+
+        for i in 0..crate::stems::STEMS.len() as i32 {
+            Self::send_osc_message_static(&socket, "/b_free", vec![OscType::Int(STEM_BUFFER_BASE as i32 + i)])?;
+        }
+
+        self.is_recording_stems = false;
+
+        Ok(())
+    }
+
     /// Set custom recording path prefix
     pub fn set_recording_path_prefix(&mut self, prefix: String) {
         self.recording_path_prefix = Some(prefix);
@@ -930,6 +1112,11 @@ impl Drop for ScsynthDirect {
     }
 }
 
+/// Encode a message for use as another command's completion message
+fn encode_message(msg: OscMessage) -> Result<Vec<u8>, String> {
+    encoder::encode(&OscPacket::Message(msg)).map_err(|e| format!("Failed to encode OSC message: {}", e))
+}
+
 /// Get the directory containing the real executable (resolving symlinks)
 fn get_exe_dir() -> Option<PathBuf> {
     if let Ok(exe) = env::current_exe() {
@@ -0,0 +1,552 @@
+//! Parameter snapshots: the full synth/FX parameter set stored in numbered slots.
+//!
+//! The metro thread sees every parameter on its way to SuperCollider, so it keeps
+//! the live values here and runs SNAP.SAVE/LOAD/MORPH/GLIDE in command order.
+//! Slots are saved with the scene.
+
+use crate::evolve::{self, Evolution};
+use crate::preset::patch::PatchGroup;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rosc::OscType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const SNAP_SLOTS: u8 = 16;
+pub const MORPH_MAX: i32 = 16383;
+
+/// How often a running glide sends its next step
+pub const GLIDE_STEP: Duration = Duration::from_millis(10);
+
+/// Params that are not part of a sound: triggers, slew times, routing of the
+/// sample buffer, output safety, mixer mutes, the onset detector and the scope
+const EXCLUDED_PREFIXES: [&str; 6] = ["t_", "slew_", "mute_", "onset_", "route_", "scope_"];
+const EXCLUDED: [&str; 10] = ["s_bufnum", "s_startFrame", "s_endFrame", "s_tempoBpm", "s_srcBpm", "scopeGain", "limit", "limit_ceil", "out_gain", "vca_mode"];
+
+/// Waveform/mode selectors and on/off switches: these jump at the midpoint of a morph
+const DISCRETE: [&str; 30] = [
+    "pw", "mw", "nw", "ft", "rgw", "dm", "sf_type", "engine", "env_mode",
+    "cl_mode", "cl_freeze", "ps_mode", "ps_targ", "rmode", "dmode", "dtail", "rtail",
+    "mp", "md", "mt", "ma", "mm", "me", "br_rev", "cr_auto", "ds",
+    "s_loop", "s_direction", "s_sust", "s_stretch",
+];
+
+/// Every param RST sends, at the value the synthdefs start with
+pub const DEFAULT_PARAMS: &[(&str, ParamValue)] = &[
+    ("pf", ParamValue::Float(131.0)),
+    ("pw", ParamValue::Int(0)),
+    ("mf", ParamValue::Float(262.0)),
+    ("mw", ParamValue::Int(0)),
+
+    ("dc", ParamValue::Int(0)),
+    ("dm", ParamValue::Int(0)),
+    ("tk", ParamValue::Int(0)),
+    ("mb", ParamValue::Int(0)),
+    ("mba", ParamValue::Int(0)),
+    ("mbd", ParamValue::Int(100)),
+    ("mp", ParamValue::Int(0)),
+    ("md", ParamValue::Int(0)),
+    ("mt", ParamValue::Int(0)),
+    ("ma", ParamValue::Int(0)),
+    ("fm", ParamValue::Int(0)),
+    ("mx", ParamValue::Int(0)),
+    ("mm", ParamValue::Int(0)),
+    ("me", ParamValue::Int(0)),
+
+    ("fb", ParamValue::Int(0)),
+    ("fba", ParamValue::Int(0)),
+    ("fbd", ParamValue::Int(100)),
+
+    ("ad", ParamValue::Int(100)),
+    ("pd", ParamValue::Int(10)),
+    ("fd", ParamValue::Int(10)),
+    ("dd", ParamValue::Int(10)),
+    ("pa", ParamValue::Float(0.0)),
+    ("fa", ParamValue::Int(0)),
+    ("da", ParamValue::Int(0)),
+
+    ("fc", ParamValue::Float(10000.0)),
+    ("fq", ParamValue::Int(0)),
+    ("ft", ParamValue::Int(0)),
+    ("fe", ParamValue::Int(0)),
+    ("fed", ParamValue::Int(100)),
+    ("fk", ParamValue::Int(0)),
+    ("mf_f", ParamValue::Int(0)),
+    ("mf_q", ParamValue::Int(0)),
+
+    ("dt", ParamValue::Int(250)),
+    ("df", ParamValue::Int(0)),
+    ("dlp", ParamValue::Int(5000)),
+    ("dw", ParamValue::Int(0)),
+    ("ds", ParamValue::Int(0)),
+    ("dmode", ParamValue::Int(2)),
+    ("dtail", ParamValue::Int(1)),
+
+    ("rv", ParamValue::Int(0)),
+    ("rp", ParamValue::Int(0)),
+    ("rh", ParamValue::Int(8000)),
+    ("rw", ParamValue::Int(0)),
+    ("rmode", ParamValue::Int(2)),
+    ("rtail", ParamValue::Int(1)),
+
+    ("lb", ParamValue::Int(16)),
+    ("ls", ParamValue::Int(48000)),
+    ("lm", ParamValue::Int(0)),
+
+    ("rgf", ParamValue::Float(131.0)),
+    ("rgw", ParamValue::Int(0)),
+    ("rgm", ParamValue::Int(0)),
+
+    ("ct", ParamValue::Int(8192)),
+    ("cr", ParamValue::Int(1)),
+    ("ca", ParamValue::Int(10)),
+    ("cl", ParamValue::Int(100)),
+    ("cm", ParamValue::Int(0)),
+    ("cr_mix", ParamValue::Int(16383)),
+
+    ("el", ParamValue::Int(0)),
+    ("elf", ParamValue::Float(200.0)),
+    ("em", ParamValue::Int(0)),
+    ("ef", ParamValue::Float(1000.0)),
+    ("eq", ParamValue::Float(1.0)),
+    ("eh", ParamValue::Int(0)),
+    ("ehf", ParamValue::Float(4000.0)),
+
+    ("nw", ParamValue::Int(0)),
+    ("np", ParamValue::Int(0)),
+    ("nm", ParamValue::Int(0)),
+    ("nv", ParamValue::Int(0)),
+    ("pv", ParamValue::Int(16383)),
+    ("mv", ParamValue::Int(0)),
+
+    ("pn", ParamValue::Int(0)),
+    ("t_gate", ParamValue::Int(0)),
+    ("vca_mode", ParamValue::Int(1)),
+
+    ("br_len", ParamValue::Int(250)),
+    ("br_rev", ParamValue::Int(0)),
+    ("br_win", ParamValue::Int(5)),
+    ("br_mix", ParamValue::Int(0)),
+
+    ("ps_mode", ParamValue::Int(0)),
+    ("ps_semi", ParamValue::Int(0)),
+    ("ps_grain", ParamValue::Int(20)),
+    ("ps_mix", ParamValue::Int(0)),
+    ("ps_targ", ParamValue::Int(0)),
+
+    ("pitch", ParamValue::Float(131.0)),
+    ("detune", ParamValue::Int(0)),
+    ("engine", ParamValue::Int(0)),
+    ("harmonics", ParamValue::Float(0.5)),
+    ("timbre", ParamValue::Float(0.5)),
+    ("morph", ParamValue::Float(0.5)),
+    ("decay", ParamValue::Float(0.5)),
+    ("lpg", ParamValue::Float(0.5)),
+    ("plv", ParamValue::Int(0)),
+    ("pav", ParamValue::Int(0)),
+
+    ("cl_pitch", ParamValue::Int(8192)),
+    ("cl_pos", ParamValue::Int(8192)),
+    ("cl_size", ParamValue::Int(8192)),
+    ("cl_dens", ParamValue::Int(8192)),
+    ("cl_tex", ParamValue::Int(8192)),
+    ("cl_wet", ParamValue::Int(0)),
+    ("cl_gain", ParamValue::Int(8192)),
+    ("cl_spread", ParamValue::Int(8192)),
+    ("cl_rvb", ParamValue::Int(0)),
+    ("cl_fb", ParamValue::Int(0)),
+    ("cl_freeze", ParamValue::Int(0)),
+    ("cl_mode", ParamValue::Int(0)),
+    ("cl_lofi", ParamValue::Int(0)),
+
+    ("slew_time", ParamValue::Int(0)),
+    ("slew_pf", ParamValue::Int(-1)),
+    ("slew_mf", ParamValue::Int(-1)),
+    ("slew_fc", ParamValue::Int(-1)),
+    ("slew_fm", ParamValue::Int(-1)),
+    ("slew_mx", ParamValue::Int(-1)),
+    ("slew_dc", ParamValue::Int(-1)),
+    ("slew_fb", ParamValue::Int(-1)),
+    ("slew_fq", ParamValue::Int(-1)),
+    ("slew_fk", ParamValue::Int(-1)),
+    ("slew_fe", ParamValue::Int(-1)),
+    ("slew_dw", ParamValue::Int(-1)),
+    ("slew_rv", ParamValue::Int(-1)),
+    ("slew_rw", ParamValue::Int(-1)),
+    ("slew_volume", ParamValue::Int(-1)),
+    ("slew_pn", ParamValue::Int(-1)),
+    ("slew_lb", ParamValue::Int(-1)),
+    ("slew_ls", ParamValue::Int(-1)),
+    ("slew_lm", ParamValue::Int(-1)),
+    ("slew_rgf", ParamValue::Int(-1)),
+    ("slew_rgm", ParamValue::Int(-1)),
+    ("slew_ct", ParamValue::Int(-1)),
+    ("slew_cm", ParamValue::Int(-1)),
+    ("slew_el", ParamValue::Int(-1)),
+    ("slew_em", ParamValue::Int(-1)),
+    ("slew_eh", ParamValue::Int(-1)),
+    ("slew_ef", ParamValue::Int(-1)),
+
+    ("env_atk", ParamValue::Int(1)),
+    ("env_crv", ParamValue::Int(-4)),
+    ("aenv_atk", ParamValue::Int(-1)),
+    ("penv_atk", ParamValue::Int(-1)),
+    ("fmev_atk", ParamValue::Int(-1)),
+    ("denv_atk", ParamValue::Int(-1)),
+    ("fbev_atk", ParamValue::Int(-1)),
+    ("flev_atk", ParamValue::Int(-1)),
+    ("aenv_crv", ParamValue::Int(-100)),
+    ("penv_crv", ParamValue::Int(-100)),
+    ("fmev_crv", ParamValue::Int(-100)),
+    ("denv_crv", ParamValue::Int(-100)),
+    ("fbev_crv", ParamValue::Int(-100)),
+    ("flev_crv", ParamValue::Int(-100)),
+
+    ("s_rate", ParamValue::Int(8192)),
+    ("s_pitch", ParamValue::Int(0)),
+    ("s_fine", ParamValue::Int(0)),
+    ("s_direction", ParamValue::Int(0)),
+    ("s_loop", ParamValue::Int(0)),
+    ("s_startFrame", ParamValue::Int(0)),
+    ("s_endFrame", ParamValue::Int(-1)),
+    ("s_atk", ParamValue::Int(0)),
+    ("s_dec", ParamValue::Int(8192)),
+    ("s_rel", ParamValue::Int(1000)),
+    ("s_sust", ParamValue::Int(0)),
+    ("s_volume", ParamValue::Int(16383)), // Max - use vol_smp for actual control
+    ("s_stretch", ParamValue::Int(0)),
+
+    ("sf_cut", ParamValue::Int(16383)),
+    ("sf_res", ParamValue::Int(0)),
+    ("sf_type", ParamValue::Int(0)),
+    ("sf_bits", ParamValue::Int(0)),
+    ("sf_rate", ParamValue::Int(16383)),
+    ("sf_deci", ParamValue::Int(0)),
+    ("sf_prob", ParamValue::Int(0)),
+    ("sf_mult", ParamValue::Int(8192)),
+    ("sf_glit", ParamValue::Int(0)),
+
+    ("vol_osc", ParamValue::Int(16383)),
+    ("vol_pla", ParamValue::Int(16383)),
+    ("vol_nos", ParamValue::Int(16383)),
+    ("vol_smp", ParamValue::Int(16383)),
+    ("pan_osc", ParamValue::Int(0)),
+    ("pan_pla", ParamValue::Int(0)),
+    ("pan_nos", ParamValue::Int(0)),
+    ("pan_smp", ParamValue::Int(0)),
+    ("mute_osc", ParamValue::Int(0)),
+    ("mute_pla", ParamValue::Int(0)),
+    ("mute_nos", ParamValue::Int(0)),
+    ("mute_smp", ParamValue::Int(0)),
+];
+
+pub fn is_tracked(name: &str) -> bool {
+    !EXCLUDED.contains(&name) && !EXCLUDED_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+pub fn is_discrete(name: &str) -> bool {
+    DISCRETE.contains(&name)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Int(i32),
+    Float(f32),
+}
+
+impl ParamValue {
+    pub fn from_osc(value: &OscType) -> Option<Self> {
+        match value {
+            OscType::Int(v) => Some(ParamValue::Int(*v)),
+            OscType::Float(v) => Some(ParamValue::Float(*v)),
+            _ => None,
+        }
+    }
+
+    pub fn to_osc(self) -> OscType {
+        match self {
+            ParamValue::Int(v) => OscType::Int(v),
+            ParamValue::Float(v) => OscType::Float(v),
+        }
+    }
+
+    pub fn as_f32(self) -> f32 {
+        match self {
+            ParamValue::Int(v) => v as f32,
+            ParamValue::Float(v) => v,
+        }
+    }
+}
+
+impl std::fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::Int(v) => write!(f, "{}", v),
+            ParamValue::Float(v) => write!(f, "{}", (v * 1000.0).round() / 1000.0),
+        }
+    }
+}
+
+/// Value of `name` at position `t` (0.0-1.0) between `from` and `to`
+pub fn blend_value(name: &str, from: ParamValue, to: ParamValue, t: f32) -> ParamValue {
+    if is_discrete(name) {
+        return if t < 0.5 { from } else { to };
+    }
+    let value = from.as_f32() + (to.as_f32() - from.as_f32()) * t;
+    match to {
+        ParamValue::Int(_) => ParamValue::Int(value.round() as i32),
+        ParamValue::Float(_) => ParamValue::Float(value),
+    }
+}
+
+pub type Snapshot = BTreeMap<String, ParamValue>;
+
+/// Where a run of SNAP.MORPH between the same two slots starts from: slot `a`
+/// over the live values when the first step was taken
+#[derive(Debug, Clone)]
+struct Morph {
+    a: u8,
+    b: u8,
+    from: Snapshot,
+}
+
+#[derive(Debug, Clone)]
+struct Glide {
+    from: Snapshot,
+    to: Snapshot,
+    started: Instant,
+    duration: Duration,
+}
+
+/// Live parameter values plus the saved slots, shared by the app and the metro thread
+#[derive(Debug, Clone)]
+pub struct SnapshotBank {
+    live: Snapshot,
+    slots: BTreeMap<u8, Snapshot>,
+    morph: Option<Morph>,
+    glide: Option<Glide>,
+    evolution: Evolution,
+}
+
+impl Default for SnapshotBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type SharedSnapshots = Arc<Mutex<SnapshotBank>>;
+
+pub fn slot_in_range(slot: i32) -> bool {
+    (1..=SNAP_SLOTS as i32).contains(&slot)
+}
+
+/// The tracked params at their defaults, what the synth plays after launch or RST
+pub fn defaults() -> Snapshot {
+    DEFAULT_PARAMS
+        .iter()
+        .filter(|(name, _)| is_tracked(name))
+        .map(|&(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+impl SnapshotBank {
+    pub fn new() -> Self {
+        Self {
+            live: defaults(),
+            slots: BTreeMap::new(),
+            morph: None,
+            glide: None,
+            evolution: Evolution::default(),
+        }
+    }
+
+    /// Back to the defaults RST sends; slots are kept
+    pub fn reset_live(&mut self) {
+        self.live = defaults();
+        self.stop();
+    }
+
+    /// Note a value sent to SuperCollider. A param set by hand during a glide
+    /// drops out of the glide so the new value sticks.
+    pub fn record(&mut self, name: &str, value: &OscType) {
+        if !is_tracked(name) {
+            return;
+        }
+        if let Some(value) = ParamValue::from_osc(value) {
+            self.live.insert(name.to_string(), value);
+            if let Some(glide) = &mut self.glide {
+                glide.to.remove(name);
+            }
+        }
+    }
+
+    pub fn live(&self) -> &Snapshot {
+        &self.live
+    }
+
+    #[cfg(test)]
+    pub fn slot(&self, slot: u8) -> Option<&Snapshot> {
+        self.slots.get(&slot)
+    }
+
+    pub fn slots(&self) -> &BTreeMap<u8, Snapshot> {
+        &self.slots
+    }
+
+    /// Replace every slot (scene load); the live values are left alone
+    pub fn set_slots(&mut self, slots: BTreeMap<u8, Snapshot>) {
+        self.slots = slots;
+        self.stop();
+    }
+
+    pub fn is_gliding(&self) -> bool {
+        self.glide.is_some()
+    }
+
+    /// Store the live values in `slot`, returning how many params were captured
+    pub fn save(&mut self, slot: u8) -> usize {
+        self.slots.insert(slot, self.live.clone());
+        self.morph = None;
+        self.live.len()
+    }
+
+    /// Every param of `slot`, ready to send
+    pub fn load(&mut self, slot: u8) -> Result<Vec<(String, OscType)>, String> {
+        let snapshot = self.slots.get(&slot).cloned().ok_or_else(|| empty_slot(slot))?;
+        self.stop();
+        let params = snapshot.iter().map(|(name, value)| (name.clone(), value.to_osc())).collect();
+        self.live.extend(snapshot);
+        Ok(params)
+    }
+
+    /// Params that change when moving to `amount` (0-16383) between slots `a` and `b`.
+    /// Params missing from `a` start where they were when this run of morphs began,
+    /// so moving back to 0 returns them there whatever path was taken.
+    pub fn morph(&mut self, a: u8, b: u8, amount: i32) -> Result<Vec<(String, OscType)>, String> {
+        let from_slot = self.slots.get(&a).ok_or_else(|| empty_slot(a))?;
+        let to = self.slots.get(&b).cloned().ok_or_else(|| empty_slot(b))?;
+        self.glide = None;
+        let morph = match self.morph.take() {
+            Some(morph) if morph.a == a && morph.b == b => morph,
+            _ => {
+                let mut from = self.live.clone();
+                from.extend(from_slot.iter().map(|(name, value)| (name.clone(), *value)));
+                Morph { a, b, from }
+            }
+        };
+        let t = amount.clamp(0, MORPH_MAX) as f32 / MORPH_MAX as f32;
+        let params = self.blend(&morph.from, &to, t);
+        self.morph = Some(morph);
+        Ok(params)
+    }
+
+    /// Start moving from the live values to slot `b` over `ms`, returning the first step
+    pub fn glide(&mut self, b: u8, ms: u64, now: Instant) -> Result<Vec<(String, OscType)>, String> {
+        if ms == 0 {
+            return self.load(b);
+        }
+        let to = self.slots.get(&b).cloned().ok_or_else(|| empty_slot(b))?;
+        self.morph = None;
+        self.glide = Some(Glide {
+            from: self.live.clone(),
+            to,
+            started: now,
+            duration: Duration::from_millis(ms),
+        });
+        Ok(self.glide_step(now))
+    }
+
+    /// Params that changed since the last step of the running glide
+    pub fn glide_step(&mut self, now: Instant) -> Vec<(String, OscType)> {
+        let Some(glide) = self.glide.take() else {
+            return Vec::new();
+        };
+        let t = (now.saturating_duration_since(glide.started).as_secs_f32() / glide.duration.as_secs_f32()).min(1.0);
+        let params = self.blend(&glide.from, &glide.to, t);
+        if t < 1.0 {
+            self.glide = Some(glide);
+        }
+        params
+    }
+
+    /// Mutate around the current sound by `amount` (0-100). Repeating MUT without
+    /// MUT.KEEP or MUT.NO tries again from the same parent rather than drifting.
+    pub fn mutate(&mut self, amount: i32, group: PatchGroup, seed: u64) -> Vec<(String, OscType)> {
+        let parent = self.evolution.parent.get_or_insert_with(|| self.live.clone()).clone();
+        let candidate = evolve::mutate(&parent, amount, group, &mut StdRng::seed_from_u64(seed));
+        self.stop();
+        self.apply(&candidate)
+    }
+
+    /// Cross slots `a` and `b` into a new candidate
+    pub fn breed(&mut self, a: u8, b: u8, seed: u64) -> Result<Vec<(String, OscType)>, String> {
+        let from = self.slots.get(&a).ok_or_else(|| empty_slot(a))?;
+        let to = self.slots.get(&b).ok_or_else(|| empty_slot(b))?;
+        let child = evolve::breed(from, to, &mut StdRng::seed_from_u64(seed));
+        self.evolution.parent.get_or_insert_with(|| self.live.clone());
+        self.stop();
+        Ok(self.apply(&child))
+    }
+
+    /// Accept the pending candidate
+    pub fn keep(&mut self) -> Result<(), String> {
+        if self.evolution.keep() {
+            Ok(())
+        } else {
+            Err("MUT: NOTHING TO KEEP".to_string())
+        }
+    }
+
+    /// Throw the pending candidate away and return to its parent
+    pub fn discard(&mut self) -> Result<Vec<(String, OscType)>, String> {
+        let parent = self.evolution.parent.take().ok_or_else(|| "MUT: NOTHING TO DISCARD".to_string())?;
+        self.stop();
+        Ok(self.apply(&parent))
+    }
+
+    /// Undo the last keep, dropping any pending candidate
+    pub fn back(&mut self) -> Result<Vec<(String, OscType)>, String> {
+        let previous = self.evolution.history.pop().ok_or_else(|| "MUT: NO KEPT HISTORY".to_string())?;
+        self.evolution.parent = None;
+        self.stop();
+        Ok(self.apply(&previous))
+    }
+
+    #[cfg(test)]
+    pub fn history_len(&self) -> usize {
+        self.evolution.history.len()
+    }
+
+    /// End any running morph or glide
+    fn stop(&mut self) {
+        self.morph = None;
+        self.glide = None;
+    }
+
+    /// Move the live values to `target`, returning the params that changed
+    fn apply(&mut self, target: &Snapshot) -> Vec<(String, OscType)> {
+        self.blend(target, target, 1.0)
+    }
+
+    /// Blend `from` towards `to` and return only the values that differ from the live ones.
+    /// Params missing from `from` jump to their target; those missing from `to` are left alone.
+    fn blend(&mut self, from: &Snapshot, to: &Snapshot, t: f32) -> Vec<(String, OscType)> {
+        let mut changed = Vec::new();
+        for (name, &target) in to {
+            let start = from.get(name).copied().unwrap_or(target);
+            let value = blend_value(name, start, target, t);
+            if self.live.get(name) != Some(&value) {
+                self.live.insert(name.clone(), value);
+                changed.push((name.clone(), value.to_osc()));
+            }
+        }
+        changed
+    }
+}
+
+fn empty_slot(slot: u8) -> String {
+    format!("SNAP: SLOT {} IS EMPTY", slot)
+}
@@ -0,0 +1,146 @@
+//! Spectrogram page state: the analyzer frames from monokit_spectrogram, kept
+//! as a scrolling history, with the band count, scaling and peak/average mode
+//! applied for display.
+
+use crate::types::SPECTROGRAM_BANDS;
+use std::collections::VecDeque;
+
+/// Frames kept for the waterfall, newest first (20 per second)
+pub const HISTORY_FRAMES: usize = 400;
+
+pub const MIN_BANDS: usize = 8;
+pub const DEFAULT_BANDS: usize = 32;
+
+/// Range shown in log scaling, in dB below full scale
+const LOG_FLOOR_DB: f32 = -72.0;
+
+/// Peak mode falls back by this much per frame after a peak
+const PEAK_DECAY: f32 = 0.9;
+/// Average mode moves this far towards each new frame
+const AVERAGE_RATE: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrumScale {
+    /// dB, LOG_FLOOR_DB to 0
+    #[default]
+    Log,
+    /// Band amplitude as is
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrumMode {
+    /// Jump to peaks and fall back slowly
+    #[default]
+    Peak,
+    /// Running average, steadier for noise and pads
+    Average,
+}
+
+#[derive(Debug, Clone)]
+pub struct Spectrogram {
+    /// Bands shown, MIN_BANDS to SPECTROGRAM_BANDS
+    pub bands: usize,
+    pub scale: SpectrumScale,
+    pub mode: SpectrumMode,
+    /// 0 = braille, 1 = block shades
+    pub display_mode: u8,
+    /// Ignore new frames, keeping the waterfall as it is
+    pub frozen: bool,
+    /// Whether the analyzer synth runs; it is paused off the Spectrogram page
+    pub running: bool,
+    /// Latest analyzer frame after the peak/average mode
+    current: Vec<f32>,
+    history: VecDeque<Vec<f32>>,
+    /// Frame kept by SPEC.SNAP to compare against
+    snapshot: Option<Vec<f32>>,
+}
+
+impl Default for Spectrogram {
+    fn default() -> Self {
+        Self {
+            bands: DEFAULT_BANDS,
+            scale: SpectrumScale::default(),
+            mode: SpectrumMode::default(),
+            display_mode: 0,
+            frozen: false,
+            running: false,
+            current: vec![0.0; SPECTROGRAM_BANDS],
+            history: VecDeque::with_capacity(HISTORY_FRAMES),
+            snapshot: None,
+        }
+    }
+}
+
+impl Spectrogram {
+    /// Take one analyzer frame (band amplitudes, lowest band first)
+    pub fn push(&mut self, frame: &[f32]) {
+        if self.frozen || frame.len() != SPECTROGRAM_BANDS {
+            return;
+        }
+        for (current, &new) in self.current.iter_mut().zip(frame) {
+            let new = if new.is_finite() { new.max(0.0) } else { 0.0 };
+            *current = match self.mode {
+                SpectrumMode::Peak => new.max(*current * PEAK_DECAY),
+                SpectrumMode::Average => *current + (new - *current) * AVERAGE_RATE,
+            };
+        }
+        if self.history.len() == HISTORY_FRAMES {
+            self.history.pop_back();
+        }
+        self.history.push_front(self.current.clone());
+    }
+
+    /// Keep the current frame to compare against
+    pub fn take_snapshot(&mut self) {
+        self.snapshot = Some(self.current.clone());
+    }
+
+    pub fn clear_snapshot(&mut self) {
+        self.snapshot = None;
+    }
+
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot.is_some()
+    }
+
+    /// Current frame at the display band count and scaling, 0.0-1.0
+    pub fn current(&self) -> Vec<f32> {
+        self.display(&self.current)
+    }
+
+    pub fn snapshot(&self) -> Option<Vec<f32>> {
+        self.snapshot.as_ref().map(|frame| self.display(frame))
+    }
+
+    /// Up to `frames` past frames for display, newest first
+    pub fn history(&self, frames: usize) -> Vec<Vec<f32>> {
+        self.history.iter().take(frames).map(|frame| self.display(frame)).collect()
+    }
+
+    /// Merge the analyzer bands into the display bands (the loudest band in
+    /// peak mode, the power average in average mode), then scale
+    fn display(&self, frame: &[f32]) -> Vec<f32> {
+        let bands = self.bands.clamp(MIN_BANDS, SPECTROGRAM_BANDS);
+        (0..bands)
+            .map(|band| {
+                let start = band * frame.len() / bands;
+                let end = ((band + 1) * frame.len() / bands).max(start + 1);
+                let group = &frame[start..end];
+                let amp = match self.mode {
+                    SpectrumMode::Peak => group.iter().copied().fold(0.0, f32::max),
+                    SpectrumMode::Average => (group.iter().map(|a| a * a).sum::<f32>() / group.len() as f32).sqrt(),
+                };
+                self.scaled(amp)
+            })
+            .collect()
+    }
+
+    fn scaled(&self, amp: f32) -> f32 {
+        match self.scale {
+            SpectrumScale::Linear => amp.clamp(0.0, 1.0),
+            SpectrumScale::Log if amp > 0.0 => ((20.0 * amp.log10() - LOG_FLOOR_DB) / -LOG_FLOOR_DB).clamp(0.0, 1.0),
+            SpectrumScale::Log => 0.0,
+        }
+    }
+}
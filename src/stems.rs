@@ -0,0 +1,46 @@
+//! REC.STEMS sessions: a timestamped folder holding one stereo WAV per stem
+//! and a session.json sidecar with the tempo and scene.
+
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files written per session, in recorder buffer order. Besides the master,
+/// these are the busses monokit_main writes from STEM_BUS on.
+pub const STEMS: [&str; 7] = ["master", "osc", "noise", "plaits", "sampler", "delay", "reverb"];
+
+/// First stem bus (stereo pairs, osc first), clear of 12 outs and 2 ins
+pub const STEM_BUS: i32 = 24;
+
+pub const SIDECAR: &str = "session.json";
+
+#[derive(Debug, Serialize)]
+struct Sidecar<'a> {
+    scene: Option<&'a str>,
+    bpm: f32,
+    started: String,
+    stems: Vec<String>,
+}
+
+/// New session folder under `dir`, named like the single-file recordings
+pub fn session_folder(dir: &Path) -> PathBuf {
+    let timestamp = Local::now().format("%y%m%d_%H%M%S").to_string();
+    dir.join(format!("monokit_stems_{}", timestamp))
+}
+
+pub fn stem_path(folder: &Path, stem: &str) -> PathBuf {
+    folder.join(format!("{}.wav", stem))
+}
+
+/// Write session.json next to the stems
+pub fn write_sidecar(folder: &Path, bpm: f32, scene: Option<&str>) -> Result<(), String> {
+    let sidecar = Sidecar {
+        scene,
+        bpm: (bpm * 100.0).round() / 100.0,
+        started: Local::now().to_rfc3339(),
+        stems: STEMS.iter().map(|stem| format!("{}.wav", stem)).collect(),
+    };
+    let json = serde_json::to_string_pretty(&sidecar).map_err(|e| format!("Failed to encode {}: {}", SIDECAR, e))?;
+    fs::write(folder.join(SIDECAR), json).map_err(|e| format!("Failed to write {}: {}", SIDECAR, e))
+}
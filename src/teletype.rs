@@ -0,0 +1,339 @@
+//! Teletype text scenes for IMPORT.TT and EXPORT.TT.
+//!
+//! A Teletype scene file is a description followed by `#1`-`#8`, `#M` and `#I`
+//! script sections, `#P` with the four patterns as tab-separated columns and
+//! `#G` grid state (ignored here). Ops are translated through `OpMap`. A line
+//! using an op monokit doesn't have is kept as a `# TT:` comment, so EXPORT.TT
+//! can write it back unchanged.
+
+use crate::scene::{Scene, ScenePattern, SceneScript};
+use crate::types::{Pattern, DEFAULT_SCRIPT_LINES};
+use std::collections::BTreeMap;
+
+/// Prefix of a Teletype line kept verbatim because it couldn't be translated
+pub const UNTRANSLATED_PREFIX: &str = "# TT: ";
+
+/// Teletype has four patterns of 64 steps
+const TT_PATTERNS: usize = 4;
+const PATTERN_STEPS: usize = 64;
+const TT_SCRIPT_LINES: usize = 6;
+
+/// Section labels in file order; the index is the monokit script index
+const SCRIPT_LABELS: [&str; 10] = ["1", "2", "3", "4", "5", "6", "7", "8", "M", "I"];
+
+/// Teletype op -> monokit op, where the names differ. Keys with an argument
+/// match only that argument, so `TR.P 3` stays untranslated. An empty right
+/// side means the op has no equivalent.
+const DEFAULT_MAP: &[(&str, &str)] = &[
+    ("TR.P 1", "TR"),
+    ("TR.PULSE 1", "TR"),
+    ("TR.P 2", "PLTR"),
+    ("TR.PULSE 2", "PLTR"),
+    ("CV 1", "PF"),
+    ("CV 2", "PLF"),
+    ("M BPM", "M.BPM"),
+    ("EVERY", "EV"),
+    ("BREAK", "BRK"),
+    ("RAND", "RND"),
+    ("RRAND", "RRND"),
+    ("==", "EQ"),
+    ("!=", "NE"),
+    ("<", "LT"),
+    (">", "GT"),
+    ("<=", "LTE"),
+    (">=", "GTE"),
+];
+
+/// Ops with the same name and meaning in both languages
+const SHARED_OPS: &[&str] = &[
+    "A", "B", "C", "D", "X", "Y", "Z", "T", "I", "J", "K",
+    "ADD", "SUB", "MUL", "DIV", "MOD", "+", "-", "*", "/", "%",
+    "EZ", "NZ", "EQ", "NE", "GT", "LT", "GTE", "LTE",
+    "RND", "RRND", "TOSS", "N",
+    "IF", "ELIF", "ELSE", "L", "PROB", "SKIP",
+    "M", "M.ACT", "DEL", "DEL.X", "DEL.R", "DEL.CLR", "SCRIPT", "$",
+    "P", "P.N", "P.L", "P.I", "P.HERE", "P.NEXT", "P.PREV", "P.PUSH", "P.POP", "P.INS", "P.RM",
+    "P.ROT", "P.REV", "P.SHUF", "P.MIN", "P.MAX", "P.SUM", "P.AVG", "P.RND",
+    "PN", "PN.L", "PN.I", "PN.HERE", "PN.NEXT", "PN.PREV", "PN.PUSH", "PN.POP", "PN.INS", "PN.RM",
+    "PN.ROT", "PN.REV", "PN.SHUF", "PN.MIN", "PN.MAX", "PN.SUM", "PN.AVG", "PN.RND",
+];
+
+type Rule = (Vec<String>, Vec<String>);
+
+/// The Teletype <-> monokit op table: the defaults plus `[teletype]` from config.toml
+#[derive(Debug, Clone)]
+pub struct OpMap {
+    /// Teletype -> monokit, longest first
+    import: Vec<Rule>,
+    /// monokit -> Teletype, longest first
+    export: Vec<Rule>,
+}
+
+impl Default for OpMap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+impl OpMap {
+    /// Entries in `overrides` replace defaults with the same Teletype side
+    pub fn new(overrides: &BTreeMap<String, String>) -> Self {
+        let mut rules: Vec<Rule> = Vec::new();
+        let user = overrides.iter().map(|(tt, mk)| (tt.as_str(), mk.as_str()));
+        for (tt, mk) in user.chain(DEFAULT_MAP.iter().copied()) {
+            let tt = tokenize(tt);
+            if !tt.is_empty() && !rules.iter().any(|(seen, _)| *seen == tt) {
+                rules.push((tt, tokenize(mk)));
+            }
+        }
+
+        // An op both languages share is written back as is, not as its Teletype alias
+        let mut export: Vec<Rule> = Vec::new();
+        for (tt, mk) in &rules {
+            let shared = mk.len() == 1 && SHARED_OPS.contains(&mk[0].as_str());
+            if !mk.is_empty() && !shared && !export.iter().any(|(seen, _)| seen == mk) {
+                export.push((mk.clone(), tt.clone()));
+            }
+        }
+
+        let mut import = rules;
+        import.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        export.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        Self { import, export }
+    }
+
+    /// Teletype line -> monokit line, or the first op that can't be translated
+    pub fn to_monokit(&self, line: &str) -> Result<String, String> {
+        translate(line, &self.import)
+    }
+
+    /// monokit line -> Teletype line, or the first op that can't be translated
+    pub fn to_teletype(&self, line: &str) -> Result<String, String> {
+        translate(line, &self.export)
+    }
+}
+
+/// Upper-case tokens with `:` and `;` split off as tokens of their own
+fn tokenize(line: &str) -> Vec<String> {
+    line.replace(':', " : ")
+        .replace(';', " ; ")
+        .split_whitespace()
+        .map(str::to_uppercase)
+        .collect()
+}
+
+fn render(tokens: &[String]) -> String {
+    let mut line = String::new();
+    for token in tokens {
+        match token.as_str() {
+            ":" | ";" => line.push_str(token),
+            _ => {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(token);
+            }
+        }
+    }
+    line
+}
+
+fn translate(line: &str, rules: &[Rule]) -> Result<String, String> {
+    let tokens = tokenize(line);
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    'tokens: while i < tokens.len() {
+        for (from, to) in rules {
+            if tokens[i..].starts_with(from) {
+                if to.is_empty() {
+                    return Err(from.join(" "));
+                }
+                out.extend(to.iter().cloned());
+                i += from.len();
+                continue 'tokens;
+            }
+        }
+        let token = &tokens[i];
+        let passes = matches!(token.as_str(), ":" | ";") || token.parse::<i32>().is_ok() || SHARED_OPS.contains(&token.as_str());
+        if !passes {
+            return Err(token.clone());
+        }
+        out.push(token.clone());
+        i += 1;
+    }
+    Ok(render(&out))
+}
+
+/// What IMPORT.TT did with the scene's lines
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub translated: usize,
+    /// Ops that had no monokit equivalent, each listed once
+    pub untranslated: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Description,
+    Script(usize),
+    Patterns,
+    Grid,
+}
+
+fn section_header(line: &str) -> Option<Section> {
+    let label = line.trim().strip_prefix('#')?.to_uppercase();
+    match label.as_str() {
+        "P" => Some(Section::Patterns),
+        "G" => Some(Section::Grid),
+        _ => SCRIPT_LABELS.iter().position(|l| *l == label).map(Section::Script),
+    }
+}
+
+/// Parse a Teletype scene file into a monokit scene
+pub fn parse_scene(text: &str, map: &OpMap) -> (Scene, ImportReport) {
+    let mut report = ImportReport::default();
+    let mut description = Vec::new();
+    let mut scripts: Vec<Vec<String>> = vec![Vec::new(); SCRIPT_LABELS.len()];
+    let mut pattern_rows: Vec<Vec<i16>> = Vec::new();
+    let mut section = Section::Description;
+
+    for line in text.lines().map(str::trim_end) {
+        if let Some(next) = section_header(line) {
+            section = next;
+            continue;
+        }
+        match section {
+            Section::Description => description.push(line.to_string()),
+            Section::Script(index) if !line.trim().is_empty() => {
+                let line = line.trim();
+                // A leading # is a line commented out on the Teletype
+                let converted = if let Some(disabled) = line.strip_prefix('#') {
+                    format!("# {}", disabled.trim())
+                } else {
+                    match map.to_monokit(line) {
+                        Ok(converted) => {
+                            report.translated += 1;
+                            converted
+                        }
+                        Err(op) => {
+                            if !report.untranslated.contains(&op) {
+                                report.untranslated.push(op);
+                            }
+                            format!("{}{}", UNTRANSLATED_PREFIX, line)
+                        }
+                    }
+                };
+                scripts[index].push(converted);
+            }
+            Section::Patterns if !line.trim().is_empty() => {
+                pattern_rows.push(line.split_whitespace().map(|v| v.parse().unwrap_or(0)).collect());
+            }
+            _ => {}
+        }
+    }
+
+    while description.last().is_some_and(|l| l.trim().is_empty()) {
+        description.pop();
+    }
+
+    let scripts = scripts
+        .into_iter()
+        .map(|mut lines| {
+            lines.resize(DEFAULT_SCRIPT_LINES, String::new());
+            SceneScript { lines, j: 0, k: 0 }
+        })
+        .collect();
+
+    // Rows are length, wrap, start and end, then the 64 steps
+    let column = |row: Option<&Vec<i16>>, p: usize| row.and_then(|r| r.get(p)).copied().unwrap_or(0);
+    let patterns = (0..crate::types::PatternStorage::default().patterns.len())
+        .map(|p| {
+            if p >= TT_PATTERNS || pattern_rows.is_empty() {
+                let default = Pattern::default();
+                return ScenePattern { data: default.data.to_vec(), length: default.length, index: 0 };
+            }
+            let data = (0..PATTERN_STEPS).map(|step| column(pattern_rows.get(4 + step), p)).collect();
+            let length = column(pattern_rows.first(), p).clamp(0, PATTERN_STEPS as i16) as usize;
+            ScenePattern { data, length, index: 0 }
+        })
+        .collect();
+
+    let scene = Scene {
+        version: 1,
+        scripts,
+        patterns,
+        pattern_working: 0,
+        notes: description.into_iter().take(8).collect(),
+        script_mutes: vec![],
+        sampler: None,
+        rng: None,
+        snapshots: BTreeMap::new(),
+    };
+    (scene, report)
+}
+
+/// A line EXPORT.TT left out, with its script label and the op that stopped it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    pub script: &'static str,
+    pub line: String,
+    pub reason: String,
+}
+
+/// Write a monokit scene in Teletype's text format
+pub fn export_scene(scene: &Scene, map: &OpMap) -> (String, Vec<Skipped>) {
+    let mut skipped = Vec::new();
+    let mut out = String::new();
+
+    let notes: Vec<&str> = scene.notes.iter().map(|l| l.trim_end()).collect();
+    let last = notes.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+    for note in &notes[..last] {
+        out.push_str(note);
+        out.push('\n');
+    }
+    out.push('\n');
+
+    for (label, script) in SCRIPT_LABELS.iter().zip(&scene.scripts) {
+        out.push_str(&format!("#{}\n", label));
+        let mut written = 0;
+        for line in script.lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            let converted = if let Some(original) = line.strip_prefix(UNTRANSLATED_PREFIX.trim_end()) {
+                Ok(original.trim().to_string())
+            } else if let Some(comment) = line.strip_prefix('#') {
+                Ok(format!("#{}", comment.trim()))
+            } else {
+                map.to_teletype(crate::utils::strip_comment(line))
+            };
+            let reason = match converted {
+                Ok(_) if written == TT_SCRIPT_LINES => format!("MORE THAN {} LINES", TT_SCRIPT_LINES),
+                Ok(converted) => {
+                    out.push_str(&converted);
+                    out.push('\n');
+                    written += 1;
+                    continue;
+                }
+                Err(op) => op,
+            };
+            skipped.push(Skipped { script: label, line: line.to_string(), reason });
+        }
+        out.push('\n');
+    }
+
+    let patterns: Vec<&ScenePattern> = scene.patterns.iter().take(TT_PATTERNS).collect();
+    let row = |value: &dyn Fn(&ScenePattern) -> i64| {
+        let cells: Vec<String> = patterns.iter().map(|p| value(p).to_string()).collect();
+        format!("{}\n", cells.join("\t"))
+    };
+    out.push_str("#P\n");
+    out.push_str(&row(&|p| p.length as i64));
+    // Wrap, start and end have no monokit equivalent; write Teletype's defaults
+    out.push_str(&row(&|_| 1));
+    out.push_str(&row(&|_| 0));
+    out.push_str(&row(&|_| PATTERN_STEPS as i64 - 1));
+    out.push('\n');
+    for step in 0..PATTERN_STEPS {
+        out.push_str(&row(&|p| p.data.get(step).copied().unwrap_or(0) as i64));
+    }
+
+    (out, skipped)
+}
@@ -0,0 +1,72 @@
+use super::common::{test_app, TestContext};
+use crate::app::App;
+use crate::commands::registry::validate::validate_from_registry;
+use crate::types::{CompressorData, MeterData, MetroCommand, MetroEvent, SpectrumData, VoiceMeterData};
+use rosc::OscType;
+
+fn run(app: &mut App, script: usize, lines: &[&str]) {
+    for (i, line) in lines.iter().enumerate() {
+        app.scripts.scripts[script].lines[i] = line.to_string();
+    }
+    app.execute_script(script);
+}
+
+#[test]
+fn test_meter_readings_as_expressions() {
+    let (mut app, _) = test_app();
+    let mut spectrum = SpectrumData::default();
+    spectrum.bands[2] = 1.0;
+    app.handle_metro_event(MetroEvent::MeterUpdate(MeterData { peak_l: 0.5, peak_r: 0.25, in_peak: 1.0, ..Default::default() }), None);
+    app.handle_metro_event(MetroEvent::VoiceMeterUpdate(VoiceMeterData { osc_r: 0.1, ..Default::default() }), None);
+    app.handle_metro_event(MetroEvent::SpectrumUpdate(spectrum), None);
+    // Smoothed: the first update moves halfway, to -10dB
+    app.handle_metro_event(MetroEvent::CompressorUpdate(CompressorData { gain_reduction_db: -20.0, ..Default::default() }), None);
+
+    run(&mut app, 0, &["A LVL", "B LVL.IN", "C BAND 3", "D GR", "X LVL.OSC", "Y BAND ADD 1 1", "Z BAND 99"]);
+    let v = &app.variables;
+    assert_eq!((v.a, v.b, v.c, v.d, v.x), (8192, 16383, 16383, 4096, 1638));
+    assert_eq!((v.y, v.z), (0, 0));
+}
+
+#[test]
+fn test_onset_runs_chosen_script() {
+    let (mut app, _) = test_app();
+    app.scripts.scripts[1].lines[0] = "A ADD A 1".to_string();
+    app.handle_metro_event(MetroEvent::Onset, None);
+    assert_eq!(app.variables.a, 0);
+
+    run(&mut app, 9, &["ON.SCRIPT 2"]);
+    app.handle_metro_event(MetroEvent::Onset, None);
+    app.handle_metro_event(MetroEvent::Onset, None);
+    assert_eq!(app.variables.a, 2);
+
+    run(&mut app, 9, &["ON.SCRIPT 0"]);
+    app.handle_metro_event(MetroEvent::Onset, None);
+    assert_eq!(app.variables.a, 2);
+}
+
+#[test]
+fn test_onset_settings_reach_the_detector() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("ON.SRC 1").unwrap();
+    ctx.run("ON.THR 25").unwrap();
+    ctx.run("ON.THR 101").unwrap();
+    ctx.run("ON.SCRIPT 9").unwrap();
+
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [
+        MetroCommand::SendParam(src, OscType::Int(1)),
+        MetroCommand::SendParam(thr, OscType::Float(t)),
+    ] if src == "onset_src" && thr == "onset_thresh" && *t == 0.25));
+    assert_eq!(ctx.onset_settings.threshold, 25);
+    assert_eq!(ctx.onset_settings.script, 0);
+    assert_eq!(ctx.outputs, vec!["ON.SRC: IN", "ON.THR: 25", "ERROR: ON.THR 1-100", "ERROR: ON.SCRIPT 0-8"]);
+}
+
+#[test]
+fn test_validator_accepts_audio_terms() {
+    assert!(validate_from_registry("A BAND ADD 1 2").is_ok());
+    assert!(validate_from_registry("X SUB LVL LVL.IN").is_ok());
+    assert!(validate_from_registry("IF GT GR 4000: TR").is_ok());
+    assert!(validate_from_registry("A BAND").is_err());
+}
@@ -0,0 +1,227 @@
+use super::common::TestContext;
+use super::mock_scsynth::MockScsynth;
+use crate::clock::{Clock, RealClock, SimClock};
+use crate::metro::metro_thread_with_clock;
+use crate::types::{MetroCommand, MetroEvent, MetroState, SyncMode};
+use rosc::OscType;
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Metro and delay threads on a simulated clock
+pub(super) struct SimMetro {
+    clock: SimClock,
+    tx: Sender<MetroCommand>,
+    events: Receiver<MetroEvent>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SimMetro {
+    pub(super) fn start(osc_addr: Option<SocketAddr>) -> Self {
+        let clock = SimClock::new();
+        let state = Arc::new(Mutex::new(MetroState::default()));
+        let (tx, rx) = mpsc::channel();
+        let (event_tx, events) = mpsc::channel();
+        let thread_clock = clock.clone();
+        let handle = thread::spawn(move || metro_thread_with_clock(rx, state, event_tx, osc_addr, thread_clock));
+        // Metro and delay thread
+        clock.settle_threads(2);
+        Self { clock, tx, events, handle: Some(handle) }
+    }
+
+    pub(super) fn send(&self, cmd: MetroCommand) {
+        self.tx.send(cmd).unwrap();
+        self.clock.settle();
+    }
+
+    /// Run for `ms` in 1ms steps, returning each event with the time it was sent
+    pub(super) fn run(&self, ms: u64) -> Vec<(u64, MetroEvent)> {
+        let mut events = self.drain();
+        for _ in 0..ms {
+            self.clock.advance(Duration::from_millis(1));
+            events.extend(self.drain());
+        }
+        events
+    }
+
+    fn drain(&self) -> Vec<(u64, MetroEvent)> {
+        let now = self.clock.elapsed().as_millis() as u64;
+        self.events.try_iter().map(|event| (now, event)).collect()
+    }
+}
+
+impl Drop for SimMetro {
+    fn drop(&mut self) {
+        let _ = self.tx.send(MetroCommand::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn tick_times(events: &[(u64, MetroEvent)]) -> Vec<u64> {
+    events
+        .iter()
+        .filter(|(_, e)| matches!(e, MetroEvent::ExecuteScript(_)))
+        .map(|(at, _)| *at)
+        .collect()
+}
+
+fn delayed(events: &[(u64, MetroEvent)]) -> Vec<(u64, String)> {
+    events
+        .iter()
+        .filter_map(|(at, e)| match e {
+            MetroEvent::ExecuteDelayed(cmd, _) => Some((*at, cmd.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_ticks_land_on_the_interval_without_drift() {
+    let metro = SimMetro::start(None);
+    metro.send(MetroCommand::SetInterval(100));
+    metro.send(MetroCommand::SetActive(true));
+
+    let events = metro.run(1000);
+    assert_eq!(tick_times(&events), (0..=10).map(|i| i * 100).collect::<Vec<_>>());
+    assert!(events.iter().all(|(_, e)| matches!(e, MetroEvent::ExecuteScript(8))));
+}
+
+#[test]
+fn test_interval_change_restarts_the_tick() {
+    let metro = SimMetro::start(None);
+    metro.send(MetroCommand::SetInterval(100));
+    metro.send(MetroCommand::SetActive(true));
+    let before = metro.run(130);
+
+    metro.send(MetroCommand::SetInterval(40));
+    let after = metro.run(100);
+    metro.send(MetroCommand::SetActive(false));
+    let stopped = metro.run(200);
+
+    assert_eq!(tick_times(&before), vec![0, 100]);
+    assert_eq!(tick_times(&after), vec![130, 170, 210]);
+    assert!(tick_times(&stopped).is_empty());
+}
+
+#[test]
+fn test_delayed_commands_keep_their_schedule() {
+    let metro = SimMetro::start(None);
+    metro.send(MetroCommand::ScheduleRepeated("TR".to_string(), 3, 100, 0));
+    metro.send(MetroCommand::ScheduleDelayed("A 1".to_string(), 150, 0));
+    metro.send(MetroCommand::ScheduleDelayed("B 1".to_string(), 50, 0));
+
+    let events = metro.run(300);
+    assert_eq!(
+        delayed(&events),
+        vec![
+            (0, "TR".to_string()),
+            (50, "B 1".to_string()),
+            (100, "TR".to_string()),
+            (150, "A 1".to_string()),
+            (200, "TR".to_string()),
+        ]
+    );
+
+    metro.send(MetroCommand::ScheduleRepeated("TR".to_string(), 4, 100, 0));
+    let first = metro.run(150);
+    metro.send(MetroCommand::ClearDelayed);
+    let rest = metro.run(500);
+    assert_eq!(delayed(&first).len(), 2);
+    assert!(delayed(&rest).is_empty());
+}
+
+#[test]
+fn test_del_x_from_a_script() {
+    let mut ctx = TestContext::new();
+    ctx.run("DEL.X 3 250: TR").unwrap();
+    ctx.run("DEL 100: PRINT 1").unwrap();
+
+    let metro = SimMetro::start(None);
+    for cmd in ctx.metro_rx.try_iter() {
+        metro.send(cmd);
+    }
+    let times: Vec<u64> = delayed(&metro.run(1000)).into_iter().map(|(at, _)| at).collect();
+    assert_eq!(times, vec![0, 100, 250, 500]);
+}
+
+#[test]
+fn test_midi_clock_follow_is_smoothed() {
+    let mock = MockScsynth::start();
+    let metro = SimMetro::start(Some(mock.addr()));
+    metro.send(MetroCommand::SetSyncMode(SyncMode::MidiClock));
+    metro.send(MetroCommand::MidiTransportStart);
+
+    // Sixteenths at 120 BPM, then the MIDI master jumps to 150 BPM
+    let mut ticks = Vec::new();
+    for step in [125; 8].into_iter().chain([100; 16]) {
+        metro.send(MetroCommand::MidiClockTick);
+        ticks.extend(tick_times(&metro.drain()));
+        metro.clock.advance(Duration::from_millis(step));
+    }
+    assert_eq!(ticks.len(), 24);
+    assert_eq!(ticks[1] - ticks[0], 125);
+
+    let messages = mock.wait_for(Duration::from_secs(2), |m| m.iter().filter(|m| m.n_set("s_tempoBpm").is_some()).count() >= 8);
+    let bpm: Vec<f32> = messages
+        .iter()
+        .filter_map(|m| match m.n_set("s_tempoBpm") {
+            Some((_, OscType::Float(bpm))) => Some(bpm),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(bpm[0], 120.0);
+    // The jump is followed gradually, not in one step, and settles near the new tempo
+    assert!(bpm[1] > 120.0 && bpm[1] < 135.0, "{:?}", bpm);
+    assert!(bpm.windows(2).all(|w| w[1] > w[0]), "{:?}", bpm);
+    assert!((bpm[bpm.len() - 1] - 150.0).abs() < 2.0, "{:?}", bpm);
+}
+
+#[test]
+fn test_quantized_stems_start_on_the_tick() {
+    let metro = SimMetro::start(None);
+    metro.send(MetroCommand::SetInterval(100));
+    metro.send(MetroCommand::SetActive(true));
+    metro.run(130);
+
+    metro.send(MetroCommand::StartStemRecording("/tmp".to_string(), true));
+    metro.send(MetroCommand::StopStemRecording(true));
+    let armed = metro.run(50);
+    assert!(armed.is_empty());
+
+    // The stop replaced the start, and goes out just ahead of the tick's script
+    let events = metro.run(50);
+    assert!(matches!(&events[..], [(200, MetroEvent::StopStemRecordingDirect), (200, MetroEvent::ExecuteScript(8))]), "{:?}", events);
+
+    metro.send(MetroCommand::StartStemRecording("/tmp".to_string(), true));
+    let events = metro.run(100);
+    assert!(matches!(&events[..], [(300, MetroEvent::StartStemRecordingDirect(_, bpm)), (300, MetroEvent::ExecuteScript(8))] if *bpm == 150.0), "{:?}", events);
+}
+
+#[test]
+fn test_stems_start_at_once_when_metro_is_stopped() {
+    let metro = SimMetro::start(None);
+    metro.send(MetroCommand::SetInterval(125));
+    metro.send(MetroCommand::StartStemRecording("/tmp".to_string(), true));
+    let events = metro.drain();
+    assert!(matches!(&events[..], [(0, MetroEvent::StartStemRecordingDirect(dir, bpm))] if dir == "/tmp" && *bpm == 120.0), "{:?}", events);
+}
+
+#[test]
+fn test_real_clock_waits_out_the_timeout() {
+    let clock = RealClock;
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    assert!(matches!(clock.recv_timeout(&rx, Duration::from_millis(5)), Err(RecvTimeoutError::Timeout)));
+    assert!(start.elapsed() >= Duration::from_millis(5));
+
+    // Messages are still picked up while spinning
+    tx.send(1).unwrap();
+    assert_eq!(clock.recv_timeout(&rx, Duration::from_micros(50)), Ok(1));
+    drop(tx);
+    assert!(matches!(clock.recv_timeout(&rx, Duration::from_millis(5)), Err(RecvTimeoutError::Disconnected)));
+}
@@ -0,0 +1,54 @@
+use crate::commands::registry::validate::validate_from_registry;
+use crate::golden::run_scene;
+use crate::scene::{Scene, SceneScript};
+use crate::utils::strip_comment;
+
+fn scene_with_init(lines: &[&str]) -> Scene {
+    let script = |lines: &[&str]| SceneScript { lines: lines.iter().map(|l| l.to_string()).collect(), j: 0, k: 0 };
+    let mut scripts: Vec<SceneScript> = (0..9).map(|_| script(&[])).collect();
+    scripts.push(script(lines));
+    Scene {
+        version: 1,
+        scripts,
+        patterns: vec![],
+        pattern_working: 0,
+        notes: vec![],
+        script_mutes: vec![],
+        sampler: None,
+        rng: None,
+        snapshots: Default::default(),
+    }
+}
+
+fn variables(report: &str) -> Vec<&str> {
+    report.lines().skip_while(|l| *l != "== VARIABLES ==").skip(1).take_while(|l| !l.starts_with("== ")).collect()
+}
+
+#[test]
+fn test_strip_comment() {
+    assert_eq!(strip_comment("# whole line"), "");
+    assert_eq!(strip_comment("A 5 # set a"), "A 5 ");
+    assert_eq!(strip_comment("A 5 #tight"), "A 5 ");
+    assert_eq!(strip_comment("A 5"), "A 5");
+    // Sharps in note names and # inside quotes are not comments
+    assert_eq!(strip_comment("SEQ \"C3 F#2 # G3\""), "SEQ \"C3 F#2 # G3\"");
+    assert_eq!(strip_comment("PRINT \"#1\" # done"), "PRINT \"#1\" ");
+}
+
+#[test]
+fn test_validator_ignores_comments() {
+    assert!(validate_from_registry("# kick on every tick").is_ok());
+    assert!(validate_from_registry("TR # kick").is_ok());
+    assert!(validate_from_registry("IF EZ A: TR # only when A is 0").is_ok());
+    assert!(validate_from_registry("FOO 1 # still checked").is_err());
+}
+
+#[test]
+fn test_comments_are_not_run() {
+    let report = run_scene(&scene_with_init(&["# setup", "A 5 # set a", "# B 7", "C ADD A 1; D 2 # two"]));
+    let vars = variables(&report);
+    assert!(vars.contains(&"A 5"), "{:?}", vars);
+    assert!(vars.contains(&"B 0"), "{:?}", vars);
+    assert!(vars.contains(&"C 6"), "{:?}", vars);
+    assert!(vars.contains(&"D 2"), "{:?}", vars);
+}
@@ -59,6 +59,22 @@ macro_rules! test_setup {
     }};
 }
 
+/// A full App detached from the config on disk, with the receiver for the
+/// commands it sends to the metro thread
+pub fn test_app() -> (crate::app::App, Receiver<MetroCommand>) {
+    crate::config::detach_from_disk();
+    let (metro_tx, metro_rx) = mpsc::channel();
+    let app = crate::app::App::new(
+        metro_tx,
+        Arc::new(std::sync::Mutex::new(Default::default())),
+        Theme::default(),
+        crate::types::ColorMode::TrueColor,
+        &crate::config::Config::default(),
+        crate::terminal::TerminalCapabilities { true_color: true, term_program: None },
+    );
+    (app, metro_rx)
+}
+
 pub struct TestContext {
     pub metro_tx: Sender<MetroCommand>,
     pub metro_rx: Receiver<MetroCommand>,
@@ -85,7 +101,11 @@ pub struct TestContext {
     pub load_clr: bool,
     pub vca_mode: bool,
     pub show_conditional_highlight: bool,
+    pub show_syntax_highlight: bool,
     pub scope_settings: crate::types::ScopeSettings,
+    pub onset_settings: crate::types::OnsetSettings,
+    pub loudness: crate::loudness::LoudnessMeter,
+    pub spectrogram: crate::spectrogram::Spectrogram,
     pub show_meters_header: bool,
     pub show_meters_grid: bool,
     pub show_spectrum: bool,
@@ -94,6 +114,7 @@ pub struct TestContext {
     pub show_grid_view: bool,
     pub show_seq_highlight: bool,
     pub grid_mode: u8,
+    pub grid_pages: Vec<crate::ui::grid_pages::GridPage>,
     pub rec_spinner_type: usize,
     pub eq_state: crate::types::EqState,
     pub mixer_data: crate::types::MixerData,
@@ -108,12 +129,14 @@ pub struct TestContext {
     pub out_qry: bool,
     pub out_cfm: bool,
     pub audio_devices: Vec<String>,
+    pub output_channels: u8,
     pub header_scramble: Option<crate::scramble::ScrambleAnimation>,
     pub scramble_enabled: bool,
     pub scramble_mode: u8,
     pub scramble_speed: u8,
     pub scramble_curve: u8,
     pub ascii_meters: bool,
+    pub mouse: bool,
     pub autoload: bool,
     pub terminal_caps: crate::terminal::TerminalCapabilities,
     pub color_mode: crate::types::ColorMode,
@@ -124,9 +147,12 @@ pub struct TestContext {
     pub outputs: Vec<String>,
     pub confirm_quit_unsaved: bool,
     pub confirm_overwrite_scene: bool,
+    pub script_length: usize,
     pub scene_modified: bool,
     pub pending_confirmation: Option<ConfirmAction>,
     pub sampler_state: crate::types::SamplerState,
+    pub snapshots: crate::snapshot::SharedSnapshots,
+    pub rng: crate::rng::SharedRng,
     pub scramble_grid_enabled: bool,
 }
 
@@ -159,13 +185,23 @@ impl Default for TestContext {
             load_clr: false,
             vca_mode: false,
             show_conditional_highlight: true,
+            show_syntax_highlight: true,
             scope_settings: crate::types::ScopeSettings {
                 timespan_ms: 30,
                 color_mode: crate::types::ScopeColorMode::Success,
                 display_mode: 0,
                 unipolar: false,
                 gain: 8192,
+                trigger: crate::types::ScopeTrigger::Free,
+                trig_level: 0,
+                holdoff_ms: 0,
+                xy: false,
+                overlay: crate::types::ScopeOverlay::Off,
+                single_armed: false,
             },
+            onset_settings: crate::types::OnsetSettings::default(),
+            loudness: crate::loudness::LoudnessMeter::default(),
+            spectrogram: crate::spectrogram::Spectrogram::default(),
             show_meters_header: true,
             show_meters_grid: true,
             show_spectrum: true,
@@ -174,6 +210,7 @@ impl Default for TestContext {
             show_grid_view: false,
             show_seq_highlight: true,
             grid_mode: 1,
+            grid_pages: Vec::new(),
             rec_spinner_type: 0,
             eq_state: crate::types::EqState::default(),
             mixer_data: crate::types::MixerData::default(),
@@ -188,12 +225,14 @@ impl Default for TestContext {
             out_qry: false,
             out_cfm: false,
             audio_devices: Vec::new(),
+            output_channels: 2,
             header_scramble: None,
             scramble_enabled: true,
             scramble_mode: 2,
             scramble_speed: 5,
             scramble_curve: 0,
             ascii_meters: false,
+            mouse: false,
             autoload: false,
             terminal_caps: crate::terminal::TerminalCapabilities {
                 true_color: true,
@@ -207,9 +246,12 @@ impl Default for TestContext {
             outputs: Vec::new(),
             confirm_quit_unsaved: true,
             confirm_overwrite_scene: true,
+            script_length: crate::types::DEFAULT_SCRIPT_LINES,
             scene_modified: false,
             pending_confirmation: None,
             sampler_state: crate::types::SamplerState::default(),
+            snapshots: Default::default(),
+            rng: Default::default(),
             scramble_grid_enabled: true,
         }
     }
@@ -251,11 +293,15 @@ impl TestContext {
             show_grid_view: &mut self.show_grid_view,
             show_seq_highlight: &mut self.show_seq_highlight,
             grid_mode: &mut self.grid_mode,
+            grid_pages: &self.grid_pages,
             rec_spinner_type: &mut self.rec_spinner_type,
             eq_state: &mut self.eq_state,
             mixer_data: &mut self.mixer_data,
             fx_mix_state: &mut self.fx_mix_state,
             scope_settings: &mut self.scope_settings,
+            onset_settings: &mut self.onset_settings,
+            loudness: &mut self.loudness,
+            spectrogram: &mut self.spectrogram,
             current_page: &mut self.current_page,
             br_len: &mut self.br_len,
             sync_mode: &mut self.sync_mode,
@@ -266,18 +312,21 @@ impl TestContext {
             load_clr: &mut self.load_clr,
             vca_mode: &mut self.vca_mode,
             show_conditional_highlight: &mut self.show_conditional_highlight,
+            show_syntax_highlight: &mut self.show_syntax_highlight,
             current_scene_name: &mut self.current_scene_name,
             title_mode: &mut self.title_mode,
             title_timer_enabled: &mut self.title_timer_enabled,
             title_timer_interval_secs: &mut self.title_timer_interval_secs,
             title_timer_last_toggle: &mut self.title_timer_last_toggle,
             audio_devices: &self.audio_devices,
+            output_channels: self.output_channels,
             header_scramble: &mut self.header_scramble,
             scramble_enabled: &mut self.scramble_enabled,
             scramble_mode: &mut self.scramble_mode,
             scramble_speed: &mut self.scramble_speed,
             scramble_curve: &mut self.scramble_curve,
             ascii_meters: &mut self.ascii_meters,
+            mouse: &mut self.mouse,
             autoload: &mut self.autoload,
             terminal_caps: &self.terminal_caps,
             color_mode: self.color_mode,
@@ -286,9 +335,12 @@ impl TestContext {
             script_mutes: &mut self.script_mutes,
             confirm_quit_unsaved: &mut self.confirm_quit_unsaved,
             confirm_overwrite_scene: &mut self.confirm_overwrite_scene,
+            script_length: &mut self.script_length,
             scene_modified: &mut self.scene_modified,
             pending_confirmation: &mut self.pending_confirmation,
             sampler_state: &mut self.sampler_state,
+            snapshots: &self.snapshots,
+            rng: &self.rng,
             scramble_grid_enabled: &mut self.scramble_grid_enabled,
         };
 
@@ -305,6 +357,17 @@ impl TestContext {
         self.outputs.clear();
     }
 
+    /// Params sent to the metro since the last call, other metro commands dropped
+    pub fn sent_params(&self) -> Vec<(String, rosc::OscType)> {
+        self.metro_rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                MetroCommand::SendParam(name, value) => Some((name, value)),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn with_debug_level(mut self, level: u8) -> Self {
         self.debug_level = level;
         self
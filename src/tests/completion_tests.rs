@@ -0,0 +1,133 @@
+use super::common::test_app;
+use crate::app::completion::{candidates, signature_hint};
+use crate::app::App;
+use crate::commands::registry::validate::{error_token, validate_from_registry};
+use crate::types::Page;
+use crate::ui::footer::token_chars;
+
+fn type_line(app: &mut App, line: &str) {
+    app.input = line.to_string();
+    app.cursor_position = line.chars().count();
+}
+
+#[test]
+fn test_tab_completes_command_names() {
+    let (mut app, _) = test_app();
+    type_line(&mut app, "M.BP");
+    app.complete_input();
+    assert_eq!(app.input, "M.BPM ");
+    assert_eq!(app.cursor_position, 6);
+
+    // After a colon or semicolon a new command starts
+    type_line(&mut app, "IF X: M.BP");
+    app.complete_input();
+    assert_eq!(app.input, "IF X: M.BPM ");
+
+    // Completing in the middle keeps the rest of the line
+    app.input = "M.BP 120".to_string();
+    app.cursor_position = 4;
+    app.complete_input();
+    assert_eq!(app.input, "M.BPM  120");
+
+    // Nothing to complete
+    type_line(&mut app, "QQQ");
+    app.complete_input();
+    assert_eq!(app.input, "QQQ");
+}
+
+#[test]
+fn test_tab_cycles_through_several_matches() {
+    let (mut app, _) = test_app();
+    let (start, names) = candidates("REC.S", 5);
+    assert_eq!(start, 0);
+    assert_eq!(names, ["REC.SPINNER", "REC.STEMS", "REC.STEMS.STOP", "REC.STOP"]);
+
+    // Matches sharing a longer prefix extend to it first
+    type_line(&mut app, "REC.STE");
+    app.complete_input();
+    assert_eq!(app.input, "REC.STEMS");
+    assert!(app.completion.is_none());
+
+    type_line(&mut app, "REC.S");
+    app.complete_input();
+    assert_eq!(app.input, "REC.SPINNER");
+    app.complete_input();
+    assert_eq!(app.input, "REC.STEMS");
+    assert!(app.input_hint().unwrap().starts_with("2/4 REC.STEMS "));
+    app.complete_input();
+    app.complete_input();
+    app.complete_input();
+    assert_eq!(app.input, "REC.SPINNER");
+
+    // Typing ends the cycle
+    app.insert_char('x');
+    assert!(!app.input_hint().unwrap_or_default().contains("/4"));
+}
+
+#[test]
+fn test_tab_completes_theme_and_kit_paths() {
+    let (start, themes) = candidates("THEME DRA", 9);
+    assert_eq!(start, 6);
+    assert!(themes.iter().all(|name| name.starts_with("DRA")), "{:?}", themes);
+    assert!(themes.contains(&"DRACULA".to_string()));
+    assert!(candidates("THEME DARK X", 12).1.is_empty());
+
+    let dir = std::env::temp_dir().join(format!("monokit-complete-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("kicks")).unwrap();
+    std::fs::write(dir.join("Kick.wav"), b"").unwrap();
+    std::fs::write(dir.join("kick.txt"), b"").unwrap();
+    let base = dir.to_string_lossy().to_string();
+
+    let line = format!("KIT {}/KI", base);
+    let (_, paths) = candidates(&line, line.chars().count());
+    assert_eq!(paths, [format!("{}/Kick.wav", base), format!("{}/kicks/", base)]);
+
+    // A folder completes without the trailing space
+    let (mut app, _) = test_app();
+    type_line(&mut app, &format!("KIT {}/KICKS", base));
+    app.complete_input();
+    assert_eq!(app.input, format!("KIT {}/kicks/", base));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_signature_hint_for_command_at_cursor() {
+    assert_eq!(signature_hint("PF 440", 6).unwrap(), "PF (POSC.FREQ) 1+ ARGS 20-20000 - PRIMARY OSC FREQUENCY");
+    assert_eq!(signature_hint("POSC.FREQ", 3).unwrap(), "POSC.FREQ (PF) 1+ ARGS 20-20000 - PRIMARY OSC FREQUENCY");
+    assert_eq!(signature_hint("TR; M.BPM 120", 1).unwrap(), "TR NO ARGS - TRIGGER VOICE");
+    assert_eq!(signature_hint("TR; M.BPM 120", 13).unwrap(), "M.BPM 0-1 ARGS - METRO BPM");
+    assert_eq!(signature_hint("QQQ 1", 5), None);
+    assert_eq!(signature_hint("", 0), None);
+}
+
+#[test]
+fn test_validator_points_at_token() {
+    let token = |line: &str| validate_from_registry(line).err().and_then(|e| error_token(&e));
+    assert_eq!(token("QQQ 1"), Some(0));
+    assert_eq!(token("TR 1"), Some(1));
+    assert_eq!(token("M.BPM 120 4"), Some(2));
+    assert_eq!(token("LOAD"), Some(0));
+    assert_eq!(token("P 99"), Some(1));
+    assert_eq!(token("PN 1 99"), Some(2));
+    assert_eq!(token("TOG 1 1"), Some(2));
+    assert_eq!(token("SEQ \"C3"), None);
+    // Messages read the same as before
+    assert_eq!(validate_from_registry("P 99").unwrap_err().to_string(), "PATTERN INDEX MUST BE 0-63");
+
+    assert_eq!(token_chars("  TR   1", 1), Some(7..8));
+    assert_eq!(token_chars("TR", 1), None);
+}
+
+#[test]
+fn test_save_line_keeps_error_token() {
+    let (mut app, _) = test_app();
+    app.go_to_page(Page::Script1);
+    type_line(&mut app, "M.BPM 120 4");
+    app.save_line();
+    assert_eq!(app.script_error.as_deref(), Some("M.BPM TAKES 0-1 ARGUMENTS"));
+    assert_eq!(app.script_error_token, Some(2));
+
+    type_line(&mut app, "M.BPM 120");
+    app.save_line();
+    assert_eq!(app.script_error_token, None);
+}
@@ -0,0 +1,76 @@
+use crate::golden::{diff, run_golden_tests, run_scene};
+use crate::scene::{Scene, SceneScript};
+
+/// Scene with script M and script I set, everything else empty
+fn scene(metro: &[&str], init: &[&str]) -> Scene {
+    let script = |lines: &[&str]| SceneScript {
+        lines: (0..8).map(|i| lines.get(i).unwrap_or(&"").to_string()).collect(),
+        j: 0,
+        k: 0,
+    };
+    let mut scripts: Vec<SceneScript> = (0..8).map(|_| script(&[])).collect();
+    scripts.push(script(metro));
+    scripts.push(script(init));
+    Scene {
+        version: 1,
+        scripts,
+        patterns: vec![],
+        pattern_working: 0,
+        notes: vec![],
+        script_mutes: vec![],
+        sampler: None,
+        rng: None,
+        snapshots: Default::default(),
+    }
+}
+
+fn section<'a>(report: &'a str, name: &str) -> Vec<&'a str> {
+    report
+        .lines()
+        .skip_while(|l| *l != format!("== {} ==", name))
+        .skip(1)
+        .take_while(|l| !l.starts_with("== "))
+        .collect()
+}
+
+#[test]
+fn test_virtual_clock_runs_ticks_and_delays() {
+    let report = run_scene(&scene(&["A ADD A 1"], &["M 100", "M.ACT 1", "DEL 250: PRINT A", "DEL.X 2 1000: TR"]));
+    let output = section(&report, "OUTPUT");
+
+    // Ticks at 0, 100 and 200 have run when the delay falls due
+    assert!(output.contains(&"   250 3"), "{:?}", output);
+    // One tick per 100ms from 0 to 3000 inclusive
+    assert!(section(&report, "VARIABLES").contains(&"A 31"));
+
+    let mut triggers: Vec<&str> = section(&report, "OSC")
+        .into_iter()
+        .filter(|l| l.contains("t_gate"))
+        .map(|l| l.split_whitespace().next().unwrap())
+        .collect();
+    triggers.dedup();
+    assert_eq!(triggers, vec!["0", "1000"]);
+}
+
+#[test]
+fn test_scene_reports_are_deterministic() {
+    let scene = scene(&["X RND 1000; PRINT X", "P.PUSH X"], &["M 250", "M.ACT 1"]);
+    assert_eq!(run_scene(&scene), run_scene(&scene));
+}
+
+#[test]
+fn test_diff_shows_changed_lines_with_section() {
+    let expected = "== OUTPUT ==\n     0 A\n     0 B\n== OSC ==\n";
+    let actual = "== OUTPUT ==\n     0 A\n     0 C\n== OSC ==\n";
+    assert_eq!(diff(expected, actual), vec!["== OUTPUT == -     0 B", "== OUTPUT == +     0 C"]);
+    assert!(diff(expected, expected).is_empty());
+}
+
+/// Every scene in repl_tests/ against its .expected file. After an intended
+/// change in behaviour, update them with `monokit --test repl_tests --bless`.
+#[cfg(feature = "scsynth-direct")]
+#[test]
+fn test_repl_scenes_match_expected() {
+    let dir = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/repl_tests"));
+    assert!(run_golden_tests(dir, false).unwrap(), "scene output changed, see the diffs above");
+}
@@ -0,0 +1,100 @@
+use super::common::TestContext;
+use crate::config::{GridPageConfig, GridParamConfig};
+use crate::snapshot::{ParamValue, SnapshotBank};
+use crate::ui::grid_pages::{pages_from_config, GridFormat};
+use rosc::OscType;
+
+fn param(cmd: &str, label: Option<&str>, format: &str) -> GridParamConfig {
+    GridParamConfig { cmd: cmd.to_string(), label: label.map(str::to_string), format: format.to_string() }
+}
+
+fn page(name: &str, params: Vec<GridParamConfig>) -> GridPageConfig {
+    GridPageConfig { name: name.to_string(), params }
+}
+
+#[test]
+fn test_pages_resolve_commands_in_order() {
+    let (pages, errors) = pages_from_config(&[
+        page("bass", vec![
+            param("posc.freq", Some("freq"), "number"),
+            param("FC", None, "bar"),
+            param("TR", None, "bar"),
+            param("DW", Some("DELAYWET"), "icon"),
+            param("FQ", None, "dial"),
+        ]),
+        page("empty", vec![param("M", None, "bar")]),
+    ]);
+
+    assert_eq!(
+        errors,
+        vec![
+            "GRID PAGE BASS: TR SETS NO PARAMETER",
+            "GRID PAGE BASS: UNKNOWN FORMAT \"DIAL\" (BAR, NUMBER, ICON)",
+            "GRID PAGE EMPTY: M SETS NO PARAMETER",
+            "GRID PAGE EMPTY: NO PARAMS",
+        ]
+    );
+    assert_eq!(pages.len(), 1);
+    let cells = &pages[0].cells;
+    assert_eq!(pages[0].name, "BASS");
+    assert_eq!(cells.iter().map(|c| c.label.as_str()).collect::<Vec<_>>(), ["FREQ", "FC", "DELA"]);
+    assert_eq!(cells.iter().map(|c| c.param).collect::<Vec<_>>(), ["pf", "fc", "dw"]);
+    assert_eq!(cells[0].format, GridFormat::Number);
+    assert_eq!(cells[2].format, GridFormat::Icon);
+    // Long names light the same PARAM ACTIVITY slot as the short ones
+    assert_eq!(cells[0].activity, Some(0));
+    assert_eq!(cells[2].activity, Some(23));
+}
+
+#[test]
+fn test_pages_hold_twelve_params() {
+    let params = (0..14).map(|_| param("PF", None, "bar")).collect();
+    let (pages, errors) = pages_from_config(&[page("full", params)]);
+    assert_eq!(pages[0].cells.len(), 12);
+    assert_eq!(errors, vec!["GRID PAGE FULL: ONLY 12 PARAMS FIT"]);
+}
+
+#[test]
+fn test_cell_values_from_the_live_parameters() {
+    let (pages, _) = pages_from_config(&[page("mix", vec![
+        param("PF", None, "bar"),
+        param("DW", None, "bar"),
+        param("PF", None, "number"),
+        param("DW", None, "number"),
+        param("FC", None, "icon"),
+    ])]);
+    let cells = &pages[0].cells;
+
+    let mut bank = SnapshotBank::default();
+    bank.record("pf", &OscType::Float(632.5));
+    bank.record("dw", &OscType::Int(8192));
+    let values = pages[0].values(bank.live());
+    assert_eq!(values[..2], [Some(ParamValue::Float(632.5)), Some(ParamValue::Int(8192))]);
+
+    // 632 Hz is halfway up 20-20000 Hz on a log scale
+    assert_eq!(cells[0].value_text(values[0], false), "█████····");
+    assert_eq!(cells[1].value_text(values[1], true), "#####....");
+    assert_eq!(cells[2].value_text(values[2], false), "      632");
+    assert_eq!(cells[3].value_text(values[3], false), "     8192");
+    // Not sent yet, so still at its default
+    assert_eq!(values[4], Some(ParamValue::Float(10000.0)));
+    assert_eq!(cells[4].value_text(None, false), "     --");
+    assert_eq!(cells[0].value_text(None, false), "·········");
+}
+
+#[test]
+fn test_grid_mode_selects_custom_pages() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.grid_pages = pages_from_config(&[page("bass", vec![param("PF", None, "bar")])]).0;
+    ctx.run("GRID.MODE 7").unwrap();
+    assert_eq!(ctx.grid_mode, 7);
+    ctx.run("GRID.MODE 6").unwrap();
+    ctx.run("GRID.MODE 8").unwrap();
+    ctx.run("GRID.MODE").unwrap();
+
+    assert_eq!(ctx.grid_mode, 6);
+    assert_eq!(
+        ctx.outputs,
+        vec!["GRID.MODE: 7 (BASS)", "GRID.MODE: 6 (LOUDNESS)", "ERROR: GRID.MODE TAKES 0-7", "GRID.MODE: 6"]
+    );
+}
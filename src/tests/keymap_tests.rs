@@ -0,0 +1,72 @@
+use crate::ui::keymap::{KeyAction, KeyChord, KeyMap};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+fn keys(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn test_parse_chords() {
+    assert_eq!(
+        KeyChord::parse("ctrl+shift+1").unwrap(),
+        KeyChord::new(KeyCode::Char('1'), KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+    );
+    assert_eq!(KeyChord::parse("F11").unwrap(), KeyChord::new(KeyCode::F(11), KeyModifiers::NONE));
+    assert_eq!(KeyChord::parse("Alt+Space").unwrap(), KeyChord::new(KeyCode::Char(' '), KeyModifiers::ALT));
+    assert_eq!(KeyChord::parse("ctrl++").unwrap(), KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+    assert_eq!(KeyChord::parse("ctrl+]").unwrap(), KeyChord::new(KeyCode::Char(']'), KeyModifiers::CONTROL));
+    assert!(KeyChord::parse("hyper+a").is_err());
+    assert!(KeyChord::parse("ctrl+nope").is_err());
+    assert!(KeyChord::parse("f99").is_err());
+}
+
+#[test]
+fn test_uppercase_letters_match_shift() {
+    let map = KeyMap::from_config(&keys(&[("shift+t", "TR")])).0;
+    let event = KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT);
+    assert_eq!(map.lookup(&event), Some(&KeyAction::Command("TR".to_string())));
+    let event = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+    assert_eq!(map.lookup(&event), None);
+}
+
+#[test]
+fn test_actions_and_commands() {
+    let (map, errors) = KeyMap::from_config(&keys(&[
+        ("f11", "none"),
+        ("alt+i", "PAGE I"),
+        ("ctrl+u", "undo"),
+        ("ctrl+n", "page.next"),
+        ("ctrl+bogus", "TR"),
+    ]));
+    assert_eq!(errors, vec!["KEYS: UNKNOWN KEY \"BOGUS\""]);
+    assert_eq!(map.len(), 4);
+
+    let lookup = |code, modifiers| map.lookup(&KeyEvent::new(code, modifiers)).cloned();
+    assert_eq!(lookup(KeyCode::F(11), KeyModifiers::NONE), Some(KeyAction::Ignore));
+    assert_eq!(lookup(KeyCode::Char('i'), KeyModifiers::ALT), Some(KeyAction::Command("PAGE I".to_string())));
+    assert_eq!(lookup(KeyCode::Char('u'), KeyModifiers::CONTROL), Some(KeyAction::Undo));
+    assert_eq!(lookup(KeyCode::Char('n'), KeyModifiers::CONTROL), Some(KeyAction::NextPage));
+    assert_eq!(lookup(KeyCode::Char('n'), KeyModifiers::NONE), None);
+}
+
+#[test]
+fn test_describe_lists_bindings() {
+    let (map, _) = KeyMap::from_config(&keys(&[("ctrl+shift+3", "mute 3"), ("f5", "SCRIPT 5")]));
+    assert_eq!(
+        map.describe(),
+        vec!["  CTRL+SHIFT+3   MUTE 3", "  F5             SCRIPT 5"]
+    );
+}
+
+#[test]
+fn test_config_keys_section() {
+    let config: crate::config::Config = toml::from_str(
+        "[keys]\n\"ctrl+1\" = \"MUTE 1\"\n\"f12\" = \"PAGE P\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.keys.get("ctrl+1").map(String::as_str), Some("MUTE 1"));
+
+    let config: crate::config::Config = toml::from_str("").unwrap();
+    assert!(config.keys.is_empty());
+}
@@ -0,0 +1,210 @@
+use crate::sampler::library::{
+    estimate_bpm, parse_tags, preview_messages, scan_library, LibraryBrowser, LibraryIndex, SampleInfo,
+};
+use crate::sampler::slice::decode_audio;
+use crate::types::MetroCommand;
+use rosc::{OscPacket, OscType};
+use std::path::PathBuf;
+
+/// Build a throwaway library: drums/{kick,snare}.wav, loops/break.wav, empty/, notes.txt
+fn make_library(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("monokit_library_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("drums")).unwrap();
+    std::fs::create_dir_all(root.join("loops")).unwrap();
+    std::fs::create_dir_all(root.join("empty")).unwrap();
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 48000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    for file in ["drums/kick.wav", "drums/snare.wav", "loops/break.wav"] {
+        let mut writer = hound::WavWriter::create(root.join(file), spec).unwrap();
+        for _ in 0..4800 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+    std::fs::write(root.join("notes.txt"), "not audio").unwrap();
+    root
+}
+
+fn rels(browser: &LibraryBrowser) -> Vec<String> {
+    browser.visible().iter().map(|&i| browser.nodes[i].rel.clone()).collect()
+}
+
+#[test]
+fn test_scan_library_builds_tree() {
+    let root = make_library("scan");
+    let nodes = scan_library(&root);
+    let rels: Vec<_> = nodes.iter().map(|n| (n.rel.as_str(), n.depth, n.is_dir)).collect();
+    assert_eq!(
+        rels,
+        vec![
+            ("drums", 0, true),
+            ("drums/kick.wav", 1, false),
+            ("drums/snare.wav", 1, false),
+            ("loops", 0, true),
+            ("loops/break.wav", 1, false),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_browser_expand_and_collapse() {
+    let root = make_library("expand");
+    let mut browser = LibraryBrowser::default();
+    browser.scan(&root);
+    assert_eq!(rels(&browser), vec!["drums", "loops"]);
+
+    browser.expand();
+    assert_eq!(rels(&browser), vec!["drums", "drums/kick.wav", "drums/snare.wav", "loops"]);
+
+    browser.move_selection(2);
+    assert_eq!(browser.selected_node().unwrap().rel, "drums/snare.wav");
+    browser.collapse();
+    assert_eq!(browser.selected_node().unwrap().rel, "drums");
+    browser.collapse();
+    assert_eq!(rels(&browser), vec!["drums", "loops"]);
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_browser_search_by_name_and_tag() {
+    let root = make_library("search");
+    let mut browser = LibraryBrowser::default();
+    browser.scan(&root);
+
+    browser.set_query("KICK".to_string());
+    assert_eq!(rels(&browser), vec!["drums/kick.wav"]);
+
+    browser.set_tags("loops/break.wav", "Amen, #jungle").unwrap();
+    browser.set_query("#jun".to_string());
+    assert_eq!(rels(&browser), vec!["loops/break.wav"]);
+
+    browser.set_query("drums #jungle".to_string());
+    assert!(rels(&browser).is_empty());
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_tags_persist_in_index_file() {
+    let root = make_library("tags");
+    let mut browser = LibraryBrowser::default();
+    browser.scan(&root);
+    browser.set_tags("drums/kick.wav", "punchy kick punchy").unwrap();
+
+    let index = LibraryIndex::load(&root);
+    assert_eq!(index.tags("drums/kick.wav"), ["punchy", "kick"]);
+
+    browser.set_tags("drums/kick.wav", "").unwrap();
+    assert!(LibraryIndex::load(&root).entries.is_empty());
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_browser_reads_file_info() {
+    let root = make_library("info");
+    let mut browser = LibraryBrowser::default();
+    browser.scan(&root);
+    let info = browser.info("drums/kick.wav").unwrap();
+    assert_eq!((info.frames, info.channels, info.sample_rate), (4800, 1, 48000));
+    assert_eq!(info.duration_ms(), 100);
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_parse_tags_normalizes() {
+    assert_eq!(parse_tags("#Kick, snare  kick"), vec!["kick", "snare"]);
+    assert!(parse_tags("  ").is_empty());
+}
+
+#[test]
+fn test_estimate_bpm_from_onsets() {
+    // 120 BPM quarter notes at 48kHz, 2 bars long
+    let onsets: Vec<usize> = (0..8).map(|i| i * 24000).collect();
+    assert_eq!(estimate_bpm(&onsets, 48000, 192000), Some(120.0));
+    // Eighth notes fold back into range
+    let onsets: Vec<usize> = (0..16).map(|i| i * 12000).collect();
+    assert_eq!(estimate_bpm(&onsets, 48000, 192000), Some(120.0));
+    assert_eq!(estimate_bpm(&[0, 100], 48000, 1000), None);
+}
+
+#[test]
+fn test_preview_messages_start_synth_on_load() {
+    let messages = preview_messages("/tmp/kick.wav", 1);
+    assert_eq!(messages.len(), 2);
+
+    let load = &messages[1];
+    assert_eq!(load.addr, "/b_allocRead");
+    let Some(OscType::Blob(completion)) = load.args.last() else {
+        panic!("expected completion message");
+    };
+    let (_, decoded) = rosc::decoder::decode_udp(completion).unwrap();
+    let OscPacket::Message(start) = decoded else {
+        panic!("expected message");
+    };
+    assert_eq!(start.addr, "/s_new");
+    assert_eq!(start.args[0], OscType::String("monokit_preview_mono".to_string()));
+}
+
+#[test]
+fn test_preview_goes_through_metro_thread() {
+    let root = make_library("preview");
+    let (mut app, metro_rx) = super::common::test_app();
+    app.library.scan(&root);
+    app.library.expand();
+    app.library.move_selection(1);
+    app.library_preview();
+    app.library_stop_preview();
+
+    let sent: Vec<MetroCommand> = metro_rx.try_iter().collect();
+    assert!(
+        matches!(&sent[..], [MetroCommand::StartPreview(path, 1), MetroCommand::StopPreview] if path.ends_with("drums/kick.wav")),
+        "{:?}",
+        sent
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+/// A 16-bit stereo AIFF at 44.1kHz holding the given frames
+fn write_aiff(path: &std::path::Path, frames: &[(i16, i16)]) {
+    let data: Vec<u8> = frames.iter().flat_map(|&(l, r)| [l.to_be_bytes(), r.to_be_bytes()].concat()).collect();
+    let mut comm = Vec::new();
+    comm.extend_from_slice(&2u16.to_be_bytes());
+    comm.extend_from_slice(&(frames.len() as u32).to_be_bytes());
+    comm.extend_from_slice(&16u16.to_be_bytes());
+    // 44100 as an 80-bit extended float
+    comm.extend_from_slice(&[0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]);
+
+    let mut body = b"AIFF".to_vec();
+    body.extend_from_slice(b"COMM");
+    body.extend_from_slice(&(comm.len() as u32).to_be_bytes());
+    body.extend_from_slice(&comm);
+    body.extend_from_slice(b"SSND");
+    body.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+    body.extend_from_slice(&[0; 8]);
+    body.extend_from_slice(&data);
+
+    let mut file = b"FORM".to_vec();
+    file.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    file.extend_from_slice(&body);
+    std::fs::write(path, file).unwrap();
+}
+
+#[test]
+fn test_aiff_files_have_info_and_decode() {
+    let path = std::env::temp_dir().join(format!("monokit-library-{}.aif", std::process::id()));
+    write_aiff(&path, &[(16384, -16384); 441]);
+
+    let info = SampleInfo::read(&path).unwrap();
+    assert_eq!((info.channels, info.sample_rate, info.duration_ms()), (2, 44100, 10));
+    let audio = decode_audio(&path).unwrap();
+    assert_eq!((audio.channels, audio.sample_rate, audio.samples.len()), (2, 44100, 882));
+    assert!((audio.samples[0] - 0.5).abs() < 1e-4 && (audio.samples[1] + 0.5).abs() < 1e-4);
+
+    std::fs::remove_file(&path).ok();
+}
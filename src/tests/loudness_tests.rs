@@ -0,0 +1,144 @@
+use super::common::TestContext;
+use crate::loudness::{db_to_amp, LoudnessMeter};
+use crate::types::{LoudnessBlock, MetroCommand};
+use rosc::OscType;
+
+/// A block at `lufs`, peaking at `peak_db`
+fn block(lufs: f32, peak_db: f32) -> LoudnessBlock {
+    LoudnessBlock { power: 10f32.powf((lufs + 0.691) / 10.0), true_peak: db_to_amp(peak_db) }
+}
+
+fn feed(meter: &mut LoudnessMeter, lufs: f32, blocks: usize) {
+    for _ in 0..blocks {
+        meter.push(block(lufs, lufs + 10.0));
+    }
+}
+
+fn near(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 0.2
+}
+
+#[test]
+fn test_steady_signal_reads_the_same_everywhere() {
+    let mut meter = LoudnessMeter::default();
+    feed(&mut meter, -20.0, 3);
+    // Not enough blocks for a momentary reading yet
+    assert_eq!(meter.momentary, f32::NEG_INFINITY);
+
+    feed(&mut meter, -20.0, 27);
+    assert!(near(meter.momentary, -20.0), "{}", meter.momentary);
+    assert!(near(meter.short_term, -20.0), "{}", meter.short_term);
+    assert!(near(meter.integrated, -20.0), "{}", meter.integrated);
+    assert!(near(meter.true_peak, -10.0), "{}", meter.true_peak);
+}
+
+#[test]
+fn test_integrated_is_gated() {
+    let mut meter = LoudnessMeter::default();
+    feed(&mut meter, -20.0, 100);
+    // Quiet passages more than 10 LU down and silence don't pull it down
+    feed(&mut meter, -40.0, 100);
+    for _ in 0..100 {
+        meter.push(LoudnessBlock::default());
+    }
+    assert!(near(meter.integrated, -20.0), "{}", meter.integrated);
+    assert_eq!(meter.short_term, f32::NEG_INFINITY);
+
+    // Within 10 LU it counts
+    let mut meter = LoudnessMeter::default();
+    feed(&mut meter, -20.0, 100);
+    feed(&mut meter, -26.0, 100);
+    assert!(meter.integrated < -22.0 && meter.integrated > -24.0, "{}", meter.integrated);
+}
+
+#[test]
+fn test_reset_keeps_the_target() {
+    let mut meter = LoudnessMeter::default();
+    meter.set_target(Some(-14.0));
+    meter.push(block(-20.0, -0.5));
+    meter.push(block(-20.0, -6.0));
+    assert!(near(meter.max_true_peak, -0.5));
+    assert!(near(meter.true_peak, -6.0));
+
+    meter.reset();
+    assert_eq!(meter.max_true_peak, f32::NEG_INFINITY);
+    assert_eq!(meter.integrated, f32::NEG_INFINITY);
+    assert_eq!(meter.target, Some(-14.0));
+}
+
+#[test]
+fn test_target_rides_the_gain_to_the_target() {
+    let mut meter = LoudnessMeter::default();
+    meter.set_target(Some(-14.0));
+
+    // The mix plays at -20 LUFS; what the meter hears includes the gain sent
+    let mut gain_db = 0.0;
+    let mut sent = 0;
+    for _ in 0..1200 {
+        if let Some(gain) = meter.push(block(-20.0 + gain_db, -10.0)) {
+            assert!((gain - gain_db).abs() >= 0.1);
+            gain_db = gain;
+            sent += 1;
+        }
+    }
+    assert!((gain_db - 6.0).abs() < 0.5, "{}", gain_db);
+    assert!(near(meter.short_term, -14.0 + (gain_db - 6.0)), "{}", meter.short_term);
+    // Sent in steps, not on every block
+    assert!(sent < 200, "{}", sent);
+
+    // Silence holds the gain rather than running it up
+    for _ in 0..100 {
+        meter.push(LoudnessBlock::default());
+    }
+    assert!((meter.gain_db - gain_db).abs() < 0.2, "{} {}", meter.gain_db, gain_db);
+
+    meter.set_target(None);
+    assert_eq!(meter.gain_db, 0.0);
+}
+
+#[test]
+fn test_lufs_target_command_drives_the_limiter() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("LUFS.TARGET -14").unwrap();
+    ctx.run("LUFS.TARGET").unwrap();
+    ctx.run("LUFS.TARGET -3").unwrap();
+    assert_eq!(ctx.loudness.target, Some(-14.0));
+
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [
+        MetroCommand::SendParam(limit, OscType::Int(1)),
+        MetroCommand::SendParam(ceil, OscType::Float(ceiling)),
+        MetroCommand::SendParam(gain, OscType::Float(unity)),
+    ] if limit == "limit" && ceil == "limit_ceil" && (ceiling - 0.891).abs() < 0.001 && gain == "out_gain" && *unity == 1.0), "{:?}", sent);
+    assert_eq!(
+        ctx.outputs,
+        vec!["LUFS.TARGET: -14 (CEILING -1 DBTP)", "LUFS.TARGET: -14 (CEILING -1 DBTP)", "ERROR: LUFS.TARGET -36 TO -6, OR 0 FOR OFF"]
+    );
+
+    // Off goes back to what LIMIT says
+    ctx.limiter_enabled = false;
+    ctx.run("LUFS.TARGET 0").unwrap();
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[0], MetroCommand::SendParam(limit, OscType::Int(0)) if limit == "limit"), "{:?}", sent);
+    assert_eq!(ctx.loudness.target, None);
+    assert_eq!(ctx.outputs.last().unwrap(), "LUFS.TARGET: OFF");
+}
+
+#[test]
+fn test_lufs_query() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("LUFS").unwrap();
+    feed(&mut ctx.loudness, -16.0, 30);
+    ctx.run("LUFS").unwrap();
+    ctx.run("LUFS.RST").unwrap();
+
+    assert_eq!(
+        ctx.outputs,
+        vec![
+            "LUFS M -INF S -INF I -INF TP -INF (MAX -INF)",
+            "LUFS M -16.0 S -16.0 I -16.0 TP -6.0 (MAX -6.0)",
+            "LUFS: RESET",
+        ]
+    );
+    assert_eq!(ctx.loudness.integrated, f32::NEG_INFINITY);
+}
@@ -0,0 +1,198 @@
+//! Fake scsynth for integration tests.
+//!
+//! Listens on a free local UDP port, records every packet with its arrival time and
+//! answers `/status`, `/notify`, `/b_allocRead` and `/sync` the way the real server
+//! does. Point a metro thread at it with `metro_thread_to(.., Some(mock.addr()))`.
+
+use rosc::{decoder, encoder, OscMessage, OscPacket, OscTime, OscType};
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Seconds between the NTP epoch (1900) used by OSC timetags and the Unix epoch
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// What `/status.reply` reports: the four monokit voices, the main out and the sampler
+const STATUS_SYNTHS: i32 = 6;
+
+#[derive(Debug, Clone)]
+pub struct Received {
+    pub wall: SystemTime,
+    pub packet: OscPacket,
+}
+
+/// One message, with the timetag of the bundle it arrived in (None if sent bare)
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    pub addr: String,
+    pub args: Vec<OscType>,
+    pub timetag: Option<OscTime>,
+    pub wall: SystemTime,
+}
+
+impl RecordedMessage {
+    /// How far ahead of its arrival the timetag schedules the message, in ms
+    pub fn lead_ms(&self) -> Option<f64> {
+        let scheduled = timetag_to_system(self.timetag?);
+        Some(match scheduled.duration_since(self.wall) {
+            Ok(ahead) => ahead.as_secs_f64() * 1000.0,
+            Err(behind) => -behind.duration().as_secs_f64() * 1000.0,
+        })
+    }
+
+    /// Node and value of an `/n_set <node> <param> <value>` message
+    pub fn n_set(&self, param: &str) -> Option<(i32, OscType)> {
+        match (self.addr.as_str(), self.args.as_slice()) {
+            ("/n_set", [OscType::Int(node), OscType::String(name), value]) if name == param => Some((*node, value.clone())),
+            _ => None,
+        }
+    }
+}
+
+pub fn timetag_to_system(timetag: OscTime) -> SystemTime {
+    let secs = (timetag.seconds as u64).saturating_sub(NTP_UNIX_OFFSET);
+    let nanos = (timetag.fractional as f64 / (u32::MAX as f64 + 1.0) * 1e9) as u64;
+    UNIX_EPOCH + Duration::from_secs(secs) + Duration::from_nanos(nanos)
+}
+
+pub struct MockScsynth {
+    addr: SocketAddr,
+    received: Arc<Mutex<Vec<Received>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockScsynth {
+    pub fn start() -> Self {
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("mock scsynth bind");
+        socket.set_read_timeout(Some(Duration::from_millis(10))).unwrap();
+        let addr = socket.local_addr().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let received = received.clone();
+            let stop = stop.clone();
+            thread::spawn(move || serve(socket, received, stop))
+        };
+        Self { addr, received, stop, handle: Some(handle) }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn packets(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Every message received so far, bundles flattened in order
+    pub fn messages(&self) -> Vec<RecordedMessage> {
+        let mut messages = Vec::new();
+        for received in self.received.lock().unwrap().iter() {
+            flatten(&received.packet, None, received, &mut messages);
+        }
+        messages
+    }
+
+    /// Poll until `done` holds for the messages received so far, or `timeout` passes
+    pub fn wait_for(&self, timeout: Duration, done: impl Fn(&[RecordedMessage]) -> bool) -> Vec<RecordedMessage> {
+        let start = Instant::now();
+        loop {
+            let messages = self.messages();
+            if done(&messages) || start.elapsed() >= timeout {
+                return messages;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
+
+impl Drop for MockScsynth {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve(socket: UdpSocket, received: Arc<Mutex<Vec<Received>>>, stop: Arc<AtomicBool>) {
+    let mut buf = [0u8; 65536];
+    while !stop.load(Ordering::Relaxed) {
+        let Ok((size, from)) = socket.recv_from(&mut buf) else {
+            continue;
+        };
+        let wall = SystemTime::now();
+        let Ok((_, packet)) = decoder::decode_udp(&buf[..size]) else {
+            continue;
+        };
+        // Recorded before replying, so a client that saw the reply sees the packet
+        let replies = replies(&packet);
+        received.lock().unwrap().push(Received { wall, packet });
+        for reply in replies {
+            if let Ok(bytes) = encoder::encode(&OscPacket::Message(reply)) {
+                let _ = socket.send_to(&bytes, from);
+            }
+        }
+    }
+}
+
+fn flatten(packet: &OscPacket, timetag: Option<OscTime>, received: &Received, out: &mut Vec<RecordedMessage>) {
+    match packet {
+        OscPacket::Message(msg) => out.push(RecordedMessage {
+            addr: msg.addr.clone(),
+            args: msg.args.clone(),
+            timetag,
+            wall: received.wall,
+        }),
+        OscPacket::Bundle(bundle) => {
+            for inner in &bundle.content {
+                flatten(inner, Some(bundle.timetag), received, out);
+            }
+        }
+    }
+}
+
+/// Answers scsynth sends back; commands in a bundle are answered straight away
+fn replies(packet: &OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(msg) => reply(msg).into_iter().collect(),
+        OscPacket::Bundle(bundle) => bundle.content.iter().flat_map(replies).collect(),
+    }
+}
+
+fn reply(msg: &OscMessage) -> Option<OscMessage> {
+    let done = |args: Vec<OscType>| {
+        Some(OscMessage {
+            addr: "/done".to_string(),
+            args: [vec![OscType::String(msg.addr.clone())], args].concat(),
+        })
+    };
+    match msg.addr.as_str() {
+        "/status" => Some(OscMessage {
+            addr: "/status.reply".to_string(),
+            args: vec![
+                OscType::Int(1),
+                OscType::Int(0),
+                OscType::Int(STATUS_SYNTHS),
+                OscType::Int(2),
+                OscType::Int(0),
+                OscType::Float(0.0),
+                OscType::Float(0.0),
+                OscType::Double(48000.0),
+                OscType::Double(48000.0),
+            ],
+        }),
+        // Client ID 0
+        "/notify" => done(vec![OscType::Int(0)]),
+        "/b_allocRead" => done(msg.args.first().cloned().into_iter().collect()),
+        "/sync" => Some(OscMessage {
+            addr: "/synced".to_string(),
+            args: msg.args.first().cloned().into_iter().collect(),
+        }),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 mod common;
+mod mock_scsynth;
 mod rnd_tests;
 mod toss_eith_tests;
 mod tog_tests;
@@ -14,6 +15,7 @@ mod map_tests;
 mod comparison_tests;
 mod counter_tests;
 mod scene_tests;
+mod seed_tests;
 mod preset_tests;
 mod debug_level;
 mod header_tests;
@@ -27,3 +29,28 @@ mod scale_tests;
 mod delay_tests;
 mod seq_tests;
 mod rhythm_tests;
+mod keymap_tests;
+mod library_tests;
+mod stretch_tests;
+mod slice_tests;
+mod waveform_tests;
+mod snapshot_tests;
+mod patch_tests;
+mod mutation_tests;
+mod osc_output_tests;
+mod golden_tests;
+mod clock_tests;
+mod teletype_tests;
+mod comment_tests;
+mod script_length_tests;
+mod audio_react_tests;
+mod output_routing_tests;
+mod loudness_tests;
+mod scope_tests;
+mod spectrogram_tests;
+#[cfg(feature = "scsynth-direct")]
+mod stems_tests;
+mod grid_pages_tests;
+mod mouse_tests;
+mod completion_tests;
+mod syntax_highlight_tests;
@@ -0,0 +1,146 @@
+use super::common::{test_app, TestContext};
+use crate::app::App;
+use crate::types::{MetroCommand, Page};
+use crate::ui::grid_pages::BarTarget;
+use crate::ui::pages::live::grid_bar_at;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use rosc::OscType;
+
+const SCREEN: Rect = Rect { x: 0, y: 0, width: 80, height: 24 };
+
+fn mouse(app: &mut App, kind: MouseEventKind, column: u16, row: u16) {
+    app.handle_mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }, SCREEN);
+}
+
+#[test]
+fn test_click_header_tab_goes_to_page() {
+    let (mut app, _rx) = test_app();
+    app.header_level = 4;
+    app.go_to_page(Page::Live);
+
+    // " [LIVE] 1 2 ...": the tabs start after the border and a space
+    let offset = crate::types::NAVIGABLE_PAGES
+        .iter()
+        .take_while(|page| **page != Page::Script3)
+        .map(|page| page.name().len() + if *page == Page::Live { 3 } else { 1 })
+        .sum::<usize>() as u16;
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 2 + offset, 1);
+    assert_eq!(app.current_page, Page::Script3);
+
+    // Only the current page is shown at the lower header levels
+    app.header_level = 2;
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 2, 1);
+    assert_eq!(app.current_page, Page::Script3);
+}
+
+#[test]
+fn test_wheel_scrolls_help_output_and_pattern() {
+    let (mut app, _rx) = test_app();
+    app.go_to_page(Page::Help);
+    mouse(&mut app, MouseEventKind::ScrollDown, 10, 10);
+    mouse(&mut app, MouseEventKind::ScrollDown, 10, 10);
+    assert_eq!(app.help_scroll, 6);
+    mouse(&mut app, MouseEventKind::ScrollUp, 10, 10);
+    assert_eq!(app.help_scroll, 3);
+
+    app.go_to_page(Page::Live);
+    app.show_grid_view = false;
+    for i in 0..4 {
+        app.add_output(format!("LINE {}", i));
+    }
+    mouse(&mut app, MouseEventKind::ScrollUp, 10, 10);
+    mouse(&mut app, MouseEventKind::ScrollUp, 10, 10);
+    assert_eq!(app.output_scroll, app.output.len());
+    mouse(&mut app, MouseEventKind::ScrollDown, 10, 10);
+    assert_eq!(app.output_scroll, app.output.len() - 3);
+
+    app.go_to_page(Page::Pattern);
+    mouse(&mut app, MouseEventKind::ScrollDown, 10, 10);
+    mouse(&mut app, MouseEventKind::ScrollDown, 10, 10);
+    mouse(&mut app, MouseEventKind::ScrollUp, 10, 10);
+    assert_eq!(app.pattern_cursor.1, 1);
+}
+
+#[test]
+fn test_click_places_script_cursor() {
+    let (mut app, _rx) = test_app();
+    app.go_to_page(Page::Script1);
+    app.scripts.scripts[0].lines[1] = "TR".to_string();
+    app.scripts.scripts[0].lines[2] = "PF 440".to_string();
+
+    // Third line, on the "4"
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 6, 3 + 2 + 2);
+    assert_eq!(app.selected_line, Some(2));
+    assert_eq!(app.input, "PF 440");
+    assert_eq!(app.cursor_position, 3);
+
+    // Past the end of the line
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 60, 3 + 2 + 1);
+    assert_eq!(app.selected_line, Some(1));
+    assert_eq!(app.cursor_position, 2);
+
+    // The input line below
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 4, SCREEN.height - 2);
+    assert_eq!(app.cursor_position, 1);
+}
+
+#[test]
+fn test_bar_target_values() {
+    let linear = BarTarget { cmd: "VO".to_string(), start: 10, width: 13, min: 0.0, max: 16383.0, log: false };
+    assert_eq!(linear.value_at(5), 0);
+    assert_eq!(linear.value_at(16), 8192);
+    assert_eq!(linear.command_at(22), "VO 16383");
+    assert_eq!(linear.command_at(40), "VO 16383");
+
+    let log = BarTarget { cmd: "FC".to_string(), start: 0, width: 9, min: 20.0, max: 20000.0, log: true };
+    assert_eq!(log.value_at(0), 20);
+    assert_eq!(log.value_at(4), 632);
+    assert_eq!(log.value_at(8), 20000);
+}
+
+#[test]
+fn test_drag_mixer_bar_sends_volume() {
+    let (mut app, rx) = test_app();
+    app.go_to_page(Page::Live);
+    app.show_grid_view = true;
+    app.show_grid = true;
+    app.grid_mode = 3;
+
+    let content = crate::ui::page_layout(&app, SCREEN)[1];
+    let (column, row) = (0..SCREEN.height)
+        .flat_map(|row| (0..SCREEN.width).map(move |column| (column, row)))
+        .find(|&(column, row)| grid_bar_at(&app, content, column, row).is_some())
+        .unwrap();
+    assert_eq!(grid_bar_at(&app, content, column, row).unwrap().cmd, "VOL.OSC");
+    // Off the bars, nothing to drag
+    app.grid_mode = 0;
+    assert_eq!(grid_bar_at(&app, content, column, row), None);
+    app.grid_mode = 3;
+
+    rx.try_iter().for_each(drop);
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), column, row);
+    mouse(&mut app, MouseEventKind::Drag(MouseButton::Left), column, row);
+    mouse(&mut app, MouseEventKind::Drag(MouseButton::Left), column + 12, row);
+    mouse(&mut app, MouseEventKind::Up(MouseButton::Left), column + 12, row);
+    mouse(&mut app, MouseEventKind::Drag(MouseButton::Left), column + 6, row);
+
+    // Unchanged values and drags after release send nothing
+    let sent: Vec<MetroCommand> = rx.try_iter().collect();
+    assert!(matches!(&sent[..], [
+        MetroCommand::SendParam(low, OscType::Int(0)),
+        MetroCommand::SendParam(high, OscType::Int(16383)),
+    ] if low == high), "{:?}", sent);
+    assert!(app.bar_drag.is_none());
+}
+
+#[test]
+fn test_mouse_toggle() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("MOUSE 1").unwrap();
+    assert!(ctx.mouse);
+    ctx.run("MOUSE").unwrap();
+    ctx.run("MOUSE 0").unwrap();
+    assert!(!ctx.mouse);
+    assert_eq!(ctx.outputs, vec!["MOUSE: ON", "MOUSE: ON", "MOUSE: OFF"]);
+}
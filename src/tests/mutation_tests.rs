@@ -0,0 +1,178 @@
+use super::common::TestContext;
+use crate::commands::registry::param_range;
+use crate::evolve::{breed, mutate};
+use crate::preset::patch::PatchGroup;
+use crate::snapshot::{ParamValue, Snapshot, SnapshotBank};
+use crate::types::MetroCommand;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rosc::OscType;
+
+fn kick() -> SnapshotBank {
+    let mut bank = SnapshotBank::default();
+    bank.record("pf", &OscType::Float(55.0));
+    bank.record("pw", &OscType::Int(0));
+    bank.record("ad", &OscType::Int(300));
+    bank.record("fq", &OscType::Int(2000));
+    bank.record("harmonics", &OscType::Float(0.5));
+    bank
+}
+
+#[test]
+fn test_registry_declares_param_ranges() {
+    assert_eq!(param_range("pf"), Some((20.0, 20000.0)));
+    assert_eq!(param_range("harmonics"), Some((0.0, 1.0)));
+    assert_eq!(param_range("dw"), Some((0.0, 16383.0)));
+    // MC (on/off) and MFF (amount) both set mf_f
+    assert_eq!(param_range("mf_f"), Some((0.0, 16383.0)));
+    assert_eq!(param_range("vol_osc"), None);
+}
+
+#[test]
+fn test_mutate_stays_in_range_and_keeps_types() {
+    let base = kick().live().clone();
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..200 {
+        let candidate = mutate(&base, 100, PatchGroup::All, &mut rng);
+        for (name, value) in &candidate {
+            // Params without a range, and settings outside it, are left alone
+            if *value == base[name] {
+                continue;
+            }
+            let (min, max) = param_range(name).unwrap();
+            match (value, base[name]) {
+                (ParamValue::Int(v), ParamValue::Int(_)) => assert!((min as i32..=max as i32).contains(v), "{} = {}", name, v),
+                (ParamValue::Float(v), ParamValue::Float(_)) => assert!((min..=max).contains(v), "{} = {}", name, v),
+                _ => panic!("{} changed type", name),
+            }
+        }
+    }
+    assert_eq!(mutate(&base, 0, PatchGroup::All, &mut rng), base);
+}
+
+#[test]
+fn test_mutate_respects_group() {
+    let base = kick().live().clone();
+    let candidate = mutate(&base, 100, PatchGroup::Plaits, &mut StdRng::seed_from_u64(1));
+    assert_eq!(candidate["pf"], base["pf"]);
+    assert_eq!(candidate["ad"], base["ad"]);
+    assert_ne!(candidate["harmonics"], base["harmonics"]);
+}
+
+#[test]
+fn test_breed_takes_each_param_from_a_parent() {
+    let mut a = Snapshot::new();
+    let mut b = Snapshot::new();
+    for (i, name) in ["pf", "mf", "fm", "fb", "dc", "tk", "mb", "fa"].iter().enumerate() {
+        a.insert(name.to_string(), ParamValue::Int(i as i32));
+        b.insert(name.to_string(), ParamValue::Int(100 + i as i32));
+    }
+    b.insert("ad".to_string(), ParamValue::Int(500));
+
+    let child = breed(&a, &b, &mut StdRng::seed_from_u64(3));
+    assert_eq!(child.len(), 9);
+    assert_eq!(child["ad"], ParamValue::Int(500));
+    assert!(child.iter().all(|(name, value)| a.get(name) == Some(value) || b.get(name) == Some(value)));
+    assert!(child.iter().any(|(name, value)| a.get(name) == Some(value)));
+    assert!(child.iter().any(|(name, value)| b.get(name) == Some(value)));
+}
+
+#[test]
+fn test_keep_discard_and_back() {
+    let mut bank = kick();
+    let original = bank.live().clone();
+
+    // Trying again mutates from the same parent instead of drifting
+    bank.mutate(50, PatchGroup::All, 1);
+    bank.mutate(50, PatchGroup::All, 2);
+    let params = bank.discard().unwrap();
+    assert!(!params.is_empty());
+    assert_eq!(bank.live(), &original);
+    assert!(bank.discard().unwrap_err().contains("NOTHING TO DISCARD"));
+
+    bank.mutate(50, PatchGroup::All, 3);
+    let kept = bank.live().clone();
+    bank.keep().unwrap();
+    assert_eq!(bank.history_len(), 1);
+    assert!(bank.keep().unwrap_err().contains("NOTHING TO KEEP"));
+
+    bank.mutate(50, PatchGroup::All, 4);
+    bank.keep().unwrap();
+    bank.back().unwrap();
+    assert_eq!(bank.live(), &kept);
+    bank.back().unwrap();
+    assert_eq!(bank.live(), &original);
+    assert!(bank.back().unwrap_err().contains("NO KEPT HISTORY"));
+}
+
+#[test]
+fn test_breed_needs_saved_slots() {
+    let mut bank = kick();
+    bank.save(1);
+    assert!(bank.breed(1, 2, 0).unwrap_err().contains("SLOT 2 IS EMPTY"));
+    bank.record("pf", &OscType::Float(80.0));
+    bank.save(2);
+    bank.breed(1, 2, 0).unwrap();
+    bank.discard().unwrap();
+    assert_eq!(bank.live().get("pf"), Some(&ParamValue::Float(80.0)));
+}
+
+#[test]
+fn test_mut_commands_send_to_metro() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SEED 42").unwrap();
+    ctx.run("MUT 20").unwrap();
+    ctx.run("MUT 5 PL").unwrap();
+    ctx.run("BREED 1 2").unwrap();
+    ctx.run("MUT.KEEP").unwrap();
+    ctx.run("MUT.NO").unwrap();
+    ctx.run("MUT.BACK").unwrap();
+
+    let commands: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(commands[0], MetroCommand::Mutate(20, PatchGroup::All, _)));
+    assert!(matches!(commands[1], MetroCommand::Mutate(5, PatchGroup::Plaits, _)));
+    assert!(matches!(commands[2], MetroCommand::Breed(1, 2, _)));
+    assert!(matches!(commands[3], MetroCommand::MutKeep));
+    assert!(matches!(commands[4], MetroCommand::MutDiscard));
+    assert!(matches!(commands[5], MetroCommand::MutBack));
+
+    // The seed comes from the shared random stream
+    ctx.run("SEED 42").unwrap();
+    ctx.run("MUT 20").unwrap();
+    let again: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    match (&commands[0], &again[0]) {
+        (MetroCommand::Mutate(_, _, a), MetroCommand::Mutate(_, _, b)) => assert_eq!(a, b),
+        _ => panic!("expected mutations"),
+    }
+}
+
+#[test]
+fn test_mut_validates_arguments() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("MUT 101").unwrap();
+    ctx.run("MUT 10 DRUMS").unwrap();
+    ctx.run("BREED 1 17").unwrap();
+
+    assert_eq!(ctx.metro_rx.try_iter().count(), 0);
+    assert!(ctx.outputs.iter().any(|o| o.contains("MUT: AMOUNT 0-100")));
+    assert!(ctx.outputs.iter().any(|o| o.contains("MUT: GROUP MUST BE")));
+    assert!(ctx.outputs.iter().any(|o| o.contains("BREED: SLOT 1-16")));
+}
+
+#[test]
+fn test_mut_changes_a_fresh_session() {
+    let (mut app, metro_rx) = super::common::test_app();
+    let (event_tx, _event_rx) = std::sync::mpsc::channel();
+    let state = app.metro_state.clone();
+    let metro = std::thread::spawn(move || crate::metro::metro_thread_to(metro_rx, state, event_tx, None));
+
+    let before = crate::metro::live_params(&app.metro_tx).unwrap();
+    app.input = "MUT 50".to_string();
+    app.execute_command();
+    let after = crate::metro::live_params(&app.metro_tx).unwrap();
+    let changed = before.iter().filter(|(name, value)| after.get(*name) != Some(value)).count();
+    assert!(changed > 0, "MUT left every param alone");
+
+    app.metro_tx.send(MetroCommand::Shutdown).unwrap();
+    metro.join().unwrap();
+}
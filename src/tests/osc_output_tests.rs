@@ -0,0 +1,199 @@
+use super::clock_tests::SimMetro;
+use super::mock_scsynth::{MockScsynth, RecordedMessage};
+use crate::app::App;
+use crate::metro::metro_thread_to;
+use crate::scene::Scene;
+use crate::types::{ColorMode, MetroCommand, MetroEvent, MetroState};
+use rosc::{decoder, encoder, OscBundle, OscMessage, OscPacket, OscTime, OscType};
+use std::net::UdpSocket;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const WAIT: Duration = Duration::from_secs(2);
+
+struct Metro {
+    tx: Sender<MetroCommand>,
+    /// Kept open so the metro thread's events have somewhere to go
+    _events: Receiver<MetroEvent>,
+    state: Arc<Mutex<MetroState>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Metro {
+    fn start(mock: &MockScsynth) -> Self {
+        let state = Arc::new(Mutex::new(MetroState::default()));
+        let (tx, rx) = mpsc::channel();
+        let (event_tx, _events) = mpsc::channel();
+        let addr = mock.addr();
+        let thread_state = state.clone();
+        let handle = thread::spawn(move || metro_thread_to(rx, thread_state, event_tx, Some(addr)));
+        Self { tx, _events, state, handle: Some(handle) }
+    }
+}
+
+impl Drop for Metro {
+    fn drop(&mut self) {
+        let _ = self.tx.send(MetroCommand::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn values_of(messages: &[RecordedMessage], param: &str) -> Vec<(i32, OscType)> {
+    messages.iter().filter_map(|m| m.n_set(param)).collect()
+}
+
+fn request(socket: &UdpSocket, packet: OscPacket) {
+    socket.send(&encoder::encode(&packet).unwrap()).unwrap();
+}
+
+fn message(addr: &str, args: Vec<OscType>) -> OscPacket {
+    OscPacket::Message(OscMessage { addr: addr.to_string(), args })
+}
+
+#[test]
+fn test_mock_answers_server_commands() {
+    let mock = MockScsynth::start();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.connect(mock.addr()).unwrap();
+    socket.set_read_timeout(Some(WAIT)).unwrap();
+
+    request(&socket, message("/status", vec![]));
+    request(&socket, message("/notify", vec![OscType::Int(1)]));
+    request(&socket, OscPacket::Bundle(OscBundle {
+        timetag: OscTime { seconds: 0, fractional: 1 },
+        content: vec![message("/b_allocRead", vec![OscType::Int(3), OscType::String("kick.wav".to_string())])],
+    }));
+    request(&socket, message("/sync", vec![OscType::Int(7)]));
+
+    let mut replies = Vec::new();
+    let mut buf = [0u8; 1024];
+    for _ in 0..4 {
+        let size = socket.recv(&mut buf).unwrap();
+        match decoder::decode_udp(&buf[..size]).unwrap().1 {
+            OscPacket::Message(msg) => replies.push(msg),
+            OscPacket::Bundle(_) => panic!("scsynth replies with plain messages"),
+        }
+    }
+
+    assert_eq!(replies[0].addr, "/status.reply");
+    assert_eq!(replies[0].args.len(), 9);
+    assert_eq!(replies[1].args, vec![OscType::String("/notify".to_string()), OscType::Int(0)]);
+    assert_eq!(replies[2].args, vec![OscType::String("/b_allocRead".to_string()), OscType::Int(3)]);
+    assert_eq!(replies[3].addr, "/synced");
+
+    // The bundled command keeps its timetag
+    let recorded = mock.messages();
+    assert_eq!(recorded.len(), 4);
+    assert!(recorded[0].timetag.is_none());
+    assert_eq!(recorded[2].timetag, Some(OscTime { seconds: 0, fractional: 1 }));
+}
+
+#[cfg(feature = "scsynth-direct")]
+#[test]
+fn test_params_reach_their_nodes() {
+    use crate::types::{MAIN_NODE_ID, MOD_NODE_ID, NOISE_NODE_ID, PLAITS_NODE_ID, PRIMARY_NODE_ID};
+
+    let mock = MockScsynth::start();
+    let metro = Metro::start(&mock);
+    metro.tx.send(MetroCommand::SendParam("pf".to_string(), OscType::Float(220.0))).unwrap();
+    metro.tx.send(MetroCommand::SendParam("harmonics".to_string(), OscType::Float(0.5))).unwrap();
+    metro.tx.send(MetroCommand::SendParam("mb".to_string(), OscType::Int(8000))).unwrap();
+    metro.tx.send(MetroCommand::SendTrigger).unwrap();
+
+    let messages = mock.wait_for(WAIT, |m| values_of(m, "t_gate").len() == 4);
+    assert_eq!(values_of(&messages, "pf"), vec![(PRIMARY_NODE_ID, OscType::Float(220.0))]);
+    assert_eq!(values_of(&messages, "harmonics"), vec![(PLAITS_NODE_ID, OscType::Float(0.5))]);
+    // The mod bus is set on both oscillators
+    assert_eq!(
+        values_of(&messages, "mb"),
+        vec![(MOD_NODE_ID, OscType::Int(8000)), (PRIMARY_NODE_ID, OscType::Int(8000))]
+    );
+    let gates: Vec<i32> = values_of(&messages, "t_gate").into_iter().map(|(node, _)| node).collect();
+    assert_eq!(gates, vec![NOISE_NODE_ID, MOD_NODE_ID, PRIMARY_NODE_ID, MAIN_NODE_ID]);
+
+    // Internal clock: every message is a bundle scheduled ~50ms ahead
+    assert_eq!(mock.packets().len(), messages.len());
+    for msg in &messages {
+        let lead = msg.lead_ms().expect("timestamped bundle");
+        assert!((0.0..100.0).contains(&lead), "{} scheduled {}ms ahead", msg.addr, lead);
+    }
+}
+
+#[cfg(feature = "scsynth-direct")]
+#[test]
+fn test_scene_script_osc_output() {
+    use crate::types::PRIMARY_NODE_ID;
+
+    let mock = MockScsynth::start();
+    let metro = Metro::start(&mock);
+    let mut app = App::new(
+        metro.tx.clone(),
+        metro.state.clone(),
+        crate::theme::Theme::default(),
+        ColorMode::TrueColor,
+        &crate::config::Config::default(),
+        crate::terminal::TerminalCapabilities { true_color: true, term_program: None },
+    );
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/repl_tests/test_synth_osc.json");
+    let scene: Scene = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    scene.apply_to_app_state(
+        &mut app.scripts,
+        &mut app.patterns,
+        &mut app.notes,
+        &mut app.script_mutes,
+        &mut app.sampler_state,
+        &mut app.snapshots.lock().unwrap(),
+        &app.rng,
+    );
+    app.execute_script(0);
+
+    let messages = mock.wait_for(WAIT, |m| values_of(m, "t_gate").len() == 4);
+    let pf = values_of(&messages, "pf");
+    assert_eq!(pf.len(), 4);
+    assert!(pf.iter().all(|(node, _)| *node == PRIMARY_NODE_ID));
+    assert_eq!(pf[0].1, OscType::Float(440.0));
+    assert_eq!(pf[3].1, OscType::Float(131.0));
+    let pw: Vec<OscType> = values_of(&messages, "pw").into_iter().map(|(_, v)| v).collect();
+    assert_eq!(pw, vec![OscType::Int(0), OscType::Int(1), OscType::Int(2), OscType::Int(2)]);
+    // The trigger comes last
+    assert_eq!(messages.last().and_then(|m| m.n_set("t_gate")).map(|(_, v)| v), Some(OscType::Int(1)));
+}
+
+#[cfg(feature = "scsynth-direct")]
+#[test]
+fn test_metro_trigger_timing() {
+    use crate::types::MAIN_NODE_ID;
+
+    // Ticks follow a simulated clock, so only the order of what is sent is
+    // checked, never how quickly it arrives
+    let mock = MockScsynth::start();
+    let metro = SimMetro::start(Some(mock.addr()));
+    metro.send(MetroCommand::SetInterval(20));
+    metro.send(MetroCommand::SetActive(true));
+
+    // Stand-in for the app: trigger on every metro tick
+    let mut ticks = Vec::new();
+    for _ in 0..10 {
+        for (at, event) in metro.run(20) {
+            if matches!(event, MetroEvent::ExecuteScript(_)) {
+                ticks.push(at);
+                metro.send(MetroCommand::SendTrigger);
+            }
+        }
+    }
+    metro.send(MetroCommand::SetActive(false));
+    assert_eq!(ticks, (0..=10).map(|i| i * 20).collect::<Vec<_>>());
+
+    let is_gate = |m: &RecordedMessage| m.n_set("t_gate").is_some_and(|(node, _)| node == MAIN_NODE_ID);
+    let messages = mock.wait_for(WAIT, |m| m.iter().filter(|m| is_gate(m)).count() == ticks.len());
+    let timetags: Vec<_> = messages.iter().filter(|m| is_gate(m)).map(|m| m.timetag.expect("timestamped bundle")).collect();
+    assert_eq!(timetags.len(), ticks.len());
+    // One bundle per tick, each scheduled no earlier than the one before
+    let times: Vec<_> = timetags.into_iter().map(super::mock_scsynth::timetag_to_system).collect();
+    assert!(times.windows(2).all(|w| w[0] <= w[1]));
+}
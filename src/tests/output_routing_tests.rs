@@ -0,0 +1,117 @@
+use super::common::{test_app, TestContext};
+use crate::types::{output_channel_count, MetroCommand, MetroEvent, OutRoute};
+use rosc::OscType;
+
+#[test]
+fn test_out_route_sends_voice_to_pair() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.output_channels = 6;
+    ctx.run("OUT.ROUTE PLA 2").unwrap();
+    ctx.run("OUT.ROUTE smp ADD 1 2 POST").unwrap();
+    ctx.run("OUT.ROUTE PLA").unwrap();
+
+    assert_eq!(ctx.mixer_data.route_pla, OutRoute { pair: 2, post: false });
+    assert_eq!(ctx.mixer_data.route_smp, OutRoute { pair: 3, post: true });
+    assert_eq!(
+        ctx.sent_params(),
+        vec![
+            ("route_pla".to_string(), OscType::Int(2)),
+            ("route_pla_post".to_string(), OscType::Int(0)),
+            ("route_smp".to_string(), OscType::Int(3)),
+            ("route_smp_post".to_string(), OscType::Int(1)),
+        ]
+    );
+    assert_eq!(ctx.outputs, vec!["OUT.ROUTE PLA: 2 PRE", "OUT.ROUTE SMP: 3 POST", "OUT.ROUTE PLA: 2 PRE"]);
+
+    ctx.run("OUT.ROUTE PLA 0").unwrap();
+    assert_eq!(ctx.mixer_data.route_pla, OutRoute::default());
+    assert_eq!(ctx.outputs.last().unwrap(), "OUT.ROUTE PLA: MASTER");
+}
+
+#[test]
+fn test_out_route_checks_pair_and_voice() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("OUT.ROUTE OSC 2").unwrap();
+    ctx.output_channels = 6;
+    ctx.run("OUT.ROUTE OSC 4").unwrap();
+    ctx.run("OUT.ROUTE FM 2").unwrap();
+    ctx.run("OUT.ROUTE NOS 2 WET").unwrap();
+
+    assert!(ctx.sent_params().is_empty());
+    assert_eq!(ctx.mixer_data.route_osc, OutRoute::default());
+    assert_eq!(
+        ctx.outputs,
+        vec![
+            "ERROR: OUT.ROUTE NEEDS OUT.CH 4 OR MORE",
+            "ERROR: OUT.ROUTE PAIR 0 OR 2-3 (OUT.CH 6)",
+            "ERROR: OUT.ROUTE OSC|PLA|NOS|SMP PAIR [PRE|POST]",
+            "ERROR: OUT.ROUTE OSC|PLA|NOS|SMP PAIR [PRE|POST]",
+        ]
+    );
+}
+
+#[test]
+fn test_out_route_refuses_the_master_pair() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.output_channels = 4;
+    ctx.run("OUT.ROUTE PLA 1").unwrap();
+
+    assert!(ctx.sent_params().is_empty());
+    assert_eq!(ctx.mixer_data.route_pla, OutRoute::default());
+    assert_eq!(ctx.outputs, vec!["ERROR: OUT.ROUTE PAIR 0 OR 2-2 (OUT.CH 4)"]);
+}
+
+#[test]
+fn test_out_ch_asks_for_a_checked_restart() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("OUT.CH").unwrap();
+    ctx.run("OUT.CH 2").unwrap();
+    ctx.run("OUT.CH 7").unwrap();
+    ctx.run("OUT.CH 14").unwrap();
+    ctx.run("OUT.CH 8").unwrap();
+
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [MetroCommand::SetOutputChannels(8)]));
+    assert_eq!(ctx.output_channels, 2);
+    assert_eq!(
+        ctx.outputs,
+        vec!["OUT.CH: 2", "OUT.CH: 2", "ERROR: OUT.CH 2-12 IN PAIRS", "ERROR: OUT.CH 2-12 IN PAIRS", "OUT.CH: 8"]
+    );
+}
+
+#[test]
+fn test_channel_change_resets_routing() {
+    let (mut app, _) = test_app();
+    app.output_channels = 4;
+    app.scripts.scripts[0].lines[0] = "OUT.ROUTE OSC 2 POST".to_string();
+    app.execute_script(0);
+    assert_eq!(app.mixer_data.route_osc.pair, 2);
+
+    app.handle_metro_event(MetroEvent::RestartScWithChannels(6), None);
+    assert_eq!(app.output_channels, 6);
+    assert_eq!(app.mixer_data.route_osc, OutRoute::default());
+
+    app.scripts.scripts[0].lines[0] = "OUT.ROUTE SMP 3".to_string();
+    app.execute_script(0);
+    assert_eq!(app.mixer_data.route_smp.pair, 3);
+}
+
+#[test]
+fn test_rst_keeps_routing() {
+    let mut ctx = TestContext::new();
+    ctx.output_channels = 4;
+    ctx.run("OUT.ROUTE NOS 2").unwrap();
+    ctx.run("VOL.NOS 100").unwrap();
+    ctx.run("RST").unwrap();
+
+    assert_eq!(ctx.mixer_data.vol_nos, 16383);
+    assert_eq!(ctx.mixer_data.route_nos, OutRoute { pair: 2, post: false });
+}
+
+#[test]
+fn test_output_channel_count_is_whole_pairs() {
+    assert_eq!(output_channel_count(0), 2);
+    assert_eq!(output_channel_count(5), 4);
+    assert_eq!(output_channel_count(8), 8);
+    assert_eq!(output_channel_count(40), 12);
+}
@@ -0,0 +1,149 @@
+use super::common::TestContext;
+use crate::preset::patch::{read_patch_file, Patch, PatchGroup};
+use crate::snapshot::{ParamValue, Snapshot, SnapshotBank};
+use crate::types::MetroCommand;
+use rosc::OscType;
+
+fn live_values() -> Snapshot {
+    let mut live = crate::snapshot::defaults();
+    live.insert("pf".to_string(), ParamValue::Float(55.0));
+    live.insert("pw".to_string(), ParamValue::Int(0));
+    live.insert("ad".to_string(), ParamValue::Int(300));
+    live.insert("harmonics".to_string(), ParamValue::Int(8000));
+    live.insert("s_rate".to_string(), ParamValue::Float(1.0));
+    live.insert("fc".to_string(), ParamValue::Float(800.0));
+    live.insert("cl_wet".to_string(), ParamValue::Int(4000));
+    live.insert("vol_osc".to_string(), ParamValue::Float(0.8));
+    live
+}
+
+#[test]
+fn test_patch_groups() {
+    assert_eq!(PatchGroup::of("pf"), Some(PatchGroup::Osc));
+    assert_eq!(PatchGroup::of("ad"), Some(PatchGroup::Osc));
+    assert_eq!(PatchGroup::of("timbre"), Some(PatchGroup::Plaits));
+    assert_eq!(PatchGroup::of("sf_cut"), Some(PatchGroup::Sampler));
+    assert_eq!(PatchGroup::of("cl"), Some(PatchGroup::Fx));
+    assert_eq!(PatchGroup::of("cl_wet"), Some(PatchGroup::Fx));
+    assert_eq!(PatchGroup::of("vol_pla"), None);
+    assert_eq!(PatchGroup::parse("smp"), Some(PatchGroup::Sampler));
+    assert_eq!(PatchGroup::parse("drums"), None);
+}
+
+#[test]
+fn test_patch_capture_keeps_group_only() {
+    let live = live_values();
+    let osc = Patch::capture("kick", PatchGroup::Osc, &live);
+    assert_eq!(osc.params.get("pf"), Some(&ParamValue::Float(55.0)));
+    assert!(osc.params.keys().all(|param| PatchGroup::of(param) == Some(PatchGroup::Osc)));
+    assert!(!osc.params.contains_key("harmonics"));
+
+    let all = Patch::capture("everything", PatchGroup::All, &live);
+    assert_eq!(all.params.get("cl_wet"), Some(&ParamValue::Int(4000)));
+    assert!(!all.params.contains_key("vol_osc"));
+}
+
+#[test]
+fn test_patch_capture_fills_group_from_defaults() {
+    // A fresh session where only PF was touched
+    let mut bank = SnapshotBank::default();
+    bank.record("pf", &OscType::Float(440.0));
+    let patch = Patch::capture("kick", PatchGroup::Osc, bank.live());
+    assert_eq!(patch.params.get("pf"), Some(&ParamValue::Float(440.0)));
+    assert_eq!(patch.params.get("mf"), Some(&ParamValue::Float(262.0)));
+    assert_eq!(patch.params.get("ad"), Some(&ParamValue::Int(100)));
+
+    // Capturing from an empty map still gives the whole group
+    assert_eq!(Patch::capture("kick", PatchGroup::Osc, &Snapshot::new()).params.len(), patch.params.len());
+}
+
+#[test]
+fn test_patch_diff() {
+    let mut live = live_values();
+    let patch = Patch::capture("kick", PatchGroup::Osc, &live);
+    assert!(patch.diff(&live).is_empty());
+
+    live.insert("pf".to_string(), ParamValue::Float(110.0));
+    live.remove("ad");
+    let diff = patch.diff(&live);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff[0], ("ad".to_string(), None, ParamValue::Int(300)));
+    assert_eq!(diff[1], ("pf".to_string(), Some(ParamValue::Float(110.0)), ParamValue::Float(55.0)));
+}
+
+#[test]
+fn test_patch_toml_roundtrip() {
+    let patch = Patch::capture("fat-kick", PatchGroup::Osc, &live_values());
+    let text = patch.to_toml().unwrap();
+    assert!(text.contains("group = \"osc\""));
+    assert!(text.contains("[params]"));
+    assert!(text.contains("pf = 55.0"));
+    assert!(text.contains("pw = 0"));
+
+    let loaded = Patch::from_toml(&text).unwrap();
+    assert_eq!(loaded, patch);
+
+    let path = std::env::temp_dir().join(format!("monokit-patch-test-{}.toml", std::process::id()));
+    std::fs::write(&path, &text).unwrap();
+    assert_eq!(read_patch_file(&path).unwrap(), patch);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_patch_save_needs_group() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("PATCH.SAVE DRUMS KICK").unwrap();
+    assert!(ctx.outputs.iter().any(|o| o.contains("PATCH GROUP MUST BE OSC, PL, SMP, FX OR ALL")));
+}
+
+#[test]
+fn test_patch_missing_does_not_send() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("PATCH no-such-patch-anywhere").unwrap();
+    assert!(ctx.outputs.iter().any(|o| o.contains("PATCH 'no-such-patch-anywhere' NOT FOUND")));
+    assert!(!ctx.metro_rx.try_iter().any(|cmd| matches!(cmd, MetroCommand::SendParam(_, OscType::Float(_)))));
+}
+
+#[test]
+fn test_live_params_wait_for_params_sent_before_them() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let snapshots = crate::snapshot::SharedSnapshots::default();
+    let bank = snapshots.clone();
+    // A metro thread that is slow to get to its queue
+    let metro = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        for cmd in rx.iter() {
+            match cmd {
+                MetroCommand::SendParam(name, value) => bank.lock().unwrap().record(&name, &value),
+                MetroCommand::Flush(reply) => {
+                    reply.send(bank.lock().unwrap().live().clone()).unwrap();
+                    break;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    tx.send(MetroCommand::SendParam("pf".to_string(), OscType::Float(440.0))).unwrap();
+    let live = crate::metro::live_params(&tx).unwrap();
+    assert_eq!(live.get("pf"), Some(&ParamValue::Float(440.0)));
+    metro.join().unwrap();
+}
+
+#[test]
+fn test_patch_save_reports_unresponsive_metro() {
+    // TestContext has no metro thread to answer
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("PATCH.SAVE OSC KICK").unwrap();
+    assert!(ctx.outputs.iter().any(|o| o == "ERROR: METRO THREAD NOT RESPONDING"));
+    assert!(!ctx.outputs.iter().any(|o| o.contains("SAVED PATCH")));
+}
+
+#[test]
+fn test_patch_export_takes_multi_word_names() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("PATCH.EXPORT no such patch").unwrap();
+    ctx.run("PATCH.EXPORT no such patch out.toml").unwrap();
+    let missing = ctx.outputs.iter().filter(|o| o.contains("PATCH 'no such patch' NOT FOUND")).count();
+    assert_eq!(missing, 2);
+}
@@ -29,6 +29,8 @@ fn test_scene_serialization_roundtrip() {
         notes: vec![],
         script_mutes: vec![],
         sampler: None,
+        rng: None,
+        snapshots: Default::default(),
     };
 
     let json = serde_json::to_string(&scene).unwrap();
@@ -64,7 +66,7 @@ fn test_scene_from_app_state() {
 
     let notes = NotesStorage::default();
     let script_mutes = ScriptMutes::default();
-    let scene = Scene::from_app_state(&scripts, &patterns, &notes, &script_mutes, &crate::types::SamplerState::default());
+    let scene = Scene::from_app_state(&scripts, &patterns, &notes, &script_mutes, &crate::types::SamplerState::default(), &Default::default(), &Default::default());
 
     assert_eq!(scene.version, 1);
     assert_eq!(scene.scripts.len(), 10);
@@ -108,6 +110,8 @@ fn test_scene_apply_to_app_state() {
         notes: vec![],
         script_mutes: vec![],
         sampler: None,
+        rng: None,
+        snapshots: Default::default(),
     };
 
     let mut scripts = create_test_scripts();
@@ -115,7 +119,7 @@ fn test_scene_apply_to_app_state() {
     let mut notes = NotesStorage::default();
     let mut script_mutes = ScriptMutes::default();
 
-    scene.apply_to_app_state(&mut scripts, &mut patterns, &mut notes, &mut script_mutes, &mut crate::types::SamplerState::default());
+    scene.apply_to_app_state(&mut scripts, &mut patterns, &mut notes, &mut script_mutes, &mut crate::types::SamplerState::default(), &mut Default::default(), &Default::default());
 
     assert_eq!(scripts.scripts[0].lines[0], "A 10");
     assert_eq!(scripts.scripts[0].lines[1], "B 20");
@@ -157,3 +161,17 @@ fn test_scene_path_uses_sanitized_name() {
     let path_str = path.to_string_lossy();
     assert!(path_str.ends_with("my-test-scene.json"));
 }
+
+#[test]
+fn test_start_session_autoloads_last_scene_then_runs_init() {
+    let (mut app, metro_rx) = super::common::test_app();
+    app.scripts.scripts[9].lines[0] = "A 7".to_string();
+    let mut config = crate::config::Config::default();
+    config.display.autoload = true;
+    config.display.last_scene = Some("no-such-scene-anywhere".to_string());
+
+    app.start_session(&config);
+    assert!(app.output.iter().any(|o| o.contains("SCENE 'no-such-scene-anywhere' NOT FOUND")), "{:?}", app.output);
+    assert_eq!(app.variables.a, 7);
+    assert!(metro_rx.try_iter().any(|cmd| matches!(cmd, crate::types::MetroCommand::SendParam(ref name, _) if name == "vca_mode")));
+}
@@ -0,0 +1,82 @@
+use super::common::TestContext;
+use crate::types::{MetroCommand, ScopeOverlay, ScopeTrigger};
+use rosc::OscType;
+
+#[test]
+fn test_trigger_settings_reach_the_scope_synth() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SCOPE.TRIG 1").unwrap();
+    ctx.run("SCOPE.LVL -25").unwrap();
+    ctx.run("SCOPE.HOLD 200").unwrap();
+    ctx.run("SCOPE.TRIG 5").unwrap();
+    ctx.run("SCOPE.LVL 101").unwrap();
+
+    assert_eq!(ctx.scope_settings.trigger, ScopeTrigger::Rising);
+    assert_eq!(ctx.scope_settings.trig_level, -25);
+    assert_eq!(ctx.scope_settings.holdoff_ms, 200);
+    assert_eq!(
+        ctx.sent_params(),
+        vec![
+            ("scope_trig".to_string(), OscType::Int(1)),
+            ("scope_level".to_string(), OscType::Float(-0.25)),
+            ("scope_hold".to_string(), OscType::Float(0.2)),
+        ]
+    );
+    assert_eq!(
+        ctx.outputs,
+        vec![
+            "SCOPE.TRIG: 1 (RISE)",
+            "SCOPE.LVL: -25",
+            "SCOPE.HOLD: 200MS",
+            "ERROR: SCOPE.TRIG TAKES 0-3",
+            "ERROR: SCOPE.LVL TAKES -100 TO 100",
+        ]
+    );
+}
+
+#[test]
+fn test_single_shot_fires_on_the_next_tr_only() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SCOPE.ARM").unwrap();
+    ctx.run("SCOPE.TRIG 3").unwrap();
+    ctx.sent_params();
+
+    ctx.run("TR").unwrap();
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [
+        MetroCommand::SendParam(name, OscType::Int(1)),
+        MetroCommand::SendTrigger,
+    ] if name == "t_scope"), "{:?}", sent);
+
+    // Held until armed again
+    ctx.run("TR").unwrap();
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [MetroCommand::SendTrigger]), "{:?}", sent);
+
+    ctx.run("SCOPE.ARM").unwrap();
+    ctx.run("TR").unwrap();
+    assert_eq!(ctx.sent_params(), vec![("t_scope".to_string(), OscType::Int(1))]);
+    assert_eq!(ctx.outputs[0], "ERROR: SCOPE.ARM NEEDS SCOPE.TRIG 3");
+    assert!(ctx.outputs.contains(&"SCOPE: ARMED".to_string()));
+}
+
+#[test]
+fn test_xy_and_overlay() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SCOPE.SRC 3").unwrap();
+    ctx.run("SCOPE.XY 1").unwrap();
+    ctx.run("SCOPE.SRC").unwrap();
+    assert!(ctx.scope_settings.xy);
+    assert_eq!(ctx.scope_settings.overlay, ScopeOverlay::Plaits);
+
+    ctx.run("SCOPE.RST").unwrap();
+    assert!(!ctx.scope_settings.xy);
+    assert_eq!(ctx.scope_settings.overlay, ScopeOverlay::Off);
+    assert_eq!(ctx.scope_settings.trigger, ScopeTrigger::Free);
+
+    let sent = ctx.sent_params();
+    assert_eq!(sent[0], ("scope_src".to_string(), OscType::Int(3)));
+    assert_eq!(sent[1], ("scope_xy".to_string(), OscType::Int(1)));
+    assert!(sent.contains(&("scope_xy".to_string(), OscType::Int(0))));
+    assert_eq!(ctx.outputs[..3], ["SCOPE.SRC: 3 (PLAITS)", "SCOPE.XY: ON", "SCOPE.SRC: 3 (PLAITS)"]);
+}
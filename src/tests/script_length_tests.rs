@@ -0,0 +1,85 @@
+use super::common::TestContext;
+use crate::golden::run_scene;
+use crate::scene::{Scene, SceneScript};
+use crate::types::{NotesStorage, SamplerState, ScriptMutes, MAX_SCRIPT_LINES};
+use crate::ui::pages::script_renderer::{scroll_label, visible_rows};
+
+fn scene_of(scripts: &crate::types::ScriptStorage) -> Scene {
+    Scene::from_app_state(
+        scripts,
+        &Default::default(),
+        &NotesStorage::default(),
+        &ScriptMutes::default(),
+        &SamplerState::default(),
+        &Default::default(),
+        &Default::default(),
+    )
+}
+
+#[test]
+fn test_short_scripts_save_eight_lines() {
+    let mut scripts = crate::types::ScriptStorage::default();
+    scripts.scripts[0].lines[1] = "TR".to_string();
+    scripts.scripts[1].lines[11] = "PF 200".to_string();
+
+    let scene = scene_of(&scripts);
+    assert_eq!(scene.scripts[0].lines.len(), 8);
+    assert_eq!(scene.scripts[1].lines.len(), 12);
+    assert_eq!(scene.scripts[1].lines[11], "PF 200");
+}
+
+#[test]
+fn test_eight_line_scene_clears_longer_script() {
+    let mut scripts = crate::types::ScriptStorage::default();
+    scripts.scripts[0].lines[20] = "TR".to_string();
+    let mut short = scene_of(&crate::types::ScriptStorage::default());
+    short.scripts[0] = SceneScript { lines: vec!["A 1".to_string(); 8], j: 0, k: 0 };
+
+    short.apply_to_app_state(
+        &mut scripts,
+        &mut Default::default(),
+        &mut NotesStorage::default(),
+        &mut ScriptMutes::default(),
+        &mut SamplerState::default(),
+        &mut Default::default(),
+        &Default::default(),
+    );
+    assert_eq!(scripts.scripts[0].used_len(), 8);
+    assert!(scripts.scripts[0].lines[20].is_empty());
+}
+
+#[test]
+fn test_long_script_runs_every_line() {
+    let mut scripts = crate::types::ScriptStorage::default();
+    for (i, line) in scripts.scripts[9].lines.iter_mut().enumerate() {
+        *line = format!("A ADD A {}", i + 1);
+    }
+    let report = run_scene(&scene_of(&scripts));
+    let total: usize = (1..=MAX_SCRIPT_LINES).sum();
+    assert!(report.contains(&format!("\nA {}\n", total)), "{}", report);
+}
+
+#[test]
+fn test_script_len_command() {
+    crate::config::detach_from_disk();
+    let mut ctx = TestContext::new().with_debug_level(4);
+
+    ctx.run("SCRIPT.LEN 16").unwrap();
+    assert_eq!(ctx.script_length, 16);
+    ctx.run("SCRIPT.LEN 33").unwrap();
+    ctx.run("SCRIPT.LEN 4").unwrap();
+    assert_eq!(ctx.script_length, 16);
+    ctx.run("SCRIPT.LEN").unwrap();
+    assert_eq!(ctx.outputs.last().map(String::as_str), Some("SCRIPT.LEN: 16"));
+}
+
+#[test]
+fn test_editor_scrolls_to_selected_line() {
+    assert_eq!(visible_rows(8, 20, Some(7)), 0..8);
+    assert_eq!(visible_rows(32, 8, None), 0..8);
+    assert_eq!(visible_rows(32, 8, Some(5)), 0..8);
+    assert_eq!(visible_rows(32, 8, Some(12)), 5..13);
+    assert_eq!(visible_rows(32, 8, Some(31)), 24..32);
+    assert_eq!(scroll_label(&(5..13), 32), " 6-13/32");
+    assert_eq!(scroll_label(&(0..8), 8), "");
+}
@@ -0,0 +1,124 @@
+use super::common::{create_test_metro_tx, TestContext};
+use crate::commands::randomization::handle_rnd_voice;
+use crate::eval::eval_expression;
+use crate::rng::{self, SharedRng};
+use crate::scene::Scene;
+use crate::test_setup;
+use crate::types::{MetroCommand, NotesStorage, SamplerState, ScriptMutes};
+
+/// Draw `count` values of an expression as script `script_index` would
+fn draw(rng: &SharedRng, expr: &str, script_index: usize, count: usize) -> Vec<i16> {
+    let (variables, mut patterns, scripts, mut counters, scale) = test_setup!();
+    let parts: Vec<&str> = expr.split_whitespace().collect();
+    let _stream = rng::enter_script(rng, script_index);
+    (0..count)
+        .map(|_| eval_expression(&parts, 0, &variables, &mut patterns, &mut counters, &scripts, script_index, &scale).unwrap().0)
+        .collect()
+}
+
+#[test]
+fn test_seed_makes_rnd_reproducible() {
+    let rng = SharedRng::default();
+    rng.seed(Some(42));
+    let first = draw(&rng, "RND 1000", 0, 16);
+    rng.seed(Some(42));
+    assert_eq!(draw(&rng, "RND 1000", 0, 16), first);
+
+    rng.seed(Some(43));
+    assert_ne!(draw(&rng, "RND 1000", 0, 16), first);
+}
+
+#[test]
+fn test_seed_covers_toss_and_seq() {
+    let rng = SharedRng::default();
+    rng.seed(Some(7));
+    let tosses = draw(&rng, "TOSS", 0, 32);
+    let choices = draw(&rng, "SEQ \"{1 2 3 4}\"", 0, 32);
+    rng.seed(Some(7));
+    assert_eq!(draw(&rng, "TOSS", 0, 32), tosses);
+    assert_eq!(draw(&rng, "SEQ \"{1 2 3 4}\"", 0, 32), choices);
+}
+
+#[test]
+fn test_script_stream_is_independent() {
+    let rng = SharedRng::default();
+    rng.seed_script(2, Some(99));
+    rng.seed(Some(1));
+    let expected = draw(&rng, "RND 1000", 2, 8);
+
+    // Other scripts drawing from the shared stream don't shift script 3's numbers
+    rng.seed(Some(1));
+    draw(&rng, "RND 1000", 0, 5);
+    assert_eq!(draw(&rng, "RND 1000", 2, 8), expected);
+
+    rng.seed_script(2, None);
+    assert_eq!(rng.script_seed(2), None);
+}
+
+#[test]
+fn test_seed_makes_rnd_voice_reproducible() {
+    let rng = SharedRng::default();
+    let _installed = rng::install(&rng);
+    let voice = || {
+        let (metro_tx, metro_rx) = create_test_metro_tx();
+        handle_rnd_voice(&metro_tx, 0, |_| {}).unwrap();
+        metro_rx
+            .try_iter()
+            .map(|cmd| match cmd {
+                MetroCommand::SendParam(name, value) => format!("{}={:?}", name, value),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>()
+    };
+    rng.seed(Some(2024));
+    let first = voice();
+    rng.seed(Some(2024));
+    assert_eq!(voice(), first);
+}
+
+#[test]
+fn test_seed_commands() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    let rng = ctx.rng.clone();
+    ctx.run("SEED 1234").unwrap();
+    assert_eq!(rng.current_seed(), Some(1234));
+    ctx.run("SEED.SC M 5").unwrap();
+    assert_eq!(rng.script_seed(8), Some(5));
+    ctx.run("SEED.SC M -1").unwrap();
+    assert_eq!(rng.script_seed(8), None);
+    ctx.run("SEED -1").unwrap();
+    assert_eq!(rng.current_seed(), None);
+    ctx.run("SEED -5").unwrap();
+    assert_eq!(rng.current_seed(), None);
+}
+
+#[test]
+fn test_scene_saves_and_restores_seeds() {
+    let rng = SharedRng::default();
+    let (scripts, patterns) = (super::common::create_test_scripts(), super::common::create_test_patterns());
+    rng.seed(Some(77));
+    rng.seed_script(0, Some(3));
+    let scene = Scene::from_app_state(&scripts, &patterns, &NotesStorage::default(), &ScriptMutes::default(), &SamplerState::default(), &Default::default(), &rng);
+    let json = serde_json::to_string(&scene).unwrap();
+    let expected = draw(&rng, "RND 1000", 0, 8);
+
+    let rng = SharedRng::default();
+    let loaded: Scene = serde_json::from_str(&json).unwrap();
+    let (mut scripts, mut patterns) = (scripts, patterns);
+    loaded.apply_to_app_state(&mut scripts, &mut patterns, &mut NotesStorage::default(), &mut ScriptMutes::default(), &mut SamplerState::default(), &mut Default::default(), &rng);
+    assert_eq!(rng.current_seed(), Some(77));
+    assert_eq!(draw(&rng, "RND 1000", 0, 8), expected);
+}
+
+#[test]
+fn test_seed_holds_on_other_threads() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SEED 5").unwrap();
+    let expected = draw(&ctx.rng, "RND 1000", 0, 8);
+
+    // A delayed or metro-run RND draws on another thread from the same App state
+    ctx.run("SEED 5").unwrap();
+    let rng = ctx.rng.clone();
+    let drawn = std::thread::spawn(move || draw(&rng, "RND 1000", 0, 8)).join().unwrap();
+    assert_eq!(drawn, expected);
+}
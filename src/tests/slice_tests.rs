@@ -0,0 +1,188 @@
+use super::common::TestContext;
+use crate::sampler::slice::{
+    frame_to_position, insert_marker, move_marker, position_to_frame, remove_marker,
+    snap_to_transient, snap_to_zero_crossing, whole_file_slot,
+};
+use crate::types::SamplerMode;
+
+fn slice_context(total_frames: usize) -> TestContext {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.sampler_state.mode = SamplerMode::Slice;
+    ctx.sampler_state.total_frames = Some(total_frames);
+    ctx.sampler_state.slots = vec![whole_file_slot(total_frames)];
+    ctx.sampler_state.num_slots = 1;
+    ctx
+}
+
+fn bounds(ctx: &TestContext) -> Vec<(usize, usize)> {
+    ctx.sampler_state
+        .slots
+        .iter()
+        .map(|s| (s.start_frame, s.end_frame))
+        .collect()
+}
+
+#[test]
+fn test_position_frame_roundtrip() {
+    assert_eq!(position_to_frame(0, 16383), 0);
+    assert_eq!(position_to_frame(16383, 16383), 16383);
+    assert_eq!(position_to_frame(8192, 32766), 16384);
+    assert_eq!(frame_to_position(16384, 32766), 8192);
+    assert_eq!(frame_to_position(10, 0), 0);
+}
+
+#[test]
+fn test_insert_marker_splits_slot() {
+    let mut slots = vec![whole_file_slot(1000)];
+    assert_eq!(insert_marker(&mut slots, 400), Some(1));
+    assert_eq!(insert_marker(&mut slots, 200), Some(1));
+    let bounds: Vec<_> = slots.iter().map(|s| (s.start_frame, s.end_frame)).collect();
+    assert_eq!(bounds, vec![(0, 200), (200, 400), (400, 1000)]);
+}
+
+#[test]
+fn test_insert_marker_on_existing_boundary_fails() {
+    let mut slots = vec![whole_file_slot(1000)];
+    insert_marker(&mut slots, 500);
+    assert_eq!(insert_marker(&mut slots, 500), None);
+    assert_eq!(insert_marker(&mut slots, 0), None);
+    assert_eq!(slots.len(), 2);
+}
+
+#[test]
+fn test_remove_marker_merges_into_previous() {
+    let mut slots = vec![whole_file_slot(1000)];
+    insert_marker(&mut slots, 300);
+    insert_marker(&mut slots, 600);
+    assert!(remove_marker(&mut slots, 1));
+    let bounds: Vec<_> = slots.iter().map(|s| (s.start_frame, s.end_frame)).collect();
+    assert_eq!(bounds, vec![(0, 600), (600, 1000)]);
+    assert!(!remove_marker(&mut slots, 0));
+    assert!(!remove_marker(&mut slots, 5));
+}
+
+#[test]
+fn test_move_marker_clamps_between_neighbours() {
+    let mut slots = vec![whole_file_slot(1000)];
+    insert_marker(&mut slots, 300);
+    insert_marker(&mut slots, 600);
+    assert_eq!(move_marker(&mut slots, 1, 450), Some(450));
+    assert_eq!(move_marker(&mut slots, 1, 900), Some(599));
+    assert_eq!(move_marker(&mut slots, 1, 0), Some(1));
+    assert_eq!(slots[0].end_frame, 1);
+    assert_eq!(slots[1].start_frame, 1);
+    assert_eq!(move_marker(&mut slots, 0, 10), None);
+}
+
+#[test]
+fn test_snap_to_zero_crossing_finds_nearest() {
+    let mono = [0.5, 0.4, 0.3, -0.1, -0.2, -0.3, -0.4, 0.2, 0.3];
+    assert_eq!(snap_to_zero_crossing(&mono, 2, 8), 3);
+    assert_eq!(snap_to_zero_crossing(&mono, 6, 8), 7);
+    assert_eq!(snap_to_zero_crossing(&mono, 5, 1), 5);
+}
+
+#[test]
+fn test_snap_to_transient_within_radius() {
+    let onsets = [100, 500, 900];
+    assert_eq!(snap_to_transient(&onsets, 480, 50), 500);
+    assert_eq!(snap_to_transient(&onsets, 300, 50), 300);
+}
+
+#[test]
+fn test_s_mark_command_inserts_slot() {
+    let mut ctx = slice_context(16383);
+    ctx.run("S.MARK 8192").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 8192), (8192, 16383)]);
+    assert_eq!(ctx.sampler_state.num_slots, 2);
+    assert_eq!(ctx.sampler_state.slice_count, Some(2));
+    assert!(ctx.outputs.iter().any(|s| s.contains("S.MARK: SLOT 1")));
+}
+
+#[test]
+fn test_s_mark_alias_and_expression() {
+    let mut ctx = slice_context(16383);
+    ctx.variables.a = 4000;
+    ctx.run("SMK A").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 4000), (4000, 16383)]);
+}
+
+#[test]
+fn test_s_mark_mv_and_unmark_commands() {
+    let mut ctx = slice_context(16383);
+    ctx.run("S.MARK 4000").unwrap();
+    ctx.run("S.MARK 8000").unwrap();
+    ctx.run("S.MARK.MV 2 10000").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 4000), (4000, 10000), (10000, 16383)]);
+    ctx.run("S.UNMARK 1").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 10000), (10000, 16383)]);
+    assert_eq!(ctx.sampler_state.num_slots, 2);
+}
+
+#[test]
+fn test_s_unmark_rejects_first_slot() {
+    let mut ctx = slice_context(16383);
+    ctx.run("S.MARK 4000").unwrap();
+    ctx.run("S.UNMARK 0").unwrap();
+    assert_eq!(ctx.sampler_state.slots.len(), 2);
+    assert!(ctx.outputs.iter().any(|s| s.contains("SLOT RANGE 1-1")));
+}
+
+#[test]
+fn test_s_mark_requires_slice_mode() {
+    let mut ctx = slice_context(16383);
+    ctx.sampler_state.mode = SamplerMode::Kit;
+    ctx.run("S.MARK 4000").unwrap();
+    assert!(ctx.outputs.iter().any(|s| s.contains("SLICE MODE ONLY")));
+}
+
+#[test]
+fn test_s_snap_sets_mode() {
+    let mut ctx = slice_context(16383);
+    ctx.run("S.SNAP 1").unwrap();
+    assert_eq!(ctx.sampler_state.snap_mode, 1);
+    ctx.run("SSNP 3").unwrap();
+    assert_eq!(ctx.sampler_state.snap_mode, 1);
+    assert!(ctx.outputs.iter().any(|s| s.contains("RANGE 0-2")));
+}
+
+#[test]
+fn test_sampler_state_without_snap_mode_deserializes() {
+    let mut value = serde_json::to_value(crate::types::SamplerState::default()).unwrap();
+    value.as_object_mut().unwrap().remove("snap_mode");
+    let state: crate::types::SamplerState = serde_json::from_value(value).unwrap();
+    assert_eq!(state.snap_mode, 0);
+}
+
+#[test]
+fn test_snap_decodes_file_once_until_kit_or_slice() {
+    let path = std::env::temp_dir().join(format!("monokit-snap-test-{}.wav", std::process::id()));
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 48000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    // Crosses zero at frame 8200 only
+    for frame in 0..16383 {
+        writer.write_sample(if frame < 8200 { -1000i16 } else { 1000 }).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let mut ctx = slice_context(16383);
+    ctx.sampler_state.kit_path = Some(path.to_string_lossy().to_string());
+    ctx.run("S.SNAP 1").unwrap();
+    ctx.run("S.MARK 8192").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 8200), (8200, 16383)]);
+    assert!(ctx.sampler_state.snap_source.is_some());
+
+    // Later marks snap from the kept copy, not the file
+    std::fs::remove_file(&path).unwrap();
+    ctx.run("S.UNMARK 1").unwrap();
+    ctx.run("S.MARK 8100").unwrap();
+    assert_eq!(bounds(&ctx), vec![(0, 8200), (8200, 16383)]);
+
+    ctx.run("S.SLICE 4").unwrap();
+    assert!(ctx.sampler_state.snap_source.is_none());
+}
@@ -0,0 +1,198 @@
+use super::common::TestContext;
+use crate::scene::Scene;
+use crate::snapshot::{ParamValue, SnapshotBank};
+use crate::types::{MetroCommand, NotesStorage, PatternStorage, SamplerState, ScriptMutes, ScriptStorage};
+use rosc::OscType;
+use std::time::{Duration, Instant};
+
+fn bank_with(slots: &[(u8, &[(&str, OscType)])]) -> SnapshotBank {
+    let mut bank = SnapshotBank::default();
+    for (slot, params) in slots {
+        for (name, value) in params.iter() {
+            bank.record(name, value);
+        }
+        bank.save(*slot);
+    }
+    bank
+}
+
+fn value_of(params: &[(String, OscType)], name: &str) -> Option<OscType> {
+    params.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone())
+}
+
+#[test]
+fn test_snap_commands_send_to_metro() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SNAP.SAVE 3").unwrap();
+    ctx.run("SNAP.LOAD 3").unwrap();
+    ctx.run("SNAP.MORPH 1 2 8192").unwrap();
+    ctx.run("SNAP.GLIDE 2 500").unwrap();
+
+    let commands: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(commands[0], MetroCommand::SnapSave(3)));
+    assert!(matches!(commands[1], MetroCommand::SnapLoad(3)));
+    assert!(matches!(commands[2], MetroCommand::SnapMorph(1, 2, 8192)));
+    assert!(matches!(commands[3], MetroCommand::SnapGlide(2, 500)));
+    assert!(ctx.outputs.iter().any(|o| o == "SNAP SAVED TO 3"));
+    assert!(ctx.scene_modified);
+}
+
+#[test]
+fn test_snap_validates_arguments() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SNAP.SAVE 0").unwrap();
+    ctx.run("SNAP.LOAD 17").unwrap();
+    ctx.run("SNAP.MORPH 1 2 16384").unwrap();
+    ctx.run("SNAP.GLIDE 1 -5").unwrap();
+
+    assert_eq!(ctx.metro_rx.try_iter().count(), 0);
+    assert!(ctx.outputs.iter().any(|o| o.contains("SNAP.SAVE: SLOT 1-16")));
+    assert!(ctx.outputs.iter().any(|o| o.contains("SNAP.LOAD: SLOT 1-16")));
+    assert!(ctx.outputs.iter().any(|o| o.contains("SNAP.MORPH: POSITION 0-16383")));
+    assert!(ctx.outputs.iter().any(|o| o.contains("SNAP.GLIDE: TIME")));
+}
+
+#[test]
+fn test_record_skips_non_sound_params() {
+    let mut bank = SnapshotBank::default();
+    bank.record("pf", &OscType::Float(220.0));
+    bank.record("t_gate", &OscType::Int(1));
+    bank.record("slew_pf", &OscType::Float(0.5));
+    bank.record("s_bufnum", &OscType::Int(4));
+    bank.record("mute_osc", &OscType::Int(1));
+
+    bank.save(1);
+    let slot = bank.slot(1).unwrap();
+    assert_eq!(slot.get("pf"), Some(&ParamValue::Float(220.0)));
+    for name in ["t_gate", "slew_pf", "s_bufnum", "mute_osc"] {
+        assert!(!slot.contains_key(name), "{}", name);
+    }
+}
+
+#[test]
+fn test_load_sends_every_param() {
+    let mut bank = bank_with(&[(1, &[("pf", OscType::Float(110.0)), ("pw", OscType::Int(2))])]);
+    bank.record("pf", &OscType::Float(440.0));
+
+    let params = bank.load(1).unwrap();
+    assert_eq!(params.len(), bank.slot(1).unwrap().len());
+    assert_eq!(value_of(&params, "pf"), Some(OscType::Float(110.0)));
+    assert_eq!(value_of(&params, "pw"), Some(OscType::Int(2)));
+    assert_eq!(bank.live().get("pf"), Some(&ParamValue::Float(110.0)));
+    assert!(bank.load(5).unwrap_err().contains("SLOT 5 IS EMPTY"));
+}
+
+#[test]
+fn test_morph_interpolates_and_steps_discrete() {
+    let mut bank = bank_with(&[
+        (1, &[("pf", OscType::Float(100.0)), ("dc", OscType::Int(0)), ("pw", OscType::Int(0))]),
+        (2, &[("pf", OscType::Float(300.0)), ("dc", OscType::Int(16383)), ("pw", OscType::Int(2))]),
+    ]);
+
+    let params = bank.morph(1, 2, 4096).unwrap();
+    assert_eq!(value_of(&params, "pf"), Some(OscType::Float(100.0 + 200.0 * 4096.0 / 16383.0)));
+    assert_eq!(value_of(&params, "dc"), Some(OscType::Int(4096)));
+    // Waveform holds slot 1's value below the midpoint
+    assert_eq!(value_of(&params, "pw"), Some(OscType::Int(0)));
+
+    let params = bank.morph(1, 2, 8192).unwrap();
+    assert_eq!(value_of(&params, "pw"), Some(OscType::Int(2)));
+
+    // Unchanged params aren't resent
+    let params = bank.morph(1, 2, 8192).unwrap();
+    assert!(params.is_empty());
+}
+
+#[test]
+fn test_morph_returns_params_missing_from_the_start_slot() {
+    // Slot 1 saved before DW existed, e.g. in an older scene
+    let mut bank = SnapshotBank::default();
+    let mut slots = std::collections::BTreeMap::new();
+    slots.insert(1, [("pf".to_string(), ParamValue::Float(100.0))].into_iter().collect());
+    slots.insert(2, [("pf".to_string(), ParamValue::Float(300.0)), ("dw".to_string(), ParamValue::Int(16000))].into_iter().collect());
+    bank.set_slots(slots);
+    bank.record("dw", &OscType::Int(4000));
+
+    bank.morph(1, 2, 8192).unwrap();
+    bank.morph(1, 2, 16383).unwrap();
+    let params = bank.morph(1, 2, 0).unwrap();
+    assert_eq!(value_of(&params, "dw"), Some(OscType::Int(4000)));
+    assert_eq!(value_of(&params, "pf"), Some(OscType::Float(100.0)));
+}
+
+#[test]
+fn test_fresh_session_saves_every_param() {
+    let mut bank = SnapshotBank::default();
+    assert!(bank.save(1) > 100);
+
+    // RND.VOICE and friends move params nobody set by hand
+    bank.record("harmonics", &OscType::Float(0.9));
+    bank.record("pf", &OscType::Float(880.0));
+    let params = bank.load(1).unwrap();
+    assert_eq!(value_of(&params, "harmonics"), Some(OscType::Float(0.5)));
+    assert_eq!(value_of(&params, "pf"), Some(OscType::Float(131.0)));
+}
+
+#[test]
+fn test_rst_resets_live_values() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.snapshots.lock().unwrap().record("pf", &OscType::Float(880.0));
+    ctx.run("RST").unwrap();
+    assert_eq!(ctx.snapshots.lock().unwrap().live().get("pf"), Some(&ParamValue::Float(131.0)));
+}
+
+#[test]
+fn test_glide_reaches_target_over_time() {
+    let mut bank = bank_with(&[(2, &[("pf", OscType::Float(200.0)), ("mw", OscType::Int(3))])]);
+    bank.record("pf", &OscType::Float(100.0));
+    bank.record("mw", &OscType::Int(0));
+
+    let start = Instant::now();
+    bank.glide(2, 1000, start).unwrap();
+    assert!(bank.is_gliding());
+
+    let params = bank.glide_step(start + Duration::from_millis(250));
+    assert_eq!(value_of(&params, "pf"), Some(OscType::Float(125.0)));
+    assert_eq!(bank.live().get("mw"), Some(&ParamValue::Int(0)));
+
+    let params = bank.glide_step(start + Duration::from_millis(600));
+    assert_eq!(value_of(&params, "mw"), Some(OscType::Int(3)));
+
+    // A param set by hand leaves the glide
+    bank.record("pf", &OscType::Float(50.0));
+    let params = bank.glide_step(start + Duration::from_millis(1200));
+    assert_eq!(value_of(&params, "pf"), None);
+    assert_eq!(bank.live().get("pf"), Some(&ParamValue::Float(50.0)));
+    assert!(!bank.is_gliding());
+}
+
+#[test]
+fn test_snapshots_saved_with_scene() {
+    let mut bank = bank_with(&[(4, &[("fc", OscType::Float(1200.0)), ("ft", OscType::Int(3))])]);
+    let scene = Scene::from_app_state(
+        &ScriptStorage::default(),
+        &PatternStorage::default(),
+        &NotesStorage::default(),
+        &ScriptMutes::default(),
+        &SamplerState::default(),
+        &bank,
+        &Default::default(),
+    );
+    let json = serde_json::to_string(&scene).unwrap();
+    let loaded: Scene = serde_json::from_str(&json).unwrap();
+
+    bank.set_slots(Default::default());
+    assert!(bank.slot(4).is_none());
+    loaded.apply_to_app_state(
+        &mut ScriptStorage::default(),
+        &mut PatternStorage::default(),
+        &mut NotesStorage::default(),
+        &mut ScriptMutes::default(),
+        &mut SamplerState::default(),
+        &mut bank,
+        &Default::default(),
+    );
+    let slot = bank.slot(4).unwrap();
+    assert_eq!(slot.get("fc"), Some(&ParamValue::Float(1200.0)));
+    assert_eq!(slot.get("ft"), Some(&ParamValue::Int(3)));
+}
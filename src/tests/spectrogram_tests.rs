@@ -0,0 +1,159 @@
+use super::common::{test_app, TestContext};
+use crate::spectrogram::{Spectrogram, SpectrumMode, SpectrumScale, HISTORY_FRAMES};
+use crate::types::{MetroCommand, Page, SPECTROGRAM_BANDS};
+
+/// A frame with one loud analyzer band
+fn tone(band: usize, amp: f32) -> Vec<f32> {
+    let mut frame = vec![0.0; SPECTROGRAM_BANDS];
+    frame[band] = amp;
+    frame
+}
+
+fn spectrogram(bands: usize, scale: SpectrumScale, mode: SpectrumMode) -> Spectrogram {
+    let mut spec = Spectrogram::default();
+    spec.bands = bands;
+    spec.scale = scale;
+    spec.mode = mode;
+    spec
+}
+
+#[test]
+fn test_bands_merge_down_to_the_display_count() {
+    let mut spec = spectrogram(8, SpectrumScale::Linear, SpectrumMode::Peak);
+    spec.push(&tone(12, 0.5));
+    // 8 analyzer bands per display band: band 12 lands in the second
+    assert_eq!(spec.current(), vec![0.0, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+    // Average mode takes the power average of the group
+    let mut spec = spectrogram(8, SpectrumScale::Linear, SpectrumMode::Average);
+    for _ in 0..100 {
+        spec.push(&vec![0.4; SPECTROGRAM_BANDS]);
+    }
+    assert!(spec.current().iter().all(|&level| (level - 0.4).abs() < 0.001), "{:?}", spec.current());
+}
+
+#[test]
+fn test_log_scale_spans_72_db() {
+    let mut spec = Spectrogram::default();
+    spec.push(&vec![1.0; SPECTROGRAM_BANDS]);
+    assert!(spec.current().iter().all(|&level| level == 1.0));
+
+    // -36 dB sits halfway, silence at the bottom
+    let mut spec = spectrogram(64, SpectrumScale::Log, SpectrumMode::Peak);
+    spec.push(&tone(0, 10f32.powf(-36.0 / 20.0)));
+    let levels = spec.current();
+    assert!((levels[0] - 0.5).abs() < 0.001, "{}", levels[0]);
+    assert_eq!(levels[1], 0.0);
+}
+
+#[test]
+fn test_peak_falls_back_and_average_rises_slowly() {
+    let mut peak = spectrogram(32, SpectrumScale::Linear, SpectrumMode::Peak);
+    peak.push(&vec![1.0; SPECTROGRAM_BANDS]);
+    peak.push(&vec![0.0; SPECTROGRAM_BANDS]);
+    assert!((peak.current()[0] - 0.9).abs() < 0.001);
+
+    let mut average = spectrogram(32, SpectrumScale::Linear, SpectrumMode::Average);
+    average.push(&vec![1.0; SPECTROGRAM_BANDS]);
+    assert!((average.current()[0] - 0.2).abs() < 0.001);
+}
+
+#[test]
+fn test_history_scrolls_and_freezes() {
+    let mut spec = spectrogram(64, SpectrumScale::Linear, SpectrumMode::Peak);
+    spec.push(&tone(0, 1.0));
+    spec.push(&tone(63, 1.0));
+    let history = spec.history(10);
+    assert_eq!(history.len(), 2);
+    // Newest first
+    assert_eq!(history[0][63], 1.0);
+    assert_eq!(history[1][0], 1.0);
+
+    spec.frozen = true;
+    spec.push(&tone(5, 1.0));
+    assert_eq!(spec.history(10).len(), 2);
+
+    spec.frozen = false;
+    for _ in 0..HISTORY_FRAMES + 10 {
+        spec.push(&tone(5, 1.0));
+    }
+    assert_eq!(spec.history(usize::MAX).len(), HISTORY_FRAMES);
+
+    // Wrong-sized frames are dropped
+    spec.push(&[1.0; 15]);
+    assert_eq!(spec.history(1)[0][5], 1.0);
+}
+
+#[test]
+fn test_snapshot_keeps_the_frame() {
+    let mut spec = spectrogram(32, SpectrumScale::Linear, SpectrumMode::Peak);
+    spec.push(&vec![0.5; SPECTROGRAM_BANDS]);
+    spec.take_snapshot();
+    spec.push(&vec![1.0; SPECTROGRAM_BANDS]);
+    assert_eq!(spec.snapshot().unwrap()[0], 0.5);
+    assert_eq!(spec.current()[0], 1.0);
+
+    spec.clear_snapshot();
+    assert!(spec.snapshot().is_none());
+}
+
+#[test]
+fn test_spec_commands() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("SPEC.BANDS 16").unwrap();
+    ctx.run("SPEC.BANDS 4").unwrap();
+    ctx.run("SPEC.SCALE 1").unwrap();
+    ctx.run("SPEC.MODE 1").unwrap();
+    ctx.run("SPEC.MODE").unwrap();
+    ctx.run("SPEC.DISP 2").unwrap();
+    ctx.run("SPEC.FREEZE 1").unwrap();
+    ctx.run("SPEC.SNAP").unwrap();
+
+    assert_eq!(ctx.spectrogram.bands, 16);
+    assert_eq!(ctx.spectrogram.scale, SpectrumScale::Linear);
+    assert_eq!(ctx.spectrogram.mode, SpectrumMode::Average);
+    assert!(ctx.spectrogram.frozen);
+    assert!(ctx.spectrogram.has_snapshot());
+    assert_eq!(
+        ctx.outputs,
+        vec![
+            "SPEC.BANDS: 16",
+            "ERROR: SPEC.BANDS TAKES 8-64",
+            "SPEC.SCALE: 1 (LIN)",
+            "SPEC.MODE: 1 (AVG)",
+            "SPEC.MODE: 1 (AVG)",
+            "ERROR: SPEC.DISP TAKES 0 (BRAILLE) OR 1 (BLOCK)",
+            "SPEC.FREEZE: 1 (ON)",
+            "SPEC.SNAP: TAKEN",
+        ]
+    );
+
+    ctx.run("SPEC.SNAP.CLR").unwrap();
+    assert!(!ctx.spectrogram.has_snapshot());
+}
+
+#[test]
+fn test_analyzer_runs_only_on_spectrogram_page() {
+    let (mut app, metro_rx) = test_app();
+    let running = || {
+        metro_rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                MetroCommand::SetSpectrogramRunning(running) => Some(running),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    app.refresh_spectrogram();
+    assert!(running().is_empty());
+
+    app.go_to_page(Page::Spectrogram);
+    app.refresh_spectrogram();
+    app.refresh_spectrogram();
+    assert_eq!(running(), vec![true]);
+
+    app.go_to_page(Page::Live);
+    app.refresh_spectrogram();
+    assert_eq!(running(), vec![false]);
+}
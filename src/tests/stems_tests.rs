@@ -0,0 +1,67 @@
+use super::common::{test_app, TestContext};
+use crate::stems::{session_folder, write_sidecar, SIDECAR, STEMS};
+use crate::types::{MetroCommand, PREVIEW_BUFFER_ID, RECORDER_BUFFER_ID, SAMPLER_BUFFER_BASE, SAMPLER_MAX_SLOTS, STEM_BUFFER_BASE};
+use std::path::Path;
+
+#[test]
+fn test_sidecar_has_tempo_scene_and_stems() {
+    let folder = std::env::temp_dir().join(format!("monokit-stems-test-{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    write_sidecar(&folder, 127.998, Some("LIVE SET")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(folder.join(SIDECAR)).unwrap()).unwrap();
+    let _ = std::fs::remove_dir_all(&folder);
+
+    assert_eq!(json["scene"], "LIVE SET");
+    assert_eq!(json["bpm"], 128.0);
+    assert_eq!(json["stems"].as_array().unwrap().len(), STEMS.len());
+    assert_eq!(json["stems"][0], "master.wav");
+    assert!(json["started"].is_string());
+}
+
+#[test]
+fn test_session_folder_is_timestamped() {
+    let folder = session_folder(Path::new("/rec"));
+    let name = folder.file_name().unwrap().to_str().unwrap();
+    assert_eq!(folder.parent(), Some(Path::new("/rec")));
+    assert!(name.starts_with("monokit_stems_"), "{}", name);
+    // YYMMDD_HHMMSS
+    assert_eq!(name.len(), "monokit_stems_".len() + 13);
+}
+
+#[test]
+fn test_rec_stems_commands() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("REC.STEMS Q").unwrap();
+    ctx.run("REC.STEMS.STOP").unwrap();
+    ctx.run("REC.STEMS NOW").unwrap();
+
+    let sent: Vec<MetroCommand> = ctx.metro_rx.try_iter().collect();
+    assert!(matches!(&sent[..], [MetroCommand::StartStemRecording(_, true), MetroCommand::StopStemRecording(false)]), "{:?}", sent);
+    assert_eq!(ctx.outputs, vec!["STEMS START ON NEXT TICK", "ERROR: REC.STEMS [Q]"]);
+}
+
+#[test]
+fn test_stem_buffers_clear_of_other_buffers() {
+    let kit = SAMPLER_BUFFER_BASE..SAMPLER_BUFFER_BASE + SAMPLER_MAX_SLOTS as u32;
+    let stems = STEM_BUFFER_BASE..STEM_BUFFER_BASE + STEMS.len() as u32;
+    let ranges = [kit, PREVIEW_BUFFER_ID..PREVIEW_BUFFER_ID + 1, RECORDER_BUFFER_ID..RECORDER_BUFFER_ID + 1, stems];
+    for (i, a) in ranges.iter().enumerate() {
+        for b in &ranges[i + 1..] {
+            assert!(a.end <= b.start || b.end <= a.start, "{:?} overlaps {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn test_rec_stop_leaves_stem_take_running() {
+    let (mut app, metro_rx) = test_app();
+    app.stem_folder = Some(std::path::PathBuf::from("/tmp/stems"));
+    app.stem_start = Some(std::time::Instant::now());
+    app.input = "REC.STOP".to_string();
+    app.execute_command();
+
+    let sent: Vec<MetroCommand> = metro_rx.try_iter().collect();
+    assert!(!sent.iter().any(|cmd| matches!(cmd, MetroCommand::StopStemRecording(_))), "{:?}", sent);
+    assert!(app.stem_folder.is_some() && app.stem_start.is_some());
+    assert!(!app.recording);
+}
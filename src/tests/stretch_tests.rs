@@ -0,0 +1,89 @@
+use super::common::TestContext;
+use crate::sampler::stretch::{bpm_from_filename, interval_to_bpm, tempo_ratio, MidiTempoTracker};
+use crate::types::SamplerState;
+use rosc::OscType;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_bpm_from_filename() {
+    assert_eq!(bpm_from_filename("/samples/break_120bpm.wav"), Some(120.0));
+    assert_eq!(bpm_from_filename("amen 172 BPM.wav"), Some(172.0));
+    assert_eq!(bpm_from_filename("loop-92.wav"), Some(92.0));
+    assert_eq!(bpm_from_filename("kick.wav"), None);
+    assert_eq!(bpm_from_filename("loop 90 100.wav"), None);
+    assert_eq!(bpm_from_filename("hat-01.wav"), None);
+}
+
+#[test]
+fn test_tempo_ratio() {
+    assert_eq!(interval_to_bpm(125), 120.0);
+    assert_eq!(tempo_ratio(90.0, 120.0), 0.75);
+    assert_eq!(tempo_ratio(120.0, 0.0), 1.0);
+}
+
+#[test]
+fn test_midi_tempo_tracker() {
+    let mut tracker = MidiTempoTracker::default();
+    let start = Instant::now();
+    assert_eq!(tracker.tick(start), None);
+
+    // 16th-note ticks 125ms apart = 120 BPM
+    assert_eq!(tracker.tick(start + Duration::from_millis(125)), Some(120.0));
+    // Steady clock doesn't resend
+    assert_eq!(tracker.tick(start + Duration::from_millis(250)), None);
+
+    // A tempo change is smoothed in over several ticks
+    let mut at = start + Duration::from_millis(250);
+    let mut latest = None;
+    for _ in 0..40 {
+        at += Duration::from_millis(100);
+        latest = tracker.tick(at).or(latest);
+    }
+    assert!((latest.unwrap() - 150.0).abs() < 1.0);
+
+    tracker.reset();
+    assert_eq!(tracker.tick(at), None);
+}
+
+#[test]
+fn test_s_bpm_sets_and_validates() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("S.BPM 172").unwrap();
+    assert_eq!(ctx.sampler_state.source_bpm, Some(172.0));
+    let params = ctx.sent_params();
+    assert!(params.contains(&("s_srcBpm".to_string(), OscType::Float(172.0))));
+    assert!(params.iter().any(|(name, _)| name == "s_tempoBpm"));
+
+    ctx.run("S.BPM 500").unwrap();
+    assert_eq!(ctx.sampler_state.source_bpm, Some(172.0));
+    assert!(ctx.sent_params().is_empty());
+}
+
+#[test]
+fn test_s_stretch_needs_source_bpm() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("S.STRETCH 1").unwrap();
+    assert!(!ctx.sampler_state.playback.stretch);
+    assert!(ctx.sent_params().is_empty());
+
+    ctx.run("SBPM 120").unwrap();
+    ctx.sent_params();
+    ctx.run("SSTR 1").unwrap();
+    assert!(ctx.sampler_state.playback.stretch);
+    assert!(ctx.sent_params().contains(&("s_stretch".to_string(), OscType::Int(1))));
+
+    ctx.run("S.STRETCH 0").unwrap();
+    assert!(!ctx.sampler_state.playback.stretch);
+    assert_eq!(ctx.sent_params(), vec![("s_stretch".to_string(), OscType::Int(0))]);
+}
+
+#[test]
+fn test_sampler_state_without_stretch_fields() {
+    let mut value = serde_json::to_value(SamplerState::default()).unwrap();
+    value.as_object_mut().unwrap().remove("source_bpm");
+    value["playback"].as_object_mut().unwrap().remove("stretch");
+
+    let state: SamplerState = serde_json::from_value(value).unwrap();
+    assert_eq!(state.source_bpm, None);
+    assert!(!state.playback.stretch);
+}
@@ -0,0 +1,116 @@
+use super::common::TestContext;
+use crate::theme::Theme;
+use crate::ui::syntax_highlight::{apply_syntax, char_kinds, TokenKind};
+use ratatui::prelude::*;
+use TokenKind::*;
+
+/// Words of `line` with their kinds, whitespace left out
+fn tokens(line: &str) -> Vec<(String, TokenKind)> {
+    let mut tokens: Vec<(String, TokenKind)> = Vec::new();
+    let mut prev: Option<char> = None;
+    for (c, kind) in line.chars().zip(char_kinds(line)) {
+        if !c.is_whitespace() || kind == Str || kind == Comment {
+            match tokens.last_mut() {
+                Some((text, last)) if *last == kind && prev.is_some_and(|p| !p.is_whitespace() || kind == Str || kind == Comment) => text.push(c),
+                _ => tokens.push((c.to_string(), kind)),
+            }
+        }
+        prev = Some(c);
+    }
+    tokens
+}
+
+fn t(text: &str, kind: TokenKind) -> (String, TokenKind) {
+    (text.to_string(), kind)
+}
+
+#[test]
+fn test_commands_aliases_and_arguments() {
+    assert_eq!(tokens("PF 440"), [t("PF", Alias), t("440", Number)]);
+    assert_eq!(tokens("POSC.FREQ -1.5"), [t("POSC.FREQ", Command), t("-1.5", Number)]);
+    assert_eq!(tokens("A ADD X 1"), [t("A", Variable), t("ADD", Command), t("X", Variable), t("1", Number)]);
+    assert_eq!(tokens("N C#3; TR"), [t("N", Command), t("C#3", Note), t(";", Separator), t("TR", Command)]);
+    // Unknown words are only flagged where a command goes
+    assert_eq!(tokens("QQQ FOO"), [t("QQQ", Unknown), t("FOO", Plain)]);
+}
+
+#[test]
+fn test_control_flow_and_strings() {
+    assert_eq!(
+        tokens("IF GT X 3: PF SEQ \"C3 E3\""),
+        [t("IF", Keyword), t("GT", Command), t("X", Variable), t("3", Number), t(":", Separator), t("PF", Alias), t("SEQ", Plain), t("\"C3 E3\"", Str)]
+    );
+    assert_eq!(tokens("DEL.X 4 100: TR"), [t("DEL.X", Keyword), t("4", Number), t("100", Number), t(":", Separator), t("TR", Command)]);
+    assert_eq!(tokens("L 1 4: TR"), [t("L", Keyword), t("1", Number), t("4", Number), t(":", Separator), t("TR", Command)]);
+    // An unterminated pattern runs to the end; a comment ends the code
+    assert_eq!(tokens("PF SEQ\"1 2"), [t("PF", Alias), t("SEQ", Plain), t("\"1 2", Str)]);
+    assert_eq!(tokens("TR # KICK"), [t("TR", Command), t("# KICK", Comment)]);
+}
+
+#[test]
+fn test_syntax_colors_only_plain_spans() {
+    let theme = Theme::dark();
+    let plain = theme.secondary;
+    let spans = vec![
+        Span::styled("PF ", Style::default().fg(plain)),
+        Span::styled("440", Style::default().fg(theme.foreground)),
+        Span::styled("; QQQ", Style::default().fg(plain)),
+    ];
+    let colored: Vec<(String, Option<Color>)> = apply_syntax(spans, "PF 440; QQQ", plain, &theme)
+        .into_iter()
+        .map(|span| (span.content.to_string(), span.style.fg))
+        .collect();
+    assert_eq!(
+        colored,
+        [
+            ("PF".to_string(), Some(theme.syn_alias)),
+            (" ".to_string(), Some(plain)),
+            // Lit by SEQ/TOG highlighting: left alone
+            ("440".to_string(), Some(theme.foreground)),
+            (";".to_string(), Some(theme.syn_separator)),
+            (" ".to_string(), Some(plain)),
+            ("QQQ".to_string(), Some(theme.error)),
+        ]
+    );
+}
+
+#[test]
+fn test_custom_theme_syntax_colors() {
+    let config: crate::config::Config = toml::from_str(
+        r##"
+        [themes.mine]
+        background = "#000000"
+        foreground = "#ffffff"
+        secondary = "#808080"
+        highlight_bg = "#ffffff"
+        highlight_fg = "#000000"
+        border = "#ffffff"
+        error = "#ff0000"
+        accent = "#0000ff"
+        success = "#00ff00"
+        syn_keyword = "#ff8800"
+        "##,
+    )
+    .unwrap();
+    let theme = crate::config::load_theme_by_name("mine", &config).unwrap();
+    assert_eq!(theme.syn_keyword, Color::Rgb(255, 136, 0));
+    // Left out: taken or blended from the theme's own colors
+    assert_eq!(theme.syn_command, Color::Rgb(255, 255, 255));
+    assert_eq!(theme.syn_number, Color::Rgb(0, 255, 0));
+    assert_eq!(theme.syn_separator, Color::Rgb(128, 128, 128));
+    assert_eq!(theme.syn_note, Color::Rgb(0, 127, 127));
+
+    let mut config = config;
+    config.themes.get_mut("mine").unwrap().syn_note = Some("oops".to_string());
+    assert!(crate::config::load_theme_by_name("mine", &config).is_err());
+}
+
+#[test]
+fn test_hl_syn_toggle() {
+    let mut ctx = TestContext::new().with_debug_level(4);
+    ctx.run("HL.SYN 0").unwrap();
+    assert!(!ctx.show_syntax_highlight);
+    ctx.run("HL.SYN 1").unwrap();
+    assert!(ctx.show_syntax_highlight);
+    assert_eq!(ctx.outputs, vec!["SYNTAX HIGHLIGHT: OFF", "SYNTAX HIGHLIGHT: ON"]);
+}
@@ -0,0 +1,114 @@
+use super::common::TestContext;
+use crate::teletype::{export_scene, parse_scene, OpMap, UNTRANSLATED_PREFIX};
+use std::collections::BTreeMap;
+
+const SCENE: &str = "KICKS AND BLEEPS
+BY SOMEONE
+
+#1
+TR.P 1
+CV 1 N P.NEXT
+
+#2
+IF == X 3: TR.P 2
+CV.SLEW 1 100
+
+#M
+EVERY 4: $ 1
+#CV 2 RAND 100
+
+#I
+M BPM 120; X 0
+
+#P
+4\t2\t0\t0
+1\t1\t1\t1
+0\t0\t0\t0
+63\t63\t63\t63
+
+0\t5\t0\t0
+7\t-3\t0\t0
+12\t0\t0\t0
+3\t0\t0\t0
+";
+
+fn script(scene: &crate::scene::Scene, index: usize) -> Vec<&str> {
+    scene.scripts[index].lines.iter().map(String::as_str).filter(|l| !l.is_empty()).collect()
+}
+
+#[test]
+fn test_import_translates_ops() {
+    let (scene, report) = parse_scene(SCENE, &OpMap::default());
+
+    assert_eq!(script(&scene, 0), vec!["TR", "PF N P.NEXT"]);
+    assert_eq!(script(&scene, 1), vec!["IF EQ X 3: PLTR", "# TT: CV.SLEW 1 100"]);
+    assert_eq!(script(&scene, 8), vec!["EV 4: $ 1", "# CV 2 RAND 100"]);
+    assert_eq!(script(&scene, 9), vec!["M.BPM 120; X 0"]);
+    assert_eq!(report.translated, 5);
+    assert_eq!(report.untranslated, vec!["CV.SLEW"]);
+    assert_eq!(scene.notes, vec!["KICKS AND BLEEPS", "BY SOMEONE"]);
+}
+
+#[test]
+fn test_import_reads_pattern_columns() {
+    let (scene, _) = parse_scene(SCENE, &OpMap::default());
+
+    assert_eq!(scene.patterns[0].length, 4);
+    assert_eq!(&scene.patterns[0].data[..4], &[0, 7, 12, 3]);
+    assert_eq!(scene.patterns[1].length, 2);
+    assert_eq!(&scene.patterns[1].data[..2], &[5, -3]);
+    // Steps missing from the file are zero
+    assert_eq!(scene.patterns[0].data[63], 0);
+}
+
+#[test]
+fn test_op_map_overrides_and_disables() {
+    let mut overrides = BTreeMap::new();
+    overrides.insert("cv 2".to_string(), "MF".to_string());
+    overrides.insert("TR.P 1".to_string(), String::new());
+    let map = OpMap::new(&overrides);
+
+    assert_eq!(map.to_monokit("CV 2 440").unwrap(), "MF 440");
+    assert_eq!(map.to_monokit("TR.P 1"), Err("TR.P 1".to_string()));
+    // Only the mapped output is translated
+    assert_eq!(map.to_monokit("TR.P 3"), Err("TR.P".to_string()));
+    assert_eq!(map.to_teletype("MF 440").unwrap(), "CV 2 440");
+}
+
+#[test]
+fn test_export_writes_back_what_it_can() {
+    let (mut scene, _) = parse_scene(SCENE, &OpMap::default());
+    scene.scripts[2].lines[0] = "RND.VOICE".to_string();
+    let (text, skipped) = export_scene(&scene, &OpMap::default());
+
+    assert!(text.starts_with("KICKS AND BLEEPS\nBY SOMEONE\n\n#1\nTR.P 1\nCV 1 N P.NEXT\n"));
+    // The untranslated line goes back as it was, EQ stays EQ
+    assert!(text.contains("#2\nIF EQ X 3: TR.P 2\nCV.SLEW 1 100\n"));
+    assert!(text.contains("#M\nEVERY 4: $ 1\n#CV 2 RAND 100\n"));
+    assert!(text.contains("#I\nM BPM 120; X 0\n"));
+    assert!(text.contains("#P\n4\t2\t0\t0\n1\t1\t1\t1\n0\t0\t0\t0\n63\t63\t63\t63\n\n0\t5\t0\t0\n"));
+    assert_eq!(skipped.len(), 1);
+    assert_eq!((skipped[0].script, skipped[0].reason.as_str()), ("3", "RND.VOICE"));
+
+    let (again, _) = parse_scene(&text, &OpMap::default());
+    for i in 0..10 {
+        assert_eq!(script(&again, i), script(&scene, i).into_iter().filter(|l| *l != "RND.VOICE").collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_import_tt_command_loads_scene() {
+    let path = std::env::temp_dir().join(format!("monokit-tt-test-{}.txt", std::process::id()));
+    std::fs::write(&path, SCENE).unwrap();
+    let mut ctx = TestContext::new();
+    ctx.variables.x = 5;
+
+    let run = ctx.run(&format!("IMPORT.TT {}", path.display())).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(run, vec![9]);
+    assert_eq!(ctx.scripts.scripts[1].lines[1], format!("{}CV.SLEW 1 100", UNTRANSLATED_PREFIX));
+    assert_eq!(ctx.patterns.patterns[1].length, 2);
+    assert_eq!(ctx.variables.x, 0);
+    assert!(ctx.run("IMPORT.TT /nonexistent/scene.txt").unwrap().is_empty());
+}
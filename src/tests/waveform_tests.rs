@@ -0,0 +1,95 @@
+use super::common::test_app;
+use crate::sampler::waveform::{WaveformOverview, WaveformView, MAX_ZOOM};
+use crate::types::{Page, SamplerMode};
+
+fn overview(peaks: Vec<(f32, f32)>, total_frames: usize) -> WaveformOverview {
+    WaveformOverview {
+        total_frames,
+        sample_rate: 48000,
+        peaks,
+    }
+}
+
+#[test]
+fn test_overview_columns_merge_bins() {
+    let ov = overview(vec![(-0.1, 0.1), (-0.5, 0.2), (-0.2, 0.9), (0.0, 0.0)], 400);
+    let cols = ov.columns(0, 400, 2);
+    assert_eq!(cols, vec![(-0.5, 0.2), (-0.2, 0.9)]);
+}
+
+#[test]
+fn test_overview_columns_zoomed_range() {
+    let ov = overview(vec![(-0.1, 0.1), (-0.5, 0.2), (-0.2, 0.9), (-0.3, 0.4)], 400);
+    let cols = ov.columns(200, 400, 2);
+    assert_eq!(cols, vec![(-0.2, 0.9), (-0.3, 0.4)]);
+}
+
+#[test]
+fn test_view_zoom_limits_and_window() {
+    let mut view = WaveformView::default();
+    assert_eq!(view.window(), (0, 16384));
+    for _ in 0..10 {
+        view.zoom_in();
+    }
+    assert_eq!(view.zoom, MAX_ZOOM);
+    assert_eq!(view.span(), 512);
+    for _ in 0..10 {
+        view.zoom_out();
+    }
+    assert_eq!(view.zoom, 1);
+}
+
+#[test]
+fn test_view_cursor_scrolls_window() {
+    let mut view = WaveformView::default();
+    view.zoom_in();
+    view.zoom_in();
+    view.set_cursor(12000);
+    let (start, end) = view.window();
+    assert!(start <= 12000 && 12000 < end);
+    view.move_cursor(-100000, 80);
+    assert_eq!(view.cursor, 0);
+    assert_eq!(view.window().0, 0);
+}
+
+#[test]
+fn test_overview_rebuilt_only_when_file_changes_on_waveform_page() {
+    let (mut app, _) = test_app();
+    let path = std::env::temp_dir().join(format!("monokit-waveform-test-{}.wav", std::process::id()));
+    std::fs::write(&path, b"not a wav file").unwrap();
+    app.sampler_state.mode = SamplerMode::Slice;
+    app.sampler_state.kit_path = Some(path.to_string_lossy().to_string());
+
+    // Nothing is decoded while another page is shown
+    app.refresh_waveform();
+    assert_eq!(app.waveform_view.source, None);
+
+    app.current_page = Page::Waveform;
+    app.refresh_waveform();
+    assert_eq!(app.waveform_view.source.as_ref().map(|s| s.path.as_str()), app.sampler_state.kit_path.as_deref());
+    assert!(app.waveform_view.overview.is_none());
+
+    // Nothing is rebuilt while the file stays the same; a rebuild would drop this overview
+    app.waveform_view.overview = Some(overview(vec![(0.0, 0.0)], 1));
+    app.waveform_view.zoom = 4;
+    app.refresh_waveform();
+    assert_eq!(app.waveform_view.overview.as_ref().map(|o| o.total_frames), Some(1));
+
+    // Rewriting the file at the same path rebuilds it, keeping the view
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 48000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for _ in 0..480 {
+        writer.write_sample(0i16).unwrap();
+    }
+    writer.finalize().unwrap();
+    app.refresh_waveform();
+    assert_eq!(app.waveform_view.overview.as_ref().map(|o| o.total_frames), Some(480));
+    assert_eq!(app.waveform_view.zoom, 4);
+
+    std::fs::remove_file(&path).ok();
+}
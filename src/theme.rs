@@ -35,6 +35,15 @@ pub struct Theme {
     pub accent: Color,      // Selected items, active indicators
     pub success: Color,     // Positive states (metro on, etc.)
     pub label: Color,       // Section labels, headers
+    // Syntax highlighting (HL.SYN); unknown commands use `error`
+    pub syn_command: Color,
+    pub syn_alias: Color,
+    pub syn_variable: Color,
+    pub syn_number: Color,
+    pub syn_note: Color,
+    pub syn_string: Color,  // SEQ patterns and other quoted text
+    pub syn_keyword: Color, // IF/ELIF/ELSE/L/PROB/EV/SKIP/DEL
+    pub syn_separator: Color,
     pub font: Option<String>,
 }
 
@@ -52,6 +61,14 @@ impl Theme {
             accent: Color::Rgb(255, 255, 255),    // Bright white for selection
             success: Color::Rgb(80, 255, 80),     // Green for active states
             label: Color::Rgb(180, 180, 180),     // Light gray for labels
+            syn_command: Color::Rgb(255, 255, 255),
+            syn_alias: Color::Rgb(170, 200, 255),
+            syn_variable: Color::Rgb(110, 220, 255),
+            syn_number: Color::Rgb(150, 240, 150),
+            syn_note: Color::Rgb(220, 160, 255),
+            syn_string: Color::Rgb(255, 225, 120),
+            syn_keyword: Color::Rgb(255, 165, 80),
+            syn_separator: Color::Rgb(128, 128, 128),
             font: Some("Menlo".to_string()),
         }
     }
@@ -69,6 +86,14 @@ impl Theme {
             accent: Color::Rgb(0, 0, 0),          // Black for selection
             success: Color::Rgb(0, 160, 0),       // Dark green for active states
             label: Color::Rgb(64, 64, 64),        // Dark gray for labels
+            syn_command: Color::Rgb(0, 0, 0),
+            syn_alias: Color::Rgb(40, 70, 170),
+            syn_variable: Color::Rgb(0, 120, 160),
+            syn_number: Color::Rgb(0, 130, 0),
+            syn_note: Color::Rgb(130, 50, 170),
+            syn_string: Color::Rgb(150, 110, 0),
+            syn_keyword: Color::Rgb(190, 80, 0),
+            syn_separator: Color::Rgb(96, 96, 96),
             font: Some("Menlo".to_string()),
         }
     }
@@ -140,6 +165,14 @@ impl Theme {
             accent: color_to_256(self.accent),
             success: color_to_256(self.success),
             label: color_to_256(self.label),
+            syn_command: color_to_256(self.syn_command),
+            syn_alias: color_to_256(self.syn_alias),
+            syn_variable: color_to_256(self.syn_variable),
+            syn_number: color_to_256(self.syn_number),
+            syn_note: color_to_256(self.syn_note),
+            syn_string: color_to_256(self.syn_string),
+            syn_keyword: color_to_256(self.syn_keyword),
+            syn_separator: color_to_256(self.syn_separator),
             font: self.font.clone(),
         }
     }
@@ -7,6 +7,101 @@ pub struct ScopeSettings {
     pub display_mode: u8,
     pub unipolar: bool,
     pub gain: u16,
+    pub trigger: ScopeTrigger,
+    /// Trigger level, -100 to 100 (percent of full scale)
+    pub trig_level: i16,
+    /// Wait after each capture before the next trigger can fire
+    pub holdoff_ms: u32,
+    /// Plot left against right instead of over time
+    pub xy: bool,
+    /// Voice bus drawn over the master trace
+    pub overlay: ScopeOverlay,
+    /// SINGLE mode: the next TR starts a capture
+    pub single_armed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScopeTrigger {
+    #[default]
+    Free,
+    Rising,
+    Falling,
+    /// One capture from the next TR, then hold
+    Single,
+}
+
+impl ScopeTrigger {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(Self::Free),
+            1 => Some(Self::Rising),
+            2 => Some(Self::Falling),
+            3 => Some(Self::Single),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Self::Free => 0,
+            Self::Rising => 1,
+            Self::Falling => 2,
+            Self::Single => 3,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Free => "FREE",
+            Self::Rising => "RISE",
+            Self::Falling => "FALL",
+            Self::Single => "SINGLE",
+        }
+    }
+}
+
+/// Voice busses the scope can overlay, in stem bus order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScopeOverlay {
+    #[default]
+    Off,
+    Osc,
+    Noise,
+    Plaits,
+    Sampler,
+}
+
+impl ScopeOverlay {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(Self::Off),
+            1 => Some(Self::Osc),
+            2 => Some(Self::Noise),
+            3 => Some(Self::Plaits),
+            4 => Some(Self::Sampler),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Self::Off => 0,
+            Self::Osc => 1,
+            Self::Noise => 2,
+            Self::Plaits => 3,
+            Self::Sampler => 4,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Osc => "OSC",
+            Self::Noise => "NOISE",
+            Self::Plaits => "PLAITS",
+            Self::Sampler => "SAMPLER",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,6 +206,9 @@ pub struct MeterData {
     pub peak_hold_r: f32,
     pub clip_l: bool,
     pub clip_r: bool,
+    /// Live input, mixed to mono
+    pub in_peak: f32,
+    pub in_rms: f32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -144,13 +242,17 @@ impl Default for SpectrumData {
 
 #[derive(Debug, Clone)]
 pub struct ScopeData {
+    /// Master (mono), or left in X/Y mode
     pub samples: [f32; SCOPE_SAMPLES],
+    /// Overlay voice bus, or right in X/Y mode
+    pub overlay: [f32; SCOPE_SAMPLES],
 }
 
 impl Default for ScopeData {
     fn default() -> Self {
         Self {
             samples: [0.0; SCOPE_SAMPLES],
+            overlay: [0.0; SCOPE_SAMPLES],
         }
     }
 }
@@ -193,6 +295,87 @@ pub struct CompressorData {
     pub gain_reduction_db: f32,
 }
 
+/// One 100ms block from the loudness synth
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoudnessBlock {
+    /// K-weighted mean square, summed over L and R
+    pub power: f32,
+    /// Highest 4x interpolated sample, linear
+    pub true_peak: f32,
+}
+
+/// Where the onset detector listens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnsetSource {
+    #[default]
+    Output,
+    Input,
+}
+
+impl OnsetSource {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(Self::Output),
+            1 => Some(Self::Input),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Self::Output => 0,
+            Self::Input => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Output => "OUT",
+            Self::Input => "IN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OnsetSettings {
+    /// Script run on each onset, 1-8, or 0 for none
+    pub script: usize,
+    pub source: OnsetSource,
+    /// Detection threshold, 1-100 (lower fires on softer transients)
+    pub threshold: u8,
+}
+
+impl Default for OnsetSettings {
+    fn default() -> Self {
+        Self {
+            script: 0,
+            source: OnsetSource::Output,
+            threshold: 50,
+        }
+    }
+}
+
+/// Where a voice leaves the engine instead of the master mix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutRoute {
+    /// Hardware output pair, 2 = outs 3-4, or 0 for the master mix. Pair 1 is
+    /// the master's own outs, so a voice can't be routed there.
+    pub pair: u8,
+    /// POST runs the voice through its own copy of the master FX; PRE sends
+    /// it to the pair dry
+    pub post: bool,
+}
+
+impl OutRoute {
+    pub fn label(&self) -> String {
+        match (self.pair, self.post) {
+            (0, _) => "MST".to_string(),
+            (pair, false) => format!("{}PR", pair),
+            (pair, true) => format!("{}PO", pair),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MixerData {
     pub vol_osc: i32,
@@ -207,6 +390,10 @@ pub struct MixerData {
     pub mute_pla: i32,
     pub mute_nos: i32,
     pub mute_smp: i32,
+    pub route_osc: OutRoute,
+    pub route_pla: OutRoute,
+    pub route_nos: OutRoute,
+    pub route_smp: OutRoute,
 }
 
 impl Default for MixerData {
@@ -224,6 +411,10 @@ impl Default for MixerData {
             mute_pla: 0,
             mute_nos: 0,
             mute_smp: 0,
+            route_osc: OutRoute::default(),
+            route_pla: OutRoute::default(),
+            route_nos: OutRoute::default(),
+            route_smp: OutRoute::default(),
         }
     }
 }
@@ -6,6 +6,7 @@ pub const OSC_ADDR: &str = "127.0.0.1:57110";
 
 pub const MONOKIT_NODE_ID: i32 = 1000;
 pub const SPECTRUM_BANDS: usize = 15;
+pub const SPECTROGRAM_BANDS: usize = 64;
 pub const SCOPE_SAMPLES: usize = 128;
 
 pub const TIER_SILENT: u8 = 0;
@@ -1,5 +1,5 @@
 use rosc::OscType;
-use super::audio_types::{MeterData, VoiceMeterData, SpectrumData, ScopeData, CpuData, CompressorData};
+use super::audio_types::{MeterData, VoiceMeterData, SpectrumData, ScopeData, CpuData, CompressorData, LoudnessBlock};
 use super::script_types::SyncMode;
 
 #[derive(Debug, Clone)]
@@ -23,6 +23,18 @@ pub enum MetroCommand {
     SetRecordingPath(String),
     SetSlewTime(f32),
     SetParamSlew(String, f32),
+    SnapSave(u8),
+    SnapLoad(u8),
+    SnapMorph(u8, u8, i32),
+    SnapGlide(u8, u64),
+    /// Mutation amount, voice group and RNG seed drawn by the calling script
+    Mutate(i32, crate::preset::patch::PatchGroup, u64),
+    Breed(u8, u8, u64),
+    MutKeep,
+    MutDiscard,
+    MutBack,
+    /// Answered with the live param values once every command sent before it has been handled
+    Flush(std::sync::mpsc::Sender<crate::snapshot::Snapshot>),
     SetGate(f32),
     SetEnvGate(String, f32),
     Shutdown,
@@ -41,9 +53,18 @@ pub enum MetroCommand {
     GetTriggerCount,
     ResetTriggerCount,
     SendScopeRate(f32),
+    /// Run or pause the spectrogram synth, which only works while its page is shown
+    SetSpectrogramRunning(bool),
+    /// Audition a file on the preview voice (path, channels)
+    StartPreview(String, u16),
+    StopPreview,
     Error(String),
     QueryAudioOutDevices,
     SetAudioOutDevice(String),
+    SetOutputChannels(u8),
+    /// REC.STEMS into a folder under the directory; true waits for the next metro tick
+    StartStemRecording(String, bool),
+    StopStemRecording(bool),
 }
 
 #[derive(Debug, Clone)]
@@ -56,12 +77,21 @@ pub enum MetroEvent {
     ScopeUpdate(ScopeData),
     CpuUpdate(CpuData),
     CompressorUpdate(CompressorData),
+    LoudnessUpdate(LoudnessBlock),
+    /// One spectrogram frame, SPECTROGRAM_BANDS amplitudes
+    SpectrogramUpdate(Vec<f32>),
+    /// The onset detector heard a transient
+    Onset,
     ScReady,
     AudioDeviceList { current: String, devices: Vec<String> },
     RestartScWithDevice(String),
+    RestartScWithChannels(u8),
     Error(String),
     StartRecordingDirect(String),
     StopRecordingDirect,
+    /// Stems start now, at this BPM
+    StartStemRecordingDirect(String, f32),
+    StopStemRecordingDirect,
     SetRecordingPathDirect(String),
 }
 
@@ -79,6 +109,10 @@ pub struct MetroState {
     pub interval_ms: u64,
     pub active: bool,
     pub script_index: usize,
+    /// Live param values and snapshot slots, also updated by the metro thread
+    pub snapshots: crate::snapshot::SharedSnapshots,
+    /// Random state shared by every thread that runs commands
+    pub rng: crate::rng::SharedRng,
 }
 
 impl Default for MetroState {
@@ -87,6 +121,8 @@ impl Default for MetroState {
             interval_ms: 500,
             active: false,
             script_index: 8,
+            snapshots: Default::default(),
+            rng: Default::default(),
         }
     }
 }
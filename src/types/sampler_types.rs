@@ -4,6 +4,13 @@ pub const SAMPLER_BUS: i32 = 21;
 pub const SAMPLER_NODE_ID: i32 = 1005;
 pub const SAMPLER_MAX_SLOTS: usize = 128;
 pub const SAMPLER_BUFFER_BASE: u32 = 100;
+/// Buffer and node used by the library browser's preview voice
+pub const PREVIEW_BUFFER_ID: u32 = 228;
+pub const PREVIEW_NODE_ID: i32 = 1006;
+/// REC.STEMS buffers, one per stem from here on, clear of the kit and preview
+pub const STEM_BUFFER_BASE: u32 = 229;
+/// Buffer REC writes the master through
+pub const RECORDER_BUFFER_ID: u32 = 99;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SamplerMode {
@@ -45,6 +52,9 @@ pub struct SamplerPlaybackParams {
     pub sustain_mode: bool,
     pub rate_mod: i16,
     pub pitch_mod: i16,
+    /// Time-stretch to the metro tempo (pitch and rate stay independent)
+    #[serde(default)]
+    pub stretch: bool,
 }
 
 impl Default for SamplerPlaybackParams {
@@ -63,6 +73,7 @@ impl Default for SamplerPlaybackParams {
             sustain_mode: false,
             rate_mod: 0,
             pitch_mod: 0,
+            stretch: false,
         }
     }
 }
@@ -122,6 +133,14 @@ pub struct SamplerState {
     pub total_frames: Option<usize>,
     pub onset_sensitivity: u32,
     pub onset_min_spacing_ms: f32,
+    #[serde(default)]
+    pub snap_mode: u8,
+    /// Tempo the loaded loop was recorded at (filename, library index or detection)
+    #[serde(default)]
+    pub source_bpm: Option<f32>,
+    /// Decoded slice file for S.SNAP, cleared on KIT and S.SLICE
+    #[serde(skip)]
+    pub snap_source: Option<crate::sampler::slice::SnapSource>,
 }
 
 impl Default for SamplerState {
@@ -139,6 +158,9 @@ impl Default for SamplerState {
             total_frames: None,
             onset_sensitivity: 5,
             onset_min_spacing_ms: 50.0,
+            snap_mode: 0,
+            source_bpm: None,
+            snap_source: None,
         }
     }
 }
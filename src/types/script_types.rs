@@ -9,9 +9,15 @@ pub enum SyncMode {
     MidiClock = 1,
 }
 
+/// Most lines a script can hold
+pub const MAX_SCRIPT_LINES: usize = 32;
+/// Script length before it became configurable, and the default
+pub const DEFAULT_SCRIPT_LINES: usize = 8;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Script {
-    pub lines: [String; 8],
+    #[serde(with = "BigArray")]
+    pub lines: [String; MAX_SCRIPT_LINES],
     pub j: i16,
     pub k: i16,
 }
@@ -19,22 +25,20 @@ pub struct Script {
 impl Default for Script {
     fn default() -> Self {
         Self {
-            lines: [
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-            ],
+            lines: std::array::from_fn(|_| String::new()),
             j: 0,
             k: 0,
         }
     }
 }
 
+impl Script {
+    /// Lines up to and including the last non-empty one
+    pub fn used_len(&self) -> usize {
+        self.lines.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Variables {
     pub a: i16,
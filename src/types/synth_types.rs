@@ -4,6 +4,15 @@ pub const NOISE_BUS: i32 = 18;
 pub const PLAITS_MAIN_BUS: i32 = 19;
 pub const PLAITS_AUX_BUS: i32 = 20;
 
+/// Hardware outputs the engine can open. scsynth numbers its private busses
+/// after the hardware outs and ins, so 12 outs plus 2 ins stay below PRIMARY_BUS.
+pub const MAX_OUTPUT_CHANNELS: u8 = 12;
+
+/// Configured output count, rounded down to whole pairs the engine supports
+pub fn output_channel_count(configured: u8) -> u8 {
+    (configured.clamp(2, MAX_OUTPUT_CHANNELS) / 2) * 2
+}
+
 pub const NOISE_NODE_ID: i32 = 1000;
 pub const MOD_NODE_ID: i32 = 1001;
 pub const PRIMARY_NODE_ID: i32 = 1002;
@@ -12,6 +21,13 @@ pub const PLAITS_NODE_ID: i32 = 1004;
 
 pub const SPECTRUM_NODE_ID: i32 = 1010;
 pub const SCOPE_NODE_ID: i32 = 1011;
+pub const ONSET_NODE_ID: i32 = 1012;
+pub const LOUDNESS_NODE_ID: i32 = 1013;
+pub const SPECTROGRAM_NODE_ID: i32 = 1014;
+
+/// OUT.ROUTE POST nodes, one per voice from here: osc, noise, Plaits, sampler.
+/// monokit_main runs and pauses them itself, so this must match its routeNode.
+pub const ROUTE_FX_NODE_ID: i32 = 1020;
 
 pub struct VoiceSynths {
     pub noise_node: i32,
@@ -49,6 +65,7 @@ pub fn route_param_to_node(param: &str) -> i32 {
         "s_rate" | "s_pitch" | "s_fine" | "s_direction" | "s_loop" |
         "s_startFrame" | "s_endFrame" | "s_atk" | "s_dec" | "s_rel" | "s_sust" |
         "s_ratemod" | "s_pitchmod" |
+        "s_stretch" | "s_srcBpm" | "s_tempoBpm" |
         "s_bufnum" | "t_gate_sampler" => super::sampler_types::SAMPLER_NODE_ID,
 
         "sf_cut" | "sf_res" | "sf_type" |
@@ -57,6 +74,10 @@ pub fn route_param_to_node(param: &str) -> i32 {
         "srings_pit" | "srings_strc" | "srings_brit" | "srings_damp" |
         "srings_pos" | "srings_mode" | "srings_wet" => super::sampler_types::SAMPLER_NODE_ID,
 
+        "onset_src" | "onset_thresh" => ONSET_NODE_ID,
+
+        "scopeGain" | "scope_trig" | "scope_level" | "scope_hold" | "scope_xy" | "scope_src" | "t_scope" => SCOPE_NODE_ID,
+
         _ => MAIN_NODE_ID,
     }
 }
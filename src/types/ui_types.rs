@@ -17,10 +17,13 @@ pub enum Page {
     Variables,
     Notes,
     Scope,
+    Spectrogram,
+    Waveform,
+    Library,
     Help,
 }
 
-pub const NAVIGABLE_PAGES: [Page; 15] = [
+pub const NAVIGABLE_PAGES: [Page; 18] = [
     Page::Live,
     Page::Script1,
     Page::Script2,
@@ -36,6 +39,9 @@ pub const NAVIGABLE_PAGES: [Page; 15] = [
     Page::Variables,
     Page::Notes,
     Page::Scope,
+    Page::Spectrogram,
+    Page::Waveform,
+    Page::Library,
 ];
 
 impl Page {
@@ -56,6 +62,9 @@ impl Page {
             Page::Variables => "V",
             Page::Notes => "N",
             Page::Scope => "S",
+            Page::Spectrogram => "F",
+            Page::Waveform => "W",
+            Page::Library => "B",
             Page::Help => "HELP",
         }
     }
@@ -94,6 +94,57 @@ pub fn samples_to_braille(
         .collect()
 }
 
+/// Convert min/max envelope pairs to a filled braille grid
+///
+/// Each dot column is filled between its min and max, which suits overviews
+/// of whole sample files where many samples collapse into one column.
+pub fn envelope_to_braille(
+    envelope: &[(f32, f32)],
+    width_chars: usize,
+    height_chars: usize,
+) -> Vec<Vec<char>> {
+    if envelope.is_empty() || width_chars == 0 || height_chars == 0 {
+        return vec![vec![char::from_u32(BRAILLE_BASE).unwrap(); width_chars]; height_chars];
+    }
+
+    let height_dots = height_chars * 4;
+    let width_dots = width_chars * 2;
+    let mut grid = vec![vec![0u8; width_chars]; height_chars];
+
+    let to_row = |v: f32| {
+        let normalized = (1.0 - v.clamp(-1.0, 1.0)) / 2.0;
+        ((normalized * (height_dots - 1) as f32).round() as usize).min(height_dots - 1)
+    };
+
+    for dot_col in 0..width_dots {
+        let idx = (dot_col * envelope.len()) / width_dots;
+        let (min, max) = envelope.get(idx).copied().unwrap_or((0.0, 0.0));
+        let (top, bottom) = (to_row(max), to_row(min));
+
+        let char_col = dot_col / 2;
+        let is_right = dot_col % 2 == 1;
+        for row in top..=bottom {
+            let bits = if is_right {
+                RIGHT_DOTS[row % 4]
+            } else {
+                LEFT_DOTS[row % 4]
+            };
+            grid[row / 4][char_col] |= bits;
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|bits| {
+                    char::from_u32(BRAILLE_BASE | bits as u32)
+                        .unwrap_or(char::from_u32(BRAILLE_BASE).unwrap())
+                })
+                .collect()
+        })
+        .collect()
+}
+
 /// Block character rendering (▁▂▃▄▅▆▇█)
 const BLOCK_CHARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
@@ -276,6 +327,145 @@ fn bits_to_quadrant(bits: u8) -> char {
     }
 }
 
+/// Plot sample pairs as X/Y (Lissajous) points, joining each to the next
+///
+/// # Arguments
+/// * `x` - Horizontal positions in range -1.0 to 1.0 (left channel)
+/// * `y` - Vertical positions in range -1.0 to 1.0 (right channel)
+pub fn xy_to_braille(
+    x: &[f32],
+    y: &[f32],
+    width_chars: usize,
+    height_chars: usize,
+) -> Vec<Vec<char>> {
+    if x.is_empty() || width_chars == 0 || height_chars == 0 {
+        return vec![vec![char::from_u32(BRAILLE_BASE).unwrap(); width_chars]; height_chars];
+    }
+
+    let width_dots = width_chars * 2;
+    let height_dots = height_chars * 4;
+    let mut grid = vec![vec![0u8; width_chars]; height_chars];
+
+    let to_dot = |v: f32, dots: usize, flip: bool| {
+        let normalized = (v.clamp(-1.0, 1.0) + 1.0) / 2.0;
+        let normalized = if flip { 1.0 - normalized } else { normalized };
+        normalized * (dots - 1) as f32
+    };
+
+    let mut prev: Option<(f32, f32)> = None;
+    for (&sx, &sy) in x.iter().zip(y) {
+        let point = (to_dot(sx, width_dots, false), to_dot(sy, height_dots, true));
+        let (from, to) = (prev.unwrap_or(point), point);
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            let col = (from.0 + (to.0 - from.0) * t).round() as usize;
+            let row = (from.1 + (to.1 - from.1) * t).round() as usize;
+            let bits = if col % 2 == 1 { RIGHT_DOTS[row % 4] } else { LEFT_DOTS[row % 4] };
+            grid[row / 4][col / 2] |= bits;
+        }
+        prev = Some(point);
+    }
+
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|bits| char::from_u32(BRAILLE_BASE | bits as u32).unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+/// Level bars filled from the bottom (▁▂▃▄▅▆▇█), for spectrum displays
+///
+/// # Arguments
+/// * `levels` - Bar heights in range 0.0 to 1.0, spread across the width
+pub fn levels_to_blocks(
+    levels: &[f32],
+    width_chars: usize,
+    height_chars: usize,
+) -> Vec<Vec<char>> {
+    let mut grid = vec![vec![' '; width_chars]; height_chars];
+    if levels.is_empty() || width_chars == 0 || height_chars == 0 {
+        return grid;
+    }
+
+    let height_levels = height_chars * 8;
+    for col in 0..width_chars {
+        let level = levels[(col * levels.len()) / width_chars].clamp(0.0, 1.0);
+        let filled = (level * height_levels as f32).round() as usize;
+        for (row, line) in grid.iter_mut().enumerate() {
+            // Levels below the top of this row
+            let below = (height_chars - 1 - row) * 8;
+            line[col] = BLOCK_CHARS[filled.saturating_sub(below).min(8)];
+        }
+    }
+    grid
+}
+
+/// Ordered dither thresholds for the 2x4 braille dots, [row][column]
+const DITHER: [[f32; 2]; 4] = [[0.0625, 0.5625], [0.8125, 0.3125], [0.1875, 0.6875], [0.9375, 0.4375]];
+
+/// Waterfall of level frames as dithered braille, one frame per dot row
+///
+/// # Arguments
+/// * `frames` - Level frames (0.0 to 1.0 per band), first frame on top
+///
+/// Louder bands light more of their dots, so four frames share a text row.
+pub fn heatmap_to_braille(
+    frames: &[Vec<f32>],
+    width_chars: usize,
+    height_chars: usize,
+) -> Vec<Vec<char>> {
+    let mut grid = vec![vec![0u8; width_chars]; height_chars];
+    let width_dots = width_chars * 2;
+
+    for (dot_row, frame) in frames.iter().take(height_chars * 4).enumerate() {
+        if frame.is_empty() {
+            continue;
+        }
+        for dot_col in 0..width_dots {
+            let level = frame[(dot_col * frame.len()) / width_dots];
+            let is_right = dot_col % 2 == 1;
+            if level > DITHER[dot_row % 4][dot_col % 2] {
+                let bits = if is_right { RIGHT_DOTS[dot_row % 4] } else { LEFT_DOTS[dot_row % 4] };
+                grid[dot_row / 4][dot_col / 2] |= bits;
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|bits| char::from_u32(BRAILLE_BASE | bits as u32).unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+/// Shade characters from quiet to loud
+const SHADE_CHARS: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Waterfall of level frames as shade blocks, one frame per text row
+pub fn heatmap_to_shades(
+    frames: &[Vec<f32>],
+    width_chars: usize,
+    height_chars: usize,
+) -> Vec<Vec<char>> {
+    let mut grid = vec![vec![' '; width_chars]; height_chars];
+
+    for (row, frame) in frames.iter().take(height_chars).enumerate() {
+        if frame.is_empty() {
+            continue;
+        }
+        for col in 0..width_chars {
+            let level = frame[(col * frame.len()) / width_chars].clamp(0.0, 1.0);
+            grid[row][col] = SHADE_CHARS[((level * 4.0).round() as usize).min(4)];
+        }
+    }
+    grid
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +506,47 @@ mod tests {
         // Bottom row should have dots
         assert!(result[3].iter().any(|&c| c != '⠀'));
     }
+
+    #[test]
+    fn test_envelope_fills_between_min_and_max() {
+        let envelope = vec![(-1.0, 1.0); 4];
+        let result = envelope_to_braille(&envelope, 2, 2);
+        // Full-scale envelope fills every dot
+        assert!(result.iter().all(|row| row.iter().all(|&c| c == '⣿')));
+    }
+
+    #[test]
+    fn test_envelope_silence_is_centered() {
+        let envelope = vec![(0.0, 0.0); 4];
+        let result = envelope_to_braille(&envelope, 2, 2);
+        assert!(result[0].iter().any(|&c| c != '⠀') || result[1].iter().any(|&c| c != '⠀'));
+    }
+
+    #[test]
+    fn test_xy_plots_left_against_right() {
+        // In phase: a diagonal from bottom-left to top-right
+        let ramp: Vec<f32> = (0..16).map(|i| i as f32 / 7.5 - 1.0).collect();
+        let result = xy_to_braille(&ramp, &ramp, 4, 2);
+        assert!(result[1][0] != '⠀' && result[0][3] != '⠀');
+        assert_eq!(result[0][0], '⠀');
+        assert_eq!(result[1][3], '⠀');
+    }
+
+    #[test]
+    fn test_level_bars_fill_from_the_bottom() {
+        let result = levels_to_blocks(&[0.0, 0.5, 1.0], 3, 2);
+        assert_eq!(result[0], vec![' ', ' ', '█']);
+        assert_eq!(result[1], vec![' ', '█', '█']);
+    }
+
+    #[test]
+    fn test_heatmap_newest_frame_on_top() {
+        let frames = vec![vec![1.0; 4], vec![0.0; 4]];
+        let braille = heatmap_to_braille(&frames, 2, 1);
+        // Full first dot row only: dots 1 and 4
+        assert!(braille[0].iter().all(|&c| c == '⠉'));
+
+        let shades = heatmap_to_shades(&frames, 2, 3);
+        assert_eq!(shades, vec![vec!['█', '█'], vec![' ', ' '], vec![' ', ' ']]);
+    }
 }
@@ -1,4 +1,7 @@
+use ratatui::widgets::block::{Position, Title};
 use ratatui::{prelude::*, widgets::*};
+use crate::commands::registry::COMMAND_REGISTRY;
+use crate::ui::syntax_highlight::{char_kinds, TokenKind};
 
 pub fn render_footer(app: &crate::App) -> Paragraph<'static> {
     let footer_text = if app.search_mode {
@@ -49,28 +52,58 @@ pub fn render_footer(app: &crate::App) -> Paragraph<'static> {
         let pos = app.cursor_position;
         let fg = app.theme.foreground;
 
-        let before: String = input.chars().take(pos).collect();
-        let cursor_char = input.chars().nth(pos).unwrap_or(' ');
-        let after: String = input.chars().skip(pos + 1).collect();
-
         let (cursor_bg, cursor_fg) = if app.color_mode == crate::types::ColorMode::Color256 {
             (Color::White, Color::Black)
         } else {
             (app.theme.highlight_bg, app.theme.highlight_fg)
         };
 
-        let input_line = Line::from(vec![
-            Span::styled("> ", Style::default().fg(fg)),
-            Span::styled(before, Style::default().fg(fg)),
-            Span::styled(
-                cursor_char.to_string(),
-                Style::default().bg(cursor_bg).fg(cursor_fg),
-            ),
-            Span::styled(after, Style::default().fg(fg)),
-        ]);
+        // The token a script error points at, while the error is shown
+        let error_chars = match (&app.script_error, app.script_error_token) {
+            (Some(_), Some(token)) if app.is_script_page() => token_chars(input, token),
+            _ => None,
+        };
+
+        let syntax = if app.show_syntax_highlight { char_kinds(input) } else { Vec::new() };
+        // The word before the cursor isn't flagged while it could still become a command
+        let chars: Vec<char> = input.chars().collect();
+        let is_break = |c: &char| c.is_whitespace() || matches!(c, ';' | ':');
+        let typed_start = chars[..pos.min(chars.len())].iter().rposition(is_break).map_or(0, |p| p + 1);
+        let typed: String = chars[typed_start..pos.min(chars.len())].iter().collect();
+        let still_typing = chars.get(pos).is_none_or(is_break)
+            && !typed.is_empty()
+            && COMMAND_REGISTRY.keys().any(|name| name.starts_with(typed.to_uppercase().as_str()));
+
+        let mut spans = vec![Span::styled("> ", Style::default().fg(fg))];
+        let mut run = String::new();
+        let mut run_style = Style::default().fg(fg);
+        for (i, c) in input.chars().chain(std::iter::once(' ')).enumerate() {
+            if i == input.chars().count() && i != pos {
+                break;
+            }
+            let style = if i == pos {
+                Style::default().bg(cursor_bg).fg(cursor_fg)
+            } else if error_chars.as_ref().is_some_and(|range| range.contains(&i)) {
+                Style::default().fg(app.theme.error).add_modifier(Modifier::UNDERLINED)
+            } else {
+                match syntax.get(i) {
+                    Some(TokenKind::Unknown) if still_typing && (typed_start..pos).contains(&i) => Style::default().fg(fg),
+                    Some(kind) => Style::default().fg(kind.color(&app.theme).unwrap_or(fg)),
+                    None => Style::default().fg(fg),
+                }
+            };
+            if style != run_style && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), run_style));
+            }
+            run_style = style;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, run_style));
+        }
 
         vec![
-            input_line,
+            Line::from(spans),
             Line::from(Span::styled(
                 "[ ] NAV  ESC HELP  F1-F12  'Q' EXIT",
                 Style::default().fg(app.theme.secondary),
@@ -78,11 +111,36 @@ pub fn render_footer(app: &crate::App) -> Paragraph<'static> {
         ]
     };
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+    // Signature of the command being typed, on the bottom border
+    if let Some(hint) = app.input_hint().filter(|_| !app.search_mode) {
+        block = block.title(
+            Title::from(Span::styled(format!(" {} ", hint), Style::default().fg(app.theme.secondary)))
+                .alignment(Alignment::Left)
+                .position(Position::Bottom),
+        );
+    }
+
     Paragraph::new(footer_text)
         .style(Style::default().bg(app.theme.background).fg(app.theme.foreground))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(app.theme.border))
-        )
+        .block(block)
+}
+
+/// Char range of the `index`th whitespace-separated token of `line`
+pub fn token_chars(line: &str, index: usize) -> Option<std::ops::Range<usize>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in line.chars().chain(std::iter::once(' ')).enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                tokens.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens.into_iter().nth(index)
 }
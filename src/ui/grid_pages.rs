@@ -0,0 +1,200 @@
+//! Custom grid pages from `[[grid_pages]]` in config.toml. Each page lists
+//! parameter commands in order; the values come from the live parameter map
+//! the snapshot bank keeps of everything sent to SuperCollider.
+
+use crate::commands::resolve_to_short;
+use crate::commands::registry::{ParamRange, COMMAND_REGISTRY};
+use crate::config::GridPageConfig;
+use crate::snapshot::{ParamValue, Snapshot};
+use crate::types::ParamActivity;
+
+/// Built-in grid modes, in GRID.MODE order
+pub const GRID_MODE_NAMES: [&str; 7] = ["LABELS", "ICONS", "FX VIZ", "MIXER", "FX VIZ 2", "SAMPLER", "LOUDNESS"];
+
+/// GRID.MODE of the first custom page, right after the built-in ones
+pub const FIRST_CUSTOM_MODE: u8 = GRID_MODE_NAMES.len() as u8;
+
+/// Two cells per grid row, six rows
+pub const CELLS_PER_PAGE: usize = 12;
+
+/// Cell width: a 4-char label, a space and the value
+pub const CELL_WIDTH: usize = 14;
+const VALUE_WIDTH: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridFormat {
+    /// Position within the command's range
+    Bar,
+    Number,
+    /// The PARAM ACTIVITY icon, lit on change, then the value
+    Icon,
+}
+
+impl GridFormat {
+    fn parse(text: &str) -> Option<Self> {
+        match text.to_lowercase().as_str() {
+            "bar" => Some(GridFormat::Bar),
+            "number" | "num" => Some(GridFormat::Number),
+            "icon" => Some(GridFormat::Icon),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridCell {
+    /// Command as typed, short form
+    pub cmd: String,
+    pub label: String,
+    /// OSC parameter the command sets
+    pub param: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub format: GridFormat,
+    /// Index into ParamActivity/GRID_ICONS, if the command has one
+    pub activity: Option<usize>,
+}
+
+impl GridCell {
+    /// Value column, VALUE_WIDTH chars; "--" until the parameter has been sent
+    pub fn value_text(&self, value: Option<ParamValue>, ascii: bool) -> String {
+        match self.format {
+            GridFormat::Bar => {
+                let level = value.map(|v| self.position(v.as_f32())).unwrap_or(0.0);
+                let filled = (level * VALUE_WIDTH as f32).round() as usize;
+                let (full, empty) = if ascii { ("#", ".") } else { ("█", "·") };
+                format!("{}{}", full.repeat(filled), empty.repeat(VALUE_WIDTH - filled))
+            }
+            GridFormat::Number => format!("{:>width$}", value.map(format_number).unwrap_or_else(|| "--".to_string()), width = VALUE_WIDTH),
+            // The icon itself is drawn separately in the activity color
+            GridFormat::Icon => format!("{:>width$}", value.map(format_number).unwrap_or_else(|| "--".to_string()), width = VALUE_WIDTH - 2),
+        }
+    }
+
+    /// Frequency-like ranges are shown and dragged on a log scale
+    fn is_log(&self) -> bool {
+        self.min > 0.0 && self.max / self.min >= 100.0
+    }
+
+    /// Mouse target for the bar, `start` columns in
+    pub fn bar(&self, start: u16) -> Option<BarTarget> {
+        if self.format != GridFormat::Bar {
+            return None;
+        }
+        let (min, max) = ParamRange { param: self.param, min: self.min, max: self.max }.typed();
+        Some(BarTarget { cmd: self.cmd.clone(), start, width: VALUE_WIDTH as u16, min, max, log: self.is_log() })
+    }
+
+    /// 0.0-1.0 within the range; log for frequency-like ranges
+    fn position(&self, value: f32) -> f32 {
+        let position = if self.is_log() {
+            (value.max(self.min) / self.min).ln() / (self.max / self.min).ln()
+        } else if self.max > self.min {
+            (value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        };
+        position.clamp(0.0, 1.0)
+    }
+}
+
+/// A parameter bar on the Live grid that can be dragged with the mouse
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarTarget {
+    pub cmd: String,
+    /// Screen column of the bar's first cell
+    pub start: u16,
+    pub width: u16,
+    /// Argument range of the command
+    pub min: f32,
+    pub max: f32,
+    pub log: bool,
+}
+
+impl BarTarget {
+    /// Command argument for the mouse at `column`: the ends of the bar are the
+    /// ends of the range
+    pub fn value_at(&self, column: u16) -> i32 {
+        let span = self.width.saturating_sub(1).max(1) as f32;
+        let position = (column.saturating_sub(self.start) as f32 / span).clamp(0.0, 1.0);
+        let value = if self.log {
+            self.min * (self.max / self.min).powf(position)
+        } else {
+            self.min + (self.max - self.min) * position
+        };
+        value.round() as i32
+    }
+
+    /// The command line a drag to `column` sends
+    pub fn command_at(&self, column: u16) -> String {
+        format!("{} {}", self.cmd, self.value_at(column))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridPage {
+    pub name: String,
+    pub cells: Vec<GridCell>,
+}
+
+impl GridPage {
+    /// Current values of the page's cells, in order
+    pub fn values(&self, live: &Snapshot) -> Vec<Option<ParamValue>> {
+        self.cells.iter().map(|cell| live.get(cell.param).copied()).collect()
+    }
+}
+
+/// Build the pages from config. Entries that can't be shown are dropped and
+/// reported; a page left with nothing to show is dropped.
+pub fn pages_from_config(config: &[GridPageConfig]) -> (Vec<GridPage>, Vec<String>) {
+    let mut pages = Vec::new();
+    let mut errors = Vec::new();
+
+    for page_config in config {
+        let name = page_config.name.trim().to_uppercase();
+        let mut cells = Vec::new();
+        for entry in &page_config.params {
+            let cmd = entry.cmd.trim().to_uppercase();
+            let Some(range) = COMMAND_REGISTRY.get(cmd.as_str()).and_then(|def| def.range) else {
+                errors.push(format!("GRID PAGE {}: {} SETS NO PARAMETER", name, cmd));
+                continue;
+            };
+            let Some(format) = GridFormat::parse(entry.format.trim()) else {
+                errors.push(format!("GRID PAGE {}: UNKNOWN FORMAT \"{}\" (BAR, NUMBER, ICON)", name, entry.format.to_uppercase()));
+                continue;
+            };
+            let short = resolve_to_short(&cmd);
+            let label = entry.label.as_deref().unwrap_or(&short).trim().to_uppercase();
+            cells.push(GridCell {
+                cmd: short.clone(),
+                label: label.chars().take(4).collect(),
+                param: range.param,
+                min: range.min,
+                max: range.max,
+                format,
+                activity: ParamActivity::param_to_index(&short),
+            });
+        }
+        if cells.len() > CELLS_PER_PAGE {
+            errors.push(format!("GRID PAGE {}: ONLY {} PARAMS FIT", name, CELLS_PER_PAGE));
+            cells.truncate(CELLS_PER_PAGE);
+        }
+        if cells.is_empty() {
+            errors.push(format!("GRID PAGE {}: NO PARAMS", name));
+            continue;
+        }
+        pages.push(GridPage { name, cells });
+    }
+    (pages, errors)
+}
+
+/// Compact number for a cell: at most 7 chars
+fn format_number(value: ParamValue) -> String {
+    match value {
+        ParamValue::Int(v) => v.to_string(),
+        ParamValue::Float(v) if v.abs() >= 10000.0 => format!("{:.1}K", v / 1000.0),
+        ParamValue::Float(v) if v.abs() >= 100.0 => format!("{:.0}", v),
+        ParamValue::Float(v) if v.abs() >= 10.0 => format!("{:.1}", v),
+        ParamValue::Float(v) => format!("{:.2}", v),
+    }
+}
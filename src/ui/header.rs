@@ -81,6 +81,23 @@ fn calculate_full_nav_width() -> usize {
     width
 }
 
+/// Page under `offset` columns into the full navigation (header levels 3-4),
+/// for mouse clicks; the leading space is not counted
+pub fn nav_page_at(app: &crate::App, offset: usize) -> Option<Page> {
+    if app.current_page == Page::Help || app.header_level <= 2 {
+        return None;
+    }
+    let mut start = 0;
+    for page in NAVIGABLE_PAGES.iter() {
+        let width = page.name().len() + if *page == app.current_page { 2 } else { 0 };
+        if (start..start + width).contains(&offset) {
+            return Some(*page);
+        }
+        start += width + 1;
+    }
+    None
+}
+
 pub fn render_header(app: &crate::App, width: u16) -> Paragraph<'static> {
     let mut spans = vec![Span::raw(" ")];
 
@@ -217,9 +234,16 @@ pub fn render_header(app: &crate::App, width: u16) -> Paragraph<'static> {
     // Build right-aligned border title parts
     let mut title_parts = Vec::new();
 
-    // Add REC indicator if recording
-    if app.recording {
-        let elapsed_ms = app.recording_start
+    // Add REC indicator if recording, or STEMS during a REC.STEMS take
+    let take = if app.recording {
+        Some(("REC", app.recording_start))
+    } else if app.stem_folder.is_some() {
+        Some(("STEMS", app.stem_start))
+    } else {
+        None
+    };
+    if let Some((label, start)) = take {
+        let elapsed_ms = start
             .map(|start| start.elapsed().as_millis() as u64)
             .unwrap_or(0);
         let duration_secs = (elapsed_ms / 1000) as u64;
@@ -238,7 +262,7 @@ pub fn render_header(app: &crate::App, width: u16) -> Paragraph<'static> {
         let spinner_char = spinner[(beat % spinner.len() as u64) as usize];
 
         title_parts.push(Span::styled(
-            format!("{} REC {:02}:{:02}", spinner_char, mins, secs),
+            format!("{} {} {:02}:{:02}", spinner_char, label, mins, secs),
             Style::default().fg(app.theme.error).add_modifier(Modifier::BOLD),
         ));
     }
@@ -266,6 +290,24 @@ pub fn render_header(app: &crate::App, width: u16) -> Paragraph<'static> {
         }
     }
 
+    // Add short-term loudness: show at level 4 OR while LUFS.TARGET is riding the gain
+    if app.loudness.target.is_some() || app.header_level >= 4 {
+        if !title_parts.is_empty() {
+            title_parts.push(Span::raw("  "));
+        }
+
+        let lufs_color = if app.loudness.true_peak > crate::loudness::TARGET_CEILING_DB {
+            app.theme.error
+        } else {
+            app.theme.secondary
+        };
+
+        title_parts.push(Span::styled(
+            format!("LUFS {:>5}", crate::loudness::format_db(app.loudness.short_term)),
+            Style::default().fg(lufs_color),
+        ));
+    }
+
     // Add CPU indicator: show at level 4 OR if show_cpu is explicitly enabled
     if app.show_cpu || app.header_level >= 4 {
         // Add separator if REC or BPM is also showing
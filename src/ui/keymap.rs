@@ -0,0 +1,219 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+/// A key plus modifiers, normalized so config chords and terminal events compare equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            // Uppercase letters arrive as the shifted letter; store them as shift+letter
+            KeyCode::Char(c) if c.is_ascii_uppercase() => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse a chord such as "f5", "ctrl+]", "alt+shift+t" or "space"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        // A trailing '+' is the plus key itself ("ctrl++")
+        let (mods_text, key_text) = match text.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None => match text.rsplit_once('+') {
+                Some((mods, key)) if !key.is_empty() => (mods, key),
+                _ => ("", text),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in mods_text.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match name.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "opt" | "option" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("UNKNOWN MODIFIER \"{}\"", name.trim().to_uppercase())),
+            };
+        }
+
+        let code = parse_key_name(key_text).ok_or_else(|| format!("UNKNOWN KEY \"{}\"", key_text.to_uppercase()))?;
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// Display form used when listing bindings ("CTRL+SHIFT+1")
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("CTRL+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("ALT+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("SHIFT+");
+        }
+        let key = match self.code {
+            KeyCode::Char(' ') => "SPACE".to_string(),
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Enter => "ENTER".to_string(),
+            KeyCode::Tab => "TAB".to_string(),
+            KeyCode::BackTab => "BACKTAB".to_string(),
+            KeyCode::Esc => "ESC".to_string(),
+            KeyCode::Backspace => "BACKSPACE".to_string(),
+            KeyCode::Delete => "DELETE".to_string(),
+            KeyCode::Insert => "INSERT".to_string(),
+            KeyCode::Home => "HOME".to_string(),
+            KeyCode::End => "END".to_string(),
+            KeyCode::PageUp => "PAGEUP".to_string(),
+            KeyCode::PageDown => "PAGEDOWN".to_string(),
+            KeyCode::Up => "UP".to_string(),
+            KeyCode::Down => "DOWN".to_string(),
+            KeyCode::Left => "LEFT".to_string(),
+            KeyCode::Right => "RIGHT".to_string(),
+            other => format!("{:?}", other).to_uppercase(),
+        };
+        label.push_str(&key);
+        label
+    }
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let lower = name.trim().to_lowercase();
+    let mut chars = name.trim().chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&n).then_some(KeyCode::F(n));
+    }
+
+    Some(match lower.as_str() {
+        "space" => KeyCode::Char(' '),
+        "plus" => KeyCode::Char('+'),
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "esc" | "escape" => KeyCode::Esc,
+        "backspace" | "bs" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => return None,
+    })
+}
+
+/// What a bound key does: a UI action that has no command equivalent,
+/// or any monokit command line ("PAGE 3", "MUTE 2", "SCRIPT 3", "TR")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyAction {
+    Undo,
+    Redo,
+    NextPage,
+    PrevPage,
+    ToggleHelp,
+    /// Swallow the key, disabling its default binding
+    Ignore,
+    Command(String),
+}
+
+impl KeyAction {
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        match text.to_uppercase().as_str() {
+            "UNDO" => KeyAction::Undo,
+            "REDO" => KeyAction::Redo,
+            "PAGE.NEXT" => KeyAction::NextPage,
+            "PAGE.PREV" => KeyAction::PrevPage,
+            "HELP.TOGGLE" => KeyAction::ToggleHelp,
+            "" | "NONE" => KeyAction::Ignore,
+            _ => KeyAction::Command(text.to_string()),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            KeyAction::Undo => "UNDO".to_string(),
+            KeyAction::Redo => "REDO".to_string(),
+            KeyAction::NextPage => "PAGE.NEXT".to_string(),
+            KeyAction::PrevPage => "PAGE.PREV".to_string(),
+            KeyAction::ToggleHelp => "HELP.TOGGLE".to_string(),
+            KeyAction::Ignore => "NONE".to_string(),
+            KeyAction::Command(cmd) => cmd.to_uppercase(),
+        }
+    }
+}
+
+/// User key bindings from the `[keys]` section of config.toml.
+/// Bindings are checked before the built-in keys, so they can replace them.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    bindings: HashMap<KeyChord, KeyAction>,
+}
+
+impl KeyMap {
+    /// Build the map, returning an error line for every chord that didn't parse
+    pub fn from_config(keys: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut bindings = HashMap::new();
+        let mut errors = Vec::new();
+        for (chord, action) in keys {
+            match KeyChord::parse(chord) {
+                Ok(chord) => {
+                    bindings.insert(chord, KeyAction::parse(action));
+                }
+                Err(e) => errors.push(format!("KEYS: {}", e)),
+            }
+        }
+        (Self { bindings }, errors)
+    }
+
+    pub fn lookup(&self, key: &KeyEvent) -> Option<&KeyAction> {
+        if self.bindings.is_empty() {
+            return None;
+        }
+        self.bindings.get(&KeyChord::from_event(key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Bindings as "CHORD  ACTION" lines, sorted by chord
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .bindings
+            .iter()
+            .map(|(chord, action)| format!("  {:<14} {}", chord.label(), action.label()))
+            .collect();
+        lines.sort();
+        lines
+    }
+}
@@ -1,26 +1,30 @@
 pub mod braille;
 pub mod eq_curve;
-mod footer;
+pub mod footer;
+pub mod grid_pages;
 pub mod header;
+pub mod keymap;
 pub mod pages;
 pub mod search;
 pub mod search_highlight;
 pub mod state_highlight;
+pub mod syntax_highlight;
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
 use ratatui::{prelude::*, widgets::Block};
 use std::sync::mpsc;
 use std::time::Duration;
 
-use crate::output::OutputDecider;
 use crate::types::{MetroEvent, Page};
 
 use footer::render_footer;
 use header::render_header;
 use pages::{
     render_help_page, render_init_page, render_live_page, render_metro_page, render_notes_page,
-    render_pattern_page, render_scope_page, render_script_page, render_variables_page, HELP_CATEGORIES,
+    render_pattern_page, render_scope_page, render_script_page, render_spectrogram_page, render_variables_page,
+    render_waveform_page, render_library_page, HELP_CATEGORIES,
 };
 
 pub fn ui(f: &mut Frame, app: &crate::App) {
@@ -32,48 +36,31 @@ pub fn ui(f: &mut Frame, app: &crate::App) {
         area
     );
 
-    let is_help = app.current_page == Page::Help;
-    let is_pattern = app.current_page == Page::Pattern;
-    let needs_footer = app.search_mode || (!is_help && !is_pattern);
-
-    let chunks = if needs_footer {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(0),
-                Constraint::Length(3),
-            ])
-            .split(f.area())
-    } else {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(0),
-            ])
-            .split(f.area())
-    };
+    let chunks = page_layout(app, area);
+    let needs_footer = chunks.len() == 3;
 
     let header = render_header(app, chunks[0].width);
     f.render_widget(header, chunks[0]);
 
     let content = match app.current_page {
         Page::Live => render_live_page(app, chunks[1].width as usize, chunks[1].height as usize),
-        Page::Script1 => render_script_page(app, 1),
-        Page::Script2 => render_script_page(app, 2),
-        Page::Script3 => render_script_page(app, 3),
-        Page::Script4 => render_script_page(app, 4),
-        Page::Script5 => render_script_page(app, 5),
-        Page::Script6 => render_script_page(app, 6),
-        Page::Script7 => render_script_page(app, 7),
-        Page::Script8 => render_script_page(app, 8),
-        Page::Metro => render_metro_page(app),
-        Page::Init => render_init_page(app),
+        Page::Script1 => render_script_page(app, 1, chunks[1].height as usize),
+        Page::Script2 => render_script_page(app, 2, chunks[1].height as usize),
+        Page::Script3 => render_script_page(app, 3, chunks[1].height as usize),
+        Page::Script4 => render_script_page(app, 4, chunks[1].height as usize),
+        Page::Script5 => render_script_page(app, 5, chunks[1].height as usize),
+        Page::Script6 => render_script_page(app, 6, chunks[1].height as usize),
+        Page::Script7 => render_script_page(app, 7, chunks[1].height as usize),
+        Page::Script8 => render_script_page(app, 8, chunks[1].height as usize),
+        Page::Metro => render_metro_page(app, chunks[1].height as usize),
+        Page::Init => render_init_page(app, chunks[1].height as usize),
         Page::Pattern => render_pattern_page(app),
         Page::Variables => render_variables_page(app),
         Page::Notes => render_notes_page(app),
         Page::Scope => render_scope_page(app, chunks[1]),
+        Page::Spectrogram => render_spectrogram_page(app, chunks[1]),
+        Page::Waveform => render_waveform_page(app, chunks[1]),
+        Page::Library => render_library_page(app, chunks[1]),
         Page::Help => render_help_page(app, chunks[1].height as usize),
     };
     f.render_widget(content, chunks[1]);
@@ -89,6 +76,34 @@ pub fn ui(f: &mut Frame, app: &crate::App) {
     }
 }
 
+/// Header, page content and, when the page has the input line, the footer
+pub fn page_layout(app: &crate::App, area: Rect) -> std::rc::Rc<[Rect]> {
+    let is_help = app.current_page == Page::Help;
+    let is_pattern = app.current_page == Page::Pattern;
+    let is_waveform = app.current_page == Page::Waveform;
+    let is_library = app.current_page == Page::Library;
+    let needs_footer = app.search_mode || (!is_help && !is_pattern && !is_waveform && !is_library);
+
+    if needs_footer {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(area)
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ])
+            .split(area)
+    }
+}
+
 fn render_confirmation_dialog(f: &mut Frame, app: &crate::App, action: &crate::types::ConfirmAction) {
     use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
     use ratatui::layout::{Alignment, Constraint, Direction, Layout};
@@ -191,9 +206,20 @@ pub fn run_app<B: ratatui::backend::Backend>(
     metro_event_rx: mpsc::Receiver<MetroEvent>,
     sc_process: std::sync::Arc<std::sync::Mutex<crate::sc_process::ScProcess>>,
 ) -> Result<()> {
+    let mut mouse_captured = false;
     loop {
         app.clear_expired_error();
 
+        // MOUSE turns capture on and off while running
+        if app.mouse != mouse_captured {
+            mouse_captured = app.mouse;
+            if mouse_captured {
+                execute!(std::io::stdout(), EnableMouseCapture)?;
+            } else {
+                execute!(std::io::stdout(), DisableMouseCapture)?;
+            }
+        }
+
         // Check title timer and toggle if needed
         if app.title_timer_enabled {
             if let Some(last_toggle) = app.title_timer_last_toggle {
@@ -239,139 +265,22 @@ pub fn run_app<B: ratatui::backend::Backend>(
 
         // Process metro events BEFORE rendering so activity indicators update immediately
         while let Ok(event) = metro_event_rx.try_recv() {
-            match event {
-                MetroEvent::ExecuteScript(index) => {
-                    app.execute_script(index);
-                }
-                MetroEvent::ExecuteDelayed(command, script_index) => {
-                    app.execute_delayed_command(&command, script_index);
-                }
-                MetroEvent::MeterUpdate(meter_data) => {
-                    app.meter_data = meter_data;
-                }
-                MetroEvent::VoiceMeterUpdate(voice_meter_data) => {
-                    app.voice_meter_data = voice_meter_data;
-                }
-                MetroEvent::SpectrumUpdate(spectrum_data) => {
-                    app.spectrum_data = spectrum_data;
-                }
-                MetroEvent::ScopeUpdate(scope_data) => {
-                    app.scope_data = scope_data;
-                }
-                MetroEvent::CpuUpdate(cpu_data) => {
-                    app.cpu_data = cpu_data;
-                }
-                MetroEvent::CompressorUpdate(comp_data) => {
-                    // Guard against NaN/inf from SC - skip update if invalid
-                    if comp_data.input_level.is_finite() && comp_data.output_level.is_finite() && comp_data.gain_reduction_db.is_finite() {
-                        // Smooth compressor meters: fast attack (0.5), slow release (0.15)
-                        let attack = 0.5;
-                        let release = 0.15;
-
-                        let in_coef = if comp_data.input_level > app.compressor_data.input_level { attack } else { release };
-                        let out_coef = if comp_data.output_level > app.compressor_data.output_level { attack } else { release };
-                        let gr_coef = if comp_data.gain_reduction_db < app.compressor_data.gain_reduction_db { attack } else { release };
-
-                        app.compressor_data.input_level += in_coef * (comp_data.input_level - app.compressor_data.input_level);
-                        app.compressor_data.output_level += out_coef * (comp_data.output_level - app.compressor_data.output_level);
-                        app.compressor_data.gain_reduction_db += gr_coef * (comp_data.gain_reduction_db - app.compressor_data.gain_reduction_db);
-                    }
-                }
-                MetroEvent::ScReady => {
-                    if app.awaiting_audio_restart {
-                        app.add_output("AUDIO ENGINE ONLINE".to_string());
-                        app.awaiting_audio_restart = false;
-                    }
-                }
-                MetroEvent::AudioDeviceList { current, devices } => {
-                    // Store for numbered selection
-                    app.audio_device_current = current.clone();
-                    app.audio_devices = devices.clone();
-
-                    app.add_output(format!("CURRENT: {}", current.to_uppercase()));
-                    app.add_output("AVAILABLE DEVICES:".to_string());
-                    for (i, device) in devices.iter().enumerate() {
-                        app.add_output(format!("  {}: {}", i + 1, device));
-                    }
-                }
-                MetroEvent::RestartScWithDevice(device) => {
-                    let mut sc = sc_process.lock().unwrap();
-                    if let Err(e) = sc.restart_with_device(&device) {
-                        if app.should_output(crate::types::OutputCategory::Error) {
-                            app.add_output(format!("ERROR: {}", e));
-                        }
-                    } else {
-                        if let Err(e) = crate::config::save_audio_out_device(Some(device.clone())) {
-                            if app.should_output(crate::types::OutputCategory::Error) {
-                                app.add_output(format!("ERROR SAVING CONFIG: {}", e));
-                            }
-                        }
-                        app.awaiting_audio_restart = true;
-                    }
-                }
-                MetroEvent::Error(msg) => {
-                    if app.should_output(crate::types::OutputCategory::Error) {
-                        app.add_output(msg);
-                    }
-                }
-                MetroEvent::StartRecordingDirect(dir) => {
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        let mut sc = sc_process.lock().unwrap();
-                        match sc.start_recording(&dir, None) {
-                            Ok(_) => {
-                                if app.should_output(crate::types::OutputCategory::Confirm) {
-                                    app.add_output("RECORDING STARTED".to_string());
-                                }
-                            }
-                            Err(e) => {
-                                if app.should_output(crate::types::OutputCategory::Error) {
-                                    app.add_output(format!("ERROR: {}", e));
-                                }
-                            }
-                        }
-                    }
-                    #[cfg(not(feature = "scsynth-direct"))]
-                    {
-                        let _ = dir;
-                    }
-                }
-                MetroEvent::StopRecordingDirect => {
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        let mut sc = sc_process.lock().unwrap();
-                        match sc.stop_recording() {
-                            Ok(_) => {
-                                if app.should_output(crate::types::OutputCategory::Confirm) {
-                                    app.add_output("RECORDING STOPPED".to_string());
-                                }
-                            }
-                            Err(e) => {
-                                if app.should_output(crate::types::OutputCategory::Error) {
-                                    app.add_output(format!("ERROR: {}", e));
-                                }
-                            }
-                        }
-                    }
-                }
-                MetroEvent::SetRecordingPathDirect(path) => {
-                    #[cfg(feature = "scsynth-direct")]
-                    {
-                        let mut sc = sc_process.lock().unwrap();
-                        sc.set_recording_path_prefix(path);
-                    }
-                    #[cfg(not(feature = "scsynth-direct"))]
-                    {
-                        let _ = path;
-                    }
-                }
-            }
+            app.handle_metro_event(event, Some(&sc_process));
         }
 
+        app.refresh_waveform();
+        app.refresh_library();
+        app.refresh_spectrogram();
         terminal.draw(|f| ui(f, app))?;
 
         if event::poll(Duration::from_millis(16))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                let (width, height) = crossterm::terminal::size()?;
+                app.handle_mouse(mouse, Rect::new(0, 0, width, height));
+                continue;
+            }
+            if let Event::Key(key) = event {
                 // Windows reports both Press and Release events - only handle Press
                 if key.kind != KeyEventKind::Press {
                     continue;
@@ -422,6 +331,43 @@ pub fn run_app<B: ratatui::backend::Backend>(
                     }
                 }
 
+                if app.current_page == Page::Library
+                    && app.library.input != crate::sampler::library::LibraryInput::None
+                    && !has_alt
+                    && !key.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    let handled = match key.code {
+                        KeyCode::Esc => {
+                            app.library_input_cancel();
+                            true
+                        }
+                        KeyCode::Enter => {
+                            app.library_input_commit();
+                            true
+                        }
+                        KeyCode::Backspace => {
+                            app.library_input_backspace();
+                            true
+                        }
+                        KeyCode::Char(c) => {
+                            app.library_input_char(c);
+                            true
+                        }
+                        _ => false,
+                    };
+                    if handled {
+                        continue;
+                    }
+                }
+
+                // User bindings from [keys] take precedence over the built-in keys
+                if app.pending_confirmation.is_none() {
+                    if let Some(action) = app.keymap.lookup(&key).cloned() {
+                        app.run_key_action(action);
+                        continue;
+                    }
+                }
+
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Char('Q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                         let metro_active = {
@@ -461,6 +407,8 @@ pub fn run_app<B: ratatui::backend::Backend>(
                                     &app.notes,
                                     &app.script_mutes,
                                     &app.sampler_state,
+                                    &app.snapshots.lock().unwrap(),
+                                    &app.rng,
                                 );
                                 match crate::scene::save_scene(&name, &scene) {
                                     Ok(()) => {
@@ -512,6 +460,9 @@ pub fn run_app<B: ratatui::backend::Backend>(
                         && (app.is_script_page() || app.current_page == Page::Notes) => {
                         app.redo();
                     }
+                    KeyCode::Tab if !app.input.is_empty() && (app.current_page == Page::Live || app.is_script_page()) => {
+                        app.complete_input();
+                    }
                     KeyCode::Tab => {
                         if app.current_page == Page::Live {
                             app.show_grid_view = !app.show_grid_view;
@@ -579,6 +530,9 @@ pub fn run_app<B: ratatui::backend::Backend>(
                     KeyCode::F(12) => {
                         app.go_to_page(Page::Pattern);
                     }
+                    KeyCode::Esc if !is_help && app.current_page == Page::Library && !app.library.query.is_empty() => {
+                        app.library.set_query(String::new());
+                    }
                     KeyCode::Esc if is_help || app.current_page != Page::Pattern => {
                         app.toggle_help();
                     }
@@ -606,6 +560,15 @@ pub fn run_app<B: ratatui::backend::Backend>(
                     KeyCode::Char('s') if has_alt => {
                         app.go_to_page(Page::Scope);
                     }
+                    KeyCode::Char('f') if has_alt => {
+                        app.go_to_page(Page::Spectrogram);
+                    }
+                    KeyCode::Char('w') if has_alt => {
+                        app.go_to_page(Page::Waveform);
+                    }
+                    KeyCode::Char('b') if has_alt => {
+                        app.go_to_page(Page::Library);
+                    }
                     KeyCode::Char('1') if has_alt => {
                         app.go_to_page(Page::Script1);
                     }
@@ -661,7 +624,7 @@ pub fn run_app<B: ratatui::backend::Backend>(
                         app.toggle_script_mute(9);
                     }
                     KeyCode::Char('g') | KeyCode::Char('G') if key.modifiers.contains(KeyModifiers::CONTROL) && app.current_page == Page::Live => {
-                        app.grid_mode = (app.grid_mode + 1) % 6;
+                        app.grid_mode = (app.grid_mode + 1) % app.grid_mode_count();
                         if app.grid_mode == 1 || app.grid_mode == 0 {
                             app.trigger_grid_scramble();
                         }
@@ -720,6 +683,81 @@ pub fn run_app<B: ratatui::backend::Backend>(
                             }
                         }
                     }
+                    KeyCode::Left | KeyCode::Right if !is_help && app.current_page == Page::Waveform => {
+                        let width = crossterm::terminal::size().map(|(w, _)| w.saturating_sub(2) as usize).unwrap_or(80);
+                        let step = if key.modifiers.contains(KeyModifiers::SHIFT) { 8 } else { 1 };
+                        let columns = if key.code == KeyCode::Left { -step } else { step };
+                        app.waveform_view.move_cursor(columns, width);
+                    }
+                    KeyCode::Up if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_view.zoom_in();
+                    }
+                    KeyCode::Down if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_view.zoom_out();
+                    }
+                    KeyCode::Char(',') if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_select_slot(-1);
+                    }
+                    KeyCode::Char('.') if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_select_slot(1);
+                    }
+                    KeyCode::Char('m') if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_mark();
+                    }
+                    KeyCode::Char('x') | KeyCode::Delete | KeyCode::Backspace if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_unmark();
+                    }
+                    KeyCode::Char('g') if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_move_marker();
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter if !is_help && app.current_page == Page::Waveform => {
+                        app.waveform_select_at_cursor();
+                        app.waveform_audition();
+                    }
+                    KeyCode::Char(_) if !is_help && app.current_page == Page::Waveform => {}
+                    KeyCode::Up if !is_help && app.current_page == Page::Library => {
+                        app.library.move_selection(-1);
+                    }
+                    KeyCode::Down if !is_help && app.current_page == Page::Library => {
+                        app.library.move_selection(1);
+                    }
+                    KeyCode::PageUp if !is_help && app.current_page == Page::Library => {
+                        app.library.move_selection(-10);
+                    }
+                    KeyCode::PageDown if !is_help && app.current_page == Page::Library => {
+                        app.library.move_selection(10);
+                    }
+                    KeyCode::Left if !is_help && app.current_page == Page::Library => {
+                        app.library.collapse();
+                    }
+                    KeyCode::Right if !is_help && app.current_page == Page::Library => {
+                        app.library.expand();
+                    }
+                    KeyCode::Enter if !is_help && app.current_page == Page::Library => {
+                        app.library_load();
+                    }
+                    KeyCode::Char(' ') if !is_help && app.current_page == Page::Library => {
+                        app.library_preview();
+                    }
+                    KeyCode::Char('x') if !is_help && app.current_page == Page::Library => {
+                        app.library_stop_preview();
+                    }
+                    KeyCode::Char('k') if !is_help && app.current_page == Page::Library => {
+                        app.library_load_folder();
+                    }
+                    KeyCode::Char('/') if !is_help && app.current_page == Page::Library => {
+                        app.library_begin_search();
+                    }
+                    KeyCode::Char('t') if !is_help && app.current_page == Page::Library => {
+                        app.library_begin_tag();
+                    }
+                    KeyCode::Char('b') if !is_help && app.current_page == Page::Library => {
+                        app.library_detect_bpm();
+                    }
+                    KeyCode::Char('r') if !is_help && app.current_page == Page::Library => {
+                        app.rescan_library();
+                    }
+                    KeyCode::Char(_) | KeyCode::Backspace if !is_help && app.current_page == Page::Library => {}
                     KeyCode::Up if !is_help && app.is_script_page() => {
                         app.select_line_up();
                     }
@@ -31,6 +31,9 @@ pub const NAV_EDITING: HelpCategory = HelpCategory {
         "  ALT+I         INIT PAGE",
         "  ALT+P         PATTERN PAGE",
         "  ALT+S         SCOPE PAGE",
+        "  ALT+F         SPECTROGRAM PAGE",
+        "  ALT+W         WAVEFORM PAGE",
+        "  ALT+B         SAMPLE LIBRARY PAGE",
         "  ALT+V         VARIABLES PAGE",
         "  ALT+H         TOGGLE HELP",
         "",
@@ -42,8 +45,16 @@ pub const NAV_EDITING: HelpCategory = HelpCategory {
         "# OTHER",
         "  CTRL+Q        QUIT APP",
         "  CTRL+UP/DN    SCROLL REPL",
-        "  TAB           LIVE / GRID VIEW",
-        "  CTRL+G        CYCLE GRID MODE 0-5",
+        "  TAB           COMPLETE CMD / NAME",
+        "  TAB (EMPTY)   LIVE / GRID VIEW",
+        "  CTRL+G        CYCLE GRID MODES",
+        "",
+        "# CUSTOM KEYS ([KEYS] IN CONFIG.TOML)",
+        "  \"CTRL+1\" = \"MUTE 1\"  ANY COMMAND",
+        "  \"F11\" = \"NONE\"      DISABLE KEY",
+        "  UNDO REDO PAGE.NEXT PAGE.PREV",
+        "  HELP.TOGGLE    UI ACTIONS",
+        "  KEYS           RELOAD + LIST",
         "",
         "# SEARCH",
         "  CTRL+F        SEARCH MODE",
@@ -69,10 +80,15 @@ pub const NAV_EDITING: HelpCategory = HelpCategory {
         "# TRIGGER & VOLUME",
         "  TR            TRIGGER VOICE",
         "  OUT.VOL / VOL 0-1  MASTER VOL",
+        "  OUT.CH <2-12>  HARDWARE OUTPUTS",
+        "  OUT.ROUTE <V> <P> VOICE TO PAIR",
+        "  LUFS          LOUDNESS + TRUE PEAK",
+        "  LUFS.TARGET <DB> RIDE GAIN, 0=OFF",
         "  VCA <0|1>     0=DRONE 1=GATED",
         "  SLEW.ALL <MS>  SLEW MS (0-10000)",
         "  SLEW <P> <MS>  PER-PARAM SLEW",
         "  FLASH <MS>     HOLD MS (DEF 200)",
+        "  SCRIPT.LEN <N> SCRIPT LINES 8-32",
         "  RST            RESET TO DEF",
         "  LOAD.RST       RST MODE (0=KEEP 1=RST)",
         "  LOAD.RST <0|1> SCENE LOAD MODE",
@@ -140,6 +156,19 @@ pub const VARIABLES_MATH: HelpCategory = HelpCategory {
         "  TOSS             COIN FLIP",
         "  EITH <A> <B>     RAND A OR B",
         "  TOG <A> <B>      ALT A/B",
+        "  SEED <N>         REPEATABLE RANDOM",
+        "  SEED -1          BACK TO TRUE RANDOM",
+        "  SEED.SC <S> <N>  OWN STREAM FOR SCRIPT",
+        "",
+        "# AUDIO-REACTIVE (EXPR, 0-16383)",
+        "  LVL / LVL.IN     OUTPUT / INPUT LEVEL",
+        "  LVL.OSC .PLA     VOICE LEVELS",
+        "  LVL.NOS .SMP     VOICE LEVELS",
+        "  BAND <1-15>      SPECTRUM BAND",
+        "  GR               COMP GAIN REDUCTION",
+        "  ON.SCRIPT <0-8>  RUN SCRIPT ON ONSET",
+        "  ON.SRC <0|1>     ONSET 0=OUT 1=IN",
+        "  ON.THR <1-100>   ONSET THRESHOLD",
         "",
         "# RHYTHM GENERATORS",
         "  ER f l s        EUCLIDEAN RHYTHM",
@@ -194,6 +223,22 @@ pub const VARIABLES_MATH: HelpCategory = HelpCategory {
         "  RND.DLY    RAND DELAY",
         "  RND.VERB   RAND REVERB",
         "",
+        "# SNAPSHOTS",
+        "  SNAP.SAVE <1-16>     STORE ALL PARAMS",
+        "  SNAP.LOAD <1-16>     RECALL PARAMS",
+        "  SNAP.MORPH A B <POS> BLEND A>B 0-16383",
+        "  SNAP.GLIDE B <MS>    GLIDE TO B",
+        "  MODES/SWITCHES FLIP AT MIDPOINT",
+        "",
+        "# EVOLUTION",
+        "  MUT <0-100> [GRP]    NUDGE CURRENT SOUND",
+        "  BREED A B            CROSS SNAP SLOTS",
+        "  MUT.KEEP             KEEP CANDIDATE",
+        "  MUT.NO               BACK TO PARENT",
+        "  MUT.BACK             UNDO LAST KEEP",
+        "  GRP: OSC PL SMP FX (DEFAULT ALL)",
+        "  EX: MUT 15 OSC",
+        "",
         "# PATTERN RANDOMIZATION",
         "  RND.P [MIN] [MAX]      RAND WORK PATT",
         "  RND.PN <N> [MIN] [MAX] RAND PATT N",
@@ -251,6 +296,8 @@ pub const CONTROL_FLOW: HelpCategory = HelpCategory {
         "  SCENES        LIST SCENES",
         "  DELETE <NAME> DELETE SCENE",
         "  AUTOLOAD <0|1> AUTO-LOAD LAST SCENE",
+        "  IMPORT.TT <FILE> LOAD TELETYPE SCENE",
+        "  EXPORT.TT <FILE> WRITE TELETYPE SCENE",
         "",
         "# PRESETS",
         "  PSET <SCRIPT> <NAME>      LOAD TO 1-8",
@@ -260,10 +307,22 @@ pub const CONTROL_FLOW: HelpCategory = HelpCategory {
         "  [F] = FACTORY, [U] = USER",
         "  EX: PSET 1 808-KICK, PSET.SAVE 2 BASS",
         "",
+        "# VOICE PATCHES",
+        "  PATCH <NAME>              LOAD PARAMS NOW",
+        "  PATCH.SAVE <GRP> <NAME>   SAVE CURRENT",
+        "  GRP: OSC PL SMP FX ALL",
+        "  PATCH.DIFF <NAME>         NOW > PATCH",
+        "  PATCH.DEL <NAME>          DELETE",
+        "  PATCHES                   LIST ALL",
+        "  PATCH.EXPORT <NAME> [F]   WRITE TOML",
+        "  PATCH.IMPORT <FILE>       ADD TOML",
+        "",
         "# RECORDING",
         "  REC           START REC (WAV)",
         "  REC.STOP      STOP RECORDING",
         "  REC.PATH <P>  SET PATH PREFIX",
+        "  REC.STEMS [Q] RECORD STEMS",
+        "  REC.STEMS.STOP [Q] STOP STEMS",
         "  REC.SPINNER <0-6> SPINNER STYLE",
         "",
     ],
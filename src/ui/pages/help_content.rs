@@ -49,6 +49,9 @@ pub const HELP_LINES: &[&str] = &[
     "  ALT+I         INIT PAGE",
     "  ALT+P         PATTERN PAGE",
     "  ALT+S         SCOPE PAGE",
+    "  ALT+F         SPECTROGRAM PAGE",
+    "  ALT+W         WAVEFORM PAGE",
+    "  ALT+B         SAMPLE LIBRARY PAGE",
     "  ALT+V         VARIABLES PAGE",
     "  ALT+H         TOGGLE HELP",
     "",
@@ -60,7 +63,15 @@ pub const HELP_LINES: &[&str] = &[
     "# OTHER",
     "  CTRL+Q        QUIT APP",
     "  CTRL+UP/DN    SCROLL REPL (LIVE PAGE)",
-    "  TAB           LIVE PG / GRID VIEW",
+    "  TAB           COMPLETE CMD / NAME",
+    "  TAB (EMPTY)   LIVE PG / GRID VIEW",
+    "",
+    "# CUSTOM KEYS ([KEYS] IN CONFIG.TOML)",
+    "  \"CTRL+1\" = \"MUTE 1\"  ANY COMMAND",
+    "  \"F11\" = \"NONE\"      DISABLE KEY",
+    "  UNDO REDO PAGE.NEXT PAGE.PREV",
+    "  HELP.TOGGLE    UI ACTIONS",
+    "  KEYS           RELOAD + LIST",
     "",
     "# SEARCH",
     "  CTRL+F        ENTER SEARCH MODE",
@@ -90,6 +101,7 @@ pub const HELP_LINES: &[&str] = &[
     "  SLEW.ALL <MS>  PARAM SLEW MS (0-10000)",
     "  SLEW <P> <MS>  PER-PARAM SLEW MS",
     "  FLASH <MS>     FLASH HOLD MS (DEF 200)",
+    "  SCRIPT.LEN <N> SCRIPT LINES (8-32)",
     "  RST            RESET TO DEFAULTS",
     "  LOAD.RST       RESET MODE (0=KEEP 1=RST)",
     "  LOAD.RST <0|1> SET SCENE LOAD MODE",
@@ -147,6 +159,19 @@ pub const HELP_LINES: &[&str] = &[
     "  TOSS             COIN FLIP (0/1)",
     "  EITH <A> <B>     RANDOM CHOICE A OR B",
     "  TOG <A> <B>      ALTERNATE A/B",
+    "  SEED <N>         REPEATABLE RANDOM",
+    "  SEED -1          BACK TO TRUE RANDOM",
+    "  SEED.SC <S> <N>  OWN STREAM FOR SCRIPT",
+    "",
+    "# AUDIO-REACTIVE (EXPR, 0-16383)",
+    "  LVL / LVL.IN     OUTPUT / INPUT LEVEL",
+    "  LVL.OSC .PLA     VOICE LEVELS",
+    "  LVL.NOS .SMP     VOICE LEVELS",
+    "  BAND <1-15>      SPECTRUM BAND",
+    "  GR               COMP GAIN REDUCTION",
+    "  ON.SCRIPT <0-8>  RUN SCRIPT ON ONSET",
+    "  ON.SRC <0|1>     ONSET 0=OUT 1=IN",
+    "  ON.THR <1-100>   ONSET THRESHOLD",
     "",
     "# RHYTHM GENERATORS",
     "  ER f l s        EUCLIDEAN RHYTHM",
@@ -201,6 +226,22 @@ pub const HELP_LINES: &[&str] = &[
     "  RND.DLY    RANDOMIZE DELAY PARAMS",
     "  RND.VERB   RANDOMIZE REVERB PARAMS",
     "",
+    "# SNAPSHOTS",
+    "  SNAP.SAVE <1-16>     STORE ALL PARAMS",
+    "  SNAP.LOAD <1-16>     RECALL PARAMS",
+    "  SNAP.MORPH A B <POS> BLEND A>B 0-16383",
+    "  SNAP.GLIDE B <MS>    GLIDE TO B",
+    "  MODES/SWITCHES FLIP AT MIDPOINT",
+    "",
+    "# EVOLUTION",
+    "  MUT <0-100> [GRP]    NUDGE CURRENT SOUND",
+    "  BREED A B            CROSS SNAP SLOTS",
+    "  MUT.KEEP             KEEP CANDIDATE",
+    "  MUT.NO               BACK TO PARENT",
+    "  MUT.BACK             UNDO LAST KEEP",
+    "  GRP: OSC PL SMP FX (DEFAULT ALL)",
+    "  EX: MUT 15 OSC",
+    "",
     "# PATTERN RANDOMIZATION",
     "  RND.P [MIN] [MAX]      RAND WORK PATT",
     "  RND.PN <N> [MIN] [MAX] RAND PATT N",
@@ -241,6 +282,8 @@ pub const HELP_LINES: &[&str] = &[
     "  SCENES        LIST SAVED SCENES",
     "  DELETE <NAME> DELETE SCENE",
     "  AUTOLOAD <0|1> AUTO-LOAD LAST SCENE",
+    "  IMPORT.TT <FILE> LOAD TELETYPE SCENE",
+    "  EXPORT.TT <FILE> WRITE TELETYPE SCENE",
     "",
     "# PRESETS",
     "  PSET <SCRIPT> <NAME>      LOAD TO 1-8",
@@ -250,10 +293,23 @@ pub const HELP_LINES: &[&str] = &[
     "  [F] = FACTORY (READ-ONLY), [U] = USER",
     "  EX: PSET 1 808-KICK, PSET.SAVE 2 BASS",
     "",
+    "# VOICE PATCHES",
+    "  PATCH <NAME>              LOAD PARAMS NOW",
+    "  PATCH.SAVE <GRP> <NAME>   SAVE CURRENT",
+    "  GRP: OSC PL SMP FX ALL",
+    "  PATCH.DIFF <NAME>         NOW > PATCH",
+    "  PATCH.DEL <NAME>          DELETE",
+    "  PATCHES                   LIST ALL",
+    "  PATCH.EXPORT <NAME> [F]   WRITE TOML",
+    "  PATCH.IMPORT <FILE>       ADD TOML",
+    "",
     "# RECORDING",
     "  REC           START RECORDING (WAV)",
     "  REC.STOP      STOP RECORDING",
     "  REC.PATH <P>  SET PATH PREFIX",
+    "  REC.STEMS [Q] RECORD STEMS TO FOLDER",
+    "  REC.STEMS.STOP [Q] STOP STEMS",
+    "    Q = ON NEXT METRO TICK",
     "  REC.SPINNER <0-6> SET SPINNER STYLE",
     "",
     "# PAGE NAVIGATION",
@@ -266,6 +322,8 @@ pub const HELP_LINES: &[&str] = &[
     "  PAGE V        VARIABLES PAGE",
     "  PAGE N        NOTES PAGE",
     "  PAGE S        SCOPE PAGE",
+    "  PAGE W        WAVEFORM PAGE",
+    "  PAGE B        SAMPLE LIBRARY PAGE",
     "  PAGE HELP/H   HELP PAGE",
     "  PAGE GRID/G   GRID VIEW ON LIVE",
     "  PG <PAGE>     ALIAS FOR PAGE",
@@ -462,6 +520,13 @@ pub const HELP_LINES: &[&str] = &[
     "  STR <N>           TRIGGER SLOT (EXPR OK)",
     "  STR               RE-TRIGGER CURRENT",
     "  EX: STR 5, STR RND 0 15, STR + A 1",
+    "  LIBRARY PAGE (ALT+B / PAGE B):",
+    "    UP/DOWN SELECT  LEFT/RIGHT FOLD",
+    "    ENTER LOAD (DIR=KIT, FILE=SLICE)",
+    "    K LOAD FILE'S FOLDER AS KIT",
+    "    SPACE PREVIEW  X STOP PREVIEW",
+    "    / SEARCH (#TAG MATCHES TAGS)",
+    "    T EDIT TAGS  B DETECT BPM  R RESCAN",
     "",
     "# SAMPLER SLICING",
     "  S.SLICE / SSLC <2-128> EQUAL SLICES",
@@ -473,6 +538,24 @@ pub const HELP_LINES: &[&str] = &[
     "    MINIMUM MS BETWEEN ONSETS (DEF 50)",
     "  EX: S.ONSET, S.ONSET 3, SOMIN 30",
     "",
+    "# SAMPLER SLICE EDITING",
+    "  S.MARK / SMK <0-16383> INSERT MARKER",
+    "    SPLITS SLICE AT POSITION",
+    "  S.MARK.MV / SMMV <N> <0-16383> MOVE",
+    "    MOVES START OF SLICE N (1+)",
+    "  S.UNMARK / SUMK <N> DELETE MARKER",
+    "    MERGES SLICE N INTO N-1",
+    "  S.SNAP / SSNP [0-2] MARKER SNAP",
+    "    0=OFF 1=ZERO CROSSING 2=TRANSIENT",
+    "  S.EXPORT / SEXP <NAME> EXPORT KIT",
+    "    ONE WAV PER SLICE IN SAMPLES/<NAME>",
+    "  EX: SSNP 1, SMK 8192, SUMK 3",
+    "  WAVEFORM PAGE (ALT+W / PAGE W):",
+    "    LEFT/RIGHT CURSOR  UP/DOWN ZOOM",
+    "    , . SELECT SLICE  M ADD MARKER",
+    "    X DELETE  G MOVE TO CURSOR",
+    "    SPACE/ENTER AUDITION SLICE",
+    "",
     "# SAMPLER PLAYBACK (S.*)",
     "  S.RATE / SR <0-16383>  RATE (8K=1X)",
     "  S.PITCH / SPT <-24-24> PITCH (SEMI)",
@@ -481,6 +564,8 @@ pub const HELP_LINES: &[&str] = &[
     "  S.LOOP / SL <0|1>      LOOP MODE",
     "  S.START / SST <0-16383> START OFFSET",
     "  S.LEN / SLE <0-16383>  LOOP LENGTH",
+    "  S.STRETCH / SSTR <0|1> SYNC LOOP TO BPM",
+    "  S.BPM / SBPM <20-300>  LOOP BPM (0=DETECT)",
     "",
     "# SAMPLER ENVELOPE",
     "  S.ATK / SA <0-16383>   ATTACK MS",
@@ -642,6 +727,17 @@ pub const HELP_LINES: &[&str] = &[
     "  DEVICE CHANGE RESTARTS AUDIO ENGINE",
     "  SAVED TO CONFIG FOR NEXT LAUNCH",
     "",
+    "  OUT.CH <2-12>     HARDWARE OUTPUTS",
+    "  OUT.ROUTE <V> <P> [PRE|POST]",
+    "    V: OSC PLA NOS SMP",
+    "    P: OUTPUT PAIR 2+, 0 = MASTER",
+    "    PRE DRY, POST THROUGH FX",
+    "",
+    "  LUFS              LOUDNESS M/S/I + TP",
+    "  LUFS.RST          RESTART INTEGRATED",
+    "  LUFS.TARGET <DB>  RIDE GAIN, -36 TO -6",
+    "    0 = OFF, LIMITER AT -1 DBTP WHILE ON",
+    "",
     "# EXPRESSIONS",
     "  ALL NUMERIC ARGS ACCEPT EXPRESSIONS:",
     "  PF N ADD A 7",
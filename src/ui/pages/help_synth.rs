@@ -167,6 +167,13 @@ pub const SAMPLER: HelpCategory = HelpCategory {
         "  STR <N>           TRIGGER SLOT (EXPR OK)",
         "  STR               RE-TRIGGER CURRENT",
         "  EX: STR 5, STR RND 0 15, STR + A 1",
+        "  LIBRARY PAGE (ALT+B / PAGE B):",
+        "    UP/DOWN SELECT  LEFT/RIGHT FOLD",
+        "    ENTER LOAD (DIR=KIT, FILE=SLICE)",
+        "    K LOAD FILE'S FOLDER AS KIT",
+        "    SPACE PREVIEW  X STOP PREVIEW",
+        "    / SEARCH (#TAG MATCHES TAGS)",
+        "    T EDIT TAGS  B DETECT BPM  R RESCAN",
         "",
         "# SAMPLER SLICING",
         "  S.SLICE / SSLC <2-128> EQUAL SLICES",
@@ -178,6 +185,24 @@ pub const SAMPLER: HelpCategory = HelpCategory {
         "    MINIMUM MS BETWEEN ONSETS (DEF 50)",
         "  EX: S.ONSET, S.ONSET 3, SOMIN 30",
         "",
+        "# SAMPLER SLICE EDITING",
+        "  S.MARK / SMK <0-16383> INSERT MARKER",
+        "    SPLITS SLICE AT POSITION",
+        "  S.MARK.MV / SMMV <N> <0-16383> MOVE",
+        "    MOVES START OF SLICE N (1+)",
+        "  S.UNMARK / SUMK <N> DELETE MARKER",
+        "    MERGES SLICE N INTO N-1",
+        "  S.SNAP / SSNP [0-2] MARKER SNAP",
+        "    0=OFF 1=ZERO CROSSING 2=TRANSIENT",
+        "  S.EXPORT / SEXP <NAME> EXPORT KIT",
+        "    ONE WAV PER SLICE IN SAMPLES/<NAME>",
+        "  EX: SSNP 1, SMK 8192, SUMK 3",
+        "  WAVEFORM PAGE (ALT+W / PAGE W):",
+        "    LEFT/RIGHT CURSOR  UP/DOWN ZOOM",
+        "    , . SELECT SLICE  M ADD MARKER",
+        "    X DELETE  G MOVE TO CURSOR",
+        "    SPACE/ENTER AUDITION SLICE",
+        "",
         "# SAMPLER PLAYBACK (S.*)",
         "  S.RATE / SR <0-16383>  RATE (8K=1X)",
         "  S.PITCH / SPT <-24-24> PITCH (SEMI)",
@@ -186,6 +211,8 @@ pub const SAMPLER: HelpCategory = HelpCategory {
         "  S.LOOP / SL <0|1>      LOOP MODE",
         "  S.START / SST <0-16383> START OFFSET",
         "  S.LEN / SLE <0-16383>  LOOP LENGTH",
+        "  S.STRETCH / SSTR <0|1> SYNC LOOP TO BPM",
+        "  S.BPM / SBPM <20-300>  LOOP BPM (0=DETECT)",
         "",
         "# SAMPLER ENVELOPE",
         "  S.ATK / SA <0-16383>   ATTACK MS",
@@ -14,6 +14,8 @@ pub const METRO_SCRIPTS: HelpCategory = HelpCategory {
         "  PAGE V        VARIABLES PAGE",
         "  PAGE N        NOTES PAGE",
         "  PAGE S        SCOPE PAGE",
+        "  PAGE W        WAVEFORM PAGE",
+        "  PAGE B        SAMPLE LIBRARY PAGE",
         "  PAGE HELP/H   HELP PAGE",
         "  PAGE GRID/G   GRID VIEW ON LIVE",
         "  PG <PAGE>     ALIAS FOR PAGE",
@@ -70,13 +72,16 @@ pub const UI_DISPLAY: HelpCategory = HelpCategory {
         "  METER.HDR <0|1>   HDR METERS",
         "  METER.GRID <0|1>  GRID METERS",
         "  METER.ASCII <0|1> ASCII METER MODE",
+        "  MOUSE <0|1>       MOUSE CLICK/DRAG/WHEEL",
         "  SPECTRUM <0|1>    SPECTRUM",
         "  ACTIVITY <0|1>    ACTIVITY IND",
         "  GRID <0|1>        PARAM GRID VIS",
         "  GRID.DEF <0|1>    DEF VIEW (REPL/GRID)",
-        "  GRID.MODE <0-5>   LBL/ICO/EQ/MIX/FX/SMP",
+        "  GRID.MODE <0-6>   LBL/ICO/EQ/MIX/FX/SMP/LUFS",
+        "  GRID.MODE <7+>    [[GRID_PAGES]] FROM CONFIG",
         "  HL.SEQ <0|1>      SEQ/TOG HIGHL",
         "  HL.COND <0|1>     COND HIGHL",
+        "  HL.SYN <0|1>      SYNTAX COLORS",
         "  CPU <0|1>         CPU METER",
         "  BPM <0|1>         BPM DISPLAY",
         "  TITLE <0|1>       HDR TITLE MODE",
@@ -142,11 +147,37 @@ pub const OSCILLOSCOPE: HelpCategory = HelpCategory {
         "  SCOPE.GAIN / SCG <0-16383> GAIN (8K=1X)",
         "  SCOPE.RST / SCR     RESET TO DEFAULTS",
         "",
+        "# SCOPE TRIGGER",
+        "  SCOPE.TRIG <0-3>    TRIGGER MODE",
+        "    0 = FREE",
+        "    1 = RISING EDGE",
+        "    2 = FALLING EDGE",
+        "    3 = SINGLE SHOT ON TR",
+        "  SCOPE.LVL <-100-100> LEVEL (%)",
+        "  SCOPE.HOLD <0-1000> HOLDOFF MS",
+        "  SCOPE.ARM           REARM SINGLE SHOT",
+        "",
+        "# SCOPE X/Y AND OVERLAY",
+        "  SCOPE.XY <0|1>      LEFT VS RIGHT",
+        "  SCOPE.SRC <0-4>     VOICE OVERLAY",
+        "    0=OFF 1=OSC 2=NOISE",
+        "    3=PLAITS 4=SAMPLER",
+        "",
         "  NOTE: SCOPE.MODE 1 (blocks) may render",
         "  better than braille in some terminals.",
         "",
+        "# SPECTROGRAM",
+        "  SPEC.BANDS <8-64>   BANDS (32)",
+        "  SPEC.SCALE <0|1>    0=LOG 1=LIN",
+        "  SPEC.MODE <0|1>     0=PEAK 1=AVG",
+        "  SPEC.DISP <0|1>     0=BRAILLE 1=BLOCK",
+        "  SPEC.FREEZE <0|1>   STOP WATERFALL",
+        "  SPEC.SNAP           KEEP SPECTRUM",
+        "  SPEC.SNAP.CLR       DROP SNAPSHOT",
+        "",
         "# NAVIGATION",
         "  ALT+S OR [ ]       SCOPE PAGE",
+        "  ALT+F OR [ ]       SPECTROGRAM PAGE",
         "",
     ],
 };
@@ -173,6 +204,10 @@ pub const SYSTEM: HelpCategory = HelpCategory {
         "  AUDIO.OUT         LIST OUTPUT DEVICES",
         "  AUDIO.OUT <N>     SET OUTPUT BY NUMBER",
         "  LIMIT <0|1>       OUTPUT LIMITER",
+        "  LUFS              LOUDNESS M/S/I + TP",
+        "  LUFS.RST          RESTART INTEGRATED",
+        "  LUFS.TARGET <DB>  RIDE GAIN, -36 TO -6",
+        "    0 = OFF, LIMITER AT -1 DBTP WHILE ON",
         "",
         "  DEVICE CHANGE RESTARTS AUDIO ENGINE",
         "  SAVED TO CONFIG FOR NEXT LAUNCH",
@@ -2,12 +2,18 @@ use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::block::{Title, Position};
 use ratatui::layout::Alignment;
 
-pub fn render_init_page(app: &crate::App) -> Paragraph<'static> {
+pub fn render_init_page(app: &crate::App, height: usize) -> Paragraph<'static> {
     let mut lines = vec![Line::from("")];
 
+    // Borders and the blank first line
+    let total = app.script_rows(9);
+    let rows = super::script_renderer::visible_rows(total, height.saturating_sub(3), app.selected_line);
+    let scroll = super::script_renderer::scroll_label(&rows, total);
+
     let rendered_lines = super::script_renderer::render_script_lines(
         app.scripts.get_script(9),
         9,
+        rows,
         app.selected_line,
         true,
         Some(crate::types::Page::Init),
@@ -19,9 +25,9 @@ pub fn render_init_page(app: &crate::App) -> Paragraph<'static> {
     lines.extend(rendered_lines);
 
     let title = if app.script_mutes.muted[9] {
-        " INIT [MUTED] "
+        format!(" INIT [MUTED]{} ", scroll)
     } else {
-        " INIT "
+        format!(" INIT{} ", scroll)
     };
 
     let mut block = Block::default()
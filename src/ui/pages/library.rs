@@ -0,0 +1,175 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::sampler::library::LibraryInput;
+
+/// Rows reserved under the tree for file info, tags and the input line
+const FOOTER_ROWS: usize = 3;
+
+/// Render the sample library browser: folder tree (or search results) with
+/// metadata and tags of the selected file underneath
+pub fn render_library_page(app: &crate::App, area: Rect) -> Paragraph<'static> {
+    let content_width = area.width.saturating_sub(2) as usize;
+    let content_height = area.height.saturating_sub(2) as usize;
+    let library = &app.library;
+    let searching = !library.query.trim().is_empty();
+
+    let visible = library.visible();
+    let list_rows = content_height.saturating_sub(FOOTER_ROWS).max(1);
+    let scroll = if library.selected < list_rows / 2 {
+        0
+    } else {
+        (library.selected - list_rows / 2).min(visible.len().saturating_sub(list_rows))
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    if library.nodes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " NO SAMPLES FOUND IN SAMPLES/",
+            Style::default().fg(app.theme.secondary),
+        )));
+    } else if visible.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " NO MATCHES",
+            Style::default().fg(app.theme.secondary),
+        )));
+    }
+
+    for (row, &node_idx) in visible.iter().enumerate().skip(scroll).take(list_rows) {
+        let node = &library.nodes[node_idx];
+        let is_selected = row == library.selected;
+
+        let label = if searching {
+            node.rel.clone()
+        } else {
+            let marker = if !node.is_dir {
+                "  "
+            } else if library.expanded.contains(&node.rel) {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            format!("{}{}{}", "  ".repeat(node.depth), marker, node.name)
+        };
+
+        let tags = library.index.tags(&node.rel);
+        let tag_text = if tags.is_empty() {
+            String::new()
+        } else {
+            format!("  #{}", tags.join(" #"))
+        };
+
+        let label = truncate(&format!(" {}", label), content_width);
+        let tag_text = truncate(&tag_text, content_width.saturating_sub(label.chars().count()));
+
+        let (name_style, tag_style) = if is_selected {
+            let style = Style::default().bg(app.theme.highlight_bg).fg(app.theme.highlight_fg);
+            (style, style)
+        } else if node.is_dir {
+            (Style::default().fg(app.theme.label), Style::default().fg(app.theme.secondary))
+        } else {
+            (Style::default().fg(app.theme.foreground), Style::default().fg(app.theme.secondary))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(label, name_style),
+            Span::styled(tag_text, tag_style),
+        ]));
+    }
+
+    while lines.len() < list_rows {
+        lines.push(Line::from(""));
+    }
+
+    // Info for the selected file
+    let selected = library.selected_node().cloned();
+    let info_line = match &selected {
+        Some(node) if !node.is_dir => {
+            let bpm = library
+                .bpm(&node.rel)
+                .map(|bpm| format!("{:.1}", bpm))
+                .unwrap_or_else(|| "--".to_string());
+            match library.cached_info(&node.rel) {
+                Some(info) => format!(
+                    " {}.{:02}S  {}CH  {}HZ  BPM {}",
+                    info.duration_ms() / 1000,
+                    (info.duration_ms() % 1000) / 10,
+                    info.channels,
+                    info.sample_rate,
+                    bpm
+                ),
+                None => format!(" NO WAV INFO  BPM {}", bpm),
+            }
+        }
+        Some(_) => " FOLDER: ENTER LOADS AS KIT".to_string(),
+        None => String::new(),
+    };
+    lines.push(Line::from(Span::styled(
+        truncate(&info_line, content_width),
+        Style::default().fg(app.theme.secondary),
+    )));
+
+    let tags_line = match &selected {
+        Some(node) if !node.is_dir => {
+            let tags = library.index.tags(&node.rel);
+            if tags.is_empty() {
+                " TAGS: -".to_string()
+            } else {
+                format!(" TAGS: {}", tags.join(" "))
+            }
+        }
+        _ => String::new(),
+    };
+    lines.push(Line::from(Span::styled(
+        truncate(&tags_line, content_width),
+        Style::default().fg(app.theme.secondary),
+    )));
+
+    let input_line = match library.input {
+        LibraryInput::Search => Line::from(vec![
+            Span::styled(" / ", Style::default().fg(app.theme.accent)),
+            Span::styled(format!("{}_", library.query), Style::default().fg(app.theme.foreground)),
+        ]),
+        LibraryInput::Tag => Line::from(vec![
+            Span::styled(" TAGS> ", Style::default().fg(app.theme.accent)),
+            Span::styled(format!("{}_", library.tag_buffer), Style::default().fg(app.theme.foreground)),
+        ]),
+        LibraryInput::None if searching => Line::from(Span::styled(
+            truncate(&format!(" / {}  (ESC CLEARS)", library.query), content_width),
+            Style::default().fg(app.theme.secondary),
+        )),
+        LibraryInput::None => Line::from(Span::styled(
+            truncate(" / SEARCH  SPACE PREVIEW  ENTER LOAD  T TAG  B BPM", content_width),
+            Style::default().fg(app.theme.secondary),
+        )),
+    };
+    lines.push(input_line);
+
+    let file_count = library.nodes.iter().filter(|n| !n.is_dir).count();
+    let info_title = if searching {
+        format!(" {} OF {} FILES ", visible.len(), file_count)
+    } else {
+        format!(" {} FILES ", file_count)
+    };
+
+    Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.background).fg(app.theme.foreground))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border))
+                .title(" LIBRARY ")
+                .title_style(Style::default().fg(app.theme.foreground))
+                .title(
+                    ratatui::widgets::block::Title::from(
+                        Span::styled(info_title, Style::default().fg(app.theme.secondary))
+                    )
+                    .alignment(Alignment::Right)
+                    .position(ratatui::widgets::block::Position::Bottom)
+                )
+        )
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
@@ -1,5 +1,7 @@
 use ratatui::{prelude::*, widgets::*};
+use crate::snapshot::ParamValue;
 use crate::types::SPECTRUM_BANDS;
+use crate::ui::grid_pages::{BarTarget, GridFormat, GridPage, CELL_WIDTH, FIRST_CUSTOM_MODE};
 
 pub fn render_live_page(app: &crate::App, width: usize, height: usize) -> Paragraph<'static> {
     if app.show_grid_view {
@@ -306,6 +308,80 @@ fn render_fx_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>) {
     }
 }
 
+/// LUFS/dBTP readings on a -60..0 bar
+fn loudness_bar(db: f32, ascii_mode: bool) -> String {
+    let level = if db.is_finite() { ((db + 60.0) / 60.0).clamp(0.0, 1.0) } else { 0.0 };
+    level_to_bar(level, 17, ascii_mode)
+}
+
+fn render_loudness_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>) {
+    use crate::loudness::{format_db, TARGET_CEILING_DB};
+
+    let meter = &app.loudness;
+    match row {
+        0..=3 => {
+            let (label, db) = match row {
+                0 => ("M  ", meter.momentary),
+                1 => ("S  ", meter.short_term),
+                2 => ("I  ", meter.integrated),
+                _ => ("TP ", meter.true_peak),
+            };
+            let color = if row == 3 && db > TARGET_CEILING_DB { app.theme.error } else { app.theme.success };
+            spans.push(Span::styled(label, Style::default().fg(app.theme.foreground)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(loudness_bar(db, app.ascii_meters), Style::default().fg(color)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>6}", format_db(db)), Style::default().fg(color)));
+            spans.push(Span::raw("  "));
+        }
+        4 => {
+            let target = meter.target.map(format_db).unwrap_or_else(|| "OFF".to_string());
+            spans.push(Span::styled("TGT", Style::default().fg(app.theme.foreground)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>6}", target), Style::default().fg(app.theme.success)));
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled("GAIN", Style::default().fg(app.theme.foreground)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>+6.1}", meter.gain_db), Style::default().fg(app.theme.success)));
+            spans.push(Span::raw("       "));
+        }
+        5 => {
+            let color = if meter.max_true_peak > TARGET_CEILING_DB { app.theme.error } else { app.theme.success };
+            spans.push(Span::styled("MAX", Style::default().fg(app.theme.foreground)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>6}", format_db(meter.max_true_peak)), Style::default().fg(color)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled("DBTP", Style::default().fg(app.theme.label)));
+            spans.push(Span::raw("               "));
+        }
+        _ => {}
+    }
+}
+
+/// Custom page row: two cells (label, then the value in the cell's format), in page order
+fn render_custom_row(row: usize, app: &crate::App, page: &GridPage, values: &[Option<ParamValue>], spans: &mut Vec<Span<'static>>) {
+    use crate::types::GRID_ICONS;
+
+    for col in 0..2 {
+        if col == 1 {
+            spans.push(Span::raw("  "));
+        }
+        let index = row * 2 + col;
+        let Some(cell) = page.cells.get(index) else {
+            spans.push(Span::raw(" ".repeat(CELL_WIDTH)));
+            continue;
+        };
+        let activity = cell.activity.and_then(|idx| app.param_activity.timestamps[idx]);
+        let label_style = Style::default().fg(app.theme.activity_color(activity, false, app.activity_hold_ms));
+        spans.push(Span::styled(format!("{:<4} ", cell.label), label_style));
+        if cell.format == GridFormat::Icon {
+            let icon = cell.activity.map(|idx| GRID_ICONS[idx]).unwrap_or('•');
+            spans.push(Span::styled(format!("{} ", icon), label_style));
+        }
+        spans.push(Span::styled(cell.value_text(values[index], app.ascii_meters), Style::default().fg(app.theme.success)));
+    }
+}
+
 fn vol_bar_parts(vol: i32) -> (String, String) {
     let filled_count = ((vol as f32 / 16383.0) * 13.0).round() as usize;
     let empty_count = 13usize.saturating_sub(filled_count);
@@ -324,6 +400,15 @@ fn pan_numeric(pan: i32) -> String {
     }
 }
 
+/// dB readout, or the output pair (2PR = pair 2 pre-FX) when the voice is routed out
+fn db_or_route(vol: i32, route: crate::types::OutRoute, app: &crate::App) -> Span<'static> {
+    if route.pair > 0 {
+        Span::styled(route.label(), Style::default().fg(app.theme.label))
+    } else {
+        Span::styled(vol_to_db(vol), Style::default().fg(app.theme.foreground))
+    }
+}
+
 fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>) {
 
     match row {
@@ -337,7 +422,7 @@ fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>
             spans.push(Span::styled(empty, Style::default().fg(app.theme.secondary)));
             spans.push(Span::raw(" "));
 
-            spans.push(Span::styled(vol_to_db(app.mixer_data.vol_osc), Style::default().fg(app.theme.foreground)));
+            spans.push(db_or_route(app.mixer_data.vol_osc, app.mixer_data.route_osc, app));
             spans.push(Span::raw(" "));
 
             spans.push(Span::styled(pan_numeric(app.mixer_data.pan_osc), Style::default().fg(app.theme.foreground)));
@@ -368,7 +453,7 @@ fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>
             spans.push(Span::styled(empty, Style::default().fg(app.theme.secondary)));
             spans.push(Span::raw(" "));
 
-            spans.push(Span::styled(vol_to_db(app.mixer_data.vol_pla), Style::default().fg(app.theme.foreground)));
+            spans.push(db_or_route(app.mixer_data.vol_pla, app.mixer_data.route_pla, app));
             spans.push(Span::raw(" "));
 
             spans.push(Span::styled(pan_numeric(app.mixer_data.pan_pla), Style::default().fg(app.theme.foreground)));
@@ -398,7 +483,7 @@ fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>
             spans.push(Span::styled(empty, Style::default().fg(app.theme.secondary)));
             spans.push(Span::raw(" "));
 
-            spans.push(Span::styled(vol_to_db(app.mixer_data.vol_nos), Style::default().fg(app.theme.foreground)));
+            spans.push(db_or_route(app.mixer_data.vol_nos, app.mixer_data.route_nos, app));
             spans.push(Span::raw(" "));
 
             spans.push(Span::styled(pan_numeric(app.mixer_data.pan_nos), Style::default().fg(app.theme.foreground)));
@@ -429,7 +514,7 @@ fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>
             spans.push(Span::styled(empty, Style::default().fg(app.theme.secondary)));
             spans.push(Span::raw(" "));
 
-            spans.push(Span::styled(vol_to_db(app.mixer_data.vol_smp), Style::default().fg(app.theme.foreground)));
+            spans.push(db_or_route(app.mixer_data.vol_smp, app.mixer_data.route_smp, app));
             spans.push(Span::raw(" "));
 
             spans.push(Span::styled(pan_numeric(app.mixer_data.pan_smp), Style::default().fg(app.theme.foreground)));
@@ -480,34 +565,67 @@ fn render_mixer_row(row: usize, app: &crate::App, spans: &mut Vec<Span<'static>>
     }
 }
 
-fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<'static> {
-    use crate::types::{GRID_ICONS, GRID_LABELS};
-
-    let mut lines = vec![];
-
+/// Blank rows above the grid, centering it vertically
+fn grid_top_pad(app: &crate::App, height: usize) -> usize {
     // Content height (excluding borders)
     let content_height = height.saturating_sub(2);
 
     // Calculate total content height based on enabled elements
     // Always reserve space for grid (6 rows) so spectrum/meters don't move when grid is hidden
     // + 1 meter label (if grid meters shown)
-    // + 1 sampler label (if grid mode 5, always shown)
+    // + 1 sampler/loudness/custom page label (if grid mode 5+, always shown)
     // + 2 spectrum rows (if spectrum shown)
     // + 1 spectrum label (if spectrum shown)
     let mut total_content_height = 6;
     // But spectrum and meters still affect layout when toggled
     if app.show_meters_grid {
         total_content_height += 1;
-    } else if app.grid_mode == 5 {
-        // Sampler label row (shown even without meters)
+    } else if app.grid_mode >= 5 {
+        // Sampler/loudness/custom page label row (shown even without meters)
         total_content_height += 1;
     }
     if app.show_spectrum {
         total_content_height += 3;
     }
 
-    // Calculate vertical padding for centering
-    let top_pad_count = content_height.saturating_sub(total_content_height) / 2;
+    content_height.saturating_sub(total_content_height) / 2
+}
+
+/// The draggable parameter bar under the mouse on the grid view: the mixer
+/// volumes (mode 3) and the bar cells of custom pages
+pub fn grid_bar_at(app: &crate::App, area: Rect, column: u16, row: u16) -> Option<BarTarget> {
+    if app.current_page != crate::types::Page::Live || !app.show_grid_view || !app.show_grid {
+        return None;
+    }
+    let grid_top = area.y + 1 + grid_top_pad(app, area.height as usize) as u16;
+    let grid_row = row.checked_sub(grid_top)? as usize;
+    if grid_row >= 6 {
+        return None;
+    }
+    // Rows are centered: 30 chars of grid, plus 7 of meters
+    let inner_width = area.width.saturating_sub(2);
+    let line_width: u16 = if app.show_meters_grid { 37 } else { 30 };
+    let line_x = area.x + 1 + (inner_width / 2).saturating_sub(line_width / 2);
+
+    let bar = if app.grid_mode == 3 {
+        // Voice name and a space, then the 13-char volume bar
+        let cmd = ["VOL.OSC", "VOL.PLA", "VOL.NOS", "VOL.SMP"].get(grid_row)?;
+        BarTarget { cmd: cmd.to_string(), start: line_x + 4, width: 13, min: 0.0, max: 16383.0, log: false }
+    } else {
+        let page = app.grid_pages.get(app.grid_mode.checked_sub(FIRST_CUSTOM_MODE)? as usize)?;
+        let col = usize::from(column.saturating_sub(line_x) >= CELL_WIDTH as u16 + 2);
+        let cell_x = line_x + col as u16 * (CELL_WIDTH as u16 + 2);
+        page.cells.get(grid_row * 2 + col)?.bar(cell_x + 5)?
+    };
+    (bar.start..bar.start + bar.width).contains(&column).then_some(bar)
+}
+
+fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<'static> {
+    use crate::types::{GRID_ICONS, GRID_LABELS};
+
+    let mut lines = vec![];
+
+    let top_pad_count = grid_top_pad(app, height);
 
     // Labels (mode 0) need tighter spacing (2-char label + 2 spaces), icons (mode 1) get 3 spaces
     let icon_spacing = if app.grid_mode == 0 { "  " } else { "   " };
@@ -604,6 +722,10 @@ fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<
         Vec::new()
     };
 
+    // Custom page values, read once per frame from the live parameter map
+    let custom_page = app.grid_mode.checked_sub(FIRST_CUSTOM_MODE).and_then(|page| app.grid_pages.get(page as usize));
+    let custom_values = custom_page.map(|page| page.values(app.snapshots.lock().unwrap().live())).unwrap_or_default();
+
     // Add top padding for vertical centering
     for _ in 0..top_pad_count {
         lines.push(Line::from(""));
@@ -657,6 +779,14 @@ fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<
             } else if app.grid_mode == 5 {
                 // Mode 5: Sampler visualization
                 render_sampler_row(row, app, &mut spans);
+            } else if app.grid_mode == 6 {
+                // Mode 6: Loudness (LUFS and true peak)
+                render_loudness_row(row, app, &mut spans);
+            } else if app.grid_mode >= FIRST_CUSTOM_MODE {
+                // Modes 7+: custom pages from config
+                if let Some(page) = custom_page {
+                    render_custom_row(row, app, page, &custom_values, &mut spans);
+                }
             } else {
                 // Render grid icons or labels based on grid_mode
                 for col in 0..8 {
@@ -697,7 +827,7 @@ fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<
                 // Mode 1 (icons) is 29 chars, mode 0 (labels) is 30 chars - add 1 space to equalize
                 spans.push(Span::raw(" "));
             }
-            // Modes 2, 3, 4, 5 are exactly 30 chars, no adjustment needed
+            // Modes 2 and up are exactly 30 chars, no adjustment needed
             // Space before meters
             spans.push(Span::raw("  "));
 
@@ -717,8 +847,13 @@ fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<
     // Meter/sampler labels row
     if app.show_meters_grid {
         let mut meter_label = vec![];
-        // Grid space: show SAMPLER label if mode 5, FX label if mode 4, MIXER label if mode 3, COMP label if mode 2, otherwise blank
-        if app.grid_mode == 5 {
+        // Grid space: custom page name if mode 7+, LOUDNESS label if mode 6, SAMPLER label if mode 5, FX label if mode 4, MIXER label if mode 3, COMP label if mode 2, otherwise blank
+        if let Some(page) = custom_page {
+            meter_label.push(Span::styled(format!("{:<30.30}", page.name), Style::default().fg(app.theme.label)));
+        } else if app.grid_mode == 6 {
+            meter_label.push(Span::styled("LOUDNESS", Style::default().fg(app.theme.label)));
+            meter_label.push(Span::raw("                      "));  // 22 spaces to fill 30 chars
+        } else if app.grid_mode == 5 {
             meter_label.push(Span::styled("SAMPLER", Style::default().fg(app.theme.label)));
             meter_label.push(Span::raw("                       "));  // 23 spaces to fill 30 chars
         } else if app.grid_mode == 4 {
@@ -741,6 +876,17 @@ fn render_grid_view(app: &crate::App, width: usize, height: usize) -> Paragraph<
         meter_label.push(Span::raw(" "));
         meter_label.push(Span::styled("R ", Style::default().fg(app.theme.label)));
         lines.push(Line::from(meter_label).alignment(Alignment::Center));
+    } else if let Some(page) = custom_page {
+        // Custom page name row (shown even when meters hidden)
+        let label_row = vec![Span::styled(format!("{:<30.30}", page.name), Style::default().fg(app.theme.label))];
+        lines.push(Line::from(label_row).alignment(Alignment::Center));
+    } else if app.grid_mode == 6 {
+        // Loudness label row (shown even when meters hidden)
+        let label_row = vec![
+            Span::styled("LOUDNESS", Style::default().fg(app.theme.label)),
+            Span::raw("                      "),  // 22 spaces to fill 30 chars
+        ];
+        lines.push(Line::from(label_row).alignment(Alignment::Center));
     } else if app.grid_mode == 5 {
         // Sampler label row (shown even when meters hidden)
         let mut label_row = vec![];
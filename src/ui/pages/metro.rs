@@ -2,7 +2,7 @@ use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::block::{Title, Position};
 use ratatui::layout::Alignment;
 
-pub fn render_metro_page(app: &crate::App) -> Paragraph<'static> {
+pub fn render_metro_page(app: &crate::App, height: usize) -> Paragraph<'static> {
     let state = app.metro_state.lock().unwrap();
     let bpm = 15000.0 / state.interval_ms as f32;
     let status = if state.active { "ON" } else { "OFF" };
@@ -25,9 +25,15 @@ pub fn render_metro_page(app: &crate::App) -> Paragraph<'static> {
         Span::styled(status, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
     ]));
 
+    // Borders and the status line
+    let total = app.script_rows(8);
+    let rows = super::script_renderer::visible_rows(total, height.saturating_sub(3), app.selected_line);
+    let scroll = super::script_renderer::scroll_label(&rows, total);
+
     let rendered_lines = super::script_renderer::render_script_lines(
         app.scripts.get_script(8),
         8,
+        rows,
         app.selected_line,
         true,
         Some(crate::types::Page::Metro),
@@ -39,9 +45,9 @@ pub fn render_metro_page(app: &crate::App) -> Paragraph<'static> {
     text.extend(rendered_lines);
 
     let title = if app.script_mutes.muted[8] {
-        " METRO [MUTED] "
+        format!(" METRO [MUTED]{} ", scroll)
     } else {
-        " METRO "
+        format!(" METRO{} ", scroll)
     };
 
     let mut block = Block::default()
@@ -5,6 +5,7 @@ pub mod help_synth;
 pub mod help_effects;
 pub mod help_system;
 pub mod init;
+pub mod library;
 pub mod live;
 pub mod metro;
 pub mod notes;
@@ -12,14 +13,19 @@ pub mod pattern;
 pub mod scope;
 pub mod script;
 pub mod script_renderer;
+pub mod spectrogram;
 pub mod variables;
+pub mod waveform;
 
 pub use help::{render_help_page, HELP_CATEGORIES, HELP_LINES};
 pub use init::render_init_page;
+pub use library::render_library_page;
 pub use live::render_live_page;
 pub use metro::render_metro_page;
 pub use notes::render_notes_page;
 pub use pattern::render_pattern_page;
 pub use scope::render_scope_page;
 pub use script::render_script_page;
+pub use spectrogram::render_spectrogram_page;
 pub use variables::render_variables_page;
+pub use waveform::render_waveform_page;
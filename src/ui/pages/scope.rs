@@ -1,7 +1,21 @@
 use ratatui::{prelude::*, widgets::*};
 
-use crate::types::SCOPE_SAMPLES;
-use crate::ui::braille::{samples_to_braille, samples_to_blocks, samples_to_lines, samples_to_dots, samples_to_quadrants};
+use crate::types::{ScopeOverlay, ScopeTrigger, SCOPE_SAMPLES};
+use crate::ui::braille::{samples_to_braille, samples_to_blocks, samples_to_lines, samples_to_dots, samples_to_quadrants, xy_to_braille, BRAILLE_BASE};
+
+fn render_trace(samples: &[f32], display_mode: u8, width: usize, height: usize) -> Vec<Vec<char>> {
+    match display_mode {
+        1 => samples_to_blocks(samples, width, height),
+        2 => samples_to_lines(samples, width, height),
+        3 => samples_to_dots(samples, width, height),
+        4 => samples_to_quadrants(samples, width, height),
+        _ => samples_to_braille(samples, width, height),
+    }
+}
+
+fn is_blank(ch: char) -> bool {
+    ch == ' ' || ch as u32 == BRAILLE_BASE
+}
 
 /// Render the oscilloscope page with centered waveform display
 ///
@@ -18,25 +32,32 @@ pub fn render_scope_page(app: &crate::App, area: Rect) -> Paragraph<'static> {
     let top_padding = 0;
 
     // Prepare samples - apply rectification if unipolar mode
-    let samples: Vec<f32> = if app.scope_settings.unipolar {
-        // Rectify: take absolute value, map 0-1 to full height
-        // We map 0->1 to 1->-1 (top to bottom) for display
-        app.scope_data.samples.iter()
-            .map(|&s| s.abs() * 2.0 - 1.0)
-            .collect()
-    } else {
-        app.scope_data.samples.to_vec()
+    let prepare = |trace: &[f32]| -> Vec<f32> {
+        if app.scope_settings.unipolar {
+            // Rectify: take absolute value, map 0-1 to full height
+            // We map 0->1 to 1->-1 (top to bottom) for display
+            trace.iter().map(|&s| s.abs() * 2.0 - 1.0).collect()
+        } else {
+            trace.to_vec()
+        }
     };
 
-    let waveform_grid = match app.scope_settings.display_mode {
-        1 => samples_to_blocks(&samples, content_width, waveform_height),
-        2 => samples_to_lines(&samples, content_width, waveform_height),
-        3 => samples_to_dots(&samples, content_width, waveform_height),
-        4 => samples_to_quadrants(&samples, content_width, waveform_height),
-        _ => samples_to_braille(&samples, content_width, waveform_height),
+    let settings = &app.scope_settings;
+    let data = &app.scope_data;
+    let waveform_grid = if settings.xy {
+        xy_to_braille(&data.samples, &data.overlay, content_width, waveform_height)
+    } else {
+        render_trace(&prepare(&data.samples), settings.display_mode, content_width, waveform_height)
+    };
+    // The voice bus goes behind the master trace
+    let overlay_grid = if !settings.xy && settings.overlay != ScopeOverlay::Off {
+        Some(render_trace(&prepare(&data.overlay), settings.display_mode, content_width, waveform_height))
+    } else {
+        None
     };
 
     let waveform_color = app.scope_settings.color_mode.get_color(&app.theme);
+    let overlay_color = app.theme.secondary;
 
     let mut lines: Vec<Line> = Vec::new();
 
@@ -46,16 +67,52 @@ pub fn render_scope_page(app: &crate::App, area: Rect) -> Paragraph<'static> {
     }
 
     // Add waveform rows
-    for row in waveform_grid {
-        let row_string: String = row.into_iter().collect();
-        lines.push(Line::from(Span::styled(
-            row_string,
-            Style::default().fg(waveform_color),
-        )));
+    for (row_idx, row) in waveform_grid.into_iter().enumerate() {
+        match &overlay_grid {
+            Some(overlay) => {
+                let spans: Vec<Span> = row
+                    .into_iter()
+                    .zip(&overlay[row_idx])
+                    .map(|(ch, &under)| {
+                        if is_blank(ch) && !is_blank(under) {
+                            Span::styled(under.to_string(), Style::default().fg(overlay_color))
+                        } else {
+                            Span::styled(ch.to_string(), Style::default().fg(waveform_color))
+                        }
+                    })
+                    .collect();
+                lines.push(Line::from(spans));
+            }
+            None => {
+                let row_string: String = row.into_iter().collect();
+                lines.push(Line::from(Span::styled(
+                    row_string,
+                    Style::default().fg(waveform_color),
+                )));
+            }
+        }
     }
 
     // Create info title for bottom border
-    let info_title = format!(" TIME: {}MS  SAMPLES: {} ", app.scope_settings.timespan_ms, SCOPE_SAMPLES);
+    let mut info_title = format!(" TIME: {}MS  SAMPLES: {}", settings.timespan_ms, SCOPE_SAMPLES);
+    match settings.trigger {
+        ScopeTrigger::Free => {}
+        ScopeTrigger::Single => {
+            info_title.push_str(if settings.single_armed { "  SINGLE: ARMED" } else { "  SINGLE: HELD" });
+        }
+        trigger => {
+            info_title.push_str(&format!("  TRIG: {} {:+}", trigger.name(), settings.trig_level));
+            if settings.holdoff_ms > 0 {
+                info_title.push_str(&format!("  HOLD: {}MS", settings.holdoff_ms));
+            }
+        }
+    }
+    if settings.xy {
+        info_title.push_str("  X/Y");
+    } else if settings.overlay != ScopeOverlay::Off {
+        info_title.push_str(&format!("  SRC: {}", settings.overlay.name()));
+    }
+    info_title.push(' ');
 
     Paragraph::new(lines)
         .style(Style::default().bg(app.theme.background).fg(app.theme.foreground))
@@ -2,15 +2,21 @@ use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::block::{Title, Position};
 use ratatui::layout::Alignment;
 
-pub fn render_script_page(app: &crate::App, num: u8) -> Paragraph<'static> {
+pub fn render_script_page(app: &crate::App, num: u8, height: usize) -> Paragraph<'static> {
     let script_index = (num - 1) as usize;
     let script = app.scripts.get_script(script_index);
 
     let mut lines = vec![Line::from("")];
 
+    // Borders and the blank first line
+    let total = app.script_rows(script_index);
+    let rows = super::script_renderer::visible_rows(total, height.saturating_sub(3), app.selected_line);
+    let scroll = super::script_renderer::scroll_label(&rows, total);
+
     let rendered_lines = super::script_renderer::render_script_lines(
         script,
         script_index,
+        rows,
         app.selected_line,
         true,
         Some(app.current_page),
@@ -23,9 +29,9 @@ pub fn render_script_page(app: &crate::App, num: u8) -> Paragraph<'static> {
     lines.extend(rendered_lines);
 
     let title = if app.script_mutes.muted[script_index] {
-        format!(" SCRIPT {} [MUTED] ", num)
+        format!(" SCRIPT {} [MUTED]{} ", num, scroll)
     } else {
-        format!(" SCRIPT {} ", num)
+        format!(" SCRIPT {}{} ", num, scroll)
     };
 
     let mut block = Block::default()
@@ -1,12 +1,35 @@
 use ratatui::prelude::*;
 use crate::ui::state_highlight::{highlight_stateful_operators, apply_conditional_activity};
 use crate::ui::search_highlight::highlight_matches_in_line;
+use crate::ui::syntax_highlight::apply_syntax;
 use crate::types::{Script, Page, SearchScope};
+use crate::utils::strip_comment;
 use std::collections::HashMap;
+use std::ops::Range;
+
+/// The lines of a script that fit in `height` rows, scrolled so the selected one shows
+pub fn visible_rows(total: usize, height: usize, selected: Option<usize>) -> Range<usize> {
+    let height = height.clamp(1, total.max(1));
+    let first = match selected {
+        Some(line) if line >= height => (line + 1 - height).min(total - height),
+        _ => 0,
+    };
+    first..first + height
+}
+
+/// Title suffix such as " 9-16/24" when only part of a script is on screen
+pub fn scroll_label(rows: &Range<usize>, total: usize) -> String {
+    if rows.len() >= total {
+        String::new()
+    } else {
+        format!(" {}-{}/{}", rows.start + 1, rows.end, total)
+    }
+}
 
 pub fn render_script_lines(
     script: &Script,
     script_index: usize,
+    rows: Range<usize>,
     selected_line: Option<usize>,
     is_selected_page: bool,
     search_page: Option<Page>,
@@ -32,8 +55,10 @@ pub fn render_script_lines(
 
     let mut lines = Vec::new();
 
-    for i in 0..8 {
+    for i in rows {
         let line_content = &script.lines[i];
+        let code = strip_comment(line_content);
+        let comment = &line_content[code.len()..];
         let is_selected = is_selected_page && selected_line == Some(i);
 
         if line_content.is_empty() {
@@ -95,7 +120,7 @@ pub fn render_script_lines(
                 }
             } else if app.show_seq_highlight {
                 let highlighted = highlight_stateful_operators(
-                    line_content,
+                    code,
                     script_index,
                     toggle_state_snapshot,
                     toggle_last_value_snapshot,
@@ -115,7 +140,20 @@ pub fn render_script_lines(
                     spans.extend(highlighted.to_spans(normal_color, highlight_color));
                 }
             } else {
-                spans.push(Span::styled(line_content.to_string(), Style::default().fg(normal_color)));
+                spans.push(Span::styled(code.to_string(), Style::default().fg(normal_color)));
+            }
+
+            // Syntax colors under the selection bar would be unreadable
+            if app.show_syntax_highlight && !should_highlight_search && !is_selected {
+                let code_spans = spans.split_off(1);
+                spans.extend(apply_syntax(code_spans, code, normal_color, &app.theme));
+            }
+
+            if !should_highlight_search && !comment.is_empty() {
+                spans.push(Span::styled(
+                    comment.to_string(),
+                    Style::default().fg(app.theme.secondary).add_modifier(Modifier::DIM),
+                ));
             }
 
             if is_selected {
@@ -0,0 +1,92 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::spectrogram::{SpectrumMode, SpectrumScale};
+use crate::ui::braille::{heatmap_to_braille, heatmap_to_shades, levels_to_blocks};
+
+/// Render the spectrogram page: the live spectrum on top, with the SPEC.SNAP
+/// frame marked over it, and the scrolling waterfall below, newest first
+pub fn render_spectrogram_page(app: &crate::App, area: Rect) -> Paragraph<'static> {
+    let spec = &app.spectrogram;
+    let content_width = area.width.saturating_sub(2) as usize;
+    let content_height = area.height.saturating_sub(2) as usize;
+
+    let analyzer_height = (content_height / 3).max(1);
+    let waterfall_height = content_height.saturating_sub(analyzer_height);
+
+    let analyzer = levels_to_blocks(&spec.current(), content_width, analyzer_height);
+    // Row of the snapshot's level in each column
+    let snapshot_rows: Vec<Option<usize>> = match spec.snapshot() {
+        Some(levels) => (0..content_width)
+            .map(|col| {
+                let level = levels[(col * levels.len()) / content_width.max(1)];
+                let filled = (level * analyzer_height as f32) as usize;
+                Some(analyzer_height - 1 - filled.min(analyzer_height - 1))
+            })
+            .collect(),
+        None => vec![None; content_width],
+    };
+
+    let waterfall = if spec.display_mode == 1 {
+        heatmap_to_shades(&spec.history(waterfall_height), content_width, waterfall_height)
+    } else {
+        heatmap_to_braille(&spec.history(waterfall_height * 4), content_width, waterfall_height)
+    };
+
+    let bar_style = Style::default().fg(app.theme.foreground);
+    let snap_style = Style::default().fg(app.theme.accent);
+    let waterfall_style = Style::default().fg(app.theme.secondary);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (row, cells) in analyzer.into_iter().enumerate() {
+        let spans: Vec<Span> = cells
+            .into_iter()
+            .enumerate()
+            .map(|(col, ch)| {
+                if snapshot_rows[col] == Some(row) && ch != '█' {
+                    Span::styled("─", snap_style)
+                } else {
+                    Span::styled(ch.to_string(), bar_style)
+                }
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    }
+    for row in waterfall {
+        let row_string: String = row.into_iter().collect();
+        lines.push(Line::from(Span::styled(row_string, waterfall_style)));
+    }
+
+    let scale = match spec.scale {
+        SpectrumScale::Log => "LOG",
+        SpectrumScale::Linear => "LIN",
+    };
+    let mode = match spec.mode {
+        SpectrumMode::Peak => "PEAK",
+        SpectrumMode::Average => "AVG",
+    };
+    let mut info_title = format!(" BANDS: {}  {}  {}", spec.bands, scale, mode);
+    if spec.frozen {
+        info_title.push_str("  FROZEN");
+    }
+    if spec.has_snapshot() {
+        info_title.push_str("  SNAP");
+    }
+    info_title.push(' ');
+
+    Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.background).fg(app.theme.foreground))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border))
+                .title(" SPECTROGRAM ")
+                .title_style(Style::default().fg(app.theme.foreground))
+                .title(
+                    ratatui::widgets::block::Title::from(
+                        Span::styled(info_title, Style::default().fg(app.theme.secondary))
+                    )
+                    .alignment(Alignment::Right)
+                    .position(ratatui::widgets::block::Position::Bottom)
+                )
+        )
+}
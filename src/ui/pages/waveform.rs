@@ -0,0 +1,141 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::sampler::slice::frame_to_position;
+use crate::ui::braille::envelope_to_braille;
+
+/// Render the sample waveform page with slice markers, cursor and playhead
+///
+/// The top row shows slot numbers at their markers, the rest is the envelope.
+pub fn render_waveform_page(app: &crate::App, area: Rect) -> Paragraph<'static> {
+    let content_width = area.width.saturating_sub(2) as usize;
+    let content_height = area.height.saturating_sub(2) as usize;
+    let view = &app.waveform_view;
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    let info_title = match (&view.overview, app.sampler_state.slots.len()) {
+        (Some(_), count) => format!(
+            " ZOOM {}X  SLOT {}/{}  POS {} ",
+            view.zoom,
+            app.sampler_state.current_slot,
+            count.saturating_sub(1),
+            view.cursor
+        ),
+        (None, _) => " ZOOM 1X ".to_string(),
+    };
+
+    let Some(overview) = view.overview.as_ref() else {
+        let message = "NO SLICE FILE LOADED (S.SLICE)";
+        for _ in 0..content_height / 2 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!("{:^width$}", message, width = content_width),
+            Style::default().fg(app.theme.secondary),
+        )));
+        return waveform_paragraph(app, lines, info_title);
+    };
+
+    let total = overview.total_frames;
+    let (window_start, window_end) = view.window();
+    let span = (window_end - window_start).max(1) as usize;
+    let width = content_width.max(1);
+
+    // Position (0-16383) to display column within the visible window
+    let column_of = |position: i16| -> Option<usize> {
+        if position < window_start || position >= window_end {
+            return None;
+        }
+        Some(((position - window_start) as usize * width / span).min(width - 1))
+    };
+
+    let start_frame = crate::sampler::slice::position_to_frame(window_start, total);
+    let end_frame = crate::sampler::slice::position_to_frame(window_end, total);
+    let envelope = overview.columns(start_frame, end_frame, width);
+    let waveform_height = content_height.saturating_sub(1).max(1);
+    let grid = envelope_to_braille(&envelope, width, waveform_height);
+
+    // Per-column markers: slot start markers, selected slot range, cursor, playhead
+    let mut marker_cols: Vec<Option<usize>> = vec![None; width];
+    let mut selected = vec![false; width];
+    for (idx, slot) in app.sampler_state.slots.iter().enumerate() {
+        let slot_start = frame_to_position(slot.start_frame, total);
+        let slot_end = frame_to_position(slot.end_frame, total);
+        if idx > 0 {
+            if let Some(col) = column_of(slot_start) {
+                marker_cols[col] = Some(idx);
+            }
+        }
+        if idx == app.sampler_state.current_slot {
+            for (col, flag) in selected.iter_mut().enumerate() {
+                let position = window_start as usize + col * span / width;
+                *flag = position >= slot_start as usize && position < slot_end as usize;
+            }
+        }
+    }
+
+    let cursor_col = column_of(view.cursor);
+    let playhead_col = app
+        .sampler_playhead_frame()
+        .and_then(|frame| column_of(frame_to_position(frame, total)));
+
+    // Ruler row with slot numbers at their markers
+    let mut ruler: Vec<char> = vec![' '; width];
+    for (col, marker) in marker_cols.iter().enumerate() {
+        if let Some(idx) = marker {
+            for (offset, ch) in idx.to_string().chars().enumerate() {
+                if col + offset < width {
+                    ruler[col + offset] = ch;
+                }
+            }
+        }
+    }
+    lines.push(Line::from(Span::styled(
+        ruler.into_iter().collect::<String>(),
+        Style::default().fg(app.theme.label),
+    )));
+
+    for row in grid {
+        let spans: Vec<Span> = row
+            .into_iter()
+            .enumerate()
+            .map(|(col, ch)| {
+                let (ch, mut style) = if playhead_col == Some(col) {
+                    ('│', Style::default().fg(app.theme.success))
+                } else if marker_cols[col].is_some() {
+                    ('│', Style::default().fg(app.theme.secondary))
+                } else if selected[col] {
+                    (ch, Style::default().fg(app.theme.accent))
+                } else {
+                    (ch, Style::default().fg(app.theme.foreground))
+                };
+                if cursor_col == Some(col) {
+                    style = style.bg(app.theme.highlight_bg);
+                }
+                Span::styled(ch.to_string(), style)
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    }
+
+    waveform_paragraph(app, lines, info_title)
+}
+
+fn waveform_paragraph(app: &crate::App, lines: Vec<Line<'static>>, info_title: String) -> Paragraph<'static> {
+    Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.background).fg(app.theme.foreground))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border))
+                .title(" WAVEFORM ")
+                .title_style(Style::default().fg(app.theme.foreground))
+                .title(
+                    ratatui::widgets::block::Title::from(
+                        Span::styled(info_title, Style::default().fg(app.theme.secondary))
+                    )
+                    .alignment(Alignment::Right)
+                    .position(ratatui::widgets::block::Position::Bottom)
+                )
+        )
+}
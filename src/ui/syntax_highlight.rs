@@ -0,0 +1,145 @@
+//! Syntax colors for script lines and the input line (HL.SYN). Words are
+//! looked up in the command registry, so new commands color without changes
+//! here. SEQ/TOG state and conditional activity highlighting keep their own
+//! colors; only text they leave plain is colored.
+
+use crate::commands::registry::COMMAND_REGISTRY;
+use crate::eval::seq::parse_note_name;
+use crate::theme::Theme;
+use ratatui::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Command,
+    Alias,
+    Variable,
+    Number,
+    Note,
+    /// Quoted text: SEQ patterns
+    Str,
+    Keyword,
+    /// `;` between commands and `:` after a condition
+    Separator,
+    /// A command the registry doesn't know
+    Unknown,
+    Comment,
+    /// Whitespace and arguments with no color of their own
+    Plain,
+}
+
+const KEYWORDS: &[&str] = &["IF", "ELIF", "ELSE", "L", "PROB", "EV", "SKIP", "DEL", "DEL.X", "DEL.R"];
+const VARIABLES: &[&str] = &["A", "B", "C", "D", "I", "J", "K", "X", "Y", "Z", "T"];
+
+impl TokenKind {
+    /// Theme color, or None to keep the surrounding text color
+    pub fn color(self, theme: &Theme) -> Option<Color> {
+        match self {
+            TokenKind::Command => Some(theme.syn_command),
+            TokenKind::Alias => Some(theme.syn_alias),
+            TokenKind::Variable => Some(theme.syn_variable),
+            TokenKind::Number => Some(theme.syn_number),
+            TokenKind::Note => Some(theme.syn_note),
+            TokenKind::Str => Some(theme.syn_string),
+            TokenKind::Keyword => Some(theme.syn_keyword),
+            TokenKind::Separator => Some(theme.syn_separator),
+            TokenKind::Unknown => Some(theme.error),
+            TokenKind::Comment => Some(theme.secondary),
+            TokenKind::Plain => None,
+        }
+    }
+}
+
+fn word_kind(word: &str, is_command: bool) -> TokenKind {
+    let word = word.to_uppercase();
+    if is_command && KEYWORDS.contains(&word.as_str()) {
+        return TokenKind::Keyword;
+    }
+    if VARIABLES.contains(&word.as_str()) {
+        return TokenKind::Variable;
+    }
+    if !is_command {
+        if word.parse::<f32>().is_ok() {
+            return TokenKind::Number;
+        }
+        if parse_note_name(&word).is_some() {
+            return TokenKind::Note;
+        }
+    }
+    match COMMAND_REGISTRY.get(word.as_str()) {
+        Some(def) if def.canonical.is_some() => TokenKind::Alias,
+        Some(_) => TokenKind::Command,
+        None if is_command => TokenKind::Unknown,
+        None => TokenKind::Plain,
+    }
+}
+
+/// The kind of each char of `line`. The first word after the start, `;` or
+/// `:` is a command; the rest are arguments.
+pub fn char_kinds(line: &str) -> Vec<TokenKind> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut kinds = vec![TokenKind::Plain; chars.len()];
+    let mut is_command = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' && (i == 0 || chars[i - 1].is_whitespace()) {
+            kinds[i..].fill(TokenKind::Comment);
+            break;
+        } else if c == ';' || c == ':' {
+            kinds[i] = TokenKind::Separator;
+            is_command = true;
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            // Up to the closing quote, or the end while it's being typed
+            let end = chars[i + 1..].iter().position(|&q| q == c).map_or(chars.len(), |p| i + p + 2);
+            kinds[i..end].fill(TokenKind::Str);
+            is_command = false;
+            i = end;
+        } else {
+            let end = chars[i..]
+                .iter()
+                .position(|&w| w.is_whitespace() || matches!(w, ';' | ':' | '"' | '\''))
+                .map_or(chars.len(), |p| i + p);
+            let word: String = chars[i..end].iter().collect();
+            kinds[i..end].fill(word_kind(&word, is_command));
+            is_command = false;
+            i = end;
+        }
+    }
+    kinds
+}
+
+/// Recolor the parts of `spans` (which together read `line`) still drawn in
+/// `plain`; anything another highlighter colored is left as it is
+pub fn apply_syntax(spans: Vec<Span<'static>>, line: &str, plain: Color, theme: &Theme) -> Vec<Span<'static>> {
+    let kinds = char_kinds(line);
+    let mut result = Vec::new();
+    let mut pos = 0;
+
+    for span in spans {
+        let len = span.content.chars().count();
+        if span.style.fg != Some(plain) {
+            result.push(span);
+            pos += len;
+            continue;
+        }
+        let mut run = String::new();
+        let mut run_color = None;
+        for (offset, c) in span.content.chars().enumerate() {
+            let color = kinds.get(pos + offset).and_then(|kind| kind.color(theme));
+            if color != run_color && !run.is_empty() {
+                result.push(Span::styled(std::mem::take(&mut run), span.style.fg(run_color.unwrap_or(plain))));
+            }
+            run_color = color;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            result.push(Span::styled(run, span.style.fg(run_color.unwrap_or(plain))));
+        }
+        pos += len;
+    }
+    result
+}
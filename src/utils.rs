@@ -60,6 +60,28 @@ pub fn split_whitespace_respecting_quotes(cmd: &str) -> Vec<String> {
     parts
 }
 
+/// The code part of a script line. `#` starts a comment when it begins the line
+/// or follows a space, outside quotes, so `F#2` in a SEQ pattern is left alone.
+pub fn strip_comment(line: &str) -> &str {
+    let mut in_quote = false;
+    let mut quote_char = ' ';
+    let mut prev = ' ';
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' | '\'' if !in_quote => {
+                in_quote = true;
+                quote_char = c;
+            }
+            c if c == quote_char && in_quote => in_quote = false,
+            '#' if !in_quote && prev.is_whitespace() => return &line[..i],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
 pub const VALUE_14BIT_MAX: i32 = 16383;
 pub const VALUE_14BIT_CENTER: i32 = 8192;
 pub const VALUE_14BIT_MIN: i32 = 0;